@@ -1,74 +1,449 @@
 use anyhow::{Context, Result, bail};
-use reqwest::{Client, RequestBuilder, cookie::Jar};
+use async_trait::async_trait;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
 use serde_json::json;
+use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use super::queries::{FAVORITES_LIST_QUERY, GLOBAL_DATA_QUERY, PROBLEM_LIST_QUERY, QUESTION_DETAIL_QUERY, USER_PROFILE_QUERY};
+use super::queries::{
+    COMMUNITY_SOLUTIONS_QUERY, COMPANY_TAG_QUERY, COMPANY_TAGS_QUERY, CONTESTS_QUERY,
+    DAILY_QUESTION_QUERY, FAVORITES_LIST_QUERY, GLOBAL_DATA_QUERY, OFFICIAL_SOLUTION_QUERY,
+    PROBLEM_LIST_QUERY, QUESTION_DETAIL_QUERY, RANDOM_QUESTION_QUERY, SOLUTION_POST_QUERY,
+    UGC_OFFICIAL_SOLUTION_QUERY, USER_PROFILE_QUERY,
+};
+use super::transport::{self, Transport};
 use super::types::*;
+use crate::logging;
 
-const LEETCODE_GRAPHQL: &str = "https://leetcode.com/graphql";
-const LEETCODE_RUN: &str = "https://leetcode.com/problems/{slug}/interpret_solution/";
-const LEETCODE_SUBMIT: &str = "https://leetcode.com/problems/{slug}/submit/";
-const LEETCODE_CHECK: &str = "https://leetcode.com/submissions/detail/{id}/check/";
-const LEETCODE_LIST_API: &str = "https://leetcode.com/list/api/";
-const LEETCODE_LIST_QUESTIONS_API: &str = "https://leetcode.com/list/api/questions";
+/// The site every endpoint and page below hangs off, unless the config
+/// names another
+pub const LEETCODE_SITE: &str = "https://leetcode.com";
+const GRAPHQL_PATH: &str = "/graphql";
+const RUN_PATH: &str = "/problems/{slug}/interpret_solution/";
+const SUBMIT_PATH: &str = "/problems/{slug}/submit/";
+const CHECK_PATH: &str = "/submissions/detail/{id}/check/";
+const CONTEST_INFO_PATH: &str = "/contest/api/info/{slug}/";
+const LIST_API_PATH: &str = "/list/api/";
+const LIST_QUESTIONS_API_PATH: &str = "/list/api/questions";
+/// Every account's built-in list, which LeetCode shows as starring
+const DEFAULT_FAVORITE_LIST: &str = "Favorite";
+/// What a company lookup says instead of coming back empty
+const PREMIUM_REQUIRED: &str = "Company problem lists require LeetCode Premium";
+
+/// Failures with a cause the user can act on; anything else stays a plain
+/// `anyhow` error.
+#[derive(Debug)]
+pub enum ApiError {
+    /// No response at all
+    Network(reqwest::Error),
+    /// LeetCode refused the session cookies
+    Unauthorized(StatusCode),
+    /// Too many requests; how long LeetCode asked us to wait, if it said
+    RateLimited(Option<Duration>),
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Network(_) => write!(f, "couldn't reach LeetCode, check your connection"),
+            Self::Unauthorized(status) => write!(
+                f,
+                "LeetCode rejected the session ({status}), log in again from settings (S)"
+            ),
+            Self::RateLimited(Some(wait)) => {
+                write!(f, "rate limited by LeetCode, retry in {}s", wait.as_secs())
+            }
+            Self::RateLimited(None) => {
+                write!(f, "rate limited by LeetCode, wait a minute and retry")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ApiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Network(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// `err` for display, followed by its likely cause when that's known.
+pub fn describe_error(err: &anyhow::Error) -> String {
+    match err.chain().find_map(|e| e.downcast_ref::<ApiError>()) {
+        Some(cause) => format!("{err}: {cause}"),
+        None => format!("{err}"),
+    }
+}
+
+/// Whether LeetCode refused a query because the account isn't Premium.
+fn premium_refusal(errors: &[GraphQLError]) -> bool {
+    errors.iter().any(|e| {
+        let message = e.message.to_lowercase();
+        ["premium", "subscri", "permission"]
+            .iter()
+            .any(|w| message.contains(w))
+    })
+}
+
+/// Cooldown after a rate limit that didn't say how long to wait.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// How long to hold off when `err` came from a rate limit.
+pub fn rate_limit_wait(err: &anyhow::Error) -> Option<Duration> {
+    err.chain()
+        .find_map(|e| match e.downcast_ref::<ApiError>() {
+            Some(ApiError::RateLimited(wait)) => Some(wait.unwrap_or(DEFAULT_COOLDOWN)),
+            _ => None,
+        })
+}
+
+/// `send` through the client's transport, logging the request with its
+/// status and timing when logging is on.
+trait SendVia {
+    async fn send_via(self, transport: &dyn Transport) -> reqwest::Result<Response>;
+}
+
+impl SendVia for RequestBuilder {
+    async fn send_via(self, transport: &dyn Transport) -> reqwest::Result<Response> {
+        let request = self.build()?;
+        let (method, url) = (request.method().clone(), request.url().clone());
+        let started = Instant::now();
+        let resp = transport.execute(request).await;
+        let elapsed = started.elapsed();
+        match &resp {
+            Ok(r) => tracing::debug!(%method, %url, status = %r.status(), ?elapsed, "request"),
+            Err(e) => tracing::warn!(%method, %url, ?elapsed, error = %e, "request failed"),
+        }
+        resp
+    }
+}
+
+/// `json` that also logs the body of a response that fails to parse.
+trait LoggedJson {
+    async fn json_logged<T: DeserializeOwned>(self) -> Result<T>;
+}
+
+impl LoggedJson for Response {
+    async fn json_logged<T: DeserializeOwned>(self) -> Result<T> {
+        let (status, url) = (self.status(), self.url().clone());
+        let body = self.text().await.context("Failed to read response body")?;
+        serde_json::from_str(&body)
+            .inspect_err(|e| {
+                let body = logging::redact(&body);
+                tracing::warn!(%url, %status, error = %e, %body, "parse failed");
+            })
+            .map_err(Into::into)
+    }
+}
+
+/// Turn a send result into an `ApiError` for the failures worth explaining.
+fn classify(resp: reqwest::Result<Response>) -> std::result::Result<Response, ApiError> {
+    let resp = resp.map_err(ApiError::Network)?;
+    match resp.status() {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Err(ApiError::Unauthorized(resp.status()))
+        }
+        StatusCode::TOO_MANY_REQUESTS => {
+            let wait = resp
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok()?.trim().parse().ok())
+                .map(Duration::from_secs);
+            Err(ApiError::RateLimited(wait))
+        }
+        _ => Ok(resp),
+    }
+}
+
+impl<T> GraphQLResponse<T> {
+    /// The part of `data` that `pick` finds. When it's missing, the error
+    /// is the server's own messages if it sent any, else `missing`.
+    fn extract<U>(self, pick: impl FnOnce(T) -> Option<U>, missing: &str) -> Result<U> {
+        if let Some(found) = self.data.and_then(pick) {
+            return Ok(found);
+        }
+        if self.errors.is_empty() {
+            bail!("{missing}");
+        }
+        let messages: Vec<&str> = self.errors.iter().map(|e| e.message.as_str()).collect();
+        let message = format!("LeetCode: {}", messages.join("; "));
+        let lower = message.to_lowercase();
+        if lower.contains("rate limit") || lower.contains("too many requests") {
+            return Err(anyhow::Error::new(ApiError::RateLimited(None)).context(message));
+        }
+        bail!(message)
+    }
+}
+
+/// The page a request claims to come from, which sets its Referer. The
+/// judge rejects run and submit requests that don't name the problem's page.
+#[derive(Debug, Clone, Copy)]
+enum Page<'a> {
+    /// Requests that don't belong to any page
+    Home,
+    Problem(&'a str),
+    Editorial(&'a str),
+    Solutions(&'a str),
+    Contest(&'a str),
+}
+
+impl Page<'_> {
+    fn path(self) -> String {
+        match self {
+            Page::Home => "/".to_string(),
+            Page::Problem(slug) => format!("/problems/{slug}/"),
+            Page::Editorial(slug) => format!("/problems/{slug}/editorial/"),
+            Page::Solutions(slug) => format!("/problems/{slug}/solutions/"),
+            Page::Contest(slug) => format!("/contest/{slug}/"),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct LeetCodeClient {
     client: Client,
+    /// Sends what `client` builds; see `transport::from_env`
+    transport: Arc<dyn Transport>,
+    /// Base URL for endpoints, Origin and Referer, without a trailing slash
+    site: String,
     csrf_token: Option<String>,
+    /// `LEETCODE_SESSION` and `csrftoken`, as a Cookie header
+    cookie: Option<String>,
 }
 
 impl LeetCodeClient {
-    pub fn new(session: Option<&str>, csrf: Option<&str>) -> Result<Self> {
-        let jar = Arc::new(Jar::default());
-        let url = "https://leetcode.com".parse().unwrap();
-
-        if let Some(session) = session {
-            if !session.is_empty() {
-                jar.add_cookie_str(&format!("LEETCODE_SESSION={session}"), &url);
-            }
-        }
-        if let Some(csrf) = csrf {
-            if !csrf.is_empty() {
-                jar.add_cookie_str(&format!("csrftoken={csrf}"), &url);
-            }
-        }
+    /// A client for `site`, e.g. `https://leetcode.cn`, with the given
+    /// cookies. See `Config::site`.
+    pub fn new(site: &str, session: Option<&str>, csrf: Option<&str>) -> Result<Self> {
+        logging::add_secrets(session.into_iter().chain(csrf));
+        let session = session.filter(|s| !s.is_empty());
+        let csrf = csrf.filter(|s| !s.is_empty());
+        let cookies: Vec<String> = session
+            .map(|s| format!("LEETCODE_SESSION={s}"))
+            .into_iter()
+            .chain(csrf.map(|c| format!("csrftoken={c}")))
+            .collect();
 
         let client = Client::builder()
-            .cookie_provider(jar)
             .build()
             .context("Failed to create HTTP client")?;
 
         Ok(Self {
+            transport: transport::from_env(client.clone()).into(),
             client,
+            site: site.trim_end_matches('/').to_string(),
             csrf_token: csrf.map(String::from),
+            cookie: (!cookies.is_empty()).then(|| cookies.join("; ")),
         })
     }
 
-    fn auth_request(&self, builder: RequestBuilder) -> RequestBuilder {
-        let builder = builder
+    /// A request to `path` on the site with the headers LeetCode checks:
+    /// Origin, a Referer for `page`, and when logged in the session and
+    /// csrftoken cookies and the CSRF token. They're set here rather than by
+    /// a cookie jar so the transport sees the request as it will be sent.
+    fn request(&self, method: Method, path: &str, page: Page) -> RequestBuilder {
+        let mut builder = self
+            .client
+            .request(method, format!("{}{path}", self.site))
             .header("Content-Type", "application/json")
-            .header("Origin", "https://leetcode.com")
-            .header("Referer", "https://leetcode.com");
+            .header("Origin", &self.site)
+            .header("Referer", format!("{}{}", self.site, page.path()));
+        if let Some(ref cookie) = self.cookie {
+            builder = builder.header("Cookie", cookie);
+        }
         if let Some(ref token) = self.csrf_token {
-            builder.header("x-csrftoken", token)
-        } else {
-            builder
+            builder = builder.header("x-csrftoken", token);
         }
+        builder
     }
 
-    pub async fn fetch_problems(
+    async fn check_result(&self, slug: &str, id: &str) -> Result<CheckResponse> {
+        let path = CHECK_PATH.replace("{id}", id);
+
+        let resp = classify(
+            self.request(Method::GET, &path, Page::Problem(slug))
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to send check request")?;
+
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .context("Failed to read check response body")?;
+
+        if !status.is_success() {
+            bail!("LeetCode returned HTTP {status} for check: {body}");
+        }
+
+        let data: CheckResponse = serde_json::from_str(&body)
+            .with_context(|| format!("Failed to parse check response: {body}"))?;
+
+        Ok(data)
+    }
+
+    /// The editorial of a newer problem, as an `OfficialSolution` so it
+    /// renders like the older ones.
+    async fn fetch_ugc_editorial(&self, slug: &str) -> Result<Option<OfficialSolution>> {
+        let body = json!({
+            "query": UGC_OFFICIAL_SOLUTION_QUERY,
+            "variables": {
+                "questionSlug": slug,
+            }
+        });
+
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Editorial(slug))
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to send editorial request")?;
+
+        let data: GraphQLResponse<UgcOfficialSolutionData> = resp
+            .json_logged()
+            .await
+            .context("Failed to parse editorial response")?;
+
+        if !data.errors.is_empty() {
+            let messages: Vec<&str> = data.errors.iter().map(|e| e.message.as_str()).collect();
+            bail!("LeetCode: {}", messages.join("; "));
+        }
+        let article = data
+            .data
+            .and_then(|d| d.ugc_article_official_solution_article);
+        Ok(article.map(|a| OfficialSolution {
+            can_see_detail: a.content.is_some(),
+            paid_only: a.content.is_none(),
+            content: a.content,
+        }))
+    }
+}
+
+/// Everything the app asks of LeetCode. `App` holds it as a trait object,
+/// so its state machine can be driven by something other than the network.
+#[async_trait]
+pub trait LeetCodeApi: Send + Sync {
+    async fn fetch_problems(
         &self,
         limit: i32,
         skip: i32,
-        difficulty: Option<&str>,
+        filter: &FetchFilter,
+        search_keywords: Option<&str>,
+    ) -> Result<(Vec<ProblemSummary>, i32)>;
+
+    async fn fetch_problem_detail(&self, slug: &str) -> Result<QuestionDetail>;
+
+    /// The official editorial, or `None` if the problem doesn't have one.
+    async fn fetch_editorial(&self, slug: &str) -> Result<Option<OfficialSolution>>;
+
+    /// A page of community solutions, most upvoted first, and how many
+    /// there are in all.
+    async fn fetch_solutions(
+        &self,
+        slug: &str,
+        skip: u32,
+        first: u32,
+    ) -> Result<(Vec<CommunitySolution>, u32)>;
+
+    /// The Markdown of a community solution post, by its topic id.
+    async fn fetch_solution_post(&self, slug: &str, topic_id: &str) -> Result<String>;
+
+    /// A random problem, optionally limited to a difficulty (`EASY`,
+    /// `MEDIUM` or `HARD`) and to problems with all of `tags`.
+    async fn fetch_random_slug(&self, difficulty: Option<&str>, tags: &[String]) -> Result<String>;
+
+    /// Today's daily challenge.
+    async fn fetch_daily_slug(&self) -> Result<String>;
+
+    async fn run_code(
+        &self,
+        slug: &str,
+        question_id: &str,
+        lang: &str,
+        typed_code: &str,
+        data_input: &str,
+    ) -> Result<String>;
+
+    async fn submit_code(
+        &self,
+        slug: &str,
+        question_id: &str,
+        lang: &str,
+        typed_code: &str,
+    ) -> Result<String>;
+
+    async fn poll_result(&self, slug: &str, id: &str) -> Result<CheckResponse>;
+
+    async fn fetch_username(&self) -> Option<String>;
+
+    /// Who the cookies log in as, failing with the reason when they don't.
+    async fn check_login(&self) -> Result<String>;
+
+    async fn fetch_user_stats(&self, username: &str) -> Result<UserStats>;
+
+    /// Every contest, upcoming ones included, latest start first.
+    async fn fetch_contests(&self) -> Result<Vec<ContestSummary>>;
+
+    /// A contest and its problems, ordered Q1 to Q4. Fails with the reason
+    /// when the problems can't be listed: not started yet, or Premium only.
+    async fn fetch_contest(&self, slug: &str) -> Result<(Contest, Vec<ContestQuestion>)>;
+
+    /// Every company with tagged problems, those with the most first.
+    async fn fetch_company_tags(&self) -> Result<Vec<CompanyTag>>;
+
+    /// A company's name and the problems it has asked. Fails with a
+    /// readable reason when the account isn't Premium.
+    async fn fetch_company_problems(&self, slug: &str) -> Result<(String, Vec<CompanyProblem>)>;
+
+    async fn fetch_favorites(&self) -> Result<Vec<FavoriteList>>;
+
+    async fn create_favorite_list(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        public: bool,
+    ) -> Result<()>;
+
+    async fn rename_favorite_list(&self, id_hash: &str, name: &str) -> Result<()>;
+
+    async fn delete_favorite_list(&self, id_hash: &str) -> Result<()>;
+
+    async fn add_to_favorite(&self, id_hash: &str, question_id: &str) -> Result<()>;
+
+    /// Star or unstar a problem by adding it to or removing it from the
+    /// default favorite list. Returns whether it is starred now.
+    async fn toggle_star(&self, question_id: &str, slug: &str) -> Result<bool>;
+
+    async fn remove_from_favorite(&self, id_hash: &str, question_id: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl LeetCodeApi for LeetCodeClient {
+    async fn fetch_problems(
+        &self,
+        limit: i32,
+        skip: i32,
+        filter: &FetchFilter,
         search_keywords: Option<&str>,
     ) -> Result<(Vec<ProblemSummary>, i32)> {
         let mut filters = json!({});
-        if let Some(diff) = difficulty {
+        if let Some(ref diff) = filter.difficulty {
             filters["difficulty"] = json!(diff);
         }
+        if !filter.tags.is_empty() {
+            filters["tags"] = json!(filter.tags);
+        }
+        if let Some(ref list) = filter.list {
+            filters["listId"] = json!(list);
+        }
         if let Some(kw) = search_keywords {
             filters["searchKeywords"] = json!(kw);
         }
@@ -83,27 +458,28 @@ impl LeetCodeClient {
             }
         });
 
-        let resp = self
-            .auth_request(self.client.post(LEETCODE_GRAPHQL))
-            .json(&body)
-            .send()
-            .await
-            .context("Failed to send problem list request")?;
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Home)
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to fetch problem list")?;
 
         let data: GraphQLResponse<ProblemListData> = resp
-            .json()
+            .json_logged()
             .await
             .context("Failed to parse problem list response")?;
 
-        let list = data
-            .data
-            .and_then(|d| d.problemset_question_list)
-            .context("No problem list data in response")?;
+        let list = data.extract(
+            |d| d.problemset_question_list,
+            "No problem list data in response",
+        )?;
 
         Ok((list.questions, list.total))
     }
 
-    pub async fn fetch_problem_detail(&self, slug: &str) -> Result<QuestionDetail> {
+    async fn fetch_problem_detail(&self, slug: &str) -> Result<QuestionDetail> {
         let body = json!({
             "query": QUESTION_DETAIL_QUERY,
             "variables": {
@@ -111,25 +487,185 @@ impl LeetCodeClient {
             }
         });
 
-        let resp = self
-            .auth_request(self.client.post(LEETCODE_GRAPHQL))
-            .header("Referer", format!("https://leetcode.com/problems/{slug}/"))
-            .json(&body)
-            .send()
-            .await
-            .context("Failed to send problem detail request")?;
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Problem(slug))
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to send problem detail request")?;
 
         let data: GraphQLResponse<QuestionDetailData> = resp
-            .json()
+            .json_logged()
             .await
             .context("Failed to parse problem detail response")?;
 
-        data.data
-            .and_then(|d| d.question)
-            .context("No question data in response")
+        data.extract(|d| d.question, "No question data in response")
+    }
+
+    async fn fetch_editorial(&self, slug: &str) -> Result<Option<OfficialSolution>> {
+        let body = json!({
+            "query": OFFICIAL_SOLUTION_QUERY,
+            "variables": {
+                "titleSlug": slug,
+            }
+        });
+
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Editorial(slug))
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to send editorial request")?;
+
+        let data: GraphQLResponse<OfficialSolutionData> = resp
+            .json_logged()
+            .await
+            .context("Failed to parse editorial response")?;
+
+        let question = data.extract(|d| d.question, "No question data in response")?;
+        if question.solution.is_some() {
+            return Ok(question.solution);
+        }
+        // Only a fallback, so a failure here reads as "no editorial"
+        match self.fetch_ugc_editorial(slug).await {
+            Ok(solution) => Ok(solution),
+            Err(e) => {
+                tracing::warn!(slug, error = %e, "ugc editorial lookup failed");
+                Ok(None)
+            }
+        }
+    }
+
+    async fn fetch_solutions(
+        &self,
+        slug: &str,
+        skip: u32,
+        first: u32,
+    ) -> Result<(Vec<CommunitySolution>, u32)> {
+        let body = json!({
+            "query": COMMUNITY_SOLUTIONS_QUERY,
+            "variables": {
+                "questionSlug": slug,
+                "skip": skip,
+                "first": first,
+                "orderBy": "most_votes",
+            }
+        });
+
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Solutions(slug))
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to send solutions request")?;
+
+        let data: GraphQLResponse<QuestionSolutionsData> = resp
+            .json_logged()
+            .await
+            .context("Failed to parse solutions response")?;
+
+        let solutions = data.extract(|d| d.question_solutions, "No solutions in response")?;
+        Ok((solutions.solutions, solutions.total_num))
+    }
+
+    async fn fetch_solution_post(&self, slug: &str, topic_id: &str) -> Result<String> {
+        let topic_id: i64 = topic_id
+            .parse()
+            .with_context(|| format!("Bad solution id {topic_id}"))?;
+        let body = json!({
+            "query": SOLUTION_POST_QUERY,
+            "variables": {
+                "topicId": topic_id,
+            }
+        });
+
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Solutions(slug))
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to send solution request")?;
+
+        let data: GraphQLResponse<SolutionTopicData> = resp
+            .json_logged()
+            .await
+            .context("Failed to parse solution response")?;
+
+        let content = data
+            .extract(|d| d.topic, "Solution not found")?
+            .post
+            .content;
+        // Older posts come with their line breaks escaped
+        if !content.contains('\n') && content.contains("\\n") {
+            return Ok(content.replace("\\n", "\n").replace("\\t", "\t"));
+        }
+        Ok(content)
+    }
+
+    async fn fetch_random_slug(&self, difficulty: Option<&str>, tags: &[String]) -> Result<String> {
+        let mut filters = json!({});
+        if let Some(diff) = difficulty {
+            filters["difficulty"] = json!(diff);
+        }
+        if !tags.is_empty() {
+            filters["tags"] = json!(tags);
+        }
+        let body = json!({
+            "query": RANDOM_QUESTION_QUERY,
+            "variables": {
+                "categorySlug": "all-code-essentials",
+                "filters": filters,
+            }
+        });
+
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Home)
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to send random question request")?;
+
+        let data: GraphQLResponse<RandomQuestionData> = resp
+            .json_logged()
+            .await
+            .context("Failed to parse random question response")?;
+
+        data.extract(|d| d.random_question, "No random question in response")
+            .map(|q| q.title_slug)
+    }
+
+    async fn fetch_daily_slug(&self) -> Result<String> {
+        let body = json!({
+            "query": DAILY_QUESTION_QUERY,
+            "variables": {}
+        });
+
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Home)
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to send daily question request")?;
+
+        let data: GraphQLResponse<DailyQuestionData> = resp
+            .json_logged()
+            .await
+            .context("Failed to parse daily question response")?;
+
+        data.extract(
+            |d| d.active_daily_coding_challenge_question,
+            "No daily question in response",
+        )
+        .map(|d| d.question.title_slug)
     }
 
-    pub async fn run_code(
+    async fn run_code(
         &self,
         slug: &str,
         question_id: &str,
@@ -137,7 +673,7 @@ impl LeetCodeClient {
         typed_code: &str,
         data_input: &str,
     ) -> Result<String> {
-        let url = LEETCODE_RUN.replace("{slug}", slug);
+        let path = RUN_PATH.replace("{slug}", slug);
         let body = json!({
             "lang": lang,
             "question_id": question_id,
@@ -145,13 +681,13 @@ impl LeetCodeClient {
             "data_input": data_input,
         });
 
-        let resp = self
-            .auth_request(self.client.post(&url))
-            .header("Referer", format!("https://leetcode.com/problems/{slug}/"))
-            .json(&body)
-            .send()
-            .await
-            .context("Failed to send run request")?;
+        let resp = classify(
+            self.request(Method::POST, &path, Page::Problem(slug))
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to send run request")?;
 
         let status = resp.status();
         if !status.is_success() {
@@ -160,7 +696,7 @@ impl LeetCodeClient {
         }
 
         let data: InterpretResponse = resp
-            .json()
+            .json_logged()
             .await
             .context("Failed to parse run response")?;
 
@@ -171,27 +707,27 @@ impl LeetCodeClient {
         data.interpret_id.context("No interpret_id in response")
     }
 
-    pub async fn submit_code(
+    async fn submit_code(
         &self,
         slug: &str,
         question_id: &str,
         lang: &str,
         typed_code: &str,
     ) -> Result<String> {
-        let url = LEETCODE_SUBMIT.replace("{slug}", slug);
+        let path = SUBMIT_PATH.replace("{slug}", slug);
         let body = json!({
             "lang": lang,
             "question_id": question_id,
             "typed_code": typed_code,
         });
 
-        let resp = self
-            .auth_request(self.client.post(&url))
-            .header("Referer", format!("https://leetcode.com/problems/{slug}/"))
-            .json(&body)
-            .send()
-            .await
-            .context("Failed to send submit request")?;
+        let resp = classify(
+            self.request(Method::POST, &path, Page::Problem(slug))
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to send submit request")?;
 
         let status = resp.status();
         if !status.is_success() {
@@ -200,7 +736,7 @@ impl LeetCodeClient {
         }
 
         let data: SubmitResponse = resp
-            .json()
+            .json_logged()
             .await
             .context("Failed to parse submit response")?;
 
@@ -213,33 +749,10 @@ impl LeetCodeClient {
             .context("No submission_id in response")
     }
 
-    pub async fn check_result(&self, id: &str) -> Result<CheckResponse> {
-        let url = LEETCODE_CHECK.replace("{id}", id);
-
-        let resp = self
-            .auth_request(self.client.get(&url))
-            .header("Referer", "https://leetcode.com")
-            .send()
-            .await
-            .context("Failed to send check request")?;
-
-        let status = resp.status();
-        let body = resp.text().await.context("Failed to read check response body")?;
-
-        if !status.is_success() {
-            bail!("LeetCode returned HTTP {status} for check: {body}");
-        }
-
-        let data: CheckResponse = serde_json::from_str(&body)
-            .with_context(|| format!("Failed to parse check response: {body}"))?;
-
-        Ok(data)
-    }
-
-    pub async fn poll_result(&self, id: &str) -> Result<CheckResponse> {
+    async fn poll_result(&self, slug: &str, id: &str) -> Result<CheckResponse> {
         let mut attempts = 0u32;
         loop {
-            let result = self.check_result(id).await?;
+            let result = self.check_result(slug, id).await?;
             if result.state == "SUCCESS" {
                 return Ok(result);
             }
@@ -254,20 +767,21 @@ impl LeetCodeClient {
         }
     }
 
-    pub async fn fetch_username(&self) -> Option<String> {
+    async fn fetch_username(&self) -> Option<String> {
         let body = json!({
             "query": GLOBAL_DATA_QUERY,
             "variables": {}
         });
 
-        let resp = self
-            .auth_request(self.client.post(LEETCODE_GRAPHQL))
-            .json(&body)
-            .send()
-            .await
-            .ok()?;
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Home)
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .ok()?;
 
-        let data: GraphQLResponse<UserStatusData> = resp.json().await.ok()?;
+        let data: GraphQLResponse<UserStatusData> = resp.json_logged().await.ok()?;
         let status = data.data?.user_status?;
         if status.is_signed_in {
             status.username
@@ -276,25 +790,54 @@ impl LeetCodeClient {
         }
     }
 
-    pub async fn fetch_user_stats(&self, username: &str) -> Result<UserStats> {
+    async fn check_login(&self) -> Result<String> {
+        let body = json!({
+            "query": GLOBAL_DATA_QUERY,
+            "variables": {}
+        });
+
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Home)
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to check login")?;
+
+        let data: GraphQLResponse<UserStatusData> = resp
+            .json_logged()
+            .await
+            .context("Failed to parse login status")?;
+
+        match data.extract(|d| d.user_status, "No login status in response")? {
+            UserStatus {
+                is_signed_in: true,
+                username: Some(name),
+            } => Ok(name),
+            _ => bail!("Not signed in, the session cookie was not accepted"),
+        }
+    }
+
+    async fn fetch_user_stats(&self, username: &str) -> Result<UserStats> {
         let body = json!({
             "query": USER_PROFILE_QUERY,
             "variables": { "username": username }
         });
 
-        let resp = self
-            .auth_request(self.client.post(LEETCODE_GRAPHQL))
-            .json(&body)
-            .send()
-            .await
-            .context("Failed to send user profile request")?;
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Home)
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to send user profile request")?;
 
         let data: GraphQLResponse<UserProfileData> = resp
-            .json()
+            .json_logged()
             .await
             .context("Failed to parse user profile response")?;
 
-        let profile = data.data.context("No profile data")?;
+        let profile = data.extract(Some, "No profile data")?;
 
         let solved = profile
             .matched_user
@@ -305,7 +848,11 @@ impl LeetCodeClient {
         let totals = profile.all_questions_count.unwrap_or_default();
 
         fn find_count(items: &[DifficultyCount], diff: &str) -> i32 {
-            items.iter().find(|d| d.difficulty == diff).map(|d| d.count).unwrap_or(0)
+            items
+                .iter()
+                .find(|d| d.difficulty == diff)
+                .map(|d| d.count)
+                .unwrap_or(0)
         }
 
         Ok(UserStats {
@@ -319,40 +866,196 @@ impl LeetCodeClient {
         })
     }
 
-    pub async fn fetch_favorites(&self) -> Result<Vec<FavoriteList>> {
+    async fn fetch_contests(&self) -> Result<Vec<ContestSummary>> {
         let body = json!({
-            "query": FAVORITES_LIST_QUERY,
+            "query": CONTESTS_QUERY,
             "variables": {}
         });
 
-        let resp = self
-            .auth_request(self.client.post(LEETCODE_GRAPHQL))
-            .json(&body)
-            .send()
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Home)
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to fetch contests")?;
+
+        let data: GraphQLResponse<ContestsData> = resp
+            .json_logged()
             .await
-            .context("Failed to fetch favorites")?;
+            .context("Failed to parse contests response")?;
 
-        let data: GraphQLResponse<FavoritesListData> = resp
-            .json()
+        let data = data.extract(Some, "No contests in response")?;
+        // The full list can lag behind the upcoming one
+        let mut contests = data.contest_upcoming_contests.unwrap_or_default();
+        for contest in data.all_contests.unwrap_or_default() {
+            if !contests.iter().any(|c| c.title_slug == contest.title_slug) {
+                contests.push(contest);
+            }
+        }
+        contests.sort_by_key(|c| std::cmp::Reverse(c.start_time));
+        Ok(contests)
+    }
+
+    async fn fetch_contest(&self, slug: &str) -> Result<(Contest, Vec<ContestQuestion>)> {
+        let path = CONTEST_INFO_PATH.replace("{slug}", slug);
+        let resp = classify(
+            self.request(Method::GET, &path, Page::Contest(slug))
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to fetch contest")?;
+
+        if resp.status() == StatusCode::NOT_FOUND {
+            bail!("No contest named '{slug}'");
+        }
+        let info: ContestInfo = resp
+            .json_logged()
             .await
-            .context("Failed to parse favorites response")?;
+            .context("Failed to parse contest response")?;
+        let contest = info
+            .contest
+            .with_context(|| format!("No contest named '{slug}'"))?;
 
-        let lists = data
-            .data
-            .and_then(|d| d.favorites_lists)
-            .map(|f| f.all_favorites)
-            .unwrap_or_default();
+        if info.questions.is_empty() {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs() as i64);
+            let wait = contest.start_time - now;
+            if wait > 0 {
+                bail!(
+                    "{} hasn't started yet, it starts in {}h {:02}m",
+                    contest.title,
+                    wait / 3600,
+                    wait % 3600 / 60
+                );
+            }
+            if info.contains_premium {
+                bail!("{} is only open to Premium members", contest.title);
+            }
+            bail!("{} has no problems listed", contest.title);
+        }
 
-        Ok(lists)
+        let mut questions = info.questions;
+        questions.sort_by_key(|q| q.credit);
+        Ok((contest, questions))
     }
 
-    pub async fn create_favorite_list(&self, name: &str) -> Result<()> {
-        let resp = self
-            .auth_request(self.client.post(LEETCODE_LIST_API))
-            .json(&json!({ "name": name }))
-            .send()
+    async fn fetch_company_tags(&self) -> Result<Vec<CompanyTag>> {
+        let body = json!({
+            "query": COMPANY_TAGS_QUERY,
+            "variables": {}
+        });
+
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Home)
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to fetch companies")?;
+
+        let data: GraphQLResponse<CompanyTagsData> = resp
+            .json_logged()
             .await
-            .context("Failed to create list")?;
+            .context("Failed to parse companies response")?;
+
+        if premium_refusal(&data.errors) {
+            bail!(PREMIUM_REQUIRED);
+        }
+        let mut tags = data.extract(|d| d.company_tags, "No companies in response")?;
+        tags.sort_by(|a, b| {
+            b.question_count
+                .cmp(&a.question_count)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(tags)
+    }
+
+    async fn fetch_company_problems(&self, slug: &str) -> Result<(String, Vec<CompanyProblem>)> {
+        let body = json!({
+            "query": COMPANY_TAG_QUERY,
+            "variables": { "slug": slug }
+        });
+
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Home)
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to fetch company problems")?;
+
+        let data: GraphQLResponse<CompanyTagData> = resp
+            .json_logged()
+            .await
+            .context("Failed to parse company problems response")?;
+
+        if premium_refusal(&data.errors) {
+            bail!(PREMIUM_REQUIRED);
+        }
+        let tag = data.extract(|d| d.company_tag, &format!("No company named '{slug}'"))?;
+        // Without Premium the problems come back, but not how often they're asked
+        let Some(frequencies) = tag.frequencies.filter(|f| !matches!(f.trim(), "" | "null")) else {
+            bail!(PREMIUM_REQUIRED);
+        };
+        let frequencies: HashMap<String, Vec<Option<f64>>> =
+            serde_json::from_str(&frequencies).context("Failed to parse company frequencies")?;
+
+        let problems = tag
+            .questions
+            .into_iter()
+            .map(|q| CompanyProblem {
+                frequency: frequencies
+                    .get(&q.question_id)
+                    .map(|f| f.iter().map(|n| n.unwrap_or(0.0)).collect())
+                    .unwrap_or_default(),
+                problem: q.summary,
+            })
+            .collect();
+        Ok((tag.name, problems))
+    }
+
+    async fn fetch_favorites(&self) -> Result<Vec<FavoriteList>> {
+        let body = json!({
+            "query": FAVORITES_LIST_QUERY,
+            "variables": {}
+        });
+
+        let resp = classify(
+            self.request(Method::POST, GRAPHQL_PATH, Page::Home)
+                .json(&body)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to fetch favorites")?;
+
+        let data: GraphQLResponse<FavoritesListData> = resp
+            .json_logged()
+            .await
+            .context("Failed to parse favorites response")?;
+
+        let lists = data.extract(|d| d.favorites_lists, "No lists in response")?;
+        Ok(lists.all_favorites)
+    }
+
+    async fn create_favorite_list(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        public: bool,
+    ) -> Result<()> {
+        let resp = classify(
+            self.request(Method::POST, LIST_API_PATH, Page::Home)
+                .json(&json!({
+                    "name": name,
+                    "description": description.unwrap_or_default(),
+                    "is_public_favorite": public,
+                }))
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to create list")?;
 
         let status = resp.status();
         if !status.is_success() {
@@ -361,13 +1064,33 @@ impl LeetCodeClient {
         Ok(())
     }
 
-    pub async fn delete_favorite_list(&self, id_hash: &str) -> Result<()> {
-        let url = format!("{}{}", LEETCODE_LIST_API, id_hash);
-        let resp = self
-            .auth_request(self.client.delete(&url))
-            .send()
-            .await
-            .context("Failed to delete list")?;
+    async fn rename_favorite_list(&self, id_hash: &str, name: &str) -> Result<()> {
+        let resp = classify(
+            self.request(Method::PUT, LIST_API_PATH, Page::Home)
+                .json(&json!({
+                    "favorite_id_hash": id_hash,
+                    "name": name,
+                }))
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to rename list")?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            bail!("Failed to rename list: HTTP {status}");
+        }
+        Ok(())
+    }
+
+    async fn delete_favorite_list(&self, id_hash: &str) -> Result<()> {
+        let path = format!("{LIST_API_PATH}{id_hash}");
+        let resp = classify(
+            self.request(Method::DELETE, &path, Page::Home)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to delete list")?;
 
         let status = resp.status();
         if !status.is_success() {
@@ -376,16 +1099,17 @@ impl LeetCodeClient {
         Ok(())
     }
 
-    pub async fn add_to_favorite(&self, id_hash: &str, question_id: &str) -> Result<()> {
-        let resp = self
-            .auth_request(self.client.post(LEETCODE_LIST_QUESTIONS_API))
-            .json(&json!({
-                "favorite_id_hash": id_hash,
-                "question_id": question_id,
-            }))
-            .send()
-            .await
-            .context("Failed to add to list")?;
+    async fn add_to_favorite(&self, id_hash: &str, question_id: &str) -> Result<()> {
+        let resp = classify(
+            self.request(Method::POST, LIST_QUESTIONS_API_PATH, Page::Home)
+                .json(&json!({
+                    "favorite_id_hash": id_hash,
+                    "question_id": question_id,
+                }))
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to add to list")?;
 
         let status = resp.status();
         if !status.is_success() {
@@ -394,13 +1118,29 @@ impl LeetCodeClient {
         Ok(())
     }
 
-    pub async fn remove_from_favorite(&self, id_hash: &str, question_id: &str) -> Result<()> {
-        let url = format!("{}/{}/{}", LEETCODE_LIST_QUESTIONS_API, id_hash, question_id);
-        let resp = self
-            .auth_request(self.client.delete(&url))
-            .send()
-            .await
-            .context("Failed to remove from list")?;
+    async fn toggle_star(&self, question_id: &str, slug: &str) -> Result<bool> {
+        let lists = self.fetch_favorites().await?;
+        let Some(default) = lists.iter().find(|l| l.name == DEFAULT_FAVORITE_LIST) else {
+            bail!("No \"{DEFAULT_FAVORITE_LIST}\" list on this account");
+        };
+        if default.questions.iter().any(|q| q.title_slug == slug) {
+            self.remove_from_favorite(&default.id_hash, question_id)
+                .await?;
+            Ok(false)
+        } else {
+            self.add_to_favorite(&default.id_hash, question_id).await?;
+            Ok(true)
+        }
+    }
+
+    async fn remove_from_favorite(&self, id_hash: &str, question_id: &str) -> Result<()> {
+        let path = format!("{LIST_QUESTIONS_API_PATH}/{id_hash}/{question_id}");
+        let resp = classify(
+            self.request(Method::DELETE, &path, Page::Home)
+                .send_via(&*self.transport)
+                .await,
+        )
+        .context("Failed to remove from list")?;
 
         let status = resp.status();
         if !status.is_success() {
@@ -409,3 +1149,95 @@ impl LeetCodeClient {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::transport::Canned;
+
+    /// A client whose requests go to `canned` instead of the network.
+    fn client(session: Option<&str>, csrf: Option<&str>, canned: &Arc<Canned>) -> LeetCodeClient {
+        let mut client = LeetCodeClient::new(LEETCODE_SITE, session, csrf).unwrap();
+        client.transport = canned.clone();
+        client
+    }
+
+    #[tokio::test]
+    async fn submit_sends_cookies_referer_and_csrf_token() {
+        let canned = Arc::new(Canned::new(r#"{"submission_id": 7}"#));
+        let leetcode = client(
+            Some("header-test-session"),
+            Some("header-test-csrf"),
+            &canned,
+        );
+        let id = leetcode
+            .submit_code("two-sum", "1", "rust", "fn main() {}")
+            .await
+            .unwrap();
+        assert_eq!(id, "7");
+
+        let sent = canned.sent.lock().unwrap();
+        let headers = sent[0].headers();
+        assert_eq!(headers["Referer"], "https://leetcode.com/problems/two-sum/");
+        assert_eq!(headers["x-csrftoken"], "header-test-csrf");
+        assert_eq!(
+            headers["Cookie"],
+            "LEETCODE_SESSION=header-test-session; csrftoken=header-test-csrf"
+        );
+    }
+
+    #[tokio::test]
+    async fn anonymous_requests_carry_no_credentials() {
+        let canned = Arc::new(Canned::new(r#"{"data":{"userStatus":{"username":""}}}"#));
+        let leetcode = client(None, Some(""), &canned);
+        let _ = leetcode.fetch_username().await;
+
+        let sent = canned.sent.lock().unwrap();
+        let headers = sent[0].headers();
+        assert_eq!(headers["Referer"], "https://leetcode.com/");
+        assert!(!headers.contains_key("x-csrftoken"));
+        assert!(!headers.contains_key("Cookie"));
+    }
+
+    #[tokio::test]
+    async fn requests_go_to_the_configured_site() {
+        let canned = Arc::new(Canned::new(r#"{"submission_id": 7}"#));
+        let mut leetcode = LeetCodeClient::new("https://leetcode.cn/", None, None).unwrap();
+        leetcode.transport = canned.clone();
+        leetcode
+            .submit_code("two-sum", "1", "rust", "fn main() {}")
+            .await
+            .unwrap();
+
+        let sent = canned.sent.lock().unwrap();
+        assert_eq!(
+            sent[0].url().as_str(),
+            "https://leetcode.cn/problems/two-sum/submit/"
+        );
+        let headers = sent[0].headers();
+        assert_eq!(headers["Origin"], "https://leetcode.cn");
+        assert_eq!(headers["Referer"], "https://leetcode.cn/problems/two-sum/");
+    }
+
+    #[tokio::test]
+    async fn graphql_reads_report_rate_limits() {
+        let throttled = Arc::new(Canned {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            ..Canned::new("")
+        });
+        let err = client(None, None, &throttled)
+            .fetch_problem_detail("two-sum")
+            .await
+            .unwrap_err();
+        assert_eq!(rate_limit_wait(&err), Some(DEFAULT_COOLDOWN));
+
+        let refused = Arc::new(Canned::new(
+            r#"{"errors":[{"message":"Too many requests"}]}"#,
+        ));
+        let err = client(None, None, &refused)
+            .fetch_daily_slug()
+            .await
+            .unwrap_err();
+        assert_eq!(rate_limit_wait(&err), Some(DEFAULT_COOLDOWN));
+    }
+}
@@ -0,0 +1,270 @@
+use anyhow::{Result, anyhow, bail};
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+use super::client::LeetCodeApi;
+use super::types::{
+    CheckResponse, CommunitySolution, CompanyProblem, CompanyTag, Contest, ContestQuestion,
+    ContestSummary, FavoriteList, FetchFilter, OfficialSolution, ProblemSummary, QuestionDetail,
+    UserStats,
+};
+
+/// A `LeetCodeApi` that answers from scripted data, so tests can drive
+/// `App` without a network or an account.
+#[derive(Default)]
+pub struct MockClient {
+    pub problems: Vec<ProblemSummary>,
+    pub details: Vec<QuestionDetail>,
+    /// What `poll_result` returns for every run and submission
+    pub check: CheckResponse,
+    pub favorites: Vec<FavoriteList>,
+    /// Fail every call with this message instead
+    pub error: Option<String>,
+    /// Methods called so far, in order
+    pub calls: Mutex<Vec<&'static str>>,
+}
+
+impl MockClient {
+    fn call(&self, name: &'static str) -> Result<()> {
+        self.calls.lock().unwrap().push(name);
+        match self.error {
+            Some(ref e) => bail!("{e}"),
+            None => Ok(()),
+        }
+    }
+
+    pub fn calls(&self) -> Vec<&'static str> {
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+/// A problem list row for `slug`, numbered `id`.
+pub fn problem(id: &str, slug: &str) -> ProblemSummary {
+    ProblemSummary {
+        frontend_question_id: id.to_string(),
+        title: slug.replace('-', " "),
+        title_slug: slug.to_string(),
+        difficulty: "Easy".to_string(),
+        status: None,
+        ac_rate: 50.0,
+        is_paid_only: false,
+        is_favor: false,
+        topic_tags: Vec::new(),
+    }
+}
+
+/// A problem's detail, with a Rust snippet and one example case.
+pub fn detail(id: &str, slug: &str) -> QuestionDetail {
+    serde_json::from_value(serde_json::json!({
+        "questionId": id,
+        "frontendQuestionId": id,
+        "title": slug.replace('-', " "),
+        "titleSlug": slug,
+        "difficulty": "Easy",
+        "content": "<p>Return the answer.</p>",
+        "isPaidOnly": false,
+        "topicTags": [],
+        "codeSnippets": [{
+            "lang": "Rust",
+            "langSlug": "rust",
+            "code": "impl Solution {\n    pub fn f() -> i32 {\n        \n    }\n}",
+        }],
+        "exampleTestcaseList": ["1"],
+        "sampleTestCase": "1",
+        "hints": [],
+        "status": null,
+    }))
+    .expect("valid QuestionDetail")
+}
+
+/// A verdict with LeetCode's status code and message.
+pub fn verdict(status_code: i32, status_msg: &str) -> CheckResponse {
+    CheckResponse {
+        state: "SUCCESS".to_string(),
+        status_code: Some(status_code),
+        status_msg: Some(status_msg.to_string()),
+        ..CheckResponse::default()
+    }
+}
+
+#[async_trait]
+impl LeetCodeApi for MockClient {
+    async fn fetch_problems(
+        &self,
+        limit: i32,
+        skip: i32,
+        _filter: &FetchFilter,
+        search_keywords: Option<&str>,
+    ) -> Result<(Vec<ProblemSummary>, i32)> {
+        self.call("fetch_problems")?;
+        let matching: Vec<ProblemSummary> = self
+            .problems
+            .iter()
+            .filter(|p| {
+                search_keywords.is_none_or(|k| {
+                    p.frontend_question_id == k || p.title.contains(k) || p.title_slug.contains(k)
+                })
+            })
+            .cloned()
+            .collect();
+        let total = matching.len() as i32;
+        let page = matching
+            .into_iter()
+            .skip(skip as usize)
+            .take(limit as usize)
+            .collect();
+        Ok((page, total))
+    }
+
+    async fn fetch_problem_detail(&self, slug: &str) -> Result<QuestionDetail> {
+        self.call("fetch_problem_detail")?;
+        self.details
+            .iter()
+            .find(|d| d.title_slug == slug)
+            .cloned()
+            .ok_or_else(|| anyhow!("Problem '{slug}' not found"))
+    }
+
+    async fn fetch_editorial(&self, _slug: &str) -> Result<Option<OfficialSolution>> {
+        self.call("fetch_editorial")?;
+        Ok(None)
+    }
+
+    async fn fetch_solutions(
+        &self,
+        _slug: &str,
+        _skip: u32,
+        _first: u32,
+    ) -> Result<(Vec<CommunitySolution>, u32)> {
+        self.call("fetch_solutions")?;
+        Ok((Vec::new(), 0))
+    }
+
+    async fn fetch_solution_post(&self, _slug: &str, _topic_id: &str) -> Result<String> {
+        self.call("fetch_solution_post")?;
+        Ok(String::new())
+    }
+
+    async fn fetch_random_slug(
+        &self,
+        _difficulty: Option<&str>,
+        _tags: &[String],
+    ) -> Result<String> {
+        self.call("fetch_random_slug")?;
+        self.problems
+            .first()
+            .map(|p| p.title_slug.clone())
+            .ok_or_else(|| anyhow!("No problems"))
+    }
+
+    async fn fetch_daily_slug(&self) -> Result<String> {
+        self.call("fetch_daily_slug")?;
+        self.fetch_random_slug(None, &[]).await
+    }
+
+    async fn run_code(
+        &self,
+        _slug: &str,
+        _question_id: &str,
+        _lang: &str,
+        _typed_code: &str,
+        _data_input: &str,
+    ) -> Result<String> {
+        self.call("run_code")?;
+        Ok("run-1".to_string())
+    }
+
+    async fn submit_code(
+        &self,
+        _slug: &str,
+        _question_id: &str,
+        _lang: &str,
+        _typed_code: &str,
+    ) -> Result<String> {
+        self.call("submit_code")?;
+        Ok("1".to_string())
+    }
+
+    async fn poll_result(&self, _slug: &str, _id: &str) -> Result<CheckResponse> {
+        self.call("poll_result")?;
+        Ok(self.check.clone())
+    }
+
+    async fn fetch_username(&self) -> Option<String> {
+        self.call("fetch_username").ok()?;
+        Some("tester".to_string())
+    }
+
+    async fn check_login(&self) -> Result<String> {
+        self.call("check_login")?;
+        Ok("tester".to_string())
+    }
+
+    async fn fetch_user_stats(&self, username: &str) -> Result<UserStats> {
+        self.call("fetch_user_stats")?;
+        Ok(UserStats {
+            username: username.to_string(),
+            easy_solved: 0,
+            easy_total: 0,
+            medium_solved: 0,
+            medium_total: 0,
+            hard_solved: 0,
+            hard_total: 0,
+        })
+    }
+
+    async fn fetch_contests(&self) -> Result<Vec<ContestSummary>> {
+        self.call("fetch_contests")?;
+        Ok(Vec::new())
+    }
+
+    async fn fetch_contest(&self, slug: &str) -> Result<(Contest, Vec<ContestQuestion>)> {
+        self.call("fetch_contest")?;
+        bail!("Contest '{slug}' not found")
+    }
+
+    async fn fetch_company_tags(&self) -> Result<Vec<CompanyTag>> {
+        self.call("fetch_company_tags")?;
+        Ok(Vec::new())
+    }
+
+    async fn fetch_company_problems(&self, slug: &str) -> Result<(String, Vec<CompanyProblem>)> {
+        self.call("fetch_company_problems")?;
+        Ok((slug.to_string(), Vec::new()))
+    }
+
+    async fn fetch_favorites(&self) -> Result<Vec<FavoriteList>> {
+        self.call("fetch_favorites")?;
+        Ok(self.favorites.clone())
+    }
+
+    async fn create_favorite_list(
+        &self,
+        _name: &str,
+        _description: Option<&str>,
+        _public: bool,
+    ) -> Result<()> {
+        self.call("create_favorite_list")
+    }
+
+    async fn rename_favorite_list(&self, _id_hash: &str, _name: &str) -> Result<()> {
+        self.call("rename_favorite_list")
+    }
+
+    async fn delete_favorite_list(&self, _id_hash: &str) -> Result<()> {
+        self.call("delete_favorite_list")
+    }
+
+    async fn add_to_favorite(&self, _id_hash: &str, _question_id: &str) -> Result<()> {
+        self.call("add_to_favorite")
+    }
+
+    async fn toggle_star(&self, _question_id: &str, _slug: &str) -> Result<bool> {
+        self.call("toggle_star")?;
+        Ok(true)
+    }
+
+    async fn remove_from_favorite(&self, _id_hash: &str, _question_id: &str) -> Result<()> {
+        self.call("remove_from_favorite")
+    }
+}
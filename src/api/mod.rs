@@ -1,3 +1,6 @@
 pub mod client;
+#[cfg(test)]
+pub mod mock;
 pub mod queries;
+pub mod transport;
 pub mod types;
@@ -15,6 +15,7 @@ query problemsetQuestionList($categorySlug: String, $limit: Int, $skip: Int, $fi
       status
       acRate
       isPaidOnly
+      isFavor
       topicTags {
         name
         slug
@@ -47,6 +48,7 @@ query questionDetail($titleSlug: String!) {
     sampleTestCase
     hints
     status
+    similarQuestions
   }
 }
 "#;
@@ -66,10 +68,6 @@ query favoritesList {
     allFavorites {
       idHash
       name
-      description
-      viewCount
-      creator
-      isWatched
       isPublicFavorite
       questions {
         questionId
@@ -98,3 +96,128 @@ query getUserProfile($username: String!) {
   }
 }
 "#;
+
+pub const OFFICIAL_SOLUTION_QUERY: &str = r#"
+query officialSolution($titleSlug: String!) {
+  question(titleSlug: $titleSlug) {
+    solution {
+      id
+      content
+      paidOnly
+      canSeeDetail
+    }
+  }
+}
+"#;
+
+/// Where newer problems keep their editorial; `question.solution` is null
+/// for them.
+pub const UGC_OFFICIAL_SOLUTION_QUERY: &str = r#"
+query ugcArticleOfficialSolutionArticle($questionSlug: String!) {
+  ugcArticleOfficialSolutionArticle(questionSlug: $questionSlug) {
+    content
+  }
+}
+"#;
+
+pub const COMMUNITY_SOLUTIONS_QUERY: &str = r#"
+query communitySolutions($questionSlug: String!, $skip: Int!, $first: Int!, $orderBy: TopicSortingOption) {
+  questionSolutions(
+    filters: {questionSlug: $questionSlug, skip: $skip, first: $first, orderBy: $orderBy}
+  ) {
+    totalNum
+    solutions {
+      id
+      title
+      solutionTags {
+        name
+        slug
+      }
+      post {
+        voteCount
+        author {
+          username
+        }
+      }
+    }
+  }
+}
+"#;
+
+pub const SOLUTION_POST_QUERY: &str = r#"
+query communitySolution($topicId: Int!) {
+  topic(id: $topicId) {
+    post {
+      content
+    }
+  }
+}
+"#;
+
+pub const DAILY_QUESTION_QUERY: &str = r#"
+query questionOfToday {
+  activeDailyCodingChallengeQuestion {
+    question {
+      titleSlug
+    }
+  }
+}
+"#;
+
+pub const RANDOM_QUESTION_QUERY: &str = r#"
+query randomQuestion($categorySlug: String, $filters: QuestionListFilterInput) {
+  randomQuestion(categorySlug: $categorySlug, filters: $filters) {
+    titleSlug
+  }
+}
+"#;
+
+pub const COMPANY_TAGS_QUERY: &str = r#"
+query companyTags {
+  companyTags {
+    name
+    slug
+    questionCount
+  }
+}
+"#;
+
+pub const COMPANY_TAG_QUERY: &str = r#"
+query companyTag($slug: String!) {
+  companyTag(slug: $slug) {
+    name
+    frequencies
+    questions {
+      questionId
+      frontendQuestionId: questionFrontendId
+      title
+      titleSlug
+      difficulty
+      status
+      acRate
+      isPaidOnly
+      topicTags {
+        name
+        slug
+      }
+    }
+  }
+}
+"#;
+
+pub const CONTESTS_QUERY: &str = r#"
+query contests {
+  contestUpcomingContests {
+    title
+    titleSlug
+    startTime
+    duration
+  }
+  allContests {
+    title
+    titleSlug
+    startTime
+    duration
+  }
+}
+"#;
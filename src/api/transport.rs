@@ -0,0 +1,229 @@
+use futures::future::BoxFuture;
+use reqwest::{Client, Request, Response, ResponseBuilderExt, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::logging;
+
+/// Writes every response to this directory as it arrives.
+const RECORD_ENV: &str = "LEETUI_RECORD";
+/// Answers every request from responses recorded there, without the network.
+const REPLAY_ENV: &str = "LEETUI_REPLAY";
+
+/// Where `LeetCodeClient` sends its requests: the network, or recorded
+/// responses standing in for it.
+pub trait Transport: Send + Sync {
+    fn execute(&self, request: Request) -> BoxFuture<'_, reqwest::Result<Response>>;
+}
+
+impl Transport for Client {
+    fn execute(&self, request: Request) -> BoxFuture<'_, reqwest::Result<Response>> {
+        Box::pin(Client::execute(self, request))
+    }
+}
+
+/// The network, unless `LEETUI_REPLAY` or `LEETUI_RECORD` names a cassette
+/// directory.
+pub fn from_env(client: Client) -> Box<dyn Transport> {
+    let dir = |var| {
+        std::env::var_os(var)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    };
+    if let Some(dir) = dir(REPLAY_ENV) {
+        Box::new(Replayer { dir })
+    } else if let Some(dir) = dir(RECORD_ENV) {
+        Box::new(Recorder {
+            inner: Box::new(client),
+            dir,
+        })
+    } else {
+        Box::new(client)
+    }
+}
+
+/// One recorded exchange. The request is kept so cassettes can be read.
+#[derive(Serialize, Deserialize)]
+struct Cassette {
+    method: String,
+    url: String,
+    request: String,
+    status: u16,
+    body: String,
+}
+
+/// Same method, URL and body, same file. A check polled until it finishes
+/// keeps only its final answer, which is what a replay wants.
+fn cassette_path(dir: &std::path::Path, request: &Request) -> PathBuf {
+    let body = request_body(request);
+    // FNV-1a, so names stay the same across builds
+    let hash = format!("{} {} {body}", request.method(), request.url())
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |h, b| {
+            (h ^ b as u64).wrapping_mul(0x100000001b3)
+        });
+    let name: String = request
+        .url()
+        .path()
+        .trim_matches('/')
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    dir.join(format!("{name}-{hash:016x}.json"))
+}
+
+fn request_body(request: &Request) -> String {
+    request
+        .body()
+        .and_then(|b| b.as_bytes())
+        .map(|b| String::from_utf8_lossy(b).into_owned())
+        .unwrap_or_default()
+}
+
+fn response(url: Url, status: StatusCode, body: String) -> Response {
+    http::Response::builder()
+        .status(status)
+        .url(url)
+        .body(body)
+        .map(Response::from)
+        .unwrap_or_else(|_| http::Response::new(String::new()).into())
+}
+
+/// Sends through `inner`, writing each response down on the way back.
+struct Recorder {
+    inner: Box<dyn Transport>,
+    dir: PathBuf,
+}
+
+impl Transport for Recorder {
+    fn execute(&self, request: Request) -> BoxFuture<'_, reqwest::Result<Response>> {
+        Box::pin(async move {
+            let path = cassette_path(&self.dir, &request);
+            let (method, url) = (request.method().to_string(), request.url().clone());
+            let sent = logging::scrub(&request_body(&request));
+            let resp = self.inner.execute(request).await?;
+            let status = resp.status();
+            let body = resp.text().await?;
+
+            let cassette = Cassette {
+                method,
+                url: url.to_string(),
+                request: sent,
+                status: status.as_u16(),
+                body: logging::scrub(&body),
+            };
+            let written = std::fs::create_dir_all(&self.dir).and_then(|()| {
+                let json =
+                    serde_json::to_string_pretty(&cassette).map_err(std::io::Error::other)?;
+                std::fs::write(&path, json)
+            });
+            if let Err(e) = written {
+                tracing::warn!(path = %path.display(), error = %e, "failed to record response");
+            }
+            Ok(response(url, status, body))
+        })
+    }
+}
+
+struct Replayer {
+    dir: PathBuf,
+}
+
+impl Transport for Replayer {
+    fn execute(&self, request: Request) -> BoxFuture<'_, reqwest::Result<Response>> {
+        let path = cassette_path(&self.dir, &request);
+        let cassette = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<Cassette>(&json).ok());
+        let url = request.url().clone();
+        Box::pin(async move {
+            Ok(match cassette {
+                Some(c) => {
+                    let status = StatusCode::from_u16(c.status).unwrap_or(StatusCode::OK);
+                    response(url, status, c.body)
+                }
+                None => {
+                    tracing::warn!(%url, path = %path.display(), "no recorded response");
+                    response(url, StatusCode::NOT_FOUND, String::new())
+                }
+            })
+        })
+    }
+}
+
+/// Answers every request with `status` and `body`, keeping the requests it
+/// was sent.
+#[cfg(test)]
+pub struct Canned {
+    pub status: StatusCode,
+    pub body: &'static str,
+    pub sent: std::sync::Mutex<Vec<Request>>,
+}
+
+#[cfg(test)]
+impl Canned {
+    pub fn new(body: &'static str) -> Self {
+        Self {
+            status: StatusCode::OK,
+            body,
+            sent: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Transport for Canned {
+    fn execute(&self, request: Request) -> BoxFuture<'_, reqwest::Result<Response>> {
+        let url = request.url().clone();
+        self.sent.lock().unwrap().push(request);
+        Box::pin(async move { Ok(response(url, self.status, self.body.to_string())) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graphql(body: &str) -> Request {
+        Client::new()
+            .post("https://leetcode.com/graphql/")
+            .body(body.to_string())
+            .build()
+            .unwrap()
+    }
+
+    async fn body(transport: &dyn Transport, request: Request) -> (StatusCode, String) {
+        let resp = transport.execute(request).await.unwrap();
+        (resp.status(), resp.text().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn replay_answers_what_was_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let answer = r#"{"data":{"question":{"titleSlug":"two-sum"}}}"#;
+        let recorder = Recorder {
+            inner: Box::new(Canned::new(answer)),
+            dir: dir.path().to_path_buf(),
+        };
+        let query = r#"{"query":"questionData","variables":{"titleSlug":"two-sum"}}"#;
+        assert_eq!(
+            body(&recorder, graphql(query)).await,
+            (StatusCode::OK, answer.to_string())
+        );
+
+        let replayer = Replayer {
+            dir: dir.path().to_path_buf(),
+        };
+        assert_eq!(
+            body(&replayer, graphql(query)).await,
+            (StatusCode::OK, answer.to_string())
+        );
+
+        // Another body is another cassette, and none was recorded for it
+        let other = r#"{"query":"questionData","variables":{"titleSlug":"3sum"}}"#;
+        assert_eq!(
+            body(&replayer, graphql(other)).await,
+            (StatusCode::NOT_FOUND, String::new())
+        );
+    }
+}
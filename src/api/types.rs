@@ -18,12 +18,96 @@ where
     }
 }
 
+/// An id sent as a number by some endpoints and a string by others.
+fn deserialize_id<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::String(s) => Ok(s),
+        Value::Number(n) => Ok(n.to_string()),
+        other => Err(serde::de::Error::custom(format!("unexpected id {other}"))),
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GraphQLResponse<T> {
     pub data: Option<T>,
+    /// Why the query failed, e.g. a rate limit or a Premium-only problem
+    #[serde(default)]
+    pub errors: Vec<GraphQLError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQLError {
+    pub message: String,
 }
 
 // Problem list types
+
+/// Narrows the problem list on LeetCode's side, so less of it is
+/// downloaded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchFilter {
+    /// `EASY`, `MEDIUM` or `HARD`
+    pub difficulty: Option<String>,
+    /// Topic tag slugs; a problem needs all of them
+    pub tags: Vec<String>,
+    /// A favorite list's id hash
+    pub list: Option<String>,
+}
+
+impl FetchFilter {
+    pub fn is_empty(&self) -> bool {
+        self.difficulty.is_none() && self.tags.is_empty() && self.list.is_none()
+    }
+
+    /// What the cache file for this filter is named after: nothing for the
+    /// whole list, and just the difficulty when that's all there is.
+    pub fn cache_key(&self) -> Option<String> {
+        let mut tags = self.tags.clone();
+        tags.sort();
+        let parts: Vec<String> = self
+            .difficulty
+            .iter()
+            .map(|d| d.to_lowercase())
+            .chain(tags)
+            .chain(self.list.iter().map(|id| format!("list-{id}")))
+            .map(|part| {
+                part.chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || c == '-' {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        (!parts.is_empty()).then(|| parts.join("_"))
+    }
+
+    /// `Medium, dynamic-programming` for the title bar.
+    pub fn describe(&self) -> String {
+        let difficulty = self.difficulty.as_deref().map(|d| {
+            let mut chars = d.chars();
+            chars.next().map_or(String::new(), |first| {
+                first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect()
+            })
+        });
+        difficulty
+            .into_iter()
+            .chain(self.tags.iter().cloned())
+            .chain(self.list.iter().map(|id| format!("list {id}")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProblemListData {
@@ -47,6 +131,9 @@ pub struct ProblemSummary {
     pub status: Option<String>,
     pub ac_rate: f64,
     pub is_paid_only: bool,
+    /// In the account's default "Favorite" list (starred)
+    #[serde(default)]
+    pub is_favor: bool,
     pub topic_tags: Vec<TopicTag>,
 }
 
@@ -62,7 +149,7 @@ pub struct QuestionDetailData {
     pub question: Option<QuestionDetail>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QuestionDetail {
     pub question_id: String,
@@ -78,10 +165,57 @@ pub struct QuestionDetail {
     pub sample_test_case: Option<String>,
     pub hints: Vec<String>,
     pub status: Option<String>,
+    /// JSON-encoded list of related problems; see `similar()`
+    #[serde(default)]
+    pub similar_questions: Option<String>,
+}
+
+impl QuestionDetail {
+    /// The problem list row for this problem. The detail query has no
+    /// acceptance rate, so it reads 0.
+    pub fn summary(&self) -> ProblemSummary {
+        ProblemSummary {
+            frontend_question_id: self.frontend_question_id.clone(),
+            title: self.title.clone(),
+            title_slug: self.title_slug.clone(),
+            difficulty: self.difficulty.clone(),
+            status: self.status.clone(),
+            ac_rate: 0.0,
+            is_paid_only: self.is_paid_only,
+            is_favor: false,
+            topic_tags: self.topic_tags.clone(),
+        }
+    }
+
+    /// The example test cases, each one line per argument, as a run sends
+    /// them. Falls back to the single sample case.
+    pub fn example_cases(&self) -> Vec<String> {
+        match &self.example_testcase_list {
+            Some(cases) if !cases.is_empty() => cases.clone(),
+            _ => self.sample_test_case.iter().cloned().collect(),
+        }
+    }
+
+    /// Related problems from `similarQuestions`. A missing or malformed
+    /// string just means there are none.
+    pub fn similar(&self) -> Vec<SimilarQuestion> {
+        self.similar_questions
+            .as_deref()
+            .and_then(|s| serde_json::from_str(s).ok())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct SimilarQuestion {
+    pub title: String,
+    pub title_slug: String,
+    pub difficulty: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CodeSnippet {
     pub lang: String,
     pub lang_slug: String,
@@ -92,8 +226,6 @@ pub struct CodeSnippet {
 #[derive(Debug, Deserialize)]
 pub struct InterpretResponse {
     pub interpret_id: Option<String>,
-    pub interpret_expected_id: Option<String>,
-    pub test_case: Option<String>,
     pub error: Option<String>,
 }
 
@@ -117,6 +249,13 @@ pub struct CheckResponse {
     pub code_output: Option<Vec<String>>,
     pub expected_output: Option<String>,
     pub last_testcase: Option<String>,
+    /// `last_testcase` with parameter names, as the website shows it
+    #[serde(default)]
+    pub input_formatted: Option<String>,
+    #[serde(default)]
+    pub runtime_error: Option<String>,
+    #[serde(default)]
+    pub full_runtime_error: Option<String>,
     pub total_correct: Option<i32>,
     pub total_testcases: Option<i32>,
     pub status_runtime: Option<String>,
@@ -124,6 +263,195 @@ pub struct CheckResponse {
     pub compile_error: Option<String>,
     pub full_compile_error: Option<String>,
     pub correct_answer: Option<bool>,
+    /// What the solution printed, per test case (runs)
+    #[serde(default)]
+    pub std_output_list: Option<Vec<String>>,
+    /// What the solution printed on the failing case (submits)
+    #[serde(default)]
+    pub std_output: Option<String>,
+}
+
+/// What produced a verdict.
+#[derive(Debug, Clone, Copy)]
+pub enum ResultKind {
+    Run,
+    Submit,
+    /// The project's own tests, run locally with `t`
+    LocalTest,
+}
+
+/// A run or submission verdict, as the Result screen and history use it.
+#[derive(Debug, Clone)]
+pub struct ResultData {
+    pub status_msg: String,
+    pub status_code: i32,
+    pub total_correct: Option<i32>,
+    pub total_testcases: Option<i32>,
+    pub runtime: Option<String>,
+    pub memory: Option<String>,
+    pub code_output: Option<Vec<String>>,
+    pub expected_output: Option<String>,
+    pub last_testcase: Option<String>,
+    /// Display form of `last_testcase`, e.g. `nums = [1,2], target = 3`
+    pub input_formatted: Option<String>,
+    pub compile_error: Option<String>,
+    pub runtime_error: Option<String>,
+    /// Captured stdout per test case; a submit reports only one
+    pub stdout: Vec<String>,
+}
+
+impl ResultData {
+    pub fn from_check(resp: &CheckResponse) -> Self {
+        Self {
+            status_msg: resp.status_msg.clone().unwrap_or_default(),
+            status_code: resp.status_code.unwrap_or(-1),
+            total_correct: resp.total_correct,
+            total_testcases: resp.total_testcases,
+            runtime: resp.status_runtime.clone(),
+            memory: resp.status_memory.clone(),
+            code_output: resp.code_answer.clone().or(resp.code_output.clone()),
+            expected_output: resp
+                .expected_output
+                .clone()
+                .or_else(|| resp.expected_code_answer.as_ref().map(|v| v.join("\n"))),
+            last_testcase: resp.last_testcase.clone(),
+            input_formatted: resp
+                .input_formatted
+                .clone()
+                .filter(|i| !i.trim().is_empty()),
+            compile_error: resp
+                .full_compile_error
+                .clone()
+                .filter(|e| !e.trim().is_empty())
+                .or_else(|| resp.compile_error.clone().filter(|e| !e.trim().is_empty())),
+            runtime_error: resp
+                .full_runtime_error
+                .clone()
+                .or_else(|| resp.runtime_error.clone())
+                .filter(|e| !e.trim().is_empty()),
+            stdout: resp
+                .std_output_list
+                .clone()
+                .or_else(|| resp.std_output.clone().map(|s| vec![s]))
+                .filter(|cases| cases.iter().any(|c| !c.trim().is_empty()))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// Editorial types
+#[derive(Debug, Deserialize)]
+pub struct OfficialSolutionData {
+    pub question: Option<OfficialSolutionQuestion>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OfficialSolutionQuestion {
+    pub solution: Option<OfficialSolution>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfficialSolution {
+    /// Markdown with embedded HTML; absent when the viewer can't see it
+    pub content: Option<String>,
+    #[serde(default)]
+    pub paid_only: bool,
+    #[serde(default)]
+    pub can_see_detail: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UgcOfficialSolutionData {
+    pub ugc_article_official_solution_article: Option<UgcArticle>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UgcArticle {
+    /// Markdown; null without Premium when the editorial is paid
+    pub content: Option<String>,
+}
+
+// Community solution types
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionSolutionsData {
+    pub question_solutions: Option<QuestionSolutions>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionSolutions {
+    pub total_num: u32,
+    pub solutions: Vec<CommunitySolution>,
+}
+
+/// A community solution as the list shows it; the post itself is fetched
+/// when it's opened.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommunitySolution {
+    /// The discussion topic holding the post
+    #[serde(deserialize_with = "deserialize_id")]
+    pub id: String,
+    pub title: String,
+    /// Languages and techniques the author tagged it with
+    #[serde(default)]
+    pub solution_tags: Vec<TopicTag>,
+    pub post: SolutionPostMeta,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolutionPostMeta {
+    pub vote_count: i32,
+    pub author: Option<SolutionAuthor>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SolutionAuthor {
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SolutionTopicData {
+    pub topic: Option<SolutionTopic>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SolutionTopic {
+    pub post: SolutionTopicPost,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SolutionTopicPost {
+    /// Markdown
+    pub content: String,
+}
+
+// Random question types
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RandomQuestionData {
+    pub random_question: Option<RandomQuestion>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RandomQuestion {
+    pub title_slug: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyQuestionData {
+    pub active_daily_coding_challenge_question: Option<DailyQuestion>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DailyQuestion {
+    pub question: RandomQuestion,
 }
 
 // User status types
@@ -184,10 +512,6 @@ pub struct FavoritesLists {
 pub struct FavoriteList {
     pub id_hash: String,
     pub name: String,
-    pub description: Option<String>,
-    pub view_count: i32,
-    pub creator: String,
-    pub is_watched: bool,
     pub is_public_favorite: bool,
     pub questions: Vec<FavoriteQuestion>,
 }
@@ -201,6 +525,151 @@ pub struct FavoriteQuestion {
     pub title_slug: String,
 }
 
+// Contest list types
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContestsData {
+    pub contest_upcoming_contests: Option<Vec<ContestSummary>>,
+    pub all_contests: Option<Vec<ContestSummary>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContestSummary {
+    pub title: String,
+    pub title_slug: String,
+    /// Unix seconds
+    pub start_time: i64,
+    /// Seconds
+    pub duration: i64,
+}
+
+impl ContestSummary {
+    pub fn end_time(&self) -> i64 {
+        self.start_time + self.duration
+    }
+}
+
+// Contest types, from the REST contest info endpoint
+#[derive(Debug, Deserialize)]
+pub struct ContestInfo {
+    pub contest: Option<Contest>,
+    /// Empty until the contest starts
+    #[serde(default)]
+    pub questions: Vec<ContestQuestion>,
+    #[serde(default, rename = "containsPremium")]
+    pub contains_premium: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Contest {
+    pub title: String,
+    /// Unix seconds
+    pub start_time: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContestQuestion {
+    pub title: String,
+    pub title_slug: String,
+    /// Points, which also orders the problems Q1 to Q4
+    pub credit: i32,
+}
+
+// Company tag types, Premium only
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanyTagsData {
+    pub company_tags: Option<Vec<CompanyTag>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanyTag {
+    pub name: String,
+    pub slug: String,
+    #[serde(default)]
+    pub question_count: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanyTagData {
+    pub company_tag: Option<CompanyTagDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanyTagDetail {
+    pub name: String,
+    /// JSON text mapping each question id to how often it was asked in
+    /// each period, most recent first; null without Premium
+    pub frequencies: Option<String>,
+    #[serde(default)]
+    pub questions: Vec<CompanyQuestion>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanyQuestion {
+    pub question_id: String,
+    #[serde(flatten)]
+    pub summary: ProblemSummary,
+}
+
+/// How far back a company's interview frequencies reach.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompanyPeriod {
+    SixMonths,
+    OneYear,
+    TwoYears,
+}
+
+impl CompanyPeriod {
+    pub const ALL: [CompanyPeriod; 3] = [
+        CompanyPeriod::SixMonths,
+        CompanyPeriod::OneYear,
+        CompanyPeriod::TwoYears,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CompanyPeriod::SixMonths => "6 months",
+            CompanyPeriod::OneYear => "1 year",
+            CompanyPeriod::TwoYears => "2 years",
+        }
+    }
+
+    /// The period after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    pub fn prev(self) -> Self {
+        let i = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(i + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// A problem a company has asked, with its frequencies.
+#[derive(Debug, Clone)]
+pub struct CompanyProblem {
+    pub problem: ProblemSummary,
+    /// One per `CompanyPeriod`, in order
+    pub frequency: Vec<f64>,
+}
+
+impl CompanyProblem {
+    pub fn frequency_in(&self, period: CompanyPeriod) -> f64 {
+        let i = CompanyPeriod::ALL
+            .iter()
+            .position(|&p| p == period)
+            .unwrap_or(0);
+        self.frequency.get(i).copied().unwrap_or(0.0)
+    }
+}
+
 // Aggregated user stats
 #[derive(Debug, Clone)]
 pub struct UserStats {
@@ -1,33 +1,58 @@
-use anyhow::Result;
-use crossterm::event::KeyCode;
+use anyhow::{Context, Result, bail};
+use crossterm::event::{KeyCode, MouseEvent};
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
-use std::path::PathBuf;
-use std::process::Command;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
 use tokio::sync::mpsc;
 
-use crate::api::client::LeetCodeClient;
-use crate::api::types::{CheckResponse, FavoriteList, ProblemSummary, QuestionDetail, UserStats};
-use crate::config::Config;
-use crate::event::{Event, EventHandler};
+use crate::api::client::{
+    LEETCODE_SITE, LeetCodeApi, LeetCodeClient, describe_error, rate_limit_wait,
+};
+use crate::api::types::{
+    CheckResponse, CommunitySolution, CompanyPeriod, CompanyProblem, CompanyTag, ContestQuestion,
+    ContestSummary, FavoriteList, FetchFilter, OfficialSolution, ProblemSummary, QuestionDetail,
+    ResultData, ResultKind, UserStats,
+};
+use crate::cache;
+use crate::clipboard;
+use crate::config::{Config, EditorMode, ThemeSetting, editor_argv, gui_editor_name};
+use crate::event::{self, Event, EventHandler};
+use crate::extract;
+use crate::git;
+use crate::history::{ActivityLog, Journal, RecentProblems, SearchHistory};
+use crate::index;
+use crate::local_test::{self, LocalTestRun};
+use crate::markdown;
 use crate::scaffold;
+use crate::tasks::TaskTracker;
+use crate::ui::breadcrumb;
+use crate::ui::company::{self, CompanyAction, CompanyPicker};
+use crate::ui::contests::{self, ContestProblems, ContestsAction, ContestsState};
 use crate::ui::detail::{self, DetailAction, DetailState};
-use crate::ui::home::{self, HomeAction, HomeState};
+use crate::ui::home::{self, HomeAction, HomeFocus, HomeState, ListCandidate, StatusFilter};
+use crate::ui::icons::{self, icons};
 use crate::ui::lists::{self, ListsAction, ListsState};
-use crate::ui::result::{self, ResultAction, ResultData, ResultKind, ResultState};
-use crate::ui::setup::{self, SetupAction, SetupState};
+use crate::ui::result::{self, FailingCase, ResultAction, ResultState, ResultStatus};
+use crate::ui::setup::{self, ConnectionStatus, CookieAction, CookieForm, SetupAction, SetupState};
+use crate::ui::solutions;
+use crate::ui::theme::{Theme, set_theme, theme};
+use crate::watch::SolutionWatcher;
 
 pub enum Screen {
     Setup(SetupState),
-    Home(HomeState),
-    Detail(DetailState),
+    Home(Box<HomeState>),
+    Detail(Box<DetailState>),
     Result(ResultState),
     Lists(ListsState),
+    Contests(ContestsState),
 }
 
 pub enum ApiResult {
@@ -36,41 +61,172 @@ pub enum ApiResult {
     SubmitResult(Result<CheckResponse>),
     UserStats(Option<UserStats>),
     AuthExpired,
-    SearchResult(Result<(Vec<ProblemSummary>, i32)>),
+    ProblemBatch {
+        problems: Vec<ProblemSummary>,
+        total: i32,
+        done: bool,
+    },
+    ProblemFetchError(String),
+    /// LeetCode is rate limiting; hold off for this long
+    RateLimited(std::time::Duration),
+    /// Problems matching a server-side search, with the query it was for
+    SearchResult(String, Result<(Vec<ProblemSummary>, i32)>),
+    JumpProblem(String, Result<(Vec<ProblemSummary>, i32)>),
+    JumpDetail(Result<QuestionDetail>),
+    /// The problem named on the command line
+    LaunchDetail(Result<QuestionDetail>),
+    /// A contest's title, its problem slugs, and summaries of those
+    /// problems that weren't in the problem list
+    Contest(Result<(String, HashSet<String>, Vec<ProblemSummary>)>),
+    /// Upcoming and past contests for the Contests screen
+    Contests(Result<Vec<ContestSummary>>),
+    /// A past contest's problems, by contest slug
+    ContestProblems(String, Result<Vec<ContestQuestion>>),
+    Preview(String, Result<QuestionDetail>),
+    Editorial(String, Result<Option<OfficialSolution>>),
+    /// A page of community solutions: slug, page, and the total count
+    Solutions(String, u32, Result<(Vec<CommunitySolution>, u32)>),
+    /// A community solution's post: slug, topic id, content
+    SolutionPost(String, String, Result<String>),
     Favorites(Result<Vec<FavoriteList>>),
+    /// Problem cache rows, by slug, for listed problems Home didn't have
+    CachedListed(HashMap<String, ProblemSummary>),
     ListMutation(Result<()>, String), // (result, success_message)
+    /// Problems removed from a list, with what `u` needs to put them back
+    ListRemoval(Result<()>, String, ListUndo),
     PopupFavorites(Result<Vec<FavoriteList>>),
+    /// Slugs with a project directory in the workspace
+    Scaffolded(HashSet<String>),
+    LocalTest(Result<LocalTestRun>),
+    /// The watched solution file was saved
+    SolutionChanged(String),
+    /// A problem (by slug) starred or unstarred; `true` when now starred
+    Starred(String, Result<bool>),
+    /// Setup's connection test for a (session, csrf) pair
+    ConnectionChecked(String, String, Result<String>),
+    /// The git commit after an accepted submission; `false` when nothing
+    /// had changed
+    Committed(Result<bool>),
+    /// The repository scaffolded projects are committed to; `true` when it
+    /// was just created
+    RepoReady(Result<bool>),
+    /// The workspace README written, and how many problems it lists
+    IndexWritten(Result<(PathBuf, usize)>),
+    CompanyTags(Result<Vec<CompanyTag>>),
+    /// A company's name and problems, for the period picked
+    CompanyProblems(CompanyPeriod, Result<(String, Vec<CompanyProblem>)>),
+}
+
+/// The last removal from a list, kept for `u` while its toast is up.
+pub struct ListUndo {
+    pub id_hash: String,
+    pub list_name: String,
+    pub question_ids: Vec<String>,
 }
 
+/// Ticks the undo offer (and its toast) stays up: ~6 seconds at 100ms a
+/// tick.
+const UNDO_TICKS: u8 = 60;
+
 pub struct AddToListPopup {
     pub lists: Vec<FavoriteList>,
-    pub selected: usize,
-    pub question_id: String,
+    pub state: ListState,
+    /// More than one when adding the problems marked on Home
+    pub problems: Vec<ListCandidate>,
     pub loading: bool,
 }
 
+/// `o` on a problem that isn't scaffolded yet: the languages it can be set
+/// up in.
+pub struct LanguagePicker {
+    pub detail: QuestionDetail,
+    pub languages: Vec<&'static str>,
+    pub state: ListState,
+}
+
+impl AddToListPopup {
+    /// Whether every problem is already in `list`, making Enter a removal.
+    fn contains_all(&self, list: &FavoriteList) -> bool {
+        self.problems
+            .iter()
+            .all(|p| list.questions.iter().any(|q| q.title_slug == p.slug))
+    }
+}
+
+/// A submission waiting on the y/N confirm overlay. The language and file it
+/// names are resolved when the overlay opens, not on every frame.
+pub struct ConfirmSubmit {
+    pub detail: QuestionDetail,
+    pub language: String,
+    pub file: PathBuf,
+}
+
 pub struct App {
     pub screen: Screen,
     pub config: Option<Config>,
     pub should_quit: bool,
     pub error_overlay: Option<String>,
     pub success_message: Option<(String, u8)>, // (message, ticks remaining)
+    /// Like `success_message`, for trouble that clears up by itself
+    pub warning_message: Option<(String, u8)>,
     pub help_overlay: bool,
     pub login_prompt: bool,
     pub login_waiting: bool,
+    /// Cookies typed in by hand after the browser's couldn't be read
+    pub cookie_form: Option<CookieForm>,
     pub last_opened_dir: Option<PathBuf>,
     pub add_to_list_popup: Option<AddToListPopup>,
-    saved_home: Option<HomeState>,
+    pub confirm_submit: Option<ConfirmSubmit>,
+    pub language_picker: Option<LanguagePicker>,
+    /// Opened from the Home filter popup
+    pub company_picker: Option<CompanyPicker>,
+    /// Companies fetched for the picker, kept for the session
+    company_tags: Option<Vec<CompanyTag>>,
+    /// Undo for the last list removal, offered while its toast is up
+    list_undo: Option<ListUndo>,
+    saved_home: Option<Box<HomeState>>,
+    /// Search history and recent problems, read once at startup. Home holds
+    /// them while it exists; they come back here when it's dropped.
+    home_history: Option<(SearchHistory, RecentProblems)>,
     saved_lists: Option<ListsState>,
-    api_client: LeetCodeClient,
+    saved_contests: Option<ContestsState>,
+    /// `D` set the configured server-side filter aside for this session
+    fetch_filter_lifted: bool,
+    tasks: TaskTracker,
+    api_client: Arc<dyn LeetCodeApi>,
     api_tx: mpsc::UnboundedSender<ApiResult>,
     api_rx: mpsc::UnboundedReceiver<ApiResult>,
     search_debounce: Option<tokio::time::Instant>,
     pending_search_query: Option<String>,
+    /// Run and submit are refused until then, after a rate limit
+    rate_limited_until: Option<std::time::Instant>,
+    /// The last search sent; results for any earlier one are dropped
+    latest_search: Option<String>,
+    /// The detail being fetched was a random pick; announce it when it opens
+    random_pick: bool,
+    /// Problem details fetched this session, keyed by slug
+    detail_cache: HashMap<String, QuestionDetail>,
+    /// Editorials fetched this session, keyed by slug; `None` when the
+    /// problem has none
+    editorial_cache: HashMap<String, Option<OfficialSolution>>,
+    preview_debounce: Option<tokio::time::Instant>,
+    pending_preview: Option<String>,
+    /// Batches of a refresh running behind a list loaded from the cache
+    fetched_problems: Vec<ProblemSummary>,
+    /// `--offline`: no network at all, everything comes from the disk cache
+    offline: bool,
+    /// Watch mode (`w`): saving the solution runs it
+    watch: Option<SolutionWatcher>,
+    /// Saves come in bursts; the run starts once they stop
+    watch_debounce: Option<tokio::time::Instant>,
+    /// A save arrived while a run was pending; run again when it lands
+    watch_rerun: bool,
+    /// Slug or number from the command line to open instead of loading Home
+    pub launch_problem: Option<String>,
 }
 
 impl App {
-    pub fn new(config: Option<Config>) -> Result<Self> {
+    pub fn new(config: Option<Config>, offline: bool) -> Result<Self> {
         let (api_tx, api_rx) = mpsc::unbounded_channel();
 
         // If no config exists, create one with sensible defaults
@@ -79,34 +235,73 @@ impl App {
             None => Config::create_default()?,
         };
 
-        let api_client = LeetCodeClient::new(
+        let api_client = Arc::new(LeetCodeClient::new(
+            config.site(),
             config.leetcode_session.as_deref(),
             config.csrf_token.as_deref(),
-        )?;
-
-        let login_prompt = !config.is_authenticated();
+        )?);
 
-        let screen = Screen::Home(HomeState::new());
+        let login_prompt = !config.is_authenticated() && !offline && !config.skip_login_prompt;
 
-        Ok(Self {
-            screen,
+        let mut app = Self {
+            // Replaced with Home below, which needs the rest of the app
+            screen: Screen::Setup(SetupState::new()),
             config: Some(config),
             should_quit: false,
             error_overlay: None,
             success_message: None,
+            warning_message: None,
             help_overlay: false,
             login_prompt,
             login_waiting: false,
+            cookie_form: None,
             last_opened_dir: None,
             add_to_list_popup: None,
+            confirm_submit: None,
+            language_picker: None,
+            company_picker: None,
+            company_tags: None,
             saved_home: None,
+            home_history: Some((SearchHistory::load(), RecentProblems::load())),
             saved_lists: None,
+            saved_contests: None,
+            fetch_filter_lifted: false,
+            tasks: TaskTracker::default(),
             api_client,
             api_tx,
             api_rx,
             search_debounce: None,
             pending_search_query: None,
-        })
+            rate_limited_until: None,
+            latest_search: None,
+            random_pick: false,
+            detail_cache: HashMap::new(),
+            editorial_cache: HashMap::new(),
+            preview_debounce: None,
+            pending_preview: None,
+            fetched_problems: Vec::new(),
+            offline,
+            list_undo: None,
+            watch: None,
+            watch_debounce: None,
+            watch_rerun: false,
+            launch_problem: None,
+        };
+        app.screen = Screen::Home(Box::new(app.new_home()));
+        app.apply_theme();
+        Ok(app)
+    }
+
+    /// Use the configured theme, or say why it can't be used and keep the
+    /// default.
+    fn apply_theme(&mut self) {
+        match Theme::from_setting(self.config.as_ref().and_then(|c| c.theme.as_ref())) {
+            Ok(theme) => set_theme(theme),
+            Err(e) => {
+                set_theme(Theme::DARK);
+                self.error_overlay = Some(format!("{e:#}"));
+            }
+        }
     }
 
     pub async fn run(
@@ -114,11 +309,15 @@ impl App {
         terminal: &mut ratatui::DefaultTerminal,
         events: &mut EventHandler,
     ) -> Result<()> {
-        if matches!(self.screen, Screen::Home(_)) {
-            self.start_fetch_user_stats();
+        if let Some(problem) = self.launch_problem.take() {
+            self.start_launch_detail(problem);
+        } else if matches!(self.screen, Screen::Home(_)) {
+            self.start_loading_home();
         }
 
         loop {
+            self.sync_preview();
+            self.sync_watch();
             terminal.draw(|f| self.render(f))?;
 
             if self.should_quit {
@@ -129,8 +328,10 @@ impl App {
                 event = events.next() => {
                     match event? {
                         Event::Key(key) => self.handle_key(key, terminal, events)?,
+                        Event::Mouse(mouse) => self.handle_mouse(mouse),
+                        Event::Paste(text) => self.handle_paste(&text),
                         Event::Tick => self.handle_tick(),
-                        Event::Resize(_, _) => {}
+                        Event::Resize => {}
                     }
                 }
                 Some(api_result) = self.api_rx.recv() => {
@@ -145,21 +346,114 @@ impl App {
                 }, if self.search_debounce.is_some() => {
                     self.fire_search();
                 }
+                _ = async {
+                    if let Some(deadline) = self.preview_debounce {
+                        tokio::time::sleep_until(deadline).await;
+                    } else {
+                        std::future::pending::<()>().await;
+                    }
+                }, if self.preview_debounce.is_some() => {
+                    self.fire_preview();
+                }
+                _ = async {
+                    if let Some(deadline) = self.watch_debounce {
+                        tokio::time::sleep_until(deadline).await;
+                    } else {
+                        std::future::pending::<()>().await;
+                    }
+                }, if self.watch_debounce.is_some() => {
+                    self.fire_watch_run();
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Where the current screen sits below Home, or `None` on Home itself.
+    fn breadcrumb(&self) -> Option<Vec<String>> {
+        fn list_path(lists: &ListsState) -> Vec<String> {
+            let mut crumbs = vec!["Home".to_string(), "Lists".to_string()];
+            crumbs.extend(lists.viewing_list_ref().map(|l| l.name.clone()));
+            crumbs
+        }
+        let contests_path = || vec!["Home".to_string(), "Contests".to_string()];
+        let origin = || match (&self.saved_lists, &self.saved_contests) {
+            (Some(lists), _) => list_path(lists),
+            (None, Some(_)) => contests_path(),
+            (None, None) => vec!["Home".to_string()],
+        };
+        let problem = |d: &QuestionDetail| format!("{}. {}", d.frontend_question_id, d.title);
+
+        match &self.screen {
+            Screen::Setup(_) | Screen::Home(_) => None,
+            Screen::Lists(state) => Some(list_path(state)),
+            Screen::Contests(_) => Some(contests_path()),
+            Screen::Detail(state) => {
+                let mut crumbs = origin();
+                crumbs.push(problem(&state.detail));
+                Some(crumbs)
+            }
+            Screen::Result(state) => {
+                let mut crumbs = origin();
+                crumbs.push(problem(&state.detail));
+                crumbs.push(
+                    match state.kind {
+                        ResultKind::Run => "Run",
+                        ResultKind::Submit => "Submit",
+                        ResultKind::LocalTest => "Local tests",
+                    }
+                    .to_string(),
+                );
+                Some(crumbs)
+            }
+        }
+    }
+
     fn render(&mut self, frame: &mut Frame) {
         let area = frame.area();
 
+        // Screens below Home give up their top row to the breadcrumb
+        let screen_area = match self.breadcrumb() {
+            Some(crumbs) if area.height > 2 => {
+                breadcrumb::render_breadcrumb(frame, Rect { height: 1, ..area }, &crumbs);
+                Rect {
+                    y: area.y + 1,
+                    height: area.height - 1,
+                    ..area
+                }
+            }
+            _ => area,
+        };
+
         match &mut self.screen {
             Screen::Setup(state) => setup::render_setup(frame, state),
-            Screen::Home(state) => home::render_home(frame, area, state),
-            Screen::Detail(state) => detail::render_detail(frame, area, state),
-            Screen::Result(state) => result::render_result(frame, area, state),
-            Screen::Lists(state) => lists::render_lists(frame, area, state),
+            Screen::Home(state) => home::render_home(frame, screen_area, state),
+            Screen::Detail(state) => detail::render_detail(frame, screen_area, state),
+            Screen::Result(state) => result::render_result(frame, screen_area, state),
+            Screen::Lists(state) => lists::render_lists(frame, screen_area, state),
+            Screen::Contests(state) => contests::render_contests(frame, screen_area, state),
+        }
+
+        self.render_task_indicator(frame, area);
+
+        if self.watch.is_some() && area.width > 12 {
+            let badge = " WATCHING ";
+            let rect = Rect::new(
+                area.right() - badge.len() as u16 - 1,
+                area.y,
+                badge.len() as u16,
+                1,
+            );
+            frame.render_widget(
+                Paragraph::new(badge).style(
+                    Style::default()
+                        .fg(theme().on_accent)
+                        .bg(theme().special)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                rect,
+            );
         }
 
         // Login waiting overlay (browser redirect)
@@ -171,18 +465,22 @@ impl App {
             let overlay_area = Rect::new(x, y, overlay_width, overlay_height);
 
             frame.render_widget(Clear, overlay_area);
-            let prompt = Paragraph::new("\nOpened LeetCode login in your browser.\nAfter logging in, press Enter to retry.\n\n Esc: Cancel")
+            let prompt = Paragraph::new("\nOpened LeetCode login in your browser.\nAfter logging in, press Enter to retry.\n\n Enter: Retry  p: Paste cookies  Esc: Cancel")
                 .block(
                     Block::default()
                         .title(" Browser Login ")
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::Cyan)),
+                        .border_style(Style::default().fg(theme().accent)),
                 )
-                .style(Style::default().fg(Color::White))
+                .style(Style::default().fg(theme().text))
                 .wrap(Wrap { trim: true });
             frame.render_widget(prompt, overlay_area);
         }
 
+        if let Some(ref form) = self.cookie_form {
+            setup::render_cookie_form(frame, area, form);
+        }
+
         // Login prompt overlay
         if self.login_prompt {
             let overlay_width = 52u16.min(area.width.saturating_sub(4));
@@ -197,40 +495,39 @@ impl App {
                     Block::default()
                         .title(" Login ")
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::Cyan)),
+                        .border_style(Style::default().fg(theme().accent)),
                 )
-                .style(Style::default().fg(Color::White))
+                .style(Style::default().fg(theme().text))
                 .wrap(Wrap { trim: true });
             frame.render_widget(prompt, overlay_area);
         }
 
         // Add-to-list popup overlay
-        if let Some(ref popup) = self.add_to_list_popup {
+        if let Some(ref mut popup) = self.add_to_list_popup {
             let overlay_width = 44u16.min(area.width.saturating_sub(4));
             let overlay_height = (popup.lists.len() as u16 + 4)
-                .min(16)
-                .max(5)
+                .clamp(5, 16)
                 .min(area.height.saturating_sub(4));
             let x = area.x + (area.width.saturating_sub(overlay_width)) / 2;
             let y = area.y + (area.height.saturating_sub(overlay_height)) / 2;
             let overlay_area = Rect::new(x, y, overlay_width, overlay_height);
+            let title = match popup.problems.len() {
+                1 => " Add to List ".to_string(),
+                n => format!(" Add {n} Problems to List "),
+            };
 
             frame.render_widget(Clear, overlay_area);
 
             if popup.loading {
-                let spinner = [
-                    "\u{280b}", "\u{2819}", "\u{2839}", "\u{2838}", "\u{283c}", "\u{2834}",
-                    "\u{2826}", "\u{2827}", "\u{2807}", "\u{280f}",
-                ];
-                let s = spinner[0];
+                let s = icons::spinner(0);
                 let p = Paragraph::new(format!("\n {s} Loading lists..."))
                     .block(
                         Block::default()
-                            .title(" Add to List ")
+                            .title(title.as_str())
                             .borders(Borders::ALL)
-                            .border_style(Style::default().fg(Color::Cyan)),
+                            .border_style(Style::default().fg(theme().accent)),
                     )
-                    .style(Style::default().fg(Color::Yellow));
+                    .style(Style::default().fg(theme().warning));
                 frame.render_widget(p, overlay_area);
             } else if popup.lists.is_empty() {
                 let p = Paragraph::new(
@@ -238,11 +535,11 @@ impl App {
                 )
                 .block(
                     Block::default()
-                        .title(" Add to List ")
+                        .title(title.as_str())
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::Cyan)),
+                        .border_style(Style::default().fg(theme().accent)),
                 )
-                .style(Style::default().fg(Color::White))
+                .style(Style::default().fg(theme().text))
                 .wrap(Wrap { trim: true });
                 frame.render_widget(p, overlay_area);
             } else {
@@ -254,55 +551,127 @@ impl App {
                 );
 
                 let block = Block::default()
-                    .title(" Add to List ")
+                    .title(title.as_str())
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan));
+                    .border_style(Style::default().fg(theme().accent));
                 frame.render_widget(block, overlay_area);
 
-                let visible_height = inner_area.height as usize;
-                let items: Vec<Line> = popup
+                let items: Vec<ListItem> = popup
                     .lists
                     .iter()
-                    .enumerate()
-                    .map(|(i, list)| {
-                        let selected = i == popup.selected;
-                        let prefix = if selected { "\u{25b8} " } else { "  " };
-                        let style = if selected {
-                            Style::default()
-                                .fg(Color::Cyan)
-                                .add_modifier(Modifier::BOLD)
+                    .map(|list| {
+                        let member = popup.contains_all(list);
+                        let mark = if member {
+                            format!("{} ", icons().check)
                         } else {
-                            Style::default().fg(Color::White)
+                            "  ".to_string()
                         };
-                        Line::from(Span::styled(
-                            format!("{prefix}{} ({})", list.name, list.questions.len()),
-                            style,
-                        ))
+                        let color = if member {
+                            theme().success
+                        } else {
+                            theme().text
+                        };
+                        ListItem::new(format!("{mark}{} ({})", list.name, list.questions.len()))
+                            .style(Style::default().fg(color))
                     })
                     .collect();
+                let total = items.len();
+                let list = List::new(items)
+                    .highlight_style(
+                        Style::default()
+                            .fg(theme().accent)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .highlight_symbol(format!("{} ", icons().pointer));
+                frame.render_stateful_widget(list, inner_area, &mut popup.state);
+
+                // Say so on the border when lists are cut off
+                let offset = popup.state.offset();
+                let more_style = Style::default().fg(theme().dim);
+                let indicator_x = overlay_area.right().saturating_sub(10);
+                if offset > 0 {
+                    frame.render_widget(
+                        Paragraph::new(format!(" {} more ", icons().more_above)).style(more_style),
+                        Rect::new(indicator_x, overlay_area.y, 8, 1),
+                    );
+                }
+                if offset + (inner_area.height as usize) < total {
+                    frame.render_widget(
+                        Paragraph::new(format!(" {} more ", icons().more_below)).style(more_style),
+                        Rect::new(indicator_x, overlay_area.bottom().saturating_sub(1), 8, 1),
+                    );
+                }
+            }
+        }
 
-                // Scroll if needed
-                let scroll_offset = if popup.selected >= visible_height {
-                    popup.selected - visible_height + 1
-                } else {
-                    0
-                };
+        if let Some(ref mut picker) = self.company_picker {
+            company::render_company_picker(frame, area, picker);
+        }
 
-                let p = Paragraph::new(items).scroll((scroll_offset as u16, 0));
-                frame.render_widget(p, inner_area);
-            }
+        // Language picker overlay
+        if let Some(ref mut picker) = self.language_picker {
+            let overlay_width = 36u16.min(area.width.saturating_sub(4));
+            let overlay_height =
+                (picker.languages.len() as u16 + 4).min(area.height.saturating_sub(4));
+            let x = area.x + (area.width.saturating_sub(overlay_width)) / 2;
+            let y = area.y + (area.height.saturating_sub(overlay_height)) / 2;
+            let overlay_area = Rect::new(x, y, overlay_width, overlay_height);
+
+            frame.render_widget(Clear, overlay_area);
+            let block = Block::default()
+                .title(" Scaffold in ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent));
+            frame.render_widget(block, overlay_area);
+
+            let inner_area = Rect::new(
+                overlay_area.x + 1,
+                overlay_area.y + 1,
+                overlay_area.width.saturating_sub(2),
+                overlay_area.height.saturating_sub(3),
+            );
+            let items: Vec<ListItem> = picker
+                .languages
+                .iter()
+                .map(|language| ListItem::new(*language).style(Style::default().fg(theme().text)))
+                .collect();
+            let list = List::new(items)
+                .highlight_style(
+                    Style::default()
+                        .fg(theme().accent)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .highlight_symbol(format!("{} ", icons().pointer));
+            frame.render_stateful_widget(list, inner_area, &mut picker.state);
+
+            let hint = Paragraph::new(" Enter: scaffold  Esc: cancel")
+                .style(Style::default().fg(theme().dim));
+            frame.render_widget(
+                hint,
+                Rect::new(
+                    inner_area.x,
+                    overlay_area.bottom().saturating_sub(2),
+                    inner_area.width,
+                    1,
+                ),
+            );
         }
 
-        // Success toast (bottom center)
-        if let Some((ref msg, _)) = self.success_message {
-            let text = format!(" \u{2714} {msg} ");
+        // Toast (bottom center), a warning taking precedence
+        let toast = match (&self.warning_message, &self.success_message) {
+            (Some((msg, _)), _) => Some((icons().timer, msg, theme().warning)),
+            (None, Some((msg, _))) => Some((icons().check, msg, theme().success)),
+            (None, None) => None,
+        };
+        if let Some((icon, msg, bg)) = toast {
+            let text = format!(" {icon} {msg} ");
             let w = (text.len() as u16 + 2).min(area.width.saturating_sub(4));
             let x = area.x + (area.width.saturating_sub(w)) / 2;
             let y = area.bottom().saturating_sub(3);
             let toast_area = Rect::new(x, y, w, 1);
             frame.render_widget(Clear, toast_area);
             frame.render_widget(
-                Paragraph::new(text).style(Style::default().fg(Color::Black).bg(Color::Green)),
+                Paragraph::new(text).style(Style::default().fg(theme().on_accent).bg(bg)),
                 toast_area,
             );
         }
@@ -321,13 +690,41 @@ impl App {
                     Block::default()
                         .title(" Error ")
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::Red)),
+                        .border_style(Style::default().fg(theme().error)),
                 )
-                .style(Style::default().fg(Color::Red))
+                .style(Style::default().fg(theme().error))
                 .wrap(Wrap { trim: true });
             frame.render_widget(error_block, overlay_area);
         }
 
+        // Submit confirmation overlay
+        if let Some(ref confirm) = self.confirm_submit {
+            let overlay_width = 60u16.min(area.width.saturating_sub(4));
+            let overlay_height = 9u16.min(area.height.saturating_sub(4));
+            let x = area.x + (area.width.saturating_sub(overlay_width)) / 2;
+            let y = area.y + (area.height.saturating_sub(overlay_height)) / 2;
+            let overlay_area = Rect::new(x, y, overlay_width, overlay_height);
+
+            frame.render_widget(Clear, overlay_area);
+            let text = format!(
+                "\nSubmit solution for {}. {} in {}?\n{}\n\n (y) Submit  (t) Test locally  (N) Cancel",
+                confirm.detail.frontend_question_id,
+                confirm.detail.title,
+                confirm.language,
+                confirm.file.display()
+            );
+            let prompt = Paragraph::new(text)
+                .block(
+                    Block::default()
+                        .title(" Submit ")
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(theme().accent)),
+                )
+                .style(Style::default().fg(theme().text))
+                .wrap(Wrap { trim: true });
+            frame.render_widget(prompt, overlay_area);
+        }
+
         // Help overlay
         if self.help_overlay {
             let help_text = match &self.screen {
@@ -342,18 +739,37 @@ impl App {
                         vec![
                             ("Enter", "Search / go to results"),
                             ("Tab/\u{2193}", "Go to results table"),
+                            ("\u{2191}/\u{2193}", "Previous / next search"),
+                            ("Ctrl+r", "Recall search containing input"),
                             ("Esc", "Clear search"),
                             ("type", "Search problems"),
                         ]
                     } else {
                         vec![
                             ("j/k/\u{2191}/\u{2193}", "Navigate results"),
-                            ("g/G", "Jump to top / bottom"),
+                            ("gg/G", "Jump to top / bottom"),
+                            ("{n}j/{n}k", "Move n rows"),
+                            ("{n}G", "Jump to problem n"),
+                            ("Ctrl+d/u", "Half page down / up"),
+                            ("PgDn/PgUp", "Page down / up"),
                             ("Enter", "View problem detail"),
                             ("o", "Scaffold & open in editor"),
-                            ("a", "Add to list"),
+                            ("Space", "Select problem (Esc clears)"),
+                            ("a", "Add to list (or all selected)"),
+                            ("*", "Star / unstar problem"),
                             ("/", "Back to search"),
-                            ("f", "Filter by difficulty"),
+                            (":", "Jump to number / slug"),
+                            ("'", "Recently viewed (d remove, C clear)"),
+                            ("J", "Journal of accepted submissions"),
+                            ("A", "Recent runs and submissions"),
+                            ("C", "Browse upcoming and past contests"),
+                            ("F", "Clear the list, contest or company filter"),
+                            ("x", "Open a random problem"),
+                            ("R", "Retry / resume the problem download"),
+                            ("D", "Lift / restore the server-side filter"),
+                            ("Ctrl+g", "Write the workspace README index"),
+                            ("p", "Toggle preview pane"),
+                            ("f", "Filter by difficulty / status / company"),
                             ("L", "Browse lists"),
                             ("S", "Settings"),
                             ("q", "Quit"),
@@ -363,15 +779,36 @@ impl App {
                 Screen::Detail(_) => vec![
                     ("j/k/\u{2191}/\u{2193}", "Scroll"),
                     ("d/u", "Half page down / up"),
+                    ("e", "Switch Description / Editorial tab"),
+                    ("c", "Switch Description / Solutions tab"),
+                    ("h", "Reveal the next hint"),
+                    ("Tab/S-Tab", "Select a similar question"),
+                    ("Enter", "Open the selected similar question"),
                     ("o", "Scaffold & open in editor"),
-                    ("a", "Add to list"),
+                    ("O", "Scaffold again from scratch (backs up changes)"),
+                    ("a", "Add to / remove from a list"),
+                    ("*", "Star / unstar problem"),
+                    ("m", "Save as README.md in the project"),
+                    ("y", "Copy the solution to submit"),
                     ("r", "Run code"),
                     ("s", "Submit code"),
+                    ("t", "Run the local tests"),
+                    ("w", "Watch: run on every save"),
+                    ("H", "Your runs and submissions of it"),
                     ("b/Esc", "Back to list"),
                     ("q", "Quit"),
                 ],
                 Screen::Result(_) => vec![
                     ("j/k/\u{2191}/\u{2193}", "Scroll"),
+                    ("d/u", "Half page down / up"),
+                    ("o", "Show all / less stdout"),
+                    ("e", "Edit the solution"),
+                    ("r", "Run again"),
+                    ("s", "Submit again"),
+                    ("w", "Watch: run on every save"),
+                    ("y", "Copy failing test case"),
+                    ("t", "Add failing case as a Rust test"),
+                    ("h/l/\u{2190}/\u{2192}", "Scroll compile error sideways"),
                     ("b/Esc", "Back to problem"),
                     ("q", "Quit"),
                 ],
@@ -379,24 +816,47 @@ impl App {
                     if state.viewing_list.is_some() {
                         vec![
                             ("j/k/\u{2191}/\u{2193}", "Navigate problems"),
+                            ("gg/G", "Jump to top / bottom"),
+                            ("Ctrl+d/u", "Half page down / up"),
+                            ("PgDn/PgUp", "Page down / up"),
                             ("Enter", "View problem detail"),
                             ("d", "Remove from list"),
+                            ("u", "Undo the removal (while its toast shows)"),
+                            ("f", "Show only this list on Home"),
                             ("Esc", "Back to lists"),
                         ]
                     } else {
                         vec![
                             ("j/k/\u{2191}/\u{2193}", "Navigate lists"),
+                            ("gg/G", "Jump to top / bottom"),
                             ("Enter", "Open list"),
                             ("n", "Create new list"),
+                            ("r", "Rename list"),
                             ("d", "Delete list"),
+                            ("f", "Show only this list on Home"),
+                            ("R", "Retry after an error"),
                             ("Esc/q", "Back to home"),
                         ]
                     }
                 }
+                Screen::Contests(_) => vec![
+                    ("j/k/\u{2191}/\u{2193}", "Navigate contests"),
+                    ("gg/G", "Jump to top / bottom"),
+                    ("Ctrl+d/u", "Half page down / up"),
+                    ("PgDn/PgUp", "Page down / up"),
+                    ("Enter", "Show a past contest's problems / view one"),
+                    ("o", "Open the contest page in the browser"),
+                    ("f", "Show only this contest on Home"),
+                    ("R", "Retry after an error"),
+                    ("Esc/q/b", "Back to home"),
+                ],
                 Screen::Setup(_) => vec![
                     ("Tab/\u{2193}", "Next field"),
                     ("Shift+Tab/\u{2191}", "Previous field"),
+                    ("\u{2190}/\u{2192}", "Change language"),
                     ("Ctrl+L", "Auto-login from browser"),
+                    ("Ctrl+S", "Show / hide the cookie"),
+                    ("Ctrl+T", "Test the cookies"),
                     ("Enter", "Save settings"),
                     ("Esc", "Cancel"),
                 ],
@@ -410,10 +870,10 @@ impl App {
                         Span::styled(
                             format!("  {:>width$}", key, width = max_key_len),
                             Style::default()
-                                .fg(Color::Cyan)
+                                .fg(theme().accent)
                                 .add_modifier(Modifier::BOLD),
                         ),
-                        Span::styled(format!("  {desc}"), Style::default().fg(Color::White)),
+                        Span::styled(format!("  {desc}"), Style::default().fg(theme().text)),
                     ])
                 })
                 .collect();
@@ -430,13 +890,122 @@ impl App {
                     Block::default()
                         .title(" Keybindings ")
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::Cyan)),
+                        .border_style(Style::default().fg(theme().accent)),
                 )
-                .style(Style::default().fg(Color::White));
+                .style(Style::default().fg(theme().text));
             frame.render_widget(help_block, overlay_area);
         }
     }
 
+    /// Right end of the status bar: what's still running in the background.
+    fn render_task_indicator(&self, frame: &mut Frame, area: Rect) {
+        let labels = self.tasks.labels();
+        if labels.is_empty() || area.height == 0 {
+            return;
+        }
+        let home = match self.screen {
+            Screen::Home(ref s) => Some(s),
+            _ => self.saved_home.as_ref(),
+        };
+        let parts: Vec<String> = labels
+            .iter()
+            .map(|&label| match home {
+                Some(h) if label == "loading problems" && h.total_problems > 0 => {
+                    format!("{label} ({}/{})", h.fetched, h.total_problems)
+                }
+                _ => label.to_string(),
+            })
+            .collect();
+        let text = format!(
+            " {} {} ",
+            icons().busy,
+            parts.join(&format!(" {} ", icons().separator))
+        );
+        let width = (text.chars().count() as u16).min(area.width);
+        let rect = Rect::new(area.right() - width, area.bottom() - 1, width, 1);
+        frame.render_widget(
+            Paragraph::new(text).style(Style::default().fg(theme().warning).bg(theme().bar_bg)),
+            rect,
+        );
+    }
+
+    fn queue_search(&mut self, query: String) {
+        if let Screen::Home(ref mut state) = self.screen {
+            state.search_loading = true;
+        }
+        self.pending_search_query = Some(query);
+        self.search_debounce =
+            Some(tokio::time::Instant::now() + std::time::Duration::from_millis(300));
+    }
+
+    /// A paste only lands in an open text field; anywhere else it would be
+    /// a burst of unrelated key bindings, so it is dropped.
+    fn handle_paste(&mut self, text: &str) {
+        if self.login_prompt || self.help_overlay || self.error_overlay.is_some() {
+            return;
+        }
+        if let Some(ref mut form) = self.cookie_form {
+            form.paste(text);
+            return;
+        }
+        if let Some(ref mut picker) = self.company_picker {
+            picker.paste(text);
+            return;
+        }
+        match self.screen {
+            Screen::Setup(ref mut s) => s.paste(text),
+            Screen::Home(ref mut s) => {
+                if let HomeAction::SearchFetch(query) = s.paste(text) {
+                    self.queue_search(query);
+                }
+            }
+            Screen::Lists(ref mut s) => s.paste(text),
+            Screen::Detail(_) | Screen::Result(_) | Screen::Contests(_) => {}
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.login_prompt
+            || self.login_waiting
+            || self.cookie_form.is_some()
+            || self.help_overlay
+            || self.error_overlay.is_some()
+            || self.add_to_list_popup.is_some()
+            || self.confirm_submit.is_some()
+            || self.language_picker.is_some()
+            || self.company_picker.is_some()
+        {
+            return;
+        }
+        let slug = match &mut self.screen {
+            Screen::Home(state) => match state.handle_mouse(mouse) {
+                HomeAction::OpenDetail(slug) => Some(slug),
+                _ => None,
+            },
+            Screen::Lists(state) => match state.handle_mouse(mouse) {
+                ListsAction::OpenDetail(slug) => Some(slug),
+                _ => None,
+            },
+            Screen::Contests(state) => {
+                let action = state.handle_mouse(mouse);
+                self.handle_contests_action(action);
+                None
+            }
+            Screen::Detail(state) => {
+                state.handle_mouse(mouse);
+                None
+            }
+            Screen::Result(state) => {
+                state.handle_mouse(mouse);
+                None
+            }
+            Screen::Setup(_) => None,
+        };
+        if let Some(slug) = slug {
+            self.start_fetch_detail(&slug);
+        }
+    }
+
     fn handle_key(
         &mut self,
         key: crossterm::event::KeyEvent,
@@ -457,8 +1026,12 @@ impl App {
         if key.code == KeyCode::Char('?')
             && !self.login_prompt
             && !self.login_waiting
+            && self.cookie_form.is_none()
             && self.error_overlay.is_none()
             && self.add_to_list_popup.is_none()
+            && self.confirm_submit.is_none()
+            && self.language_picker.is_none()
+            && self.company_picker.is_none()
         {
             self.help_overlay = !self.help_overlay;
             return Ok(());
@@ -470,6 +1043,7 @@ impl App {
                 KeyCode::Enter => {
                     self.retry_browser_login();
                 }
+                KeyCode::Char('p') => self.open_cookie_form(),
                 KeyCode::Esc => {
                     self.login_waiting = false;
                 }
@@ -478,6 +1052,21 @@ impl App {
             return Ok(());
         }
 
+        if let Some(ref mut form) = self.cookie_form {
+            match form.handle_key(key) {
+                CookieAction::Cancel => self.cookie_form = None,
+                CookieAction::Submit(session, csrf) => {
+                    self.cookie_form = None;
+                    self.apply_login_cookies(Some(session), Some(csrf));
+                    if self.error_overlay.is_none() {
+                        self.success_message = Some(("Cookies saved".to_string(), 12));
+                    }
+                }
+                CookieAction::None => {}
+            }
+            return Ok(());
+        }
+
         // Handle login prompt
         if self.login_prompt {
             match key.code {
@@ -495,7 +1084,10 @@ impl App {
                         Some(c) => SetupState::from_config(c),
                         None => SetupState::new(),
                     };
-                    self.screen = Screen::Setup(setup_state);
+                    let old = std::mem::replace(&mut self.screen, Screen::Setup(setup_state));
+                    if let Screen::Home(home) = old {
+                        self.saved_home = Some(home);
+                    }
                 }
                 _ => {}
             }
@@ -508,10 +1100,10 @@ impl App {
             return Ok(());
         }
 
-        // Dismiss success message on any key
-        if self.success_message.is_some() {
-            self.success_message = None;
-        }
+        // Dismiss toasts on any key, and with the undo toast its offer
+        let undo = self.list_undo.take();
+        self.success_message = None;
+        self.warning_message = None;
 
         // Dismiss error overlay on Esc or q
         if self.error_overlay.is_some() {
@@ -522,30 +1114,98 @@ impl App {
             return Ok(());
         }
 
+        // Confirm submit: only y goes ahead; t runs the local tests instead
+        if let Some(ConfirmSubmit { detail, .. }) = self.confirm_submit.take() {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.start_submit_code(&detail),
+                KeyCode::Char('t') => self.start_local_test(&detail),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        if let Some(ref mut picker) = self.company_picker {
+            match picker.handle_key(key) {
+                CompanyAction::Close => self.company_picker = None,
+                CompanyAction::Load { slug, period } => {
+                    self.start_fetch_company_problems(slug, period);
+                }
+                CompanyAction::None => {}
+            }
+            return Ok(());
+        }
+
+        // Language for a new project
+        if let Some(ref mut picker) = self.language_picker {
+            let len = picker.languages.len();
+            let selected = picker.state.selected().unwrap_or(0);
+            match key.code {
+                KeyCode::Esc => self.language_picker = None,
+                KeyCode::Char('j') | KeyCode::Down => {
+                    picker.state.select(Some((selected + 1) % len))
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    picker.state.select(Some((selected + len - 1) % len));
+                }
+                KeyCode::Enter => {
+                    if let Some(picker) = self.language_picker.take() {
+                        let language = picker.languages[selected];
+                        self.scaffold_and_edit_in(
+                            &picker.detail,
+                            language,
+                            false,
+                            terminal,
+                            events,
+                        );
+                    }
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
         // Handle add-to-list popup
         if let Some(ref mut popup) = self.add_to_list_popup {
             match key.code {
                 KeyCode::Esc => {
                     self.add_to_list_popup = None;
                 }
-                KeyCode::Char('j') | KeyCode::Down => {
-                    if !popup.lists.is_empty() {
-                        popup.selected = (popup.selected + 1) % popup.lists.len();
-                    }
+                KeyCode::Char('j') | KeyCode::Down if !popup.lists.is_empty() => {
+                    let selected = popup.state.selected().unwrap_or(0);
+                    popup.state.select(Some((selected + 1) % popup.lists.len()));
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    if !popup.lists.is_empty() {
-                        popup.selected =
-                            (popup.selected + popup.lists.len() - 1) % popup.lists.len();
-                    }
+                KeyCode::Char('k') | KeyCode::Up if !popup.lists.is_empty() => {
+                    let len = popup.lists.len();
+                    let selected = popup.state.selected().unwrap_or(0);
+                    popup.state.select(Some((selected + len - 1) % len));
                 }
                 KeyCode::Enter => {
-                    if let Some(list) = popup.lists.get(popup.selected) {
+                    if let Some(list) = popup.state.selected().and_then(|i| popup.lists.get(i)) {
+                        // Enter toggles: a list that has everything loses it,
+                        // otherwise whatever is missing gets added
+                        let remove = popup.contains_all(list);
+                        let question_ids: Vec<String> = if remove {
+                            list.questions
+                                .iter()
+                                .filter(|q| popup.problems.iter().any(|p| p.slug == q.title_slug))
+                                .map(|q| q.question_id.clone())
+                                .collect()
+                        } else {
+                            popup
+                                .problems
+                                .iter()
+                                .filter(|p| !list.questions.iter().any(|q| q.title_slug == p.slug))
+                                .map(|p| p.question_id.clone())
+                                .collect()
+                        };
                         let id_hash = list.id_hash.clone();
                         let list_name = list.name.clone();
-                        let question_id = popup.question_id.clone();
+                        let bulk = popup.problems.len() > 1;
                         self.add_to_list_popup = None;
-                        self.start_add_to_list(&id_hash, &question_id, &list_name);
+                        if bulk && let Some(home) = self.home_mut() {
+                            home.selected.clear();
+                        }
+                        self.start_update_list(&id_hash, question_ids, &list_name, remove);
                     }
                 }
                 _ => {}
@@ -553,6 +1213,15 @@ impl App {
             return Ok(());
         }
 
+        // Undo the last list removal while its toast is up
+        if key.code == KeyCode::Char('u')
+            && !self.is_typing()
+            && let Some(undo) = undo
+        {
+            self.start_update_list(&undo.id_hash, undo.question_ids, &undo.list_name, false);
+            return Ok(());
+        }
+
         // Handle setup keys separately to avoid borrow conflicts with do_browser_login
         let setup_action = if let Screen::Setup(ref mut state) = self.screen {
             Some(state.handle_key(key))
@@ -567,48 +1236,77 @@ impl App {
                         let session = if state.fields[3].is_empty() {
                             None
                         } else {
-                            Some(state.fields[3].clone())
+                            Some(state.fields[3].value().to_string())
                         };
                         let csrf = if state.fields[4].is_empty() {
                             None
                         } else {
-                            Some(state.fields[4].clone())
+                            Some(state.fields[4].value().to_string())
                         };
-                        let config = Config {
-                            workspace_dir: state.fields[0].clone(),
-                            language: state.fields[1].clone(),
-                            editor: state.fields[2].clone(),
+                        let theme_name = state.fields[setup::THEME_FIELD].value();
+                        let language = state.fields[1].value().trim();
+                        let workspace = state.fields[0].value().trim();
+                        let mut config = Config {
+                            language: language.to_string(),
+                            editor: state.fields[2].value().to_string(),
                             leetcode_session: session,
                             csrf_token: csrf,
+                            skip_login_prompt: state.fields[setup::LOGIN_PROMPT_FIELD].value()
+                                == "skip",
+                            ..self.config.clone().unwrap_or_default()
                         };
-                        if let Err(e) = config.save() {
+                        config.set_workspace(language, workspace.to_string());
+                        match config.theme {
+                            Some(ref mut theme) => theme.set_preset(theme_name),
+                            None if theme_name != "dark" => {
+                                config.theme = Some(ThemeSetting::Preset(theme_name.to_string()));
+                            }
+                            None => {}
+                        }
+                        // Validation only lets a missing workspace through
+                        // once the user agreed to have it created
+                        let workspace_dir = config.expanded_workspace();
+                        if let Err(e) = std::fs::create_dir_all(&workspace_dir) {
+                            self.error_overlay =
+                                Some(format!("Failed to create {}: {e}", workspace_dir.display()));
+                        } else if let Err(e) = config.save() {
                             self.error_overlay = Some(format!("Failed to save config: {e}"));
                         } else {
                             if let Ok(client) = LeetCodeClient::new(
+                                config.site(),
                                 config.leetcode_session.as_deref(),
                                 config.csrf_token.as_deref(),
                             ) {
-                                self.api_client = client;
+                                self.api_client = Arc::new(client);
                             }
                             self.config = Some(config);
-                            self.screen = Screen::Home(HomeState::new());
+                            self.apply_theme();
+                            // New credentials or language mean a fresh list
+                            if let Some(home) = self.saved_home.take() {
+                                self.home_history = Some((home.history, home.recent));
+                            }
+                            self.screen = Screen::Home(Box::new(self.new_home()));
+                            self.start_fetch_problems();
                             self.start_fetch_user_stats();
+                            self.start_scan_workspace();
                         }
                     }
                 }
                 SetupAction::Cancel => {
+                    // Drop the theme being previewed
+                    self.apply_theme();
                     self.restore_home();
                 }
                 SetupAction::BrowserLogin => {
                     self.browser_login();
-                    if let Screen::Setup(ref mut s) = self.screen {
-                        if let Some(ref config) = self.config {
-                            s.fields[3] = config.leetcode_session.clone().unwrap_or_default();
-                            s.fields[4] = config.csrf_token.clone().unwrap_or_default();
-                            s.authenticated = config.is_authenticated();
-                        }
+                    if let Screen::Setup(ref mut s) = self.screen
+                        && let Some(ref config) = self.config
+                    {
+                        s.fields[3].set(config.leetcode_session.clone().unwrap_or_default());
+                        s.fields[4].set(config.csrf_token.clone().unwrap_or_default());
                     }
                 }
+                SetupAction::TestConnection => self.start_test_connection(),
                 SetupAction::Quit => self.should_quit = true,
                 SetupAction::None => {}
             }
@@ -621,33 +1319,73 @@ impl App {
                 HomeAction::OpenDetail(slug) => {
                     self.start_fetch_detail(&slug);
                 }
-                HomeAction::Scaffold(slug) => {
-                    self.start_fetch_detail_for_scaffold(&slug, terminal)?;
+                HomeAction::Scaffold(slug) => match self.existing_solution(&slug) {
+                    Some((file_path, project_dir)) => {
+                        self.open_in_editor(&file_path, &project_dir, terminal, events);
+                    }
+                    None => self.start_fetch_detail_for_scaffold(&slug, terminal)?,
+                },
+                HomeAction::SearchFetch(query) => self.queue_search(query),
+                HomeAction::OpenRandom(slug) => {
+                    self.random_pick = true;
+                    self.start_fetch_detail(&slug);
+                }
+                HomeAction::RandomRemote(avoid) => {
+                    self.random_pick = true;
+                    self.start_fetch_random(avoid);
                 }
-                HomeAction::SearchFetch(query) => {
-                    if let Screen::Home(ref mut state) = self.screen {
-                        state.search_loading = true;
+                HomeAction::JumpToId(id) => {
+                    self.start_jump_fetch(&id);
+                }
+                HomeAction::JumpToSlug(slug) => {
+                    self.start_jump_detail(&slug);
+                }
+                HomeAction::LoadContest(slug) => {
+                    self.start_fetch_contest(slug);
+                }
+                HomeAction::Contests => {
+                    let mut contests = ContestsState::new();
+                    contests.wrap_navigation =
+                        self.config.as_ref().is_some_and(|c| c.wrap_navigation);
+                    let old = std::mem::replace(&mut self.screen, Screen::Contests(contests));
+                    if let Screen::Home(home) = old {
+                        self.saved_home = Some(home);
                     }
-                    self.pending_search_query = Some(query);
-                    self.search_debounce = Some(tokio::time::Instant::now() + std::time::Duration::from_millis(300));
+                    self.start_fetch_contests();
                 }
                 HomeAction::Lists => {
                     // Save home state and switch to lists
-                    let old = std::mem::replace(&mut self.screen, Screen::Lists(ListsState::new()));
+                    let mut lists = ListsState::new();
+                    lists.wrap_navigation = self.config.as_ref().is_some_and(|c| c.wrap_navigation);
+                    let old = std::mem::replace(&mut self.screen, Screen::Lists(lists));
                     if let Screen::Home(home) = old {
                         self.saved_home = Some(home);
                     }
                     self.start_fetch_favorites();
                 }
-                HomeAction::AddToList(question_id) => {
-                    self.open_add_to_list_popup(question_id);
+                HomeAction::AddToList(problems) => {
+                    self.open_add_to_list_popup(problems);
+                }
+                HomeAction::ToggleStar(problem) => self.start_toggle_star(problem),
+                HomeAction::WriteIndex => self.start_write_index(),
+                HomeAction::PickCompany => self.open_company_picker(),
+                HomeAction::ToggleFetchFilter => self.toggle_fetch_filter(),
+                HomeAction::StatusFilter(status) => self.save_status_filter(status),
+                HomeAction::ResumeFetch => self.resume_fetch_problems(),
+                HomeAction::RetryFetch => self.retry_fetch_problems(),
+                HomeAction::ServerFilter { difficulty, reload } => {
+                    self.set_fetch_difficulty(difficulty, reload)
                 }
                 HomeAction::Settings => {
                     let setup_state = match &self.config {
                         Some(c) => SetupState::from_config(c),
                         None => SetupState::new(),
                     };
-                    self.screen = Screen::Setup(setup_state);
+                    // Kept so cancelling lands back on the same row
+                    let old = std::mem::replace(&mut self.screen, Screen::Setup(setup_state));
+                    if let Screen::Home(home) = old {
+                        self.saved_home = Some(home);
+                    }
                 }
                 HomeAction::None => {}
             },
@@ -657,18 +1395,21 @@ impl App {
                     DetailAction::Back => {
                         if let Some(lists) = self.saved_lists.take() {
                             self.screen = Screen::Lists(lists);
+                        } else if let Some(contests) = self.saved_contests.take() {
+                            self.screen = Screen::Contests(contests);
                         } else {
                             self.restore_home();
                         }
                     }
                     DetailAction::Quit => self.should_quit = true,
-                    DetailAction::Scaffold(_) => {
+                    DetailAction::Scaffold | DetailAction::Rescaffold => {
+                        let force = matches!(action, DetailAction::Rescaffold);
                         let detail = if let Screen::Detail(s) = &self.screen {
                             s.detail.clone()
                         } else {
                             unreachable!()
                         };
-                        self.do_scaffold_and_edit(&detail, terminal, events)?;
+                        self.do_scaffold_and_edit(&detail, force, terminal, events)?;
                     }
                     DetailAction::RunCode => {
                         let detail = if let Screen::Detail(s) = &self.screen {
@@ -684,10 +1425,72 @@ impl App {
                         } else {
                             unreachable!()
                         };
-                        self.start_submit_code(&detail);
+                        self.request_submit(detail);
+                    }
+                    DetailAction::ToggleWatch => {
+                        let detail = if let Screen::Detail(s) = &self.screen {
+                            s.detail.clone()
+                        } else {
+                            unreachable!()
+                        };
+                        self.toggle_watch(&detail);
+                    }
+                    DetailAction::TestLocally => {
+                        let detail = if let Screen::Detail(s) = &self.screen {
+                            s.detail.clone()
+                        } else {
+                            unreachable!()
+                        };
+                        self.start_local_test(&detail);
+                    }
+                    DetailAction::AddToList(problem) => {
+                        self.open_add_to_list_popup(vec![problem]);
+                    }
+                    DetailAction::ToggleStar(problem) => self.start_toggle_star(problem),
+                    DetailAction::WriteMarkdown => {
+                        let detail = if let Screen::Detail(s) = &self.screen {
+                            s.detail.clone()
+                        } else {
+                            unreachable!()
+                        };
+                        self.write_markdown(&detail);
+                    }
+                    DetailAction::CopySolution => {
+                        let detail = if let Screen::Detail(s) = &self.screen {
+                            s.detail.clone()
+                        } else {
+                            unreachable!()
+                        };
+                        self.copy_solution(&detail);
+                    }
+                    DetailAction::LoadEditorial(slug) => match self.editorial_cache.get(&slug) {
+                        Some(solution) => {
+                            let lines = detail::editorial_lines(solution.as_ref());
+                            if let Screen::Detail(ref mut state) = self.screen {
+                                state.editorial_lines = Some(lines);
+                            }
+                        }
+                        None => self.start_fetch_editorial(&slug),
+                    },
+                    DetailAction::LoadSolutions { slug, page } => {
+                        self.start_fetch_solutions(&slug, page);
+                    }
+                    DetailAction::LoadSolutionPost { slug, id } => {
+                        self.start_fetch_solution_post(&slug, &id);
                     }
-                    DetailAction::AddToList(question_id) => {
-                        self.open_add_to_list_popup(question_id);
+                    DetailAction::CopyCode { language, code } => match clipboard::copy(&code) {
+                        Ok(()) => {
+                            let what = if language.is_empty() {
+                                "code".to_string()
+                            } else {
+                                format!("{language} code")
+                            };
+                            self.success_message = Some((format!("Copied {what}"), 12));
+                        }
+                        Err(e) => self.error_overlay = Some(format!("{e}")),
+                    },
+                    DetailAction::OpenSimilar(slug) => {
+                        self.start_fetch_detail(&slug);
                     }
                     DetailAction::None => {}
                 }
@@ -695,8 +1498,38 @@ impl App {
             Screen::Result(state) => match state.handle_key(key) {
                 ResultAction::Back => {
                     let detail = state.detail.clone();
-                    self.screen = Screen::Detail(DetailState::new(detail));
+                    self.screen = Screen::Detail(Box::new(DetailState::new(detail)));
+                }
+                ResultAction::AddTestCase(case) => {
+                    let detail = state.detail.clone();
+                    self.add_failing_test(&detail, &case);
+                }
+                ResultAction::Edit => {
+                    let detail = state.detail.clone();
+                    self.do_scaffold_and_edit(&detail, false, terminal, events)?;
+                }
+                ResultAction::Rerun => {
+                    let detail = state.detail.clone();
+                    if matches!(state.kind, ResultKind::LocalTest) {
+                        self.start_local_test(&detail);
+                    } else {
+                        self.start_run_code(&detail);
+                    }
+                }
+                ResultAction::Resubmit => {
+                    let detail = state.detail.clone();
+                    self.request_submit(detail);
+                }
+                ResultAction::ToggleWatch => {
+                    let detail = state.detail.clone();
+                    self.toggle_watch(&detail);
                 }
+                ResultAction::CopyTestcase(input) => match clipboard::copy(&input) {
+                    Ok(()) => {
+                        self.success_message = Some(("Copied failing test case".to_string(), 12));
+                    }
+                    Err(e) => self.error_overlay = Some(format!("{e}")),
+                },
                 ResultAction::Quit => self.should_quit = true,
                 ResultAction::None => {}
             },
@@ -709,21 +1542,45 @@ impl App {
                     ListsAction::OpenDetail(slug) => {
                         self.start_fetch_detail(&slug);
                     }
-                    ListsAction::CreateList(name) => {
-                        self.start_create_list(&name);
+                    ListsAction::CreateList {
+                        name,
+                        description,
+                        public,
+                    } => {
+                        self.start_create_list(name, description, public);
                     }
                     ListsAction::DeleteList(id_hash) => {
                         self.start_delete_list(&id_hash);
                     }
+                    ListsAction::RenameList { id_hash, name } => {
+                        self.start_rename_list(id_hash, name);
+                    }
                     ListsAction::RemoveProblem {
                         id_hash,
+                        list_name,
                         question_id,
                     } => {
-                        self.start_remove_from_list(&id_hash, &question_id);
+                        self.start_update_list(&id_hash, vec![question_id], &list_name, true);
+                    }
+                    ListsAction::FilterHome(list_filter) => {
+                        self.restore_home();
+                        if let Some(home) = self.home_mut() {
+                            home.focus = HomeFocus::Table;
+                            home.set_list_filter(Some(list_filter));
+                        }
+                    }
+                    ListsAction::Retry => {
+                        state.error_message = None;
+                        state.loading = true;
+                        self.start_fetch_favorites();
                     }
                     ListsAction::None => {}
                 }
             }
+            Screen::Contests(state) => {
+                let action = state.handle_key(key);
+                self.handle_contests_action(action);
+            }
             Screen::Setup(_) => {} // handled above
         }
 
@@ -731,15 +1588,24 @@ impl App {
     }
 
     fn handle_tick(&mut self) {
-        // Auto-dismiss success messages
-        if let Some((_, ref mut ticks)) = self.success_message {
-            if *ticks == 0 {
-                self.success_message = None;
-            } else {
-                *ticks -= 1;
+        // Auto-dismiss toasts
+        for toast in [&mut self.success_message, &mut self.warning_message] {
+            if let Some((_, ref mut ticks)) = *toast {
+                if *ticks == 0 {
+                    *toast = None;
+                } else {
+                    *ticks -= 1;
+                }
             }
         }
+        // The undo offer lasts as long as its toast
+        if self.success_message.is_none() {
+            self.list_undo = None;
+        }
 
+        if let Some(ref mut picker) = self.company_picker {
+            picker.spinner_frame = picker.spinner_frame.wrapping_add(1);
+        }
         match &mut self.screen {
             Screen::Home(state) => {
                 state.spinner_frame = state.spinner_frame.wrapping_add(1);
@@ -750,33 +1616,118 @@ impl App {
             Screen::Lists(state) => {
                 state.spinner_frame = state.spinner_frame.wrapping_add(1);
             }
-            _ => {}
+            Screen::Contests(state) => {
+                state.spinner_frame = state.spinner_frame.wrapping_add(1);
+                state.now = contests::unix_now();
+            }
+            _ => {}
         }
     }
 
     fn handle_api_result(&mut self, result: ApiResult) {
         match result {
             ApiResult::Detail(Ok(detail)) => {
-                // Save current screen state before switching to detail
-                let old =
-                    std::mem::replace(&mut self.screen, Screen::Detail(DetailState::new(detail)));
-                match old {
-                    Screen::Home(home) => self.saved_home = Some(home),
-                    Screen::Lists(lists) => self.saved_lists = Some(lists),
-                    _ => {}
+                self.detail_cache
+                    .insert(detail.title_slug.clone(), detail.clone());
+                self.record_recent(&detail);
+                if std::mem::take(&mut self.random_pick) {
+                    self.success_message = Some((
+                        format!(
+                            "Random pick: {}. {}",
+                            detail.frontend_question_id, detail.title
+                        ),
+                        12,
+                    ));
+                    if let Screen::Home(ref mut home) = self.screen {
+                        home.last_random = Some(detail.title_slug.clone());
+                    }
                 }
+                self.show_detail(detail);
             }
             ApiResult::Detail(Err(e)) => {
+                self.random_pick = false;
                 self.error_overlay = Some(format!("Failed to load problem: {e}"));
             }
             ApiResult::RunResult(res) | ApiResult::SubmitResult(res) => {
+                if let Err(ref e) = res
+                    && let Some(wait) = rate_limit_wait(e)
+                {
+                    self.note_rate_limit(wait);
+                }
+                let mut accepted = None;
                 if let Screen::Result(ref mut state) = self.screen {
                     match res {
-                        Ok(resp) => state.set_result(ResultData::from_check(&resp)),
+                        Ok(resp) => {
+                            let data = ResultData::from_check(&resp);
+                            if let Some(ref config) = self.config {
+                                let detail = &state.detail;
+                                let language = config
+                                    .for_problem(&detail.frontend_question_id, &detail.title_slug)
+                                    .language;
+                                let _ = ActivityLog::append(detail, state.kind, &data, &language);
+                                if matches!(state.kind, ResultKind::Submit)
+                                    && data.status_code == 10
+                                {
+                                    let _ = Journal::append(detail, &language);
+                                    accepted = Some((detail.clone(), data.clone()));
+                                }
+                            }
+                            state.set_result(data);
+                        }
+                        Err(e) => state.set_error(describe_error(&e)),
+                    }
+                }
+                if let Some((detail, data)) = accepted {
+                    self.commit_solution(&detail, &data);
+                }
+                if std::mem::take(&mut self.watch_rerun) {
+                    self.fire_watch_run();
+                }
+            }
+            ApiResult::Committed(Ok(true)) => {
+                self.success_message = Some(("Committed the solution".to_string(), 12));
+            }
+            ApiResult::Committed(Ok(false)) => {
+                self.success_message = Some(("Nothing new to commit".to_string(), 12));
+            }
+            ApiResult::Committed(Err(e)) => {
+                self.error_overlay = Some(format!("Couldn't commit the solution: {e}"));
+            }
+            ApiResult::RepoReady(Ok(_)) => {}
+            ApiResult::RepoReady(Err(e)) => {
+                self.error_overlay = Some(format!("git init failed: {e}"));
+            }
+            ApiResult::IndexWritten(Ok((path, count))) => {
+                let plural = if count == 1 { "" } else { "s" };
+                self.success_message = Some((
+                    format!("Indexed {count} problem{plural} in {}", path.display()),
+                    15,
+                ));
+            }
+            ApiResult::IndexWritten(Err(e)) => {
+                self.error_overlay = Some(format!("Failed to write the index: {e}"));
+            }
+            ApiResult::SolutionChanged(slug) => {
+                if self.watch.as_ref().is_some_and(|w| w.slug == slug) {
+                    self.watch_debounce =
+                        Some(tokio::time::Instant::now() + std::time::Duration::from_millis(300));
+                }
+            }
+            ApiResult::LocalTest(res) => {
+                if let Screen::Result(ref mut state) = self.screen
+                    && matches!(state.kind, ResultKind::LocalTest)
+                {
+                    match res {
+                        Ok(run) => state.set_local_result(run),
                         Err(e) => state.set_error(format!("{e}")),
                     }
                 }
             }
+            ApiResult::Scaffolded(slugs) => {
+                if let Some(home) = self.home_mut() {
+                    home.set_scaffolded(slugs);
+                }
+            }
             ApiResult::UserStats(stats) => {
                 let state = if let Screen::Home(ref mut s) = self.screen {
                     Some(s)
@@ -787,6 +1738,16 @@ impl App {
                     state.user_stats = stats;
                 }
             }
+            ApiResult::ConnectionChecked(session, csrf, result) => {
+                // A result for values since edited away is simply not shown
+                if let Screen::Setup(ref mut state) = self.screen {
+                    let status = match result {
+                        Ok(name) => ConnectionStatus::LoggedIn(name),
+                        Err(e) => ConnectionStatus::Failed(describe_error(&e)),
+                    };
+                    state.connection = Some((session, csrf, status));
+                }
+            }
             ApiResult::AuthExpired => {
                 // Tokens exist but are invalid/expired — clear them and prompt re-login
                 if let Some(ref mut config) = self.config {
@@ -796,59 +1757,302 @@ impl App {
                 }
                 self.login_prompt = true;
             }
-            ApiResult::SearchResult(Ok((problems, total))) => {
+            ApiResult::ProblemBatch {
+                problems,
+                total,
+                done,
+            } => {
                 let state = if let Screen::Home(ref mut s) = self.screen {
                     Some(s)
                 } else {
                     self.saved_home.as_mut()
                 };
                 if let Some(state) = state {
-                    state.problems = problems;
-                    state.search_total = total;
-                    state.search_loading = false;
+                    state.total_problems = total;
+                    state.loading = !done;
+                    state.fetched += problems.len();
                     state.error_message = None;
-                    state.rebuild_filter();
-                    if !state.filtered_indices.is_empty() {
-                        state.table_state.select(Some(0));
+                    // A cached list stays up until the refresh is complete;
+                    // otherwise show rows as they arrive
+                    if state.from_cache {
+                        self.fetched_problems.extend(problems);
+                    } else {
+                        state.merge_problems(problems);
+                    }
+                    if done {
+                        if state.from_cache {
+                            state.replace_problems(std::mem::take(&mut self.fetched_problems));
+                        }
+                        let problems = state.problems.clone();
+                        let key = self.fetch_filter().cache_key();
+                        tokio::task::spawn_blocking(move || {
+                            let _ = cache::save_problems_cache(&problems, key.as_deref());
+                        });
                     }
                 }
             }
-            ApiResult::SearchResult(Err(e)) => {
+            ApiResult::RateLimited(wait) => {
+                self.note_rate_limit(wait);
+                self.warning_message =
+                    Some((format!("Rate limited, retrying in {}s", wait.as_secs()), 15));
+            }
+            ApiResult::ProblemFetchError(e) => {
                 let state = if let Screen::Home(ref mut s) = self.screen {
                     Some(s)
                 } else {
                     self.saved_home.as_mut()
                 };
                 if let Some(state) = state {
-                    state.search_loading = false;
-                    state.error_message = Some(format!("{e}"));
+                    state.loading = false;
+                    // Rows (and a refresh's pending batches) are kept so
+                    // `R` can resume from `fetched`
+                    if state.problems.is_empty() {
+                        state.error_message = Some(e.clone());
+                    }
+                    state.fetch_error = Some(e);
+                }
+            }
+            ApiResult::SearchResult(query, result) => {
+                // A later search is in flight; its answer is the one to show
+                if self.latest_search.as_ref() != Some(&query) {
+                    return;
+                }
+                self.latest_search = None;
+                if let Err(ref e) = result
+                    && let Some(wait) = rate_limit_wait(e)
+                {
+                    self.note_rate_limit(wait);
+                }
+                let waiting = self.pending_search_query.is_some();
+                let Some(state) = self.home_mut() else {
+                    return;
+                };
+                state.search_loading = waiting;
+                match result {
+                    Ok((problems, _)) => state.merge_problems(problems),
+                    Err(e) => state.error_message = Some(describe_error(&e)),
+                }
+            }
+            ApiResult::JumpProblem(id, res) => {
+                let state = if let Screen::Home(ref mut s) = self.screen {
+                    Some(s)
+                } else {
+                    self.saved_home.as_mut()
+                };
+                if let Some(state) = state {
+                    let found = res.ok().and_then(|(problems, _)| {
+                        problems.into_iter().find(|p| p.frontend_question_id == id)
+                    });
+                    state.finish_jump(&id, found);
+                }
+            }
+            ApiResult::JumpDetail(Ok(detail)) => {
+                self.record_recent(&detail);
+                if let Screen::Home(ref mut home) = self.screen {
+                    home.jump = None;
+                }
+                self.show_detail(detail);
+            }
+            ApiResult::JumpDetail(Err(_)) => {
+                if let Screen::Home(ref mut state) = self.screen {
+                    state.jump_missed();
+                }
+            }
+            ApiResult::LaunchDetail(Ok(detail)) => {
+                self.record_recent(&detail);
+                // Nothing is stashed, so Back builds and loads Home then
+                self.screen = Screen::Detail(Box::new(DetailState::new(detail)));
+            }
+            ApiResult::LaunchDetail(Err(e)) => {
+                self.error_overlay = Some(describe_error(&e));
+                self.start_loading_home();
+            }
+            ApiResult::CompanyTags(res) => match res {
+                Ok(tags) => {
+                    if let Some(ref mut picker) = self.company_picker {
+                        picker.set_companies(tags.clone());
+                    }
+                    self.company_tags = Some(tags);
+                }
+                Err(e) => {
+                    if let Some(ref mut picker) = self.company_picker {
+                        picker.failed(describe_error(&e));
+                    }
+                }
+            },
+            ApiResult::CompanyProblems(period, res) => self.show_company(period, res),
+            ApiResult::Contest(res) => {
+                if let Some(home) = self.home_mut() {
+                    match res {
+                        Ok((title, slugs, missing)) => home.show_contest(title, slugs, missing),
+                        Err(e) => home.jump_failed(describe_error(&e)),
+                    }
+                }
+            }
+            ApiResult::Contests(res) => {
+                if let Some(state) = self.contests_mut() {
+                    state.loading = false;
+                    match res {
+                        Ok(list) => state.set_contests(list),
+                        Err(e) => state.error_message = Some(describe_error(&e)),
+                    }
+                }
+            }
+            ApiResult::ContestProblems(slug, res) => {
+                if let Some(state) = self.contests_mut() {
+                    let problems = match res {
+                        Ok(questions) => ContestProblems::Loaded(questions),
+                        Err(e) => ContestProblems::Failed(describe_error(&e)),
+                    };
+                    state.set_problems(slug, problems);
+                }
+            }
+            ApiResult::Editorial(slug, res) => {
+                if let Ok(ref solution) = res {
+                    self.editorial_cache.insert(slug.clone(), solution.clone());
+                }
+                if let Screen::Detail(ref mut state) = self.screen
+                    && state.detail.title_slug == slug
+                {
+                    match res {
+                        Ok(solution) => {
+                            state.editorial_lines =
+                                Some(detail::editorial_lines(solution.as_ref()));
+                        }
+                        Err(e) => {
+                            state.close_editorial();
+                            self.error_overlay = Some(format!("Failed to load editorial: {e}"));
+                        }
+                    }
+                }
+            }
+            ApiResult::Solutions(slug, page, res) => {
+                if let Screen::Detail(ref mut state) = self.screen
+                    && state.detail.title_slug == slug
+                    && let Some(ref mut solutions) = state.solutions
+                {
+                    match res {
+                        Ok((list, total)) => solutions.set_page(page, list, total),
+                        Err(e) => solutions.failed(describe_error(&e)),
+                    }
+                }
+            }
+            ApiResult::SolutionPost(slug, id, res) => {
+                let language = match (&self.screen, &self.config) {
+                    (Screen::Detail(state), Some(config)) => {
+                        config
+                            .for_problem(&state.detail.frontend_question_id, &slug)
+                            .language
+                    }
+                    _ => String::new(),
+                };
+                if let Screen::Detail(ref mut state) = self.screen
+                    && state.detail.title_slug == slug
+                    && let Some(ref mut solutions) = state.solutions
+                {
+                    match res {
+                        Ok(content) => solutions.set_post(&id, &content, &language),
+                        Err(e) => solutions.post_failed(&id, &describe_error(&e)),
+                    }
+                }
+            }
+            ApiResult::Preview(slug, res) => {
+                if self.pending_preview.as_deref() == Some(&slug) {
+                    self.pending_preview = None;
+                }
+                match res {
+                    Ok(detail) => {
+                        self.detail_cache.insert(slug, detail);
+                    }
+                    Err(e) => {
+                        let state = if let Screen::Home(ref mut s) = self.screen {
+                            Some(s)
+                        } else {
+                            self.saved_home.as_mut()
+                        };
+                        if let Some(state) = state {
+                            state.preview = Some(home::Preview {
+                                slug,
+                                lines: vec![Line::from(Span::styled(
+                                    format!(" Failed to load preview: {e}"),
+                                    Style::default().fg(theme().error),
+                                ))],
+                            });
+                        }
+                    }
                 }
             }
             ApiResult::Favorites(Ok(lists)) => {
-                if let Screen::Lists(ref mut state) = self.screen {
-                    state.lists = lists;
+                let problems = self.listed_problems(&lists);
+                let missing: HashSet<String> = lists
+                    .iter()
+                    .flat_map(|l| &l.questions)
+                    .filter(|q| !problems.contains_key(&q.title_slug))
+                    .map(|q| q.title_slug.clone())
+                    .collect();
+                if !missing.is_empty() {
+                    self.start_load_cached_listed(missing);
+                }
+                if let Some(state) = self.lists_mut() {
+                    state.problems = problems;
+                    state.set_lists(lists);
                     state.loading = false;
                     state.error_message = None;
-                    if !state.lists.is_empty() && state.list_table_state.selected().is_none() {
-                        state.list_table_state.select(Some(0));
+                }
+            }
+            ApiResult::CachedListed(rows) => {
+                if let Some(state) = self.lists_mut() {
+                    for (slug, problem) in rows {
+                        state.problems.entry(slug).or_insert(problem);
                     }
                 }
             }
             ApiResult::Favorites(Err(e)) => {
-                if let Screen::Lists(ref mut state) = self.screen {
+                if let Some(state) = self.lists_mut() {
                     state.loading = false;
-                    state.error_message = Some(format!("{e}"));
+                    state.error_message = Some(describe_error(&e));
                 }
             }
             ApiResult::ListMutation(Ok(()), msg) => {
                 self.success_message = Some((msg, 12)); // ~2 seconds at 5 ticks/sec
-                if matches!(self.screen, Screen::Lists(_)) {
+                if self.lists_mut().is_some() {
+                    self.start_fetch_favorites();
+                }
+            }
+            ApiResult::Starred(slug, Ok(starred)) => {
+                if let Some(home) = self.home_mut()
+                    && let Some(p) = home.problems.iter_mut().find(|p| p.title_slug == slug)
+                {
+                    p.is_favor = starred;
+                }
+                let msg = if starred {
+                    format!("{} Starred", icons().star)
+                } else {
+                    "Unstarred".to_string()
+                };
+                self.success_message = Some((msg.to_string(), 12));
+                if self.lists_mut().is_some() {
                     self.start_fetch_favorites();
                 }
             }
+            ApiResult::Starred(_, Err(e)) => {
+                self.error_overlay =
+                    Some(format!("Failed to star problem: {}", describe_error(&e)));
+            }
             ApiResult::ListMutation(Err(e), _) => {
                 self.error_overlay = Some(format!("{e}"));
             }
+            ApiResult::ListRemoval(Ok(()), msg, undo) => {
+                self.success_message =
+                    Some((format!("{msg} \u{2014} press u to undo"), UNDO_TICKS));
+                self.list_undo = Some(undo);
+                if self.lists_mut().is_some() {
+                    self.start_fetch_favorites();
+                }
+            }
+            ApiResult::ListRemoval(Err(e), _, _) => {
+                self.error_overlay = Some(format!("{e}"));
+            }
             ApiResult::PopupFavorites(Ok(lists)) => {
                 if let Some(ref mut popup) = self.add_to_list_popup {
                     popup.lists = lists;
@@ -862,12 +2066,192 @@ impl App {
         }
     }
 
+    /// Open `detail`, keeping Home, Lists or Contests (with their
+    /// selection and scroll) for Back.
+    fn show_detail(&mut self, detail: QuestionDetail) {
+        let old = std::mem::replace(
+            &mut self.screen,
+            Screen::Detail(Box::new(DetailState::new(detail))),
+        );
+        match old {
+            Screen::Home(home) => self.saved_home = Some(home),
+            Screen::Lists(lists) => self.saved_lists = Some(lists),
+            Screen::Contests(contests) => self.saved_contests = Some(contests),
+            _ => {}
+        }
+    }
+
     fn restore_home(&mut self) {
         if let Some(home) = self.saved_home.take() {
             self.screen = Screen::Home(home);
         } else {
-            self.screen = Screen::Home(HomeState::new());
+            self.screen = Screen::Home(Box::new(self.new_home()));
+            self.start_loading_home();
+        }
+    }
+
+    /// Everything Home shows that has to be fetched or scanned.
+    fn start_loading_home(&mut self) {
+        self.start_fetch_problems();
+        self.start_fetch_user_stats();
+        self.start_scan_workspace();
+    }
+
+    fn new_home(&mut self) -> HomeState {
+        let (history, recent) = self
+            .home_history
+            .take()
+            .unwrap_or_else(|| (SearchHistory::load(), RecentProblems::load()));
+        let mut home = HomeState::new(history, recent);
+        home.offline = self.offline;
+        if let Some(ref config) = self.config {
+            home.preview_min_width = config.preview_min_width;
+            home.wrap_navigation = config.wrap_navigation;
+            if let Some(ref difficulty) = config.fetch_difficulty {
+                home.filter.restrict_download(difficulty);
+            }
+            if let Some(status) = config
+                .status_filter
+                .as_deref()
+                .and_then(StatusFilter::from_config)
+            {
+                home.filter.status = status;
+            }
+        }
+        home
+    }
+
+    /// Remember the Home status filter for the next session.
+    fn save_status_filter(&mut self, status: StatusFilter) {
+        let Some(ref mut config) = self.config else {
+            return;
+        };
+        config.status_filter = status.config_name().map(String::from);
+        if let Err(e) = config.save() {
+            self.error_overlay = Some(format!("Failed to save config: {e}"));
+        }
+    }
+
+    /// The one difficulty of the problem list that gets downloaded, if
+    /// the config narrows it to one LeetCode knows.
+    fn fetch_difficulty(&self) -> Option<String> {
+        let difficulty = self
+            .config
+            .as_ref()?
+            .fetch_difficulty
+            .as_deref()?
+            .to_lowercase();
+        ["easy", "medium", "hard"]
+            .contains(&difficulty.as_str())
+            .then_some(difficulty)
+    }
+
+    /// What the config narrows the problem download to, lifted or not.
+    fn configured_fetch_filter(&self) -> FetchFilter {
+        let Some(ref config) = self.config else {
+            return FetchFilter::default();
+        };
+        FetchFilter {
+            // The API wants `HARD`
+            difficulty: self.fetch_difficulty().map(|d| d.to_uppercase()),
+            tags: config
+                .fetch_tags
+                .iter()
+                .map(|t| t.trim().to_lowercase())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            list: config.fetch_list.clone().filter(|l| !l.trim().is_empty()),
+        }
+    }
+
+    /// The filter the problem list is downloaded and cached with.
+    fn fetch_filter(&self) -> FetchFilter {
+        if self.fetch_filter_lifted {
+            FetchFilter::default()
+        } else {
+            self.configured_fetch_filter()
+        }
+    }
+
+    /// `D` on Home: download the whole list for this session, or go back
+    /// to the configured server-side filter.
+    fn toggle_fetch_filter(&mut self) {
+        self.fetch_filter_lifted = !self.fetch_filter_lifted;
+        let lifted = self.fetch_filter_lifted;
+        let difficulty = self.fetch_difficulty();
+        let Some(state) = self.home_mut() else {
+            return;
+        };
+        match difficulty {
+            Some(d) if !lifted => state.filter.restrict_download(&d),
+            _ => state.filter.show_all_difficulties(),
+        }
+        state.replace_problems(Vec::new());
+        state.error_message = None;
+        state.loading = true;
+        self.success_message = Some((
+            if lifted {
+                "Downloading the whole problem list"
+            } else {
+                "Server-side filter back on"
+            }
+            .to_string(),
+            12,
+        ));
+        self.start_fetch_problems();
+    }
+
+    /// Point the Home preview at the selected problem: straight from the
+    /// cache when possible, otherwise via a debounced fetch so scrolling
+    /// through the table doesn't fire a request per row.
+    fn sync_preview(&mut self) {
+        let Screen::Home(ref mut state) = self.screen else {
+            return;
+        };
+        if !state.preview_visible {
+            return;
         }
+        let Some(slug) = state.selected_problem().map(|p| p.title_slug.clone()) else {
+            return;
+        };
+        if state.preview.as_ref().is_some_and(|p| p.slug == slug) {
+            return;
+        }
+        if let Some(detail) = self.detail_cache.get(&slug) {
+            state.preview = Some(home::Preview {
+                lines: detail::statement_lines(detail),
+                slug,
+            });
+            return;
+        }
+        if self.pending_preview.as_deref() != Some(&slug) {
+            self.pending_preview = Some(slug);
+            self.preview_debounce =
+                Some(tokio::time::Instant::now() + std::time::Duration::from_millis(250));
+        }
+    }
+
+    fn fire_preview(&mut self) {
+        self.preview_debounce = None;
+        let Some(slug) = self.pending_preview.clone() else {
+            return;
+        };
+        if self.offline {
+            let result = self
+                .cached_detail(&slug)
+                .ok_or_else(|| Self::offline_miss(&slug));
+            let _ = self.api_tx.send(ApiResult::Preview(slug, result));
+            return;
+        }
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("loading preview");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let result = fetch_and_cache_detail(&*client, &slug).await;
+            let _ = tx.send(ApiResult::Preview(slug, result));
+        });
     }
 
     fn fire_search(&mut self) {
@@ -875,9 +2259,6 @@ impl App {
         if let Some(query) = self.pending_search_query.take() {
             if query.is_empty() {
                 if let Screen::Home(ref mut state) = self.screen {
-                    state.problems.clear();
-                    state.filtered_indices.clear();
-                    state.search_total = 0;
                     state.search_loading = false;
                 }
                 return;
@@ -886,92 +2267,627 @@ impl App {
         }
     }
 
-    fn start_search_fetch(&self, query: &str) {
-        let client = self.api_client.clone();
-        let tx = self.api_tx.clone();
-        let query = query.to_string();
+    /// Show the cached problem list right away, then download the whole
+    /// list in batches. Offline, the cache is all there is.
+    fn start_fetch_problems(&mut self) {
+        let cached = cache::load_cached_problems(self.fetch_filter().cache_key().as_deref());
+        let configured = self.configured_fetch_filter();
+        let lifted = self.fetch_filter_lifted;
+        let state = if let Screen::Home(ref mut s) = self.screen {
+            Some(s)
+        } else {
+            self.saved_home.as_mut()
+        };
+        if let Some(state) = state {
+            state.server_filter = (!configured.is_empty()).then(|| configured.describe());
+            state.server_filter_lifted = lifted;
+            if let Some(cached) = cached {
+                state.total_problems = cached.len() as i32;
+                state.from_cache = true;
+                state.merge_problems(cached);
+            }
+            state.fetched = 0;
+            state.fetch_error = None;
+            if self.offline {
+                state.loading = false;
+                if state.problems.is_empty() {
+                    state.error_message =
+                        Some("Offline, and no problem list has been cached yet".to_string());
+                }
+            }
+        }
+        if self.offline {
+            return;
+        }
+        self.fetched_problems.clear();
+        self.spawn_problem_fetch(0);
+    }
 
-        tokio::spawn(async move {
-            let result = client.fetch_problems(50, 0, None, Some(&query)).await;
-            let _ = tx.send(ApiResult::SearchResult(result));
-        });
+    /// Start over after the Home error state. A download that's still
+    /// running isn't started twice.
+    fn retry_fetch_problems(&mut self) {
+        let Some(state) = self.home_mut() else {
+            return;
+        };
+        state.error_message = None;
+        if !state.loading {
+            state.loading = true;
+            self.start_fetch_problems();
+        }
     }
 
-    fn start_fetch_favorites(&self) {
-        let client = self.api_client.clone();
-        let tx = self.api_tx.clone();
+    /// Remember whether to download one difficulty or the whole list.
+    /// Narrowing keeps the rows already loaded, and caches them under the
+    /// difficulty for next time; widening downloads the rest unless it's
+    /// already on screen.
+    fn set_fetch_difficulty(&mut self, difficulty: Option<&'static str>, reload: bool) {
+        if let Some(ref mut config) = self.config {
+            config.fetch_difficulty = difficulty.map(str::to_lowercase);
+            if let Err(e) = config.save() {
+                self.error_overlay = Some(format!("Failed to save config: {e}"));
+            }
+        }
+        let configured = self.configured_fetch_filter();
+        // While lifted the rows are the whole list, which isn't what the
+        // configured filter's cache should hold
+        let key = (!self.fetch_filter_lifted).then(|| configured.cache_key());
+        let Some(state) = self.home_mut() else {
+            return;
+        };
+        state.server_filter = (!configured.is_empty()).then(|| configured.describe());
+        if let Some(difficulty) = difficulty {
+            let problems: Vec<ProblemSummary> = state
+                .problems
+                .iter()
+                .filter(|p| p.difficulty == difficulty)
+                .cloned()
+                .collect();
+            if let Some(key) = key {
+                tokio::task::spawn_blocking(move || {
+                    let _ = cache::save_problems_cache(&problems, key.as_deref());
+                });
+            }
+        } else if reload {
+            state.replace_problems(Vec::new());
+            state.error_message = None;
+            state.loading = true;
+            self.start_fetch_problems();
+        }
+    }
 
-        tokio::spawn(async move {
-            let result = client.fetch_favorites().await;
-            let _ = tx.send(ApiResult::Favorites(result));
-        });
+    /// Pick a failed list download back up after the rows it already got.
+    fn resume_fetch_problems(&mut self) {
+        let Some(state) = self.home_mut() else {
+            return;
+        };
+        if state.fetch_error.take().is_none() {
+            return;
+        }
+        state.loading = true;
+        state.error_message = None;
+        let skip = state.fetched as i32;
+        self.spawn_problem_fetch(skip);
     }
 
-    fn start_create_list(&self, name: &str) {
+    /// Download the problem list from `skip` on. After the first batch
+    /// gives the total, `fetch_concurrency` above 1 requests the rest
+    /// several at a time, still handing them over in order.
+    fn spawn_problem_fetch(&self, skip: i32) {
+        let (batch, concurrency) = self
+            .config
+            .as_ref()
+            .map_or((100, 1), |c| (c.fetch_batch(), c.fetch_parallelism()));
         let client = self.api_client.clone();
         let tx = self.api_tx.clone();
-        let name = name.to_string();
+        let task = self.tasks.start("loading problems");
+        let filter = self.fetch_filter();
 
         tokio::spawn(async move {
-            let msg = format!("List \"{}\" created", name);
-            let result = client.create_favorite_list(&name).await;
-            let _ = tx.send(ApiResult::ListMutation(result, msg));
+            let _task = task;
+            let started = std::time::Instant::now();
+            let mut skip = skip;
+            loop {
+                match fetch_problem_batch(&*client, &tx, batch, skip, &filter).await {
+                    Ok((problems, total)) => {
+                        skip += problems.len() as i32;
+                        let done = problems.is_empty() || skip >= total;
+                        let _ = tx.send(ApiResult::ProblemBatch {
+                            problems,
+                            total,
+                            done,
+                        });
+                        if done {
+                            break;
+                        }
+                        if concurrency > 1 {
+                            fetch_problems_concurrently(
+                                client,
+                                tx,
+                                batch,
+                                skip,
+                                total,
+                                concurrency,
+                                filter,
+                            )
+                            .await;
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(ApiResult::ProblemFetchError(e));
+                        break;
+                    }
+                }
+            }
+            tracing::info!(batch, concurrency, elapsed = ?started.elapsed(), "problem list downloaded");
         });
     }
 
-    fn start_delete_list(&self, id_hash: &str) {
+    fn start_search_fetch(&mut self, query: &str) {
         let client = self.api_client.clone();
         let tx = self.api_tx.clone();
-        let id_hash = id_hash.to_string();
+        let query = query.to_string();
+        self.latest_search = Some(query.clone());
+        let task = self.tasks.start("searching");
 
         tokio::spawn(async move {
-            let result = client.delete_favorite_list(&id_hash).await;
-            let _ = tx.send(ApiResult::ListMutation(result, "List deleted".into()));
+            let _task = task;
+            let result = client
+                .fetch_problems(50, 0, &FetchFilter::default(), Some(&query))
+                .await;
+            let _ = tx.send(ApiResult::SearchResult(query, result));
         });
     }
 
-    fn start_remove_from_list(&self, id_hash: &str, question_id: &str) {
+    fn start_jump_fetch(&mut self, id: &str) {
+        if self.offline {
+            if let Screen::Home(ref mut state) = self.screen {
+                state.jump_missed();
+            }
+            return;
+        }
         let client = self.api_client.clone();
         let tx = self.api_tx.clone();
-        let id_hash = id_hash.to_string();
-        let question_id = question_id.to_string();
+        let id = id.to_string();
+        let task = self.tasks.start("jumping");
 
         tokio::spawn(async move {
-            let result = client.remove_from_favorite(&id_hash, &question_id).await;
-            let _ = tx.send(ApiResult::ListMutation(result, "Removed from list".into()));
+            let _task = task;
+            let result = client
+                .fetch_problems(50, 0, &FetchFilter::default(), Some(&id))
+                .await;
+            let _ = tx.send(ApiResult::JumpProblem(id, result));
         });
     }
 
-    fn open_add_to_list_popup(&mut self, question_id: String) {
-        self.add_to_list_popup = Some(AddToListPopup {
-            lists: Vec::new(),
-            selected: 0,
-            question_id,
-            loading: true,
-        });
-
+    fn start_jump_detail(&self, slug: &str) {
+        if self.offline {
+            let result = self
+                .cached_detail(slug)
+                .ok_or_else(|| Self::offline_miss(slug));
+            let _ = self.api_tx.send(ApiResult::JumpDetail(result));
+            return;
+        }
         let client = self.api_client.clone();
         let tx = self.api_tx.clone();
+        let slug = slug.to_string();
+        let task = self.tasks.start("jumping");
+
         tokio::spawn(async move {
-            let result = client.fetch_favorites().await;
-            let _ = tx.send(ApiResult::PopupFavorites(result));
+            let _task = task;
+            let result = fetch_and_cache_detail(&*client, &slug).await;
+            let _ = tx.send(ApiResult::JumpDetail(result));
         });
     }
 
-    fn start_add_to_list(&self, id_hash: &str, question_id: &str, list_name: &str) {
+    /// Resolve the problem given on the command line, a slug or a problem
+    /// number, and fetch its detail without downloading the problem list.
+    fn start_launch_detail(&mut self, problem: String) {
+        let number = problem.chars().all(|c| c.is_ascii_digit());
+        if self.offline {
+            let slug = if number {
+                cache::load_cached_problems(self.fetch_filter().cache_key().as_deref())
+                    .and_then(|ps| ps.into_iter().find(|p| p.frontend_question_id == problem))
+                    .map(|p| p.title_slug)
+            } else {
+                Some(problem.to_lowercase())
+            };
+            let result = slug
+                .and_then(|slug| self.cached_detail(&slug))
+                .ok_or_else(|| Self::offline_miss(&problem));
+            let _ = self.api_tx.send(ApiResult::LaunchDetail(result));
+            return;
+        }
         let client = self.api_client.clone();
         let tx = self.api_tx.clone();
-        let id_hash = id_hash.to_string();
-        let question_id = question_id.to_string();
-        let msg = format!("Added to \"{}\"", list_name);
+        let task = self.tasks.start("opening problem");
 
         tokio::spawn(async move {
-            let result = client.add_to_favorite(&id_hash, &question_id).await;
-            let _ = tx.send(ApiResult::ListMutation(result, msg));
-        });
-    }
+            let _task = task;
+            let result = async {
+                let slug = if number {
+                    let (problems, _) = client
+                        .fetch_problems(50, 0, &FetchFilter::default(), Some(&problem))
+                        .await?;
+                    problems
+                        .into_iter()
+                        .find(|p| p.frontend_question_id == problem)
+                        .map(|p| p.title_slug)
+                        .with_context(|| format!("No problem number {problem}"))?
+                } else {
+                    problem.to_lowercase()
+                };
+                fetch_and_cache_detail(&*client, &slug)
+                    .await
+                    .with_context(|| format!("Couldn't open '{slug}'"))
+            }
+            .await;
+            let _ = tx.send(ApiResult::LaunchDetail(result));
+        });
+    }
+
+    /// Fetch a contest, then the summaries of any of its problems Home
+    /// doesn't know yet (recent contests aren't in the cached list).
+    fn start_fetch_contest(&mut self, slug: String) {
+        if self.offline {
+            if let Screen::Home(ref mut state) = self.screen {
+                state.jump_failed("Offline, contests need a connection".to_string());
+            }
+            return;
+        }
+        let known: HashSet<String> = match self.screen {
+            Screen::Home(ref home) => home.problems.iter().map(|p| p.title_slug.clone()).collect(),
+            _ => HashSet::new(),
+        };
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("loading contest");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let result = async {
+                let (contest, questions) = client.fetch_contest(&slug).await?;
+                let mut missing = Vec::new();
+                for q in questions.iter().filter(|q| !known.contains(&q.title_slug)) {
+                    let detail = fetch_and_cache_detail(&*client, &q.title_slug)
+                        .await
+                        .with_context(|| format!("Failed to load {}", q.title))?;
+                    missing.push(detail.summary());
+                }
+                let slugs = questions.into_iter().map(|q| q.title_slug).collect();
+                Ok((contest.title, slugs, missing))
+            }
+            .await;
+            let _ = tx.send(ApiResult::Contest(result));
+        });
+    }
+
+    fn handle_contests_action(&mut self, action: ContestsAction) {
+        match action {
+            ContestsAction::Back => self.restore_home(),
+            ContestsAction::OpenDetail(slug) => self.start_fetch_detail(&slug),
+            ContestsAction::LoadProblems(slug) => self.start_fetch_contest_problems(slug),
+            ContestsAction::OpenPage(url) => {
+                if let Err(e) = open_in_browser(&url) {
+                    self.error_overlay = Some(format!("Failed to open {url}: {e}"));
+                }
+            }
+            ContestsAction::FilterHome(slug) => {
+                self.restore_home();
+                if let Some(home) = self.home_mut() {
+                    home.open_contest(&slug);
+                }
+                self.start_fetch_contest(slug);
+            }
+            ContestsAction::Retry => {
+                if let Some(state) = self.contests_mut() {
+                    state.error_message = None;
+                    state.loading = true;
+                }
+                self.start_fetch_contests();
+            }
+            ContestsAction::None => {}
+        }
+    }
+
+    fn start_fetch_contests(&mut self) {
+        if self.offline {
+            if let Some(state) = self.contests_mut() {
+                state.loading = false;
+                state.error_message = Some("Offline, contests need a connection".to_string());
+            }
+            return;
+        }
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("loading contests");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let _ = tx.send(ApiResult::Contests(client.fetch_contests().await));
+        });
+    }
+
+    /// Fetch a past contest's problems to list under it on Contests.
+    fn start_fetch_contest_problems(&mut self, slug: String) {
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("loading contest");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let result = client
+                .fetch_contest(&slug)
+                .await
+                .map(|(_, questions)| questions);
+            let _ = tx.send(ApiResult::ContestProblems(slug, result));
+        });
+    }
+
+    /// Open the company picker, fetching the companies the first time.
+    fn open_company_picker(&mut self) {
+        let mut picker = CompanyPicker::new(CompanyPeriod::SixMonths);
+        if self.offline {
+            picker.failed("Offline, company lists need a connection".to_string());
+        } else if !self.config.as_ref().is_some_and(|c| c.is_authenticated()) {
+            picker.failed("Log in with a Premium account to browse companies".to_string());
+        } else if let Some(ref tags) = self.company_tags {
+            picker.set_companies(tags.clone());
+        } else {
+            let client = self.api_client.clone();
+            let tx = self.api_tx.clone();
+            let task = self.tasks.start("loading companies");
+            tokio::spawn(async move {
+                let _task = task;
+                let _ = tx.send(ApiResult::CompanyTags(client.fetch_company_tags().await));
+            });
+        }
+        self.company_picker = Some(picker);
+    }
+
+    fn start_fetch_company_problems(&mut self, slug: String, period: CompanyPeriod) {
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("loading company");
+        tokio::spawn(async move {
+            let _task = task;
+            let result = client.fetch_company_problems(&slug).await;
+            let _ = tx.send(ApiResult::CompanyProblems(period, result));
+        });
+    }
+
+    /// Narrow Home to the problems a company asked in `period`, or say in
+    /// the picker why that can't be done.
+    fn show_company(&mut self, period: CompanyPeriod, res: Result<(String, Vec<CompanyProblem>)>) {
+        let Some(picker) = self.company_picker.as_mut() else {
+            return;
+        };
+        let (name, problems) = match res {
+            Ok(found) => found,
+            Err(e) => {
+                picker.failed(describe_error(&e));
+                return;
+            }
+        };
+        let asked: Vec<ProblemSummary> = problems
+            .into_iter()
+            .filter(|p| p.frequency_in(period) > 0.0)
+            .map(|p| p.problem)
+            .collect();
+        if asked.is_empty() {
+            picker.failed(format!("No {name} problems in the last {}", period.label()));
+            return;
+        }
+        self.company_picker = None;
+        let Some(home) = self.home_mut() else {
+            return;
+        };
+        let known: HashSet<String> = home.problems.iter().map(|p| p.title_slug.clone()).collect();
+        let slugs = asked.iter().map(|p| p.title_slug.clone()).collect();
+        let missing = asked
+            .into_iter()
+            .filter(|p| !known.contains(&p.title_slug))
+            .collect();
+        home.show_company(format!("{name}, {}", period.label()), slugs, missing);
+    }
+
+    fn start_fetch_favorites(&self) {
+        if self.offline {
+            let _ = self.api_tx.send(ApiResult::Favorites(Err(anyhow::anyhow!(
+                "Offline mode: lists need a connection"
+            ))));
+            return;
+        }
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("loading lists");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let result = client.fetch_favorites().await;
+            let _ = tx.send(ApiResult::Favorites(result));
+        });
+    }
+
+    fn start_create_list(&self, name: String, description: Option<String>, public: bool) {
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("updating lists");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let msg = format!("List \"{}\" created", name);
+            let result = client
+                .create_favorite_list(&name, description.as_deref(), public)
+                .await;
+            let _ = tx.send(ApiResult::ListMutation(result, msg));
+        });
+    }
+
+    fn start_rename_list(&self, id_hash: String, name: String) {
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("updating lists");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let result = client.rename_favorite_list(&id_hash, &name).await;
+            let _ = tx.send(ApiResult::ListMutation(result, "List renamed".into()));
+        });
+    }
+
+    fn start_delete_list(&self, id_hash: &str) {
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let id_hash = id_hash.to_string();
+        let task = self.tasks.start("updating lists");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let result = client.delete_favorite_list(&id_hash).await;
+            let _ = tx.send(ApiResult::ListMutation(result, "List deleted".into()));
+        });
+    }
+
+    fn start_toggle_star(&mut self, problem: ListCandidate) {
+        if self.offline {
+            self.error_overlay = Some("Offline mode: lists need a connection".to_string());
+            return;
+        }
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("updating lists");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let result = client
+                .toggle_star(&problem.question_id, &problem.slug)
+                .await;
+            let _ = tx.send(ApiResult::Starred(problem.slug, result));
+        });
+    }
+
+    fn open_add_to_list_popup(&mut self, problems: Vec<ListCandidate>) {
+        if self.offline {
+            self.error_overlay = Some("Offline mode: lists need a connection".to_string());
+            return;
+        }
+        self.add_to_list_popup = Some(AddToListPopup {
+            lists: Vec::new(),
+            state: ListState::default().with_selected(Some(0)),
+            problems,
+            loading: true,
+        });
+
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("loading lists");
+        tokio::spawn(async move {
+            let _task = task;
+            let result = client.fetch_favorites().await;
+            let _ = tx.send(ApiResult::PopupFavorites(result));
+        });
+    }
+
+    /// Adds (or with `remove`, removes) one at a time; with several problems
+    /// the toast counts how many made it, and only a total failure is
+    /// reported as an error. Removals can be undone with `u`.
+    fn start_update_list(
+        &self,
+        id_hash: &str,
+        question_ids: Vec<String>,
+        list_name: &str,
+        remove: bool,
+    ) {
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let id_hash = id_hash.to_string();
+        let list_name = list_name.to_string();
+        let task = self.tasks.start("updating lists");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let total = question_ids.len();
+            let mut done = Vec::new();
+            let mut last_err = None;
+            for question_id in question_ids {
+                let result = if remove {
+                    client.remove_from_favorite(&id_hash, &question_id).await
+                } else {
+                    client.add_to_favorite(&id_hash, &question_id).await
+                };
+                match result {
+                    Ok(()) => done.push(question_id),
+                    Err(e) => last_err = Some(e),
+                }
+            }
+            let count = done.len();
+            let (verb, prep) = if remove {
+                ("Removed", "from")
+            } else {
+                ("Added", "to")
+            };
+            let (result, msg) = match last_err {
+                Some(e) if count == 0 => (Err(e), String::new()),
+                _ if total <= 1 => (Ok(()), format!("{verb} {prep} \"{list_name}\"")),
+                _ if count == total => (
+                    Ok(()),
+                    format!("{verb} {total} problems {prep} \"{list_name}\""),
+                ),
+                _ => (
+                    Ok(()),
+                    format!("{verb} {count} of {total} problems {prep} \"{list_name}\""),
+                ),
+            };
+            let message = if remove {
+                let undo = ListUndo {
+                    id_hash,
+                    list_name,
+                    question_ids: done,
+                };
+                ApiResult::ListRemoval(result, msg, undo)
+            } else {
+                ApiResult::ListMutation(result, msg)
+            };
+            let _ = tx.send(message);
+        });
+    }
+
+    /// Check the cookies typed into Setup with a throwaway client, without
+    /// saving them.
+    fn start_test_connection(&mut self) {
+        let Screen::Setup(ref mut state) = self.screen else {
+            return;
+        };
+        let (session, csrf) = state.credentials();
+        if self.offline {
+            let status = ConnectionStatus::Failed("Offline, not testing".to_string());
+            state.connection = Some((session, csrf, status));
+            return;
+        }
+        state.connection = Some((session.clone(), csrf.clone(), ConnectionStatus::Checking));
+
+        let site = self
+            .config
+            .as_ref()
+            .map_or(LEETCODE_SITE, Config::site)
+            .to_string();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("testing connection");
+        tokio::spawn(async move {
+            let _task = task;
+            let result = match LeetCodeClient::new(&site, Some(&session), Some(&csrf)) {
+                Ok(client) => client.check_login().await,
+                Err(e) => Err(e),
+            };
+            let _ = tx.send(ApiResult::ConnectionChecked(session, csrf, result));
+        });
+    }
 
     fn start_fetch_user_stats(&self) {
+        if self.offline {
+            return;
+        }
         let client = self.api_client.clone();
         let tx = self.api_tx.clone();
         let has_tokens = self
@@ -979,8 +2895,10 @@ impl App {
             .as_ref()
             .map(|c| c.is_authenticated())
             .unwrap_or(false);
+        let task = self.tasks.start("loading stats");
 
         tokio::spawn(async move {
+            let _task = task;
             let username = client.fetch_username().await;
             match username {
                 Some(name) => {
@@ -997,13 +2915,138 @@ impl App {
         });
     }
 
+    /// A detail from the session cache, or from disk when offline.
+    fn cached_detail(&self, slug: &str) -> Option<QuestionDetail> {
+        self.detail_cache.get(slug).cloned().or_else(|| {
+            if self.offline {
+                cache::load_cached_detail(slug)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn record_recent(&mut self, detail: &QuestionDetail) {
+        if let Some(home) = self.home_mut() {
+            home.recent.push(detail);
+        }
+    }
+
+    fn offline_miss(slug: &str) -> anyhow::Error {
+        anyhow::anyhow!("Offline, and {slug} hasn't been cached")
+    }
+
     fn start_fetch_detail(&self, slug: &str) {
+        if let Some(detail) = self.cached_detail(slug) {
+            let _ = self.api_tx.send(ApiResult::Detail(Ok(detail)));
+            return;
+        }
+        if self.offline {
+            let _ = self
+                .api_tx
+                .send(ApiResult::Detail(Err(Self::offline_miss(slug))));
+            return;
+        }
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let slug = slug.to_string();
+        let task = self.tasks.start("loading problem");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let result = fetch_and_cache_detail(&*client, &slug).await;
+            let _ = tx.send(ApiResult::Detail(result));
+        });
+    }
+
+    fn start_fetch_editorial(&self, slug: &str) {
+        if self.offline {
+            let _ = self.api_tx.send(ApiResult::Editorial(
+                slug.to_string(),
+                Err(anyhow::anyhow!(
+                    "Offline mode: editorials need a connection"
+                )),
+            ));
+            return;
+        }
         let client = self.api_client.clone();
         let tx = self.api_tx.clone();
         let slug = slug.to_string();
+        let task = self.tasks.start("loading editorial");
 
         tokio::spawn(async move {
-            let result = client.fetch_problem_detail(&slug).await;
+            let _task = task;
+            let result = client.fetch_editorial(&slug).await;
+            let _ = tx.send(ApiResult::Editorial(slug, result));
+        });
+    }
+
+    fn start_fetch_solutions(&self, slug: &str, page: u32) {
+        if self.offline {
+            let _ = self.api_tx.send(ApiResult::Solutions(
+                slug.to_string(),
+                page,
+                Err(anyhow::anyhow!("Offline mode: solutions need a connection")),
+            ));
+            return;
+        }
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let slug = slug.to_string();
+        let task = self.tasks.start("loading solutions");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let result = client
+                .fetch_solutions(&slug, page * solutions::PAGE_SIZE, solutions::PAGE_SIZE)
+                .await;
+            let _ = tx.send(ApiResult::Solutions(slug, page, result));
+        });
+    }
+
+    fn start_fetch_solution_post(&self, slug: &str, id: &str) {
+        if self.offline {
+            let _ = self.api_tx.send(ApiResult::SolutionPost(
+                slug.to_string(),
+                id.to_string(),
+                Err(anyhow::anyhow!("Offline mode: solutions need a connection")),
+            ));
+            return;
+        }
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let slug = slug.to_string();
+        let id = id.to_string();
+        let task = self.tasks.start("loading solution");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let result = client.fetch_solution_post(&slug, &id).await;
+            let _ = tx.send(ApiResult::SolutionPost(slug, id, result));
+        });
+    }
+
+    fn start_fetch_random(&self, avoid: Option<String>) {
+        let client = self.api_client.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("picking random problem");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let mut slug = client.fetch_random_slug(None, &[]).await;
+            // A couple of retries so repeated presses don't reopen the same problem
+            for _ in 0..2 {
+                match slug {
+                    Ok(ref s) if avoid.as_ref() == Some(s) => {
+                        slug = client.fetch_random_slug(None, &[]).await;
+                    }
+                    _ => break,
+                }
+            }
+            let result = match slug {
+                Ok(slug) => fetch_and_cache_detail(&*client, &slug).await,
+                Err(e) => Err(e),
+            };
             let _ = tx.send(ApiResult::Detail(result));
         });
     }
@@ -1013,69 +3056,78 @@ impl App {
         slug: &str,
         _terminal: &mut ratatui::DefaultTerminal,
     ) -> Result<()> {
+        if self.offline || self.detail_cache.contains_key(slug) {
+            self.start_fetch_detail(slug);
+            return Ok(());
+        }
         let client = self.api_client.clone();
         let tx = self.api_tx.clone();
         let slug = slug.to_string();
+        let task = self.tasks.start("loading problem");
 
         tokio::spawn(async move {
-            let result = client.fetch_problem_detail(&slug).await;
+            let _task = task;
+            let result = fetch_and_cache_detail(&*client, &slug).await;
             let _ = tx.send(ApiResult::Detail(result));
         });
         Ok(())
     }
 
-    fn read_user_code(&self, detail: &QuestionDetail) -> Result<String> {
-        let config = self
-            .config
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("No config loaded"))?;
-        let workspace = config.expanded_workspace();
-        let dir_name = format!("{}-{}", detail.frontend_question_id, detail.title_slug);
-        let file_path = match config.language.as_str() {
-            "rust" => workspace.join(&dir_name).join("src").join("main.rs"),
-            "python3" | "python" => workspace.join(&dir_name).join("solution.py"),
-            "cpp" | "c++" => workspace.join(&dir_name).join("solution.cpp"),
-            "java" => workspace.join(&dir_name).join("Solution.java"),
-            "javascript" => workspace.join(&dir_name).join("solution.js"),
-            "typescript" => workspace.join(&dir_name).join("solution.ts"),
-            "go" | "golang" => workspace.join(&dir_name).join("solution.go"),
-            _ => workspace.join(&dir_name).join("src").join("main.rs"),
+    fn add_failing_test(&mut self, detail: &QuestionDetail, case: &FailingCase) {
+        let Some(config) = self.config.as_ref() else {
+            self.error_overlay = Some("No config loaded".to_string());
+            return;
         };
-
-        let content = std::fs::read_to_string(&file_path).map_err(|e| {
-            anyhow::anyhow!(
-                "Failed to read code from {}: {e}\nScaffold the problem first with 'o'",
-                file_path.display()
-            )
-        })?;
-
-        if config.language.eq_ignore_ascii_case("rust") {
-            return extract_rust_solution(&content);
+        let config = config.for_problem(&detail.frontend_question_id, &detail.title_slug);
+        if config.language != "rust" {
+            self.error_overlay = Some("Test stubs can only be added to Rust projects".to_string());
+            return;
         }
-
-        if config.language.eq_ignore_ascii_case("go") || config.language.eq_ignore_ascii_case("golang") {
-            return extract_go_solution(&content);
+        match scaffold::rust::append_test_case(
+            &solution_file(&config, detail),
+            &case.input,
+            case.expected.as_deref(),
+            case.output.as_deref(),
+        ) {
+            Ok(name) => {
+                self.success_message = Some((format!("Added test {name}"), 12));
+            }
+            Err(e) => self.error_overlay = Some(format!("{e}")),
         }
+    }
 
-        Ok(content)
+    /// Hold off run and submit for `wait`.
+    fn note_rate_limit(&mut self, wait: std::time::Duration) {
+        let until = std::time::Instant::now() + wait;
+        self.rate_limited_until = Some(self.rate_limited_until.map_or(until, |u| u.max(until)));
     }
 
-    fn lang_slug(&self) -> &str {
-        let config = self.config.as_ref();
-        match config.map(|c| c.language.as_str()) {
-            Some("rust") => "rust",
-            Some("python3") => "python3",
-            Some("python") => "python3",
-            Some("cpp" | "c++") => "cpp",
-            Some("java") => "java",
-            Some("javascript") => "javascript",
-            Some("typescript") => "typescript",
-            Some("go" | "golang") => "golang",
-            _ => "rust",
+    /// Whether a rate limit is still cooling down, saying how long is left
+    /// if so.
+    fn cooling_down(&mut self) -> bool {
+        let Some(until) = self.rate_limited_until else {
+            return false;
+        };
+        let left = until.saturating_duration_since(std::time::Instant::now());
+        if left.is_zero() {
+            self.rate_limited_until = None;
+            return false;
         }
+        self.warning_message = Some((
+            format!("Rate limited, retry in {}s", left.as_secs() + 1),
+            12,
+        ));
+        true
     }
 
     fn start_run_code(&mut self, detail: &QuestionDetail) {
+        if self.offline {
+            self.error_overlay = Some("Offline mode: run and submit need a connection".to_string());
+            return;
+        }
+        if self.cooling_down() {
+            return;
+        }
         let config = match &self.config {
             Some(c) => c,
             None => {
@@ -1089,7 +3141,7 @@ impl App {
             return;
         }
 
-        let code = match self.read_user_code(detail) {
+        let code = match read_user_code(config, detail) {
             Ok(c) => c,
             Err(e) => {
                 self.error_overlay = Some(format!("{e}"));
@@ -1097,19 +3149,7 @@ impl App {
             }
         };
 
-        // Get test input from example testcases
-        let data_input = detail
-            .example_testcase_list
-            .as_ref()
-            .and_then(|v| {
-                if v.is_empty() {
-                    None
-                } else {
-                    Some(v.join("\n"))
-                }
-            })
-            .or_else(|| detail.sample_test_case.clone())
-            .unwrap_or_default();
+        let data_input = detail.example_cases().join("\n");
 
         let title = format!("{}. {}", detail.frontend_question_id, detail.title);
         self.screen = Screen::Result(ResultState::new(ResultKind::Run, title, detail.clone()));
@@ -1118,27 +3158,207 @@ impl App {
         let tx = self.api_tx.clone();
         let slug = detail.title_slug.clone();
         let question_id = detail.question_id.clone();
-        let lang = self.lang_slug().to_string();
+        let lang = config
+            .for_problem(&detail.frontend_question_id, &detail.title_slug)
+            .lang_slug()
+            .to_string();
+        let task = self.tasks.start("running");
 
         tokio::spawn(async move {
+            let _task = task;
             let result = async {
                 let interpret_id = client
                     .run_code(&slug, &question_id, &lang, &code, &data_input)
                     .await?;
-                client.poll_result(&interpret_id).await
+                client.poll_result(&slug, &interpret_id).await
             }
             .await;
             let _ = tx.send(ApiResult::RunResult(result));
         });
     }
 
-    fn start_submit_code(&mut self, detail: &QuestionDetail) {
-        let config = match &self.config {
-            Some(c) => c,
-            None => {
-                self.error_overlay = Some("No config loaded".to_string());
-                return;
-            }
+    /// `w` on Detail or Result: run the solution every time it's saved.
+    fn toggle_watch(&mut self, detail: &QuestionDetail) {
+        if self.watch.is_some() {
+            self.stop_watch();
+            self.success_message = Some(("Stopped watching".to_string(), 12));
+            return;
+        }
+        let Some(config) = self.config.as_ref() else {
+            self.error_overlay = Some("No config loaded".to_string());
+            return;
+        };
+        let file = solution_file(config, detail);
+        if !file.exists() {
+            self.error_overlay = Some(format!(
+                "{} doesn't exist\nScaffold the problem first with 'o'",
+                file.display()
+            ));
+            return;
+        }
+        let tx = self.api_tx.clone();
+        let slug = detail.title_slug.clone();
+        let on_change = {
+            let slug = slug.clone();
+            move || {
+                let _ = tx.send(ApiResult::SolutionChanged(slug.clone()));
+            }
+        };
+        match SolutionWatcher::start(slug, &file, on_change) {
+            Ok(watcher) => {
+                self.watch = Some(watcher);
+                self.success_message = Some(("Watching: saving runs the solution".to_string(), 12));
+            }
+            Err(e) => self.error_overlay = Some(format!("{e:#}")),
+        }
+    }
+
+    fn stop_watch(&mut self) {
+        self.watch = None;
+        self.watch_debounce = None;
+        self.watch_rerun = false;
+    }
+
+    /// Watch mode only lasts while the watched problem is on screen.
+    fn sync_watch(&mut self) {
+        let Some(ref watch) = self.watch else {
+            return;
+        };
+        let slug = match &self.screen {
+            Screen::Detail(s) => Some(&s.detail.title_slug),
+            Screen::Result(s) => Some(&s.detail.title_slug),
+            _ => None,
+        };
+        if slug != Some(&watch.slug) {
+            self.stop_watch();
+        }
+    }
+
+    /// A save burst has settled: run the code, or queue one run behind a
+    /// verdict that's still pending.
+    fn fire_watch_run(&mut self) {
+        self.watch_debounce = None;
+        let detail = match &self.screen {
+            Screen::Result(s) if matches!(s.status, ResultStatus::Pending) => {
+                self.watch_rerun = true;
+                return;
+            }
+            Screen::Result(s) => s.detail.clone(),
+            Screen::Detail(s) => s.detail.clone(),
+            _ => return,
+        };
+        if self
+            .watch
+            .as_ref()
+            .is_some_and(|w| w.slug == detail.title_slug)
+        {
+            self.start_run_code(&detail);
+        }
+    }
+
+    /// `m` on Detail: the statement as README.md in the project, replacing
+    /// the README even if it was edited.
+    fn write_markdown(&mut self, detail: &QuestionDetail) {
+        let Some(config) = self.config.as_ref() else {
+            self.error_overlay = Some("No config loaded".to_string());
+            return;
+        };
+        let config = config.for_problem(&detail.frontend_question_id, &detail.title_slug);
+        let project_dir = scaffold::project_dir(
+            &config.expanded_workspace(),
+            &config.scaffold,
+            &detail.frontend_question_id,
+            &detail.title_slug,
+        );
+        match markdown::write_readme(&project_dir, detail) {
+            Ok(path) => {
+                self.success_message = Some((format!("Wrote {}", path.display()), 15));
+            }
+            Err(e) => self.error_overlay = Some(format!("{e:#}")),
+        }
+    }
+
+    /// Copy the code `s` would submit, extracted from the solution file.
+    fn copy_solution(&mut self, detail: &QuestionDetail) {
+        let Some(config) = self.config.as_ref() else {
+            self.error_overlay = Some("No config loaded".to_string());
+            return;
+        };
+        let copied = read_user_code(config, detail).and_then(|code| {
+            clipboard::copy(&code)?;
+            Ok(code.lines().count())
+        });
+        match copied {
+            Ok(lines) => {
+                self.success_message = Some((format!("Copied solution ({lines} lines)"), 12));
+            }
+            Err(e) => self.error_overlay = Some(format!("{e}")),
+        }
+    }
+
+    /// `t` on Detail: the project's own tests, shown like a run result.
+    fn start_local_test(&mut self, detail: &QuestionDetail) {
+        let Some(config) = self.config.as_ref() else {
+            self.error_overlay = Some("No config loaded".to_string());
+            return;
+        };
+        let config = config.for_problem(&detail.frontend_question_id, &detail.title_slug);
+        let project_dir = scaffold::project_dir(
+            &config.expanded_workspace(),
+            &config.scaffold,
+            &detail.frontend_question_id,
+            &detail.title_slug,
+        );
+        let language = config.language.clone();
+        let custom = config.test_command.clone();
+
+        let title = format!("{}. {}", detail.frontend_question_id, detail.title);
+        self.screen = Screen::Result(ResultState::new(
+            ResultKind::LocalTest,
+            title,
+            detail.clone(),
+        ));
+
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("testing locally");
+
+        tokio::spawn(async move {
+            let _task = task;
+            let result = local_test::run(&project_dir, &language, custom.as_deref()).await;
+            let _ = tx.send(ApiResult::LocalTest(result));
+        });
+    }
+
+    /// `s` from Detail or Result: behind the confirm overlay unless
+    /// `confirm_submit` is off.
+    fn request_submit(&mut self, detail: QuestionDetail) {
+        if let Some(config) = self.config.as_ref().filter(|c| c.confirm_submit) {
+            self.confirm_submit = Some(ConfirmSubmit {
+                language: config
+                    .for_problem(&detail.frontend_question_id, &detail.title_slug)
+                    .language,
+                file: solution_file(config, &detail),
+                detail,
+            });
+        } else {
+            self.start_submit_code(&detail);
+        }
+    }
+
+    fn start_submit_code(&mut self, detail: &QuestionDetail) {
+        if self.offline {
+            self.error_overlay = Some("Offline mode: run and submit need a connection".to_string());
+            return;
+        }
+        if self.cooling_down() {
+            return;
+        }
+        let config = match &self.config {
+            Some(c) => c,
+            None => {
+                self.error_overlay = Some("No config loaded".to_string());
+                return;
+            }
         };
 
         if !config.is_authenticated() {
@@ -1146,7 +3366,7 @@ impl App {
             return;
         }
 
-        let code = match self.read_user_code(detail) {
+        let code = match read_user_code(config, detail) {
             Ok(c) => c,
             Err(e) => {
                 self.error_overlay = Some(format!("{e}"));
@@ -1161,145 +3381,419 @@ impl App {
         let tx = self.api_tx.clone();
         let slug = detail.title_slug.clone();
         let question_id = detail.question_id.clone();
-        let lang = self.lang_slug().to_string();
+        let lang = config
+            .for_problem(&detail.frontend_question_id, &detail.title_slug)
+            .lang_slug()
+            .to_string();
+        let task = self.tasks.start("judging");
 
         tokio::spawn(async move {
+            let _task = task;
             let result = async {
                 let submission_id = client
                     .submit_code(&slug, &question_id, &lang, &code)
                     .await?;
-                client.poll_result(&submission_id).await
+                client.poll_result(&slug, &submission_id).await
             }
             .await;
             let _ = tx.send(ApiResult::SubmitResult(result));
         });
     }
 
+    /// `o`: scaffold the problem and open it; `O` (`force`) rewrites the
+    /// solution too. A project that's already there keeps its language; a
+    /// new one asks which, when the problem has snippets for more than one
+    /// and `ask_language` is on.
     fn do_scaffold_and_edit(
         &mut self,
         detail: &QuestionDetail,
+        force: bool,
         terminal: &mut ratatui::DefaultTerminal,
         events: &EventHandler,
     ) -> Result<()> {
+        let Some(config) = self.config.as_ref() else {
+            self.error_overlay = Some("No config loaded".to_string());
+            return Ok(());
+        };
+        let config = config.for_problem(&detail.frontend_question_id, &detail.title_slug);
+        let languages = scaffold::snippet_languages(detail);
+        if config.ask_language && languages.len() > 1 && !solution_file(&config, detail).exists() {
+            let default = languages.iter().position(|l| *l == config.lang_slug());
+            self.language_picker = Some(LanguagePicker {
+                detail: detail.clone(),
+                languages,
+                state: ListState::default().with_selected(Some(default.unwrap_or(0))),
+            });
+            return Ok(());
+        }
+        self.scaffold_and_edit_in(detail, &config.language, force, terminal, events);
+        Ok(())
+    }
+
+    fn scaffold_and_edit_in(
+        &mut self,
+        detail: &QuestionDetail,
+        language: &str,
+        force: bool,
+        terminal: &mut ratatui::DefaultTerminal,
+        events: &EventHandler,
+    ) {
         let config = match &self.config {
-            Some(c) => c.clone(),
+            Some(c) => Config {
+                language: language.to_string(),
+                ..c.clone()
+            },
             None => {
                 self.error_overlay = Some("No config loaded".to_string());
-                return Ok(());
+                return;
             }
         };
 
         let workspace = config.expanded_workspace();
         std::fs::create_dir_all(&workspace).ok();
+        let project_dir = scaffold::project_dir(
+            &workspace,
+            &config.scaffold,
+            &detail.frontend_question_id,
+            &detail.title_slug,
+        );
+
+        if config.git_commit {
+            self.start_ensure_repo(workspace.clone(), project_dir.clone());
+        }
+        match scaffold::scaffold_problem(
+            &workspace,
+            &config.scaffold,
+            detail,
+            &config.language,
+            force,
+        ) {
+            Ok((file_path, outcome)) => {
+                if let Some(home) = self.home_mut() {
+                    home.mark_scaffolded(&detail.title_slug);
+                }
+                self.open_in_editor(&file_path, &project_dir, terminal, events);
+                if self.error_overlay.is_none() {
+                    self.success_message = Some((outcome.describe(), 15));
+                }
+            }
+            Err(e) => {
+                self.error_overlay = Some(format!("Scaffold failed: {e}"));
+            }
+        }
+    }
 
-        match scaffold::scaffold_problem(&workspace, detail, &config.language) {
-            Ok(file_path) => {
-                let project_dir = file_path
-                    .parent()
-                    .and_then(|p| p.parent())
-                    .unwrap_or(&workspace);
-                self.last_opened_dir = Some(project_dir.to_path_buf());
+    /// With `git_commit` on, make sure a new project has a repository to be
+    /// committed to, in the background.
+    fn start_ensure_repo(&self, workspace: PathBuf, project_dir: PathBuf) {
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("setting up git");
+        tokio::task::spawn_blocking(move || {
+            let _task = task;
+            let _ = tx.send(ApiResult::RepoReady(git::ensure_repo(
+                &workspace,
+                &project_dir,
+            )));
+        });
+    }
 
-                // Pause event reader so editor gets exclusive stdin access
-                events.pause();
-                ratatui::restore();
+    /// With `git_commit` on, commit an accepted solution's project in the
+    /// background.
+    fn commit_solution(&mut self, detail: &QuestionDetail, data: &ResultData) {
+        let Some(config) = self.config.as_ref().filter(|c| c.git_commit) else {
+            return;
+        };
+        let config = config.for_problem(&detail.frontend_question_id, &detail.title_slug);
+        let project_dir = scaffold::project_dir(
+            &config.expanded_workspace(),
+            &config.scaffold,
+            &detail.frontend_question_id,
+            &detail.title_slug,
+        );
+        let message = git::solve_message(
+            &detail.frontend_question_id,
+            &detail.title,
+            data.runtime.as_deref(),
+            data.memory.as_deref(),
+        );
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("committing");
+        tokio::task::spawn_blocking(move || {
+            let _task = task;
+            let _ = tx.send(ApiResult::Committed(git::commit_project(
+                &project_dir,
+                &message,
+            )));
+        });
+    }
 
-                let status = Command::new(&config.editor)
-                    .arg(&file_path)
-                    .current_dir(project_dir)
-                    .status();
+    /// The solution file of `slug` if it has already been scaffolded, so
+    /// `o` on Home can skip the detail fetch.
+    fn existing_solution(&self, slug: &str) -> Option<(PathBuf, PathBuf)> {
+        let config = self.config.as_ref()?;
+        let Screen::Home(ref home) = self.screen else {
+            return None;
+        };
+        if !home.scaffolded.contains(slug) {
+            return None;
+        }
+        let problem = home.problems.iter().find(|p| p.title_slug == slug)?;
+        let config = config.for_problem(&problem.frontend_question_id, slug);
+        let project_dir = scaffold::project_dir(
+            &config.expanded_workspace(),
+            &config.scaffold,
+            &problem.frontend_question_id,
+            slug,
+        );
+        let file_path = scaffold::solution_path(&project_dir, &config.scaffold, &config.language);
+        file_path.exists().then_some((file_path, project_dir))
+    }
 
-                *terminal = ratatui::init();
-                events.resume();
+    fn open_in_editor(
+        &mut self,
+        file_path: &Path,
+        project_dir: &Path,
+        terminal: &mut ratatui::DefaultTerminal,
+        events: &EventHandler,
+    ) {
+        let Some(config) = self.config.as_ref() else {
+            return;
+        };
+        let editor = config.editor.clone();
+        let mode = config.effective_editor_mode();
+        self.last_opened_dir = Some(project_dir.to_path_buf());
+        let argv = match editor_argv(&editor) {
+            Ok(argv) => argv,
+            Err(e) => {
+                self.error_overlay = Some(format!("{e:#}"));
+                return;
+            }
+        };
 
-                match status {
-                    Ok(s) if s.success() => {}
-                    Ok(s) => {
-                        self.error_overlay = Some(format!("Editor exited with status: {}", s));
+        let fallback = match mode {
+            EditorMode::Suspend => None,
+            EditorMode::Detach => {
+                // The TUI keeps the terminal; the editor gets none of it
+                let spawned = editor_command(&argv)
+                    .arg(file_path)
+                    .current_dir(project_dir)
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn();
+                match spawned {
+                    Ok(_) => {
+                        let name = gui_editor_name(&editor).unwrap_or(&editor);
+                        self.success_message = Some((format!("Opened in {name}"), 12));
                     }
                     Err(e) => {
-                        self.error_overlay = Some(format!(
-                            "Failed to launch editor '{}': {}",
-                            config.editor, e
-                        ));
+                        self.error_overlay =
+                            Some(format!("Failed to launch editor '{}': {}", editor, e));
                     }
                 }
+                return;
+            }
+            EditorMode::Tmux | EditorMode::Zellij => {
+                match open_in_pane(mode, &argv, file_path, project_dir) {
+                    Ok(()) => return,
+                    Err(e) => Some(e),
+                }
+            }
+        };
+
+        self.suspend_for_editor(&editor, &argv, file_path, project_dir, terminal, events);
+        if let Some(e) = fallback
+            && self.error_overlay.is_none()
+        {
+            self.error_overlay = Some(format!("{e:#}\nOpened the editor here instead."));
+        }
+    }
+
+    /// Tear down the TUI and wait for the editor to exit.
+    fn suspend_for_editor(
+        &mut self,
+        editor: &str,
+        argv: &[String],
+        file_path: &Path,
+        project_dir: &Path,
+        terminal: &mut ratatui::DefaultTerminal,
+        events: &EventHandler,
+    ) {
+        let mouse = self.config.as_ref().is_some_and(|c| c.mouse);
+
+        // Pause event reader so editor gets exclusive stdin access
+        events.pause();
+        if mouse {
+            event::set_mouse_capture(false);
+        }
+        event::set_bracketed_paste(false);
+        ratatui::restore();
+
+        let status = editor_command(argv)
+            .arg(file_path)
+            .current_dir(project_dir)
+            .status();
+
+        *terminal = ratatui::init();
+        event::set_bracketed_paste(true);
+        if mouse {
+            event::set_mouse_capture(true);
+        }
+        events.resume();
+
+        match status {
+            Ok(s) if s.success() => {}
+            Ok(s) => {
+                self.error_overlay = Some(format!("Editor exited with status: {}", s));
             }
             Err(e) => {
-                self.error_overlay = Some(format!("Scaffold failed: {e}"));
+                self.error_overlay = Some(format!("Failed to launch editor '{}': {}", editor, e));
             }
         }
+    }
 
-        Ok(())
+    /// Whether a key press right now would go into a text field.
+    fn is_typing(&self) -> bool {
+        match &self.screen {
+            Screen::Setup(_) => true,
+            Screen::Home(s) => matches!(s.focus, HomeFocus::Search) || s.jump.is_some(),
+            Screen::Lists(s) => s.create.is_some() || s.rename_input.is_some(),
+            Screen::Detail(_) | Screen::Result(_) | Screen::Contests(_) => false,
+        }
+    }
+
+    fn home_mut(&mut self) -> Option<&mut HomeState> {
+        if let Screen::Home(ref mut s) = self.screen {
+            Some(s)
+        } else {
+            self.saved_home.as_deref_mut()
+        }
+    }
+
+    /// Home's problem list rows for everything in `lists`. The rest come
+    /// from the cached full list, see `start_load_cached_listed`.
+    fn listed_problems(&self, lists: &[FavoriteList]) -> HashMap<String, ProblemSummary> {
+        let slugs: HashSet<&str> = lists
+            .iter()
+            .flat_map(|l| &l.questions)
+            .map(|q| q.title_slug.as_str())
+            .collect();
+        let home = match self.screen {
+            Screen::Home(ref home) => Some(home),
+            _ => self.saved_home.as_ref(),
+        };
+        home.into_iter()
+            .flat_map(|h| &h.problems)
+            .filter(|p| slugs.contains(p.title_slug.as_str()))
+            .map(|p| (p.title_slug.clone(), p.clone()))
+            .collect()
+    }
+
+    /// Look up `slugs` in the cached full problem list in the background,
+    /// for the listed problems Home doesn't have: it may be filtered,
+    /// scoped to a contest or company, or not loaded yet.
+    fn start_load_cached_listed(&self, slugs: HashSet<String>) {
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("reading cache");
+        tokio::task::spawn_blocking(move || {
+            let _task = task;
+            let rows = cache::load_cached_problems(None)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|p| slugs.contains(&p.title_slug))
+                .map(|p| (p.title_slug.clone(), p))
+                .collect();
+            let _ = tx.send(ApiResult::CachedListed(rows));
+        });
+    }
+
+    /// Lists, on screen or waiting behind a problem opened from them.
+    fn lists_mut(&mut self) -> Option<&mut ListsState> {
+        if let Screen::Lists(ref mut s) = self.screen {
+            Some(s)
+        } else {
+            self.saved_lists.as_mut()
+        }
+    }
+
+    /// Contests, on screen or waiting behind a problem opened from them.
+    fn contests_mut(&mut self) -> Option<&mut ContestsState> {
+        if let Screen::Contests(ref mut s) = self.screen {
+            Some(s)
+        } else {
+            self.saved_contests.as_mut()
+        }
+    }
+
+    fn start_scan_workspace(&self) {
+        let Some(config) = self.config.as_ref() else {
+            return;
+        };
+        let workspace = config.expanded_workspace();
+        let layout = config.scaffold.clone();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("scanning workspace");
+        tokio::task::spawn_blocking(move || {
+            let _task = task;
+            let _ = tx.send(ApiResult::Scaffolded(scaffold::scan_workspace(
+                &workspace, &layout,
+            )));
+        });
+    }
+
+    /// Write the workspace index in the background, naming problems from
+    /// the rows Home already has.
+    fn start_write_index(&mut self) {
+        let Some(config) = self.config.clone() else {
+            return;
+        };
+        let known = self
+            .home_mut()
+            .map(|h| h.problems.clone())
+            .unwrap_or_default();
+        let tx = self.api_tx.clone();
+        let task = self.tasks.start("writing index");
+        tokio::task::spawn_blocking(move || {
+            let _task = task;
+            let _ = tx.send(ApiResult::IndexWritten(index::write_index(&config, &known)));
+        });
     }
 
     fn browser_login(&mut self) {
-        let domains = vec!["leetcode.com".to_string()];
-        let cookies = match rookie::load(Some(domains)) {
-            Ok(c) => c,
+        match browser_cookies() {
+            Ok((session, csrf)) => self.apply_login_cookies(Some(session), Some(csrf)),
             Err(_) => {
-                let _ = Command::new("open")
-                    .arg("https://leetcode.com/accounts/login/")
-                    .spawn();
+                // Not logged in there yet: open the login page and wait for a retry
+                let _ = open_in_browser("https://leetcode.com/accounts/login/");
                 self.login_waiting = true;
-                return;
             }
-        };
-
-        let session = cookies
-            .iter()
-            .find(|c| c.name == "LEETCODE_SESSION")
-            .map(|c| c.value.clone());
-        let csrf = cookies
-            .iter()
-            .find(|c| c.name == "csrftoken")
-            .map(|c| c.value.clone());
-
-        if session.is_none() || csrf.is_none() {
-            // No cookies found — open browser and wait for retry
-            let _ = Command::new("open")
-                .arg("https://leetcode.com/accounts/login/")
-                .spawn();
-            self.login_waiting = true;
-            return;
         }
+    }
 
-        self.apply_login_cookies(session, csrf);
+    /// Let the cookies be pasted in when the browser's can't be read. On
+    /// Settings that's its own cookie fields.
+    fn open_cookie_form(&mut self) {
+        self.login_waiting = false;
+        self.error_overlay = None;
+        if let Screen::Setup(ref mut state) = self.screen {
+            state.active_field = setup::SESSION_FIELD;
+        } else {
+            self.cookie_form = Some(CookieForm::default());
+        }
     }
 
     fn retry_browser_login(&mut self) {
         self.login_waiting = false;
 
-        let domains = vec!["leetcode.com".to_string()];
-        let cookies = match rookie::load(Some(domains)) {
-            Ok(c) => c,
+        match browser_cookies() {
+            Ok((session, csrf)) => self.apply_login_cookies(Some(session), Some(csrf)),
             Err(e) => {
                 self.error_overlay = Some(format!(
-                    "Still can't extract cookies: {e}\n\nMake sure you logged into leetcode.com,\nthen press Enter to retry."
+                    "{e}\n\nLog into leetcode.com in your browser,\nthen press Enter to retry, or p to paste the cookies."
                 ));
                 self.login_waiting = true;
-                return;
             }
-        };
-
-        let session = cookies
-            .iter()
-            .find(|c| c.name == "LEETCODE_SESSION")
-            .map(|c| c.value.clone());
-        let csrf = cookies
-            .iter()
-            .find(|c| c.name == "csrftoken")
-            .map(|c| c.value.clone());
-
-        if session.is_none() || csrf.is_none() {
-            self.error_overlay = Some(
-                "Could not find LeetCode cookies.\n\nLog into leetcode.com in your browser,\nthen press Enter to retry.".to_string()
-            );
-            self.login_waiting = true;
-            return;
         }
-
-        self.apply_login_cookies(session, csrf);
     }
 
     fn apply_login_cookies(&mut self, session: Option<String>, csrf: Option<String>) {
@@ -1314,9 +3808,10 @@ impl App {
         }
 
         // Recreate client with new credentials
-        match LeetCodeClient::new(session.as_deref(), csrf.as_deref()) {
+        let site = self.config.as_ref().map_or(LEETCODE_SITE, Config::site);
+        match LeetCodeClient::new(site, session.as_deref(), csrf.as_deref()) {
             Ok(client) => {
-                self.api_client = client;
+                self.api_client = Arc::new(client);
                 self.start_fetch_user_stats();
             }
             Err(e) => {
@@ -1326,148 +3821,1126 @@ impl App {
     }
 }
 
-/// Extract the solution portion of a Rust file using tree-sitter.
-///
-/// Walks top-level AST nodes and keeps everything except:
-/// - Leading line comments (problem description)
-/// - `struct Solution;` (LSP shim we added)
-/// - `fn main() { ... }`
-/// - `#[cfg(test)] mod tests { ... }`
-fn extract_rust_solution(content: &str) -> Result<String> {
-    let mut parser = tree_sitter::Parser::new();
-    let language = tree_sitter_rust::LANGUAGE;
-    parser
-        .set_language(&language.into())
-        .map_err(|e| anyhow::anyhow!("Failed to set tree-sitter language: {e}"))?;
-
-    let tree = parser
-        .parse(content, None)
-        .ok_or_else(|| anyhow::anyhow!("Failed to parse Rust file"))?;
-
-    let root = tree.root_node();
-    let mut parts: Vec<&str> = Vec::new();
-    let mut in_leading_comments = true;
-    let mut skip_next = false;
-
-    let mut cursor = root.walk();
-    for child in root.children(&mut cursor) {
-        // If the previous node was #[cfg(test)], skip this node (the mod item)
-        if skip_next {
-            skip_next = false;
-            continue;
-        }
-
-        let kind = child.kind();
-        let text = &content[child.byte_range()];
-
-        // Skip leading line comments (problem description block)
-        if in_leading_comments && kind == "line_comment" {
-            continue;
-        }
-        if kind != "line_comment" {
-            in_leading_comments = false;
-        }
-
-        // Skip empty `struct Solution` in any form: `struct Solution;`, `struct Solution {}`, etc.
-        // These are LSP shims — LeetCode provides its own.
-        if kind == "struct_item" {
-            if let Some(name_node) = child.child_by_field_name("name") {
-                let name = &content[name_node.byte_range()];
-                if name == "Solution" {
-                    let has_fields = child.child_by_field_name("body").is_some_and(|body| {
-                        let mut bc = body.walk();
-                        body.children(&mut bc)
-                            .any(|c| c.kind() == "field_declaration")
-                    });
-                    if !has_fields {
-                        continue;
-                    }
+/// Open `url` in the default browser.
+fn open_in_browser(url: &str) -> std::io::Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    Command::new(opener).arg(url).spawn().map(|_| ())
+}
+
+/// The LeetCode session and CSRF cookies from a browser's cookie store.
+pub fn browser_cookies() -> Result<(String, String)> {
+    let cookies = rookie::load(Some(vec!["leetcode.com".to_string()]))
+        .map_err(|e| anyhow::anyhow!("Can't read browser cookies: {e}"))?;
+    let find = |name: &str| {
+        cookies
+            .iter()
+            .find(|c| c.name == name && !c.value.is_empty())
+            .map(|c| c.value.clone())
+    };
+    match (find("LEETCODE_SESSION"), find("csrftoken")) {
+        (Some(session), Some(csrf)) => Ok((session, csrf)),
+        _ => bail!("Could not find LeetCode cookies in any browser"),
+    }
+}
+
+/// Fetch a problem's detail, keeping a copy on disk for offline use.
+async fn fetch_and_cache_detail(client: &dyn LeetCodeApi, slug: &str) -> Result<QuestionDetail> {
+    let detail = client.fetch_problem_detail(slug).await?;
+    let _ = cache::save_detail_cache(&detail);
+    Ok(detail)
+}
+
+/// One batch of the problem list, waiting out up to a few rate limits.
+async fn fetch_problem_batch(
+    client: &dyn LeetCodeApi,
+    tx: &mpsc::UnboundedSender<ApiResult>,
+    batch: i32,
+    skip: i32,
+    filter: &FetchFilter,
+) -> std::result::Result<(Vec<ProblemSummary>, i32), String> {
+    const MAX_PAUSES: u32 = 3;
+    let mut pauses = 0;
+    loop {
+        match client.fetch_problems(batch, skip, filter, None).await {
+            Ok(result) => return Ok(result),
+            Err(e) => match rate_limit_wait(&e) {
+                // Wait out a rate limit rather than give up on the list
+                Some(wait) if pauses < MAX_PAUSES => {
+                    pauses += 1;
+                    let _ = tx.send(ApiResult::RateLimited(wait));
+                    tokio::time::sleep(wait).await;
                 }
-            }
+                _ => return Err(describe_error(&e)),
+            },
         }
+    }
+}
 
-        // Skip `fn main() { ... }`
-        if kind == "function_item" {
-            if let Some(name_node) = child.child_by_field_name("name") {
-                if &content[name_node.byte_range()] == "main" {
-                    continue;
+/// The batches from `skip` to `total`, at most `concurrency` at a time.
+/// They're sent in order, so a failure leaves Home with an unbroken
+/// prefix that a resume can continue from.
+async fn fetch_problems_concurrently(
+    client: Arc<dyn LeetCodeApi>,
+    tx: mpsc::UnboundedSender<ApiResult>,
+    batch: i32,
+    skip: i32,
+    total: i32,
+    concurrency: usize,
+    filter: FetchFilter,
+) {
+    let permits = Arc::new(tokio::sync::Semaphore::new(concurrency));
+    let mut handles = (skip..total)
+        .step_by(batch as usize)
+        .map(|at| {
+            let (client, tx, permits) = (client.clone(), tx.clone(), permits.clone());
+            let filter = filter.clone();
+            tokio::spawn(async move {
+                let _permit = permits.acquire_owned().await.map_err(|e| e.to_string())?;
+                fetch_problem_batch(&*client, &tx, batch, at, &filter).await
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .peekable();
+    while let Some(handle) = handles.next() {
+        let result = handle.await.unwrap_or_else(|e| Err(e.to_string()));
+        match result {
+            Ok((problems, total)) => {
+                let done = problems.is_empty() || handles.peek().is_none();
+                let _ = tx.send(ApiResult::ProblemBatch {
+                    problems,
+                    total,
+                    done,
+                });
+                if done {
+                    break;
                 }
             }
+            Err(e) => {
+                let _ = tx.send(ApiResult::ProblemFetchError(e));
+                break;
+            }
         }
+    }
+    handles.for_each(|h| h.abort());
+}
 
-        // Skip `#[cfg(test)]` attribute and mark to skip the next item (mod tests)
-        if kind == "attribute_item" && text.contains("cfg") && text.contains("test") {
-            skip_next = true;
-            continue;
-        }
+/// Where the scaffolded solution for `detail` lives.
+pub fn solution_file(config: &Config, detail: &QuestionDetail) -> PathBuf {
+    let config = config.for_problem(&detail.frontend_question_id, &detail.title_slug);
+    let project_dir = scaffold::project_dir(
+        &config.expanded_workspace(),
+        &config.scaffold,
+        &detail.frontend_question_id,
+        &detail.title_slug,
+    );
+    scaffold::solution_path(&project_dir, &config.scaffold, &config.language)
+}
+
+/// The editor program with its flags, from `editor_argv`.
+fn editor_command(argv: &[String]) -> Command {
+    let mut cmd = Command::new(&argv[0]);
+    cmd.args(&argv[1..]);
+    cmd
+}
 
-        parts.push(text);
+/// Open the editor in a new pane of the multiplexer this is running in.
+fn open_in_pane(
+    mode: EditorMode,
+    argv: &[String],
+    file_path: &Path,
+    project_dir: &Path,
+) -> Result<()> {
+    let (name, env_var) = match mode {
+        EditorMode::Zellij => ("zellij", "ZELLIJ"),
+        _ => ("tmux", "TMUX"),
+    };
+    if std::env::var_os(env_var).is_none() {
+        anyhow::bail!("editor_mode is {name}, but this isn't running inside {name}.");
     }
+    let output = pane_command(mode, argv, file_path, project_dir)
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("Failed to run {name}"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{name} couldn't open a pane: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
 
-    let result = parts.join("\n").trim().to_string();
-    if result.is_empty() {
-        // Fallback: return original content if parsing produced nothing
-        Ok(content.to_string())
+/// The tmux or zellij command that opens the editor in a pane to the right.
+fn pane_command(
+    mode: EditorMode,
+    argv: &[String],
+    file_path: &Path,
+    project_dir: &Path,
+) -> Command {
+    let zellij = mode == EditorMode::Zellij;
+    let mut cmd = Command::new(if zellij { "zellij" } else { "tmux" });
+    if zellij {
+        cmd.args(["run", "--direction", "right", "--cwd"])
+            .arg(project_dir)
+            .arg("--")
+            .args(argv)
+            .arg(file_path);
     } else {
-        Ok(result)
+        cmd.args(["split-window", "-h", "-c"])
+            .arg(project_dir)
+            .args(argv)
+            .arg(file_path);
     }
+    cmd
 }
 
-/// Extract the solution portion of a Go file using tree-sitter.
-///
-/// Walks top-level AST nodes and keeps everything except:
-/// - Leading line comments (problem description)
-/// - `package` clause
-/// - `func main() { ... }`
-fn extract_go_solution(content: &str) -> Result<String> {
-    let mut parser = tree_sitter::Parser::new();
-    let language = tree_sitter_go::LANGUAGE;
-    parser
-        .set_language(&language.into())
-        .map_err(|e| anyhow::anyhow!("Failed to set tree-sitter language: {e}"))?;
+/// The solution for `detail` from its scaffolded project, stripped down to
+/// what LeetCode expects.
+/// In the language the project was scaffolded in, if it recorded one.
+pub fn read_user_code(config: &Config, detail: &QuestionDetail) -> Result<String> {
+    let config = config.for_problem(&detail.frontend_question_id, &detail.title_slug);
+    let file_path = solution_file(&config, detail);
+
+    let content = std::fs::read_to_string(&file_path).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to read code from {}: {e}\nScaffold the problem first with 'o'",
+            file_path.display()
+        )
+    })?;
+
+    extract::extract_solution(&config.language, &content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(cmd: &Command) -> Vec<String> {
+        std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn editor_launch_matrix() {
+        let file = Path::new("/ws/0001-two-sum/src/main.rs");
+        let dir = Path::new("/ws/0001-two-sum");
+        let editors = [
+            ("code --wait", vec!["code", "--wait"]),
+            ("nvim +42", vec!["nvim", "+42"]),
+            ("'/opt/my editor/ed' -n", vec!["/opt/my editor/ed", "-n"]),
+        ];
+        for (editor, words) in editors {
+            let argv = editor_argv(editor).unwrap();
+            let with_file = |prefix: &[&str]| {
+                let mut expected: Vec<String> = prefix.iter().map(|s| s.to_string()).collect();
+                expected.extend(words.iter().map(|s| s.to_string()));
+                expected.push(file.display().to_string());
+                expected
+            };
+
+            // Suspend and Detach run the same argv with the file last
+            let mut cmd = editor_command(&argv);
+            cmd.arg(file);
+            assert_eq!(args(&cmd), with_file(&[]), "editor = {editor:?}");
+
+            let tmux = pane_command(EditorMode::Tmux, &argv, file, dir);
+            assert_eq!(
+                args(&tmux),
+                with_file(&["tmux", "split-window", "-h", "-c", "/ws/0001-two-sum"]),
+                "tmux, editor = {editor:?}"
+            );
+
+            let zellij = pane_command(EditorMode::Zellij, &argv, file, dir);
+            assert_eq!(
+                args(&zellij),
+                with_file(&[
+                    "zellij",
+                    "run",
+                    "--direction",
+                    "right",
+                    "--cwd",
+                    "/ws/0001-two-sum",
+                    "--"
+                ]),
+                "zellij, editor = {editor:?}"
+            );
+        }
+    }
 
-    let tree = parser
-        .parse(content, None)
-        .ok_or_else(|| anyhow::anyhow!("Failed to parse Go file"))?;
+    use crate::api::mock::{self, MockClient};
+    use crate::config::WorkspaceDir;
+    use std::time::Duration;
+
+    /// An App signed in with made-up cookies, talking to `client`.
+    fn test_app(client: MockClient) -> App {
+        let workspace = Config::config_dir().join("workspace");
+        let config = Config {
+            workspace_dir: WorkspaceDir::Single(workspace.display().to_string()),
+            leetcode_session: Some("session".to_string()),
+            csrf_token: Some("csrf".to_string()),
+            ..Config::default()
+        };
+        let mut app = App::new(Some(config), false).unwrap();
+        app.api_client = Arc::new(client);
+        app
+    }
 
-    let root = tree.root_node();
-    let mut parts: Vec<&str> = Vec::new();
-    let mut in_leading_comments = true;
+    fn failing() -> MockClient {
+        MockClient {
+            error: Some("boom".to_string()),
+            ..MockClient::default()
+        }
+    }
 
-    let mut cursor = root.walk();
-    for child in root.children(&mut cursor) {
-        let kind = child.kind();
-        let text = &content[child.byte_range()];
+    /// Hand the app everything its tasks send until they go quiet.
+    async fn settle(app: &mut App) {
+        while let Ok(Some(result)) =
+            tokio::time::timeout(Duration::from_millis(200), app.api_rx.recv()).await
+        {
+            app.handle_api_result(result);
+        }
+    }
 
-        // Skip leading line comments (problem description block)
-        if in_leading_comments && kind == "comment" {
-            continue;
+    fn home_state(app: &App) -> &HomeState {
+        match app.screen {
+            Screen::Home(ref home) => home,
+            _ => app.saved_home.as_deref().expect("Home is kept"),
         }
-        if kind != "comment" {
-            in_leading_comments = false;
+    }
+
+    fn on_detail(app: &mut App) -> &mut DetailState {
+        match app.screen {
+            Screen::Detail(ref mut state) => state,
+            _ => panic!("not on Detail"),
         }
+    }
 
-        // Skip `package` clause
-        if kind == "package_clause" {
-            continue;
+    fn on_result(app: &App) -> &ResultState {
+        match app.screen {
+            Screen::Result(ref state) => state,
+            _ => panic!("not on Result"),
         }
+    }
 
-        // Skip `func main() { ... }`
-        if kind == "function_declaration" {
-            if let Some(name_node) = child.child_by_field_name("name") {
-                if &content[name_node.byte_range()] == "main" {
-                    continue;
-                }
+    #[tokio::test]
+    async fn problem_list_loads_through_the_client() {
+        let mut app = test_app(MockClient {
+            problems: vec![
+                mock::problem("1", "two-sum"),
+                mock::problem("2", "add-two-numbers"),
+            ],
+            ..MockClient::default()
+        });
+        app.start_fetch_problems();
+        settle(&mut app).await;
+        let home = home_state(&app);
+        assert!(!home.loading);
+        assert!(home.fetch_error.is_none());
+        assert!(
+            home.problems
+                .iter()
+                .any(|p| p.title_slug == "add-two-numbers")
+        );
+
+        let mut app = test_app(failing());
+        app.start_fetch_problems();
+        settle(&mut app).await;
+        let home = home_state(&app);
+        assert!(!home.loading);
+        assert_eq!(home.fetch_error.as_deref(), Some("boom"));
+    }
+
+    #[tokio::test]
+    async fn detail_opens_or_reports_the_failure() {
+        let mut app = test_app(MockClient {
+            details: vec![mock::detail("1", "two-sum")],
+            ..MockClient::default()
+        });
+        app.start_fetch_detail("two-sum");
+        settle(&mut app).await;
+        assert_eq!(on_detail(&mut app).detail.title_slug, "two-sum");
+        assert!(app.saved_home.is_some());
+        assert!(app.detail_cache.contains_key("two-sum"));
+
+        let mut app = test_app(failing());
+        app.start_fetch_detail("not-cached-anywhere");
+        settle(&mut app).await;
+        assert!(matches!(app.screen, Screen::Home(_)));
+        assert!(app.error_overlay.as_deref().unwrap().contains("boom"));
+    }
+
+    #[tokio::test]
+    async fn run_and_submit_results_land_on_the_result_screen() {
+        let detail = mock::detail("1", "two-sum");
+        for kind in [ResultKind::Run, ResultKind::Submit] {
+            let mut app = test_app(MockClient::default());
+            app.screen =
+                Screen::Result(ResultState::new(kind, "1. two sum".into(), detail.clone()));
+            let verdict = Ok(mock::verdict(11, "Wrong Answer"));
+            app.handle_api_result(match kind {
+                ResultKind::Submit => ApiResult::SubmitResult(verdict),
+                _ => ApiResult::RunResult(verdict),
+            });
+            assert!(matches!(on_result(&app).status, ResultStatus::Success(_)));
+
+            app.handle_api_result(ApiResult::RunResult(Err(anyhow::anyhow!("boom"))));
+            assert!(matches!(on_result(&app).status, ResultStatus::Error));
+        }
+    }
+
+    #[tokio::test]
+    async fn rate_limits_warn_and_hold_off_further_requests() {
+        let mut app = test_app(MockClient::default());
+        app.handle_api_result(ApiResult::RateLimited(Duration::from_secs(30)));
+        assert!(app.success_message.is_none());
+        assert!(
+            app.warning_message
+                .as_ref()
+                .unwrap()
+                .0
+                .starts_with("Rate limited")
+        );
+
+        app.warning_message = None;
+        assert!(app.cooling_down());
+        assert!(app.warning_message.is_some());
+    }
+
+    #[tokio::test]
+    async fn local_test_results_show_on_a_local_run() {
+        let mut app = test_app(MockClient::default());
+        let detail = mock::detail("1", "two-sum");
+        app.screen = Screen::Result(ResultState::new(
+            ResultKind::LocalTest,
+            String::new(),
+            detail,
+        ));
+        app.handle_api_result(ApiResult::LocalTest(Ok(LocalTestRun {
+            passed: true,
+            exit_code: Some(0),
+            timed_out: false,
+            output: "ok".to_string(),
+        })));
+        assert!(matches!(on_result(&app).status, ResultStatus::Done));
+        app.handle_api_result(ApiResult::LocalTest(Err(anyhow::anyhow!("boom"))));
+        assert!(matches!(on_result(&app).status, ResultStatus::Error));
+    }
+
+    #[tokio::test]
+    async fn commit_and_index_outcomes_are_announced() {
+        let mut app = test_app(MockClient::default());
+        app.handle_api_result(ApiResult::Committed(Ok(true)));
+        assert_eq!(
+            app.success_message.as_ref().unwrap().0,
+            "Committed the solution"
+        );
+        app.handle_api_result(ApiResult::Committed(Ok(false)));
+        assert_eq!(
+            app.success_message.as_ref().unwrap().0,
+            "Nothing new to commit"
+        );
+        app.handle_api_result(ApiResult::Committed(Err(anyhow::anyhow!("boom"))));
+        assert!(app.error_overlay.take().unwrap().contains("boom"));
+
+        app.success_message = None;
+        app.handle_api_result(ApiResult::RepoReady(Ok(true)));
+        assert!(app.success_message.is_none() && app.error_overlay.is_none());
+        app.handle_api_result(ApiResult::RepoReady(Err(anyhow::anyhow!("boom"))));
+        assert!(
+            app.error_overlay
+                .take()
+                .unwrap()
+                .contains("git init failed")
+        );
+
+        app.handle_api_result(ApiResult::IndexWritten(Ok((
+            PathBuf::from("/ws/README.md"),
+            1,
+        ))));
+        assert_eq!(
+            app.success_message.as_ref().unwrap().0,
+            "Indexed 1 problem in /ws/README.md"
+        );
+        app.handle_api_result(ApiResult::IndexWritten(Err(anyhow::anyhow!("boom"))));
+        assert!(app.error_overlay.as_deref().unwrap().contains("boom"));
+    }
+
+    #[tokio::test]
+    async fn home_takes_stats_scaffolds_and_fetch_errors() {
+        let mut app = test_app(MockClient::default());
+        let stats = app.api_client.fetch_user_stats("tester").await.ok();
+        app.handle_api_result(ApiResult::UserStats(stats));
+        assert_eq!(
+            home_state(&app).user_stats.as_ref().unwrap().username,
+            "tester"
+        );
+        app.handle_api_result(ApiResult::UserStats(None));
+        assert!(home_state(&app).user_stats.is_none());
+
+        app.handle_api_result(ApiResult::Scaffolded(HashSet::from(
+            ["two-sum".to_string()],
+        )));
+        assert!(home_state(&app).scaffolded.contains("two-sum"));
+
+        app.handle_api_result(ApiResult::ProblemFetchError("boom".to_string()));
+        assert_eq!(home_state(&app).fetch_error.as_deref(), Some("boom"));
+    }
+
+    #[tokio::test]
+    async fn search_results_fill_home_or_show_the_error() {
+        let mut app = test_app(MockClient {
+            problems: vec![mock::problem("1", "two-sum")],
+            ..MockClient::default()
+        });
+        app.start_search_fetch("two");
+        settle(&mut app).await;
+        assert!(
+            home_state(&app)
+                .problems
+                .iter()
+                .any(|p| p.title_slug == "two-sum")
+        );
+        assert!(!home_state(&app).search_loading);
+
+        let mut app = test_app(failing());
+        app.start_search_fetch("two");
+        settle(&mut app).await;
+        assert!(
+            home_state(&app)
+                .error_message
+                .as_deref()
+                .unwrap()
+                .contains("boom")
+        );
+
+        // An answer to an older query is dropped
+        app.latest_search = Some("newer".to_string());
+        app.handle_api_result(ApiResult::SearchResult(
+            "two".to_string(),
+            Ok((Vec::new(), 0)),
+        ));
+        assert_eq!(app.latest_search.as_deref(), Some("newer"));
+    }
+
+    #[tokio::test]
+    async fn jumps_select_the_problem_or_say_it_is_missing() {
+        let mut app = test_app(MockClient {
+            problems: vec![mock::problem("7", "reverse-integer")],
+            details: vec![mock::detail("7", "reverse-integer")],
+            ..MockClient::default()
+        });
+        let prompt = || home::JumpPrompt {
+            input: "7".to_string(),
+            message: None,
+            loading: true,
+            contest: false,
+        };
+        app.home_mut().unwrap().jump = Some(prompt());
+        app.start_jump_fetch("7");
+        settle(&mut app).await;
+        let home = home_state(&app);
+        assert!(home.problems.iter().any(|p| p.frontend_question_id == "7"));
+        assert!(home.jump.as_ref().is_none_or(|j| j.message.is_none()));
+
+        app.home_mut().unwrap().jump = Some(prompt());
+        app.handle_api_result(ApiResult::JumpProblem(
+            "8".to_string(),
+            Err(anyhow::anyhow!("boom")),
+        ));
+        assert_eq!(
+            home_state(&app).jump.as_ref().unwrap().message.as_deref(),
+            Some("No such problem")
+        );
+
+        app.handle_api_result(ApiResult::JumpDetail(Err(anyhow::anyhow!("boom"))));
+        assert!(home_state(&app).jump.as_ref().unwrap().message.is_some());
+        app.start_jump_detail("reverse-integer");
+        settle(&mut app).await;
+        assert_eq!(on_detail(&mut app).detail.title_slug, "reverse-integer");
+        assert!(app.saved_home.as_ref().unwrap().jump.is_none());
+    }
+
+    #[tokio::test]
+    async fn launch_opens_detail_or_falls_back_to_home() {
+        let mut app = test_app(MockClient::default());
+        app.handle_api_result(ApiResult::LaunchDetail(Ok(mock::detail("1", "two-sum"))));
+        assert_eq!(on_detail(&mut app).detail.title_slug, "two-sum");
+
+        let mut app = test_app(MockClient::default());
+        app.handle_api_result(ApiResult::LaunchDetail(Err(anyhow::anyhow!("boom"))));
+        assert!(matches!(app.screen, Screen::Home(_)));
+        assert!(app.error_overlay.as_deref().unwrap().contains("boom"));
+        settle(&mut app).await;
+    }
+
+    #[tokio::test]
+    async fn company_picker_gets_companies_and_problems() {
+        let mut app = test_app(MockClient::default());
+        app.company_picker = Some(CompanyPicker::new(CompanyPeriod::SixMonths));
+        let tag = CompanyTag {
+            name: "Acme".to_string(),
+            slug: "acme".to_string(),
+            question_count: 1,
+        };
+        app.handle_api_result(ApiResult::CompanyTags(Ok(vec![tag])));
+        assert_eq!(app.company_picker.as_ref().unwrap().companies.len(), 1);
+        assert!(app.company_tags.is_some());
+        app.handle_api_result(ApiResult::CompanyTags(Err(anyhow::anyhow!("boom"))));
+        assert!(
+            app.company_picker
+                .as_ref()
+                .unwrap()
+                .message
+                .as_deref()
+                .unwrap()
+                .contains("boom")
+        );
+
+        app.handle_api_result(ApiResult::CompanyProblems(
+            CompanyPeriod::SixMonths,
+            Err(anyhow::anyhow!("Premium only")),
+        ));
+        assert!(app.company_picker.is_some());
+        let asked = CompanyProblem {
+            problem: mock::problem("1", "two-sum"),
+            frequency: vec![1.0, 1.0, 1.0],
+        };
+        app.handle_api_result(ApiResult::CompanyProblems(
+            CompanyPeriod::SixMonths,
+            Ok(("Acme".to_string(), vec![asked])),
+        ));
+        assert!(app.company_picker.is_none());
+        assert_eq!(
+            home_state(&app).list_filter.as_ref().unwrap().slugs.len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn contests_load_on_home_and_on_their_screen() {
+        let mut app = test_app(MockClient::default());
+        app.home_mut().unwrap().open_contest("weekly-contest-1");
+        app.handle_api_result(ApiResult::Contest(Err(anyhow::anyhow!("boom"))));
+        assert!(
+            home_state(&app)
+                .jump
+                .as_ref()
+                .unwrap()
+                .message
+                .as_deref()
+                .unwrap()
+                .contains("boom")
+        );
+        let slugs = HashSet::from(["two-sum".to_string()]);
+        let missing = vec![mock::problem("1", "two-sum")];
+        app.handle_api_result(ApiResult::Contest(Ok((
+            "Weekly 1".to_string(),
+            slugs,
+            missing,
+        ))));
+        assert!(home_state(&app).jump.is_none());
+        assert_eq!(
+            home_state(&app).list_filter.as_ref().unwrap().name,
+            "Weekly 1"
+        );
+
+        app.screen = Screen::Contests(ContestsState::new());
+        let contests = app.api_client.fetch_contests().await;
+        app.handle_api_result(ApiResult::Contests(contests));
+        assert!(app.contests_mut().unwrap().error_message.is_none());
+        app.handle_api_result(ApiResult::Contests(Err(anyhow::anyhow!("boom"))));
+        assert!(app.contests_mut().unwrap().error_message.is_some());
+
+        app.handle_api_result(ApiResult::ContestProblems("c".to_string(), Ok(Vec::new())));
+        assert!(matches!(
+            app.contests_mut().unwrap().problems["c"],
+            ContestProblems::Loaded(_)
+        ));
+        app.handle_api_result(ApiResult::ContestProblems(
+            "c".to_string(),
+            Err(anyhow::anyhow!("boom")),
+        ));
+        assert!(matches!(
+            app.contests_mut().unwrap().problems["c"],
+            ContestProblems::Failed(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn editorial_and_solutions_fill_the_detail_tabs() {
+        let mut app = test_app(MockClient::default());
+        app.screen = Screen::Detail(Box::new(DetailState::new(mock::detail("1", "two-sum"))));
+        app.handle_api_result(ApiResult::Editorial("two-sum".to_string(), Ok(None)));
+        assert!(on_detail(&mut app).editorial_lines.is_some());
+        assert!(app.editorial_cache.contains_key("two-sum"));
+        app.handle_api_result(ApiResult::Editorial(
+            "two-sum".to_string(),
+            Err(anyhow::anyhow!("boom")),
+        ));
+        assert!(app.error_overlay.as_deref().unwrap().contains("boom"));
+
+        on_detail(&mut app).solutions = Some(solutions::SolutionsState::new());
+        app.handle_api_result(ApiResult::Solutions(
+            "two-sum".to_string(),
+            0,
+            Ok((Vec::new(), 0)),
+        ));
+        assert!(!on_detail(&mut app).solutions.as_ref().unwrap().loading);
+        app.handle_api_result(ApiResult::Solutions(
+            "two-sum".to_string(),
+            1,
+            Err(anyhow::anyhow!("boom")),
+        ));
+        assert!(
+            on_detail(&mut app)
+                .solutions
+                .as_ref()
+                .unwrap()
+                .error
+                .is_some()
+        );
+
+        let post = || solutions::SolutionPost {
+            id: "42".to_string(),
+            title: "Hash map".to_string(),
+            lines: None,
+            blocks: Vec::new(),
+            block: 0,
+        };
+        on_detail(&mut app).solutions.as_mut().unwrap().post = Some(post());
+        app.handle_api_result(ApiResult::SolutionPost(
+            "two-sum".to_string(),
+            "42".to_string(),
+            Ok("Use a map".to_string()),
+        ));
+        let lines = on_detail(&mut app)
+            .solutions
+            .as_ref()
+            .unwrap()
+            .post
+            .as_ref()
+            .unwrap()
+            .lines
+            .clone();
+        assert!(lines.unwrap().len() > 2);
+        on_detail(&mut app).solutions.as_mut().unwrap().post = Some(post());
+        app.handle_api_result(ApiResult::SolutionPost(
+            "two-sum".to_string(),
+            "42".to_string(),
+            Err(anyhow::anyhow!("boom")),
+        ));
+        let lines = on_detail(&mut app)
+            .solutions
+            .as_ref()
+            .unwrap()
+            .post
+            .as_ref()
+            .unwrap()
+            .lines
+            .clone();
+        assert_eq!(lines.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn previews_are_cached_or_show_the_error() {
+        let mut app = test_app(MockClient::default());
+        app.pending_preview = Some("two-sum".to_string());
+        app.handle_api_result(ApiResult::Preview(
+            "two-sum".to_string(),
+            Ok(mock::detail("1", "two-sum")),
+        ));
+        assert!(app.pending_preview.is_none());
+        assert!(app.detail_cache.contains_key("two-sum"));
+        app.handle_api_result(ApiResult::Preview(
+            "3sum".to_string(),
+            Err(anyhow::anyhow!("boom")),
+        ));
+        assert_eq!(home_state(&app).preview.as_ref().unwrap().slug, "3sum");
+    }
+
+    fn list(name: &str) -> FavoriteList {
+        FavoriteList {
+            id_hash: format!("{name}-hash"),
+            name: name.to_string(),
+            is_public_favorite: false,
+            questions: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn lists_load_and_mutations_refresh_them() {
+        let client = Arc::new(MockClient {
+            favorites: vec![list("Graphs")],
+            ..MockClient::default()
+        });
+        let mut app = test_app(MockClient::default());
+        app.api_client = client.clone();
+        app.screen = Screen::Lists(ListsState::new());
+        app.start_fetch_favorites();
+        settle(&mut app).await;
+        let lists = app.lists_mut().unwrap();
+        assert!(!lists.loading);
+        assert_eq!(lists.lists.len(), 1);
+        app.handle_api_result(ApiResult::Favorites(Err(anyhow::anyhow!("boom"))));
+        assert!(
+            app.lists_mut()
+                .unwrap()
+                .error_message
+                .as_deref()
+                .unwrap()
+                .contains("boom")
+        );
+
+        app.handle_api_result(ApiResult::ListMutation(Ok(()), "List created".to_string()));
+        assert_eq!(app.success_message.as_ref().unwrap().0, "List created");
+        settle(&mut app).await;
+        assert!(app.lists_mut().unwrap().error_message.is_none());
+        assert_eq!(client.calls(), ["fetch_favorites", "fetch_favorites"]);
+        app.handle_api_result(ApiResult::ListMutation(
+            Err(anyhow::anyhow!("boom")),
+            String::new(),
+        ));
+        assert_eq!(app.error_overlay.take().as_deref(), Some("boom"));
+
+        let undo = || ListUndo {
+            id_hash: "Graphs-hash".to_string(),
+            list_name: "Graphs".to_string(),
+            question_ids: vec!["1".to_string()],
+        };
+        app.handle_api_result(ApiResult::ListRemoval(
+            Ok(()),
+            "Removed".to_string(),
+            undo(),
+        ));
+        assert!(app.list_undo.is_some());
+        app.handle_api_result(ApiResult::ListRemoval(
+            Err(anyhow::anyhow!("boom")),
+            String::new(),
+            undo(),
+        ));
+        assert_eq!(app.error_overlay.take().as_deref(), Some("boom"));
+    }
+
+    #[tokio::test]
+    async fn listed_problems_come_from_home_then_the_cache() {
+        // The same rows `problem_list_loads_through_the_client` caches
+        cache::save_problems_cache(
+            &[
+                mock::problem("1", "two-sum"),
+                mock::problem("2", "add-two-numbers"),
+            ],
+            None,
+        )
+        .unwrap();
+        let mut app = test_app(MockClient::default());
+        app.home_mut()
+            .unwrap()
+            .merge_problems(vec![mock::problem("1", "two-sum")]);
+        let question = |slug: &str| crate::api::types::FavoriteQuestion {
+            question_id: String::new(),
+            status: None,
+            title: slug.to_string(),
+            title_slug: slug.to_string(),
+        };
+        let lists = [FavoriteList {
+            questions: vec![
+                question("two-sum"),
+                question("add-two-numbers"),
+                question("nowhere"),
+            ],
+            ..list("Mixed")
+        }];
+        let found = app.listed_problems(&lists);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found["two-sum"].frontend_question_id, "1");
+
+        // Opened from Home, which waits behind it
+        if let Screen::Home(home) =
+            std::mem::replace(&mut app.screen, Screen::Lists(ListsState::new()))
+        {
+            app.saved_home = Some(home);
+        }
+        app.handle_api_result(ApiResult::Favorites(Ok(lists.to_vec())));
+        settle(&mut app).await;
+        let Screen::Lists(ref state) = app.screen else {
+            unreachable!()
+        };
+        assert_eq!(state.problems.len(), 2);
+        assert_eq!(state.problems["add-two-numbers"].frontend_question_id, "2");
+    }
+
+    #[tokio::test]
+    async fn stars_and_the_add_to_list_popup() {
+        let mut app = test_app(MockClient::default());
+        app.home_mut()
+            .unwrap()
+            .merge_problems(vec![mock::problem("1", "two-sum")]);
+        app.handle_api_result(ApiResult::Starred("two-sum".to_string(), Ok(true)));
+        assert!(home_state(&app).problems[0].is_favor);
+        app.handle_api_result(ApiResult::Starred(
+            "two-sum".to_string(),
+            Err(anyhow::anyhow!("boom")),
+        ));
+        assert!(app.error_overlay.take().unwrap().contains("boom"));
+
+        let popup = || AddToListPopup {
+            lists: Vec::new(),
+            state: ListState::default(),
+            problems: Vec::new(),
+            loading: true,
+        };
+        app.add_to_list_popup = Some(popup());
+        app.handle_api_result(ApiResult::PopupFavorites(Ok(vec![list("Graphs")])));
+        assert!(!app.add_to_list_popup.as_ref().unwrap().loading);
+        app.handle_api_result(ApiResult::PopupFavorites(Err(anyhow::anyhow!("boom"))));
+        assert!(app.add_to_list_popup.is_none());
+        assert!(app.error_overlay.is_some());
+    }
+
+    #[tokio::test]
+    async fn setup_shows_the_connection_check() {
+        let mut app = test_app(MockClient::default());
+        app.screen = Screen::Setup(SetupState::new());
+        let name = app.api_client.check_login().await;
+        app.handle_api_result(ApiResult::ConnectionChecked("s".into(), "c".into(), name));
+        let Screen::Setup(ref state) = app.screen else {
+            panic!("not on Setup")
+        };
+        assert!(matches!(
+            state.connection,
+            Some((_, _, ConnectionStatus::LoggedIn(_)))
+        ));
+        app.handle_api_result(ApiResult::ConnectionChecked(
+            "s".into(),
+            "c".into(),
+            Err(anyhow::anyhow!("boom")),
+        ));
+        let Screen::Setup(ref state) = app.screen else {
+            panic!("not on Setup")
+        };
+        assert!(matches!(
+            state.connection,
+            Some((_, _, ConnectionStatus::Failed(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn expired_auth_clears_the_cookies_and_prompts() {
+        let mut app = test_app(MockClient::default());
+        app.handle_api_result(ApiResult::AuthExpired);
+        assert!(app.login_prompt);
+        assert!(!app.config.as_ref().unwrap().is_authenticated());
+    }
+
+    #[tokio::test]
+    async fn a_save_is_ignored_without_a_watch() {
+        let mut app = test_app(MockClient::default());
+        app.handle_api_result(ApiResult::SolutionChanged("two-sum".to_string()));
+        assert!(app.watch_debounce.is_none());
+    }
+
+    /// Drives an App with key presses, drawing every frame so rendering is
+    /// covered along with the transitions.
+    struct Harness {
+        app: App,
+        terminal: ratatui::DefaultTerminal,
+        events: EventHandler,
+        screen: ratatui::Terminal<ratatui::backend::TestBackend>,
+    }
+
+    impl Harness {
+        fn new(app: App) -> Self {
+            // A fixed viewport never queries stdout, and nothing here draws to it
+            let terminal = ratatui::Terminal::with_options(
+                ratatui::backend::CrosstermBackend::new(std::io::stdout()),
+                ratatui::TerminalOptions {
+                    viewport: ratatui::Viewport::Fixed(Rect::new(0, 0, 100, 30)),
+                },
+            )
+            .unwrap();
+            let screen =
+                ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 30)).unwrap();
+            let mut harness = Self {
+                app,
+                terminal,
+                events: EventHandler::idle(),
+                screen,
+            };
+            harness.draw();
+            harness
+        }
+
+        fn draw(&mut self) {
+            let app = &mut self.app;
+            self.screen.draw(|f| app.render(f)).unwrap();
+        }
+
+        async fn press(&mut self, code: KeyCode) {
+            let key = crossterm::event::KeyEvent::from(code);
+            self.app
+                .handle_key(key, &mut self.terminal, &self.events)
+                .unwrap();
+            settle(&mut self.app).await;
+            self.draw();
+        }
+
+        fn screen_name(&self) -> &'static str {
+            match self.app.screen {
+                Screen::Setup(_) => "setup",
+                Screen::Home(_) => "home",
+                Screen::Detail(_) => "detail",
+                Screen::Result(_) => "result",
+                Screen::Lists(_) => "lists",
+                Screen::Contests(_) => "contests",
             }
         }
+    }
 
-        parts.push(text);
+    #[tokio::test]
+    async fn home_detail_result_and_back() {
+        let detail = mock::detail("1", "two-sum");
+        let mut app = test_app(MockClient {
+            problems: vec![
+                mock::problem("1", "two-sum"),
+                mock::problem("2", "add-two-numbers"),
+            ],
+            details: vec![detail.clone()],
+            check: mock::verdict(10, "Accepted"),
+            ..MockClient::default()
+        });
+        let config = app.config.as_ref().unwrap().for_problem("1", "two-sum");
+        let file = solution_file(&config, &detail);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(
+            &file,
+            "struct Solution;\n\nimpl Solution {\n    pub fn f() -> i32 {\n        1\n    }\n}\n",
+        )
+        .unwrap();
+        app.start_loading_home();
+        settle(&mut app).await;
+        let mut h = Harness::new(app);
+        assert_eq!(h.screen_name(), "home");
+
+        // Enter leaves the search box for the table, then opens the row
+        h.press(KeyCode::Enter).await;
+        h.press(KeyCode::Enter).await;
+        assert_eq!(h.screen_name(), "detail");
+        assert_eq!(on_detail(&mut h.app).detail.title_slug, "two-sum");
+
+        h.press(KeyCode::Char('r')).await;
+        assert_eq!(h.screen_name(), "result");
+        let ResultStatus::Success(ref data) = on_result(&h.app).status else {
+            panic!("run didn't finish: {:?}", on_result(&h.app).status);
+        };
+        assert_eq!(data.status_code, 10);
+
+        h.press(KeyCode::Char('b')).await;
+        assert_eq!(h.screen_name(), "detail");
+        h.press(KeyCode::Char('b')).await;
+        assert_eq!(h.screen_name(), "home");
+        // The same Home came back, row and all
+        let home = home_state(&h.app);
+        assert_eq!(home.selected_problem().unwrap().title_slug, "two-sum");
+        assert!(h.app.saved_home.is_none());
     }
 
-    let result = parts.join("\n").trim().to_string();
-    if result.is_empty() {
-        Ok(content.to_string())
-    } else {
-        Ok(result)
+    #[tokio::test]
+    async fn undo_is_offered_only_while_its_toast_is_up() {
+        let client = Arc::new(MockClient::default());
+        let mut app = test_app(MockClient::default());
+        app.api_client = client.clone();
+        // Out of the search box, where `u` is typed
+        app.home_mut().unwrap().focus = HomeFocus::Table;
+        let mut h = Harness::new(app);
+        let removed = || {
+            let undo = ListUndo {
+                id_hash: "Graphs-hash".to_string(),
+                list_name: "Graphs".to_string(),
+                question_ids: vec!["1".to_string()],
+            };
+            ApiResult::ListRemoval(Ok(()), "Removed".to_string(), undo)
+        };
+
+        h.app.handle_api_result(removed());
+        for _ in 0..=UNDO_TICKS {
+            h.app.handle_tick();
+        }
+        assert!(h.app.success_message.is_none());
+        h.press(KeyCode::Char('u')).await;
+        assert!(client.calls().is_empty());
+
+        // Any other key takes the toast and the offer with it
+        h.app.handle_api_result(removed());
+        h.press(KeyCode::Char('j')).await;
+        h.press(KeyCode::Char('u')).await;
+        assert!(client.calls().is_empty());
+
+        h.app.handle_api_result(removed());
+        h.press(KeyCode::Char('u')).await;
+        assert_eq!(client.calls(), ["add_to_favorite"]);
+    }
+
+    #[tokio::test]
+    async fn settings_cancel_keeps_the_config_and_save_applies_it() {
+        let mut app = test_app(MockClient {
+            problems: vec![mock::problem("1", "two-sum")],
+            ..MockClient::default()
+        });
+        std::fs::create_dir_all(app.config.as_ref().unwrap().expanded_workspace()).unwrap();
+        app.start_loading_home();
+        settle(&mut app).await;
+        let mut h = Harness::new(app);
+        h.press(KeyCode::Enter).await;
+
+        h.press(KeyCode::Char('S')).await;
+        assert_eq!(h.screen_name(), "setup");
+        // Settings opens on the session cookie; the editor is just above
+        h.press(KeyCode::Up).await;
+        h.press(KeyCode::Char('x')).await;
+        h.press(KeyCode::Esc).await;
+        assert_eq!(h.screen_name(), "home");
+        assert_eq!(h.app.config.as_ref().unwrap().editor, "vim");
+        assert_eq!(
+            home_state(&h.app).selected_problem().unwrap().title_slug,
+            "two-sum"
+        );
+
+        h.press(KeyCode::Char('S')).await;
+        h.press(KeyCode::Up).await;
+        h.press(KeyCode::Char('x')).await;
+        let key = crossterm::event::KeyEvent::from(KeyCode::Enter);
+        h.app.handle_key(key, &mut h.terminal, &h.events).unwrap();
+        assert_eq!(h.screen_name(), "home");
+        assert!(h.app.error_overlay.is_none(), "{:?}", h.app.error_overlay);
+        assert_eq!(h.app.config.as_ref().unwrap().editor, "vimx");
+    }
+
+    #[tokio::test]
+    async fn confirm_submit_names_the_file_resolved_when_it_opened() {
+        let client = Arc::new(MockClient::default());
+        let mut app = test_app(MockClient::default());
+        app.api_client = client.clone();
+        let detail = mock::detail("1", "two-sum");
+        app.screen = Screen::Detail(Box::new(DetailState::new(detail.clone())));
+        let file = solution_file(app.config.as_ref().unwrap(), &detail);
+
+        let mut h = Harness::new(app);
+        h.app.request_submit(detail);
+        h.draw();
+        let confirm = h.app.confirm_submit.as_ref().unwrap();
+        assert_eq!(confirm.language, "rust");
+        assert_eq!(confirm.file, file);
+
+        // Anything but y or t cancels without sending
+        h.press(KeyCode::Char('n')).await;
+        assert!(h.app.confirm_submit.is_none());
+        assert!(client.calls().is_empty());
     }
 }
@@ -0,0 +1,198 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::api::types::{ProblemSummary, QuestionDetail};
+use crate::config::Config;
+use crate::files;
+
+pub fn cache_dir() -> PathBuf {
+    Config::config_dir().join("cache")
+}
+
+/// The whole list, or the part of it a `FetchFilter::cache_key` names.
+fn problems_path(key: Option<&str>) -> PathBuf {
+    match key {
+        Some(key) => cache_dir().join(format!("problems-{}.json", key.to_lowercase())),
+        None => cache_dir().join("problems.json"),
+    }
+}
+
+fn detail_path(slug: &str) -> PathBuf {
+    cache_dir().join("details").join(format!("{slug}.json"))
+}
+
+/// Bumped whenever `ProblemSummary` changes shape, so a list saved by
+/// another version is fetched again rather than half-read.
+const PROBLEMS_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct ProblemsFile<'a> {
+    version: u32,
+    problems: &'a [ProblemSummary],
+}
+
+/// Only the tag, so a file too old to have one can still be told apart.
+#[derive(Deserialize)]
+struct Versioned {
+    version: Option<u32>,
+}
+
+/// The problem list from the last complete fetch, if there is a readable
+/// one of this version. Anything else is deleted, so it's downloaded again.
+pub fn load_cached_problems(key: Option<&str>) -> Option<Vec<ProblemSummary>> {
+    let path = problems_path(key);
+    let contents = std::fs::read_to_string(&path).ok()?;
+    let problems = match serde_json::from_str::<Versioned>(&contents) {
+        Ok(Versioned {
+            version: Some(PROBLEMS_VERSION),
+        }) => {
+            #[derive(Deserialize)]
+            struct Stored {
+                problems: Vec<ProblemSummary>,
+            }
+            serde_json::from_str::<Stored>(&contents)
+                .map(|f| f.problems)
+                .map_err(|e| format!("unreadable: {e}"))
+        }
+        Ok(Versioned { version }) => Err(format!(
+            "version {} instead of {PROBLEMS_VERSION}",
+            version.map_or("none".to_string(), |v| v.to_string())
+        )),
+        Err(e) => Err(format!("unreadable: {e}")),
+    };
+    problems
+        .inspect_err(|reason| {
+            tracing::info!(path = %path.display(), "discarding problem cache, {reason}");
+            let _ = std::fs::remove_file(&path);
+        })
+        .ok()
+}
+
+/// How long ago the problem list was saved, if it has been.
+pub fn problems_cache_age(key: Option<&str>) -> Option<Duration> {
+    let modified = std::fs::metadata(problems_path(key))
+        .ok()?
+        .modified()
+        .ok()?;
+    modified.elapsed().ok()
+}
+
+pub fn save_problems_cache(problems: &[ProblemSummary], key: Option<&str>) -> Result<()> {
+    let dir = cache_dir();
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create cache dir {}", dir.display()))?;
+    let path = problems_path(key);
+    let file = ProblemsFile {
+        version: PROBLEMS_VERSION,
+        problems,
+    };
+    let contents = serde_json::to_string(&file).context("Failed to serialize problem list")?;
+    files::write_atomic(&path, contents)
+        .with_context(|| format!("Failed to write problem cache to {}", path.display()))?;
+    Ok(())
+}
+
+/// Remove everything in the cache directory. Returns what was removed,
+/// with the number of files in each directory.
+pub fn clear() -> Result<Vec<(PathBuf, Option<usize>)>> {
+    clear_dir(&cache_dir())
+}
+
+fn clear_dir(dir: &Path) -> Result<Vec<(PathBuf, Option<usize>)>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    let mut removed = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            let files = std::fs::read_dir(&path).map_or(0, |d| d.count());
+            std::fs::remove_dir_all(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            removed.push((path, Some(files)));
+        } else {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            removed.push((path, None));
+        }
+    }
+    removed.sort();
+    Ok(removed)
+}
+
+pub fn load_cached_detail(slug: &str) -> Option<QuestionDetail> {
+    let contents = std::fs::read_to_string(detail_path(slug)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+pub fn save_detail_cache(detail: &QuestionDetail) -> Result<()> {
+    let path = detail_path(&detail.title_slug);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create cache dir {}", dir.display()))?;
+    }
+    let contents = serde_json::to_string(detail).context("Failed to serialize problem detail")?;
+    files::write_atomic(&path, contents)
+        .with_context(|| format!("Failed to write detail cache to {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock;
+
+    #[test]
+    fn clearing_removes_everything_in_the_cache_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        assert!(clear_dir(&cache).unwrap().is_empty());
+
+        std::fs::create_dir_all(cache.join("details")).unwrap();
+        for file in [
+            "problems.json",
+            "problems-easy.json",
+            "details/two-sum.json",
+            "details/3sum.json",
+        ] {
+            std::fs::write(cache.join(file), "{}").unwrap();
+        }
+        let removed = clear_dir(&cache).unwrap();
+        assert_eq!(
+            removed,
+            [
+                (cache.join("details"), Some(2)),
+                (cache.join("problems-easy.json"), None),
+                (cache.join("problems.json"), None),
+            ]
+        );
+        assert_eq!(std::fs::read_dir(&cache).unwrap().count(), 0);
+        assert!(clear_dir(&cache).unwrap().is_empty());
+    }
+
+    #[test]
+    fn a_problem_list_of_another_version_is_discarded() {
+        let problems = [mock::problem("1", "two-sum")];
+        let key = Some("version-test");
+        let path = problems_path(key);
+        save_problems_cache(&problems, key).unwrap();
+        let cached = load_cached_problems(key).unwrap();
+        assert_eq!(cached[0].title_slug, "two-sum");
+
+        let problems = serde_json::to_value(problems).unwrap();
+        let stale = [
+            serde_json::json!({ "problems": problems }),
+            serde_json::json!({ "version": PROBLEMS_VERSION + 1, "problems": problems }),
+            serde_json::json!({ "version": PROBLEMS_VERSION, "problems": "truncated" }),
+        ];
+        for contents in stale {
+            std::fs::write(&path, contents.to_string()).unwrap();
+            assert!(load_cached_problems(key).is_none(), "{contents}");
+            assert!(!path.exists(), "{contents}");
+        }
+    }
+}
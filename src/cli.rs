@@ -0,0 +1,885 @@
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use ratatui::text::Line;
+
+use crate::api::client::{LEETCODE_SITE, LeetCodeApi, LeetCodeClient};
+use crate::api::types::{FetchFilter, ProblemSummary, QuestionDetail, ResultData, ResultKind};
+use crate::app::{browser_cookies, read_user_code};
+use crate::cache;
+use crate::config::Config;
+use crate::extract;
+use crate::git;
+use crate::index;
+use crate::scaffold;
+use crate::ui::fuzzy::FuzzyFilter;
+use crate::ui::icons::icons;
+use crate::ui::result::{build_result_lines, stdout_lines};
+
+/// Shown after the options in `--help`.
+const CD_WRAPPER_HELP: &str = "\
+To cd into the last project opened with `o` when leetui exits, wrap it in
+a shell function:
+
+    lc() {
+        local f; f=$(mktemp) || return
+        command leetui --cd-file \"$f\" \"$@\"
+        local dir; dir=$(cat \"$f\"); rm -f \"$f\"
+        [ -n \"$dir\" ] && cd \"$dir\"
+    }
+
+The scaffold command writes the new project's directory the same way.
+Without --cd-file or LEETUI_CD_FILE the TUI prints the directory to stdout.";
+
+#[derive(Parser)]
+#[command(version, about, after_help = CD_WRAPPER_HELP)]
+pub struct Cli {
+    /// Open this problem straight away, by slug or number, e.g. two-sum or 1
+    pub problem: Option<String>,
+
+    /// Browse cached problems without touching the network
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Log API requests and parse failures to leetui.log in the config
+    /// directory (also enabled by LEETUI_LOG, which sets the filter)
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// On exit, write the last opened project directory to this file
+    /// instead of stdout
+    #[arg(long, value_name = "PATH", env = "LEETUI_CD_FILE")]
+    pub cd_file: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Scaffold a problem into the workspace and print its directory
+    Scaffold {
+        /// Problem slug, e.g. two-sum
+        slug: String,
+        /// Rewrite the solution file, backing up one with changes, and
+        /// README.md even if it was edited
+        #[arg(long)]
+        force: bool,
+    },
+    /// Submit the local solution and print the verdict; exits non-zero
+    /// unless it is accepted
+    Submit {
+        /// Problem slug, e.g. two-sum, or a solution file or project
+        /// directory in the workspace
+        problem: String,
+        /// Print the verdict as a JSON `RunReport` instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run the local solution on the example cases and print each result;
+    /// exits non-zero unless every case passes
+    Run {
+        /// Problem slug, e.g. two-sum, or a solution file or project
+        /// directory in the workspace
+        problem: String,
+        /// Print the verdict as a JSON `RunReport` instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the problem set as TSV (id, slug, title, difficulty, AC rate,
+    /// paid), from the cache when it is under a day old
+    List {
+        #[arg(long, value_enum)]
+        difficulty: Option<Difficulty>,
+        /// Only problems with this topic tag slug or name; repeat to
+        /// require several
+        #[arg(long)]
+        tag: Vec<String>,
+        /// Needs a logged-in fetch for statuses; unknown counts as todo
+        #[arg(long, value_enum)]
+        status: Option<Status>,
+        /// Fuzzy match on the title, or an exact problem number
+        #[arg(long)]
+        search: Option<String>,
+        /// Print a JSON array instead of TSV
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write a table of the scaffolded problems to README.md at the
+    /// workspace root, between marker comments so the rest is kept
+    Index,
+    /// Check LeetCode cookies and save them to the config. With no
+    /// options, prompts for them on a terminal
+    Login {
+        /// Read the cookies from a browser that is logged in to leetcode.com
+        #[arg(long, conflicts_with_all = ["session", "csrf"])]
+        from_browser: bool,
+        /// The LEETCODE_SESSION cookie
+        #[arg(long, requires = "csrf")]
+        session: Option<String>,
+        /// The csrftoken cookie
+        #[arg(long, requires = "session")]
+        csrf: Option<String>,
+    },
+    /// Maintain the download cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Maintain the config file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Open today's daily challenge
+    Daily,
+    /// Open a random problem
+    Random {
+        #[arg(long, value_enum)]
+        difficulty: Option<Difficulty>,
+        /// Only problems with this topic tag slug, e.g. dynamic-programming;
+        /// repeat to require several
+        #[arg(long)]
+        tag: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheAction {
+    /// Delete the cached problem lists and problem details; they are
+    /// downloaded again when needed
+    Clear,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Move config.toml aside, so the next start runs Setup with the
+    /// defaults. Asks first
+    Reset {
+        /// Don't ask
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// The name the API filters on.
+    fn api_name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "EASY",
+            Difficulty::Medium => "MEDIUM",
+            Difficulty::Hard => "HARD",
+        }
+    }
+
+    /// The name problems are labelled with.
+    fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Status {
+    Solved,
+    Attempted,
+    Todo,
+}
+
+impl Status {
+    fn accepts(self, status: Option<&str>) -> bool {
+        match self {
+            Status::Solved => status == Some("ac"),
+            Status::Attempted => status == Some("notac"),
+            Status::Todo => !matches!(status, Some("ac" | "notac")),
+        }
+    }
+}
+
+/// Filters for `leetui list`.
+pub struct ListQuery {
+    pub difficulty: Option<Difficulty>,
+    pub tags: Vec<String>,
+    pub status: Option<Status>,
+    pub search: Option<String>,
+}
+
+/// The problem list cache is refetched once it is older than this.
+const LIST_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A row of `leetui list --json`. Field names are part of the CLI's
+/// interface, so only add to them.
+#[derive(Serialize)]
+struct ListEntry<'a> {
+    id: &'a str,
+    slug: &'a str,
+    title: &'a str,
+    difficulty: &'a str,
+    ac_rate: f64,
+    paid: bool,
+}
+
+/// The `--json` output. Field names are part of the CLI's interface, so
+/// only add to them.
+#[derive(Serialize)]
+pub struct RunReport {
+    pub verdict: String,
+    pub passed: Option<i32>,
+    pub total: Option<i32>,
+    pub runtime: Option<String>,
+    pub memory: Option<String>,
+    pub error: Option<String>,
+}
+
+impl RunReport {
+    fn from_result(data: &ResultData) -> Self {
+        Self {
+            verdict: data.status_msg.clone(),
+            passed: data.total_correct,
+            total: data.total_testcases,
+            runtime: data.runtime.clone(),
+            memory: data.memory.clone(),
+            error: data.compile_error.clone(),
+        }
+    }
+}
+
+/// `force` rewrites the solution file, keeping a backup when it had
+/// changes, and a README.md that was edited since it was generated.
+pub async fn scaffold(
+    config: Option<Config>,
+    slug: &str,
+    force: bool,
+    cd_file: Option<&Path>,
+) -> Result<()> {
+    let config = match config {
+        Some(c) => c,
+        None => Config::create_default()?,
+    };
+    let client = LeetCodeClient::new(
+        config.site(),
+        config.leetcode_session.as_deref(),
+        config.csrf_token.as_deref(),
+    )?;
+    let detail = client
+        .fetch_problem_detail(slug)
+        .await
+        .with_context(|| format!("Failed to fetch problem {slug}"))?;
+    let config = config.for_problem(&detail.frontend_question_id, &detail.title_slug);
+
+    let workspace = config.expanded_workspace();
+    std::fs::create_dir_all(&workspace)
+        .with_context(|| format!("Failed to create workspace {}", workspace.display()))?;
+    let project_dir = scaffold::project_dir(
+        &workspace,
+        &config.scaffold,
+        &detail.frontend_question_id,
+        &detail.title_slug,
+    );
+    // stdout is only the directory, for the shell wrapper to cd into
+    if config.git_commit
+        && let Err(e) = git::ensure_repo(&workspace, &project_dir)
+    {
+        eprintln!("Couldn't set up git: {e:#}");
+    }
+    let (_, outcome) = scaffold::scaffold_problem(
+        &workspace,
+        &config.scaffold,
+        &detail,
+        &config.language,
+        force,
+    )?;
+    if let scaffold::Outcome::BackedUp(backup) = outcome {
+        eprintln!("Saved the previous solution as {}", backup.display());
+    }
+
+    println!("{}", project_dir.display());
+    if let Some(path) = cd_file {
+        write_cd_file(path, Some(&project_dir))?;
+    }
+    Ok(())
+}
+
+/// Rebuild the workspace index from the projects on disk, the journal and
+/// the cached problem list, without going online.
+pub fn index(config: Option<&Config>) -> Result<()> {
+    let config = config.cloned().unwrap_or_default();
+    let (path, count) = index::write_index(&config, &[])?;
+    println!(
+        "Indexed {count} problem{} in {}",
+        if count == 1 { "" } else { "s" },
+        path.display()
+    );
+    Ok(())
+}
+
+/// Print the problems matching `query`. Nothing here needs a login, and
+/// the terminal UI is never started.
+pub async fn list(
+    config: Option<&Config>,
+    offline: bool,
+    query: &ListQuery,
+    json: bool,
+) -> Result<()> {
+    let problems = load_problem_list(config, offline).await?;
+
+    let tag_ok = |p: &ProblemSummary| {
+        query.tags.iter().all(|tag| {
+            p.topic_tags
+                .iter()
+                .any(|t| t.slug.eq_ignore_ascii_case(tag) || t.name.eq_ignore_ascii_case(tag))
+        })
+    };
+    let mut fuzzy = FuzzyFilter::default();
+    let search = query
+        .search
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty());
+    fuzzy.set_query(search.unwrap_or_default());
+
+    let mut matches: Vec<(u32, &ProblemSummary)> = Vec::new();
+    for p in &problems {
+        if query.difficulty.is_some_and(|d| p.difficulty != d.label())
+            || query
+                .status
+                .is_some_and(|s| !s.accepts(p.status.as_deref()))
+            || !tag_ok(p)
+        {
+            continue;
+        }
+        match search {
+            None => matches.push((0, p)),
+            Some(q) if p.frontend_question_id == q => matches.push((u32::MAX, p)),
+            Some(_) => {
+                if let Some((score, _)) = fuzzy.match_str(&p.title) {
+                    matches.push((score, p));
+                }
+            }
+        }
+    }
+    // Stable, so equal scores stay in problem-number order
+    matches.sort_by_key(|m| std::cmp::Reverse(m.0));
+
+    let mut out = std::io::stdout().lock();
+    if json {
+        let entries: Vec<ListEntry> = matches
+            .iter()
+            .map(|(_, p)| ListEntry {
+                id: &p.frontend_question_id,
+                slug: &p.title_slug,
+                title: &p.title,
+                difficulty: &p.difficulty,
+                ac_rate: p.ac_rate,
+                paid: p.is_paid_only,
+            })
+            .collect();
+        serde_json::to_writer_pretty(&mut out, &entries).context("Failed to serialize problems")?;
+        writeln!(out)?;
+    } else {
+        for (_, p) in &matches {
+            let row = writeln!(
+                out,
+                "{}\t{}\t{}\t{}\t{:.1}\t{}",
+                p.frontend_question_id,
+                p.title_slug,
+                p.title.replace('\t', " "),
+                p.difficulty,
+                p.ac_rate,
+                p.is_paid_only,
+            );
+            // A closed pipe, as when piping into `head`, just ends the list
+            if row.is_err() {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The cached problem list while it is fresh, otherwise a new download,
+/// falling back to a stale cache when the download fails.
+async fn load_problem_list(config: Option<&Config>, offline: bool) -> Result<Vec<ProblemSummary>> {
+    let cached = cache::load_cached_problems(None);
+    let fresh = cache::problems_cache_age(None).is_some_and(|age| age < LIST_MAX_AGE);
+    if offline || (fresh && cached.is_some()) {
+        return cached
+            .context("No cached problem list; run leetui list without --offline to download it");
+    }
+
+    let config = config.cloned().unwrap_or_default();
+    let downloaded = async {
+        let client = LeetCodeClient::new(
+            config.site(),
+            config.leetcode_session.as_deref(),
+            config.csrf_token.as_deref(),
+        )?;
+        fetch_all_problems(&client, config.fetch_batch()).await
+    };
+    match downloaded.await {
+        Ok(problems) => {
+            if let Err(e) = cache::save_problems_cache(&problems, None) {
+                eprintln!("Warning: {e:#}");
+            }
+            Ok(problems)
+        }
+        Err(e) => match cached {
+            Some(problems) => {
+                eprintln!("Warning: {e:#}\nUsing the cached problem list.");
+                Ok(problems)
+            }
+            None => Err(e.context("No cached problem list and it couldn't be downloaded")),
+        },
+    }
+}
+
+async fn fetch_all_problems(
+    client: &dyn LeetCodeApi,
+    batch_size: i32,
+) -> Result<Vec<ProblemSummary>> {
+    let mut problems = Vec::new();
+    loop {
+        let (batch, total) = client
+            .fetch_problems(
+                batch_size,
+                problems.len() as i32,
+                &FetchFilter::default(),
+                None,
+            )
+            .await
+            .context("Failed to fetch the problem list")?;
+        let done = batch.is_empty();
+        problems.extend(batch);
+        if done || problems.len() as i32 >= total {
+            return Ok(problems);
+        }
+    }
+}
+
+/// Where `leetui login` gets the cookies from.
+pub enum LoginSource {
+    Browser,
+    Given { session: String, csrf: String },
+    Prompt,
+}
+
+/// Check the cookies against LeetCode, save them and print the username.
+pub async fn login(config: Option<Config>, source: LoginSource) -> Result<()> {
+    let (session, csrf) = match source {
+        LoginSource::Browser => browser_cookies()?,
+        LoginSource::Given { session, csrf } => (session, csrf),
+        LoginSource::Prompt => {
+            if !std::io::stdin().is_terminal() {
+                bail!("No cookies given; pass --from-browser, or --session and --csrf");
+            }
+            let session = read_hidden("LEETCODE_SESSION: ")?;
+            let csrf = read_line("csrftoken: ")?;
+            (session, csrf)
+        }
+    };
+    let (session, csrf) = (session.trim().to_string(), csrf.trim().to_string());
+    if session.is_empty() || csrf.is_empty() {
+        bail!("Both the session and csrftoken cookies are needed");
+    }
+
+    let site = config.as_ref().map_or(LEETCODE_SITE, Config::site);
+    let client = LeetCodeClient::new(site, Some(&session), Some(&csrf))?;
+    let username = client.check_login().await.context("Login failed")?;
+
+    let mut config = match config {
+        Some(c) => c,
+        None => Config::create_default()?,
+    };
+    config.leetcode_session = Some(session);
+    config.csrf_token = Some(csrf);
+    config.save()?;
+    println!("Logged in as {username}");
+    Ok(())
+}
+
+/// Delete everything in the cache directory and say what went.
+pub fn clear_cache() -> Result<()> {
+    let removed = cache::clear()?;
+    if removed.is_empty() {
+        println!(
+            "The cache at {} is already empty",
+            cache::cache_dir().display()
+        );
+    }
+    for (path, files) in removed {
+        match files {
+            Some(n) => println!(
+                "Removed {} ({n} file{})",
+                path.display(),
+                if n == 1 { "" } else { "s" }
+            ),
+            None => println!("Removed {}", path.display()),
+        }
+    }
+    Ok(())
+}
+
+/// Move the config aside, once the user agrees to lose their settings
+/// and login. Journal, history and the cache are left alone.
+pub fn reset_config(yes: bool) -> Result<()> {
+    let path = Config::config_path();
+    if !path.exists() {
+        println!("No config at {}, nothing to reset", path.display());
+        return Ok(());
+    }
+    if !yes {
+        if !std::io::stdin().is_terminal() {
+            bail!("Not a terminal; pass --yes to reset without asking");
+        }
+        let answer = read_line(&format!(
+            "Reset {}? Your settings and login will be lost [y/N] ",
+            path.display()
+        ))?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            println!("Config left as it was");
+            return Ok(());
+        }
+    }
+    let backup = path.with_extension("toml.bak");
+    std::fs::rename(&path, &backup)
+        .with_context(|| format!("Failed to move {} aside", path.display()))?;
+    println!("Moved {} to {}", path.display(), backup.display());
+    println!("The next start runs Setup with the defaults");
+    Ok(())
+}
+
+fn read_line(prompt: &str) -> Result<String> {
+    eprint!("{prompt}");
+    std::io::stderr().flush()?;
+    let mut line = String::new();
+    std::io::stdin()
+        .read_line(&mut line)
+        .context("Failed to read input")?;
+    Ok(line)
+}
+
+/// Like `read_line`, without echoing what is typed.
+fn read_hidden(prompt: &str) -> Result<String> {
+    eprint!("{prompt}");
+    std::io::stderr().flush()?;
+    terminal::enable_raw_mode().context("Failed to read input")?;
+    let value = read_keys();
+    let _ = terminal::disable_raw_mode();
+    eprintln!();
+    value
+}
+
+fn read_keys() -> Result<String> {
+    let mut value = String::new();
+    loop {
+        match event::read().context("Failed to read input")? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Enter => return Ok(value),
+                KeyCode::Esc => bail!("Cancelled"),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    bail!("Cancelled")
+                }
+                KeyCode::Char(c) => value.push(c),
+                KeyCode::Backspace => {
+                    value.pop();
+                }
+                _ => {}
+            },
+            Event::Paste(text) => value.push_str(&text),
+            _ => {}
+        }
+    }
+}
+
+/// The problem `daily` or `random` should open, looked up before the TUI
+/// starts. A failed lookup is reported on stderr and gives `None`, so the
+/// TUI opens on Home as usual.
+pub async fn launch_slug(config: Option<&Config>, command: &Command) -> Option<String> {
+    let config = config.cloned().unwrap_or_default();
+    let slug = async {
+        let client = LeetCodeClient::new(
+            config.site(),
+            config.leetcode_session.as_deref(),
+            config.csrf_token.as_deref(),
+        )?;
+        match command {
+            Command::Daily => client
+                .fetch_daily_slug()
+                .await
+                .context("Failed to fetch the daily challenge"),
+            Command::Random { difficulty, tag } => client
+                .fetch_random_slug(difficulty.map(Difficulty::api_name), tag)
+                .await
+                .context("Failed to pick a random problem"),
+            _ => bail!("Not a launch command"),
+        }
+    };
+    match slug.await {
+        Ok(slug) => Some(slug),
+        Err(e) => {
+            eprintln!("Error: {e:#}");
+            None
+        }
+    }
+}
+
+/// Write the directory a shell wrapper should cd into, or nothing when
+/// there isn't one, so a stale directory never lingers in the file.
+pub fn write_cd_file(path: &Path, dir: Option<&Path>) -> Result<()> {
+    let contents = dir
+        .map(|d| d.as_os_str().as_encoded_bytes())
+        .unwrap_or_default();
+    std::fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// The config for a headless run or submit, which needs to be logged in.
+fn authenticated(config: Option<Config>) -> Result<(Config, LeetCodeClient)> {
+    let Some(config) = config.filter(Config::is_authenticated) else {
+        bail!("Authentication required.\nRun leetui and log in from settings (S).");
+    };
+    let client = LeetCodeClient::new(
+        config.site(),
+        config.leetcode_session.as_deref(),
+        config.csrf_token.as_deref(),
+    )?;
+    Ok((config, client))
+}
+
+/// The problem and the code to send for `problem`: a slug, whose solution
+/// is found in the workspace, or a path to a solution file or project
+/// directory, whose project directory names the problem. A file's
+/// extension picks the language.
+async fn load_solution(
+    config: &mut Config,
+    client: &LeetCodeClient,
+    problem: &str,
+) -> Result<(QuestionDetail, String)> {
+    let path = Path::new(problem);
+    if !path.exists() {
+        let detail = client
+            .fetch_problem_detail(problem)
+            .await
+            .with_context(|| format!("Failed to fetch problem {problem}"))?;
+        *config = config.for_problem(&detail.frontend_question_id, &detail.title_slug);
+        let code = read_user_code(config, &detail)?;
+        return Ok((detail, code));
+    }
+
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+    if path.is_file()
+        && let Some(language) = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(language_for)
+    {
+        config.language = language.to_string();
+    }
+    // The directory right under the workspace, else the nearest one named
+    // like a project
+    let workspace = config.expanded_workspace().canonicalize().ok();
+    let slug_of = |dir: &Path| {
+        let name = dir.file_name()?.to_str()?;
+        config.scaffold.slug_from_dir(name).map(String::from)
+    };
+    let project_dir = path
+        .ancestors()
+        .find(|dir| workspace.is_some() && dir.parent() == workspace.as_deref())
+        .filter(|dir| slug_of(dir).is_some())
+        .or_else(|| path.ancestors().find(|dir| slug_of(dir).is_some()))
+        .with_context(|| format!("{} isn't inside a project directory", path.display()))?;
+    let slug = slug_of(project_dir).unwrap_or_default();
+    if path.is_dir()
+        && let Some(language) = scaffold::project_language(project_dir)
+    {
+        config.language = language;
+    }
+    let file = if path.is_dir() {
+        scaffold::solution_path(project_dir, &config.scaffold, &config.language)
+    } else {
+        path.clone()
+    };
+
+    let detail = client
+        .fetch_problem_detail(&slug)
+        .await
+        .with_context(|| format!("Failed to fetch problem {slug}"))?;
+    let content = std::fs::read_to_string(&file)
+        .with_context(|| format!("Failed to read code from {}", file.display()))?;
+    let code = extract::extract_solution(&config.language, &content)?;
+    Ok((detail, code))
+}
+
+/// The language setting for a solution file extension.
+fn language_for(extension: &str) -> Option<&'static str> {
+    Some(match extension {
+        "rs" => "rust",
+        "go" => "golang",
+        "py" => "python3",
+        "cpp" | "cc" => "cpp",
+        "c" => "c",
+        "java" => "java",
+        "js" => "javascript",
+        "ts" => "typescript",
+        _ => return None,
+    })
+}
+
+fn print_lines(lines: Vec<Line<'static>>) {
+    for line in lines {
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        println!("{}", text.trim_end());
+    }
+}
+
+fn print_report(data: &ResultData) -> Result<()> {
+    let report = serde_json::to_string_pretty(&RunReport::from_result(data))
+        .context("Failed to serialize result")?;
+    println!("{report}");
+    Ok(())
+}
+
+/// Returns whether the submission was accepted.
+pub async fn submit(config: Option<Config>, problem: &str, json: bool) -> Result<bool> {
+    let (mut config, client) = authenticated(config)?;
+    let (detail, code) = load_solution(&mut config, &client, problem).await?;
+
+    let submission_id = client
+        .submit_code(
+            &detail.title_slug,
+            &detail.question_id,
+            config.lang_slug(),
+            &code,
+        )
+        .await?;
+    let resp = client
+        .poll_result(&detail.title_slug, &submission_id)
+        .await?;
+    let data = ResultData::from_check(&resp);
+
+    if json {
+        print_report(&data)?;
+    } else {
+        println!("{}. {}", detail.frontend_question_id, detail.title);
+        let mut lines = build_result_lines(&data, ResultKind::Submit);
+        lines.extend(stdout_lines(&data, true));
+        print_lines(lines);
+    }
+    let accepted = data.status_code == 10;
+    if accepted && config.git_commit {
+        let project_dir = scaffold::project_dir(
+            &config.expanded_workspace(),
+            &config.scaffold,
+            &detail.frontend_question_id,
+            &detail.title_slug,
+        );
+        let message = git::solve_message(
+            &detail.frontend_question_id,
+            &detail.title,
+            data.runtime.as_deref(),
+            data.memory.as_deref(),
+        );
+        if let Err(e) = git::commit_project(&project_dir, &message) {
+            eprintln!("Couldn't commit: {e:#}");
+        }
+    }
+    Ok(accepted)
+}
+
+/// Returns whether the run finished and every example case matched.
+pub async fn run(config: Option<Config>, problem: &str, json: bool) -> Result<bool> {
+    let (mut config, client) = authenticated(config)?;
+    let (detail, code) = load_solution(&mut config, &client, problem).await?;
+    let cases = detail.example_cases();
+
+    let interpret_id = client
+        .run_code(
+            &detail.title_slug,
+            &detail.question_id,
+            config.lang_slug(),
+            &code,
+            &cases.join("\n"),
+        )
+        .await?;
+    let resp = client
+        .poll_result(&detail.title_slug, &interpret_id)
+        .await?;
+    let data = ResultData::from_check(&resp);
+    let passed = data.status_code == 10 && resp.correct_answer == Some(true);
+
+    if json {
+        print_report(&data)?;
+        return Ok(passed);
+    }
+    println!("{}. {}", detail.frontend_question_id, detail.title);
+    if data.status_code != 10 {
+        // Compile and runtime errors: the same report the TUI shows
+        let mut lines = build_result_lines(&data, ResultKind::Run);
+        lines.extend(stdout_lines(&data, true));
+        print_lines(lines);
+        return Ok(false);
+    }
+
+    let outputs = data.code_output.clone().unwrap_or_default();
+    let expected = resp.expected_code_answer.clone().unwrap_or_default();
+    for (i, input) in cases.iter().enumerate() {
+        let output = outputs.get(i).map_or("", String::as_str);
+        let want = expected.get(i).map_or("", String::as_str);
+        let mark = if output == want {
+            icons().check
+        } else {
+            icons().cross
+        };
+        println!();
+        println!("  Case {} {mark}", i + 1);
+        println!(
+            "    Input:    {}",
+            input.lines().collect::<Vec<_>>().join(", ")
+        );
+        println!("    Output:   {output}");
+        println!("    Expected: {want}");
+    }
+    print_lines(stdout_lines(&data, true));
+    Ok(passed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maintenance_subcommands_parse() {
+        let cli = Cli::try_parse_from(["leetui", "cache", "clear"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Cache {
+                action: CacheAction::Clear
+            })
+        ));
+        let cli = Cli::try_parse_from(["leetui", "config", "reset", "--yes"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Config {
+                action: ConfigAction::Reset { yes: true }
+            })
+        ));
+        assert!(Cli::try_parse_from(["leetui", "cache"]).is_err());
+    }
+}
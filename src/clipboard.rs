@@ -0,0 +1,47 @@
+use anyhow::{Context, Result, bail};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Copy `text` to the system clipboard by piping it into the platform's
+/// clipboard tool (pbcopy, wl-copy, xclip or xsel).
+pub fn copy(text: &str) -> Result<()> {
+    let candidates: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        &[("wl-copy", &[]), ("xclip", &["-selection", "clipboard"])]
+    } else {
+        &[
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ]
+    };
+
+    for (program, args) in candidates {
+        let mut child = match Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(_) => continue,
+        };
+        child
+            .stdin
+            .take()
+            .context("Clipboard stdin unavailable")?
+            .write_all(text.as_bytes())
+            .with_context(|| format!("Failed to write to {program}"))?;
+        let status = child
+            .wait()
+            .with_context(|| format!("Failed to wait for {program}"))?;
+        if !status.success() {
+            bail!("{program} exited with {status}");
+        }
+        return Ok(());
+    }
+
+    let names: Vec<&str> = candidates.iter().map(|(p, _)| *p).collect();
+    bail!("No clipboard tool found (tried {})", names.join(", "))
+}
@@ -1,26 +1,348 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use crate::api::client::LEETCODE_SITE;
+use crate::files::write_atomic;
+use crate::scaffold;
+
+/// Either one directory for every language, or a table keyed by language:
+///
+/// ```toml
+/// [workspace_dir]
+/// rust = "~/lc-rust"
+/// python3 = "~/lc-python"
+/// default = "~/leetcode"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WorkspaceDir {
+    Single(String),
+    PerLanguage(BTreeMap<String, String>),
+}
+
+/// A built-in theme by name, or a table that picks one and overrides
+/// single colors:
+///
+/// ```toml
+/// [theme]
+/// preset = "light"
+/// accent = "#268bd2"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ThemeSetting {
+    Preset(String),
+    Custom(ThemeTable),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThemeTable {
+    #[serde(default)]
+    pub preset: Option<String>,
+    /// Color names or `#rrggbb`, keyed by `Theme` field
+    #[serde(flatten)]
+    pub colors: BTreeMap<String, String>,
+}
+
+impl ThemeSetting {
+    pub fn preset_name(&self) -> &str {
+        match self {
+            Self::Preset(name) => name,
+            Self::Custom(table) => table.preset.as_deref().unwrap_or("dark"),
+        }
+    }
+
+    /// Switch to another preset, keeping any color overrides.
+    pub fn set_preset(&mut self, name: &str) {
+        match self {
+            Self::Preset(current) => *current = name.to_string(),
+            Self::Custom(table) => table.preset = Some(name.to_string()),
+        }
+    }
+}
+
+/// How `o` runs the editor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EditorMode {
+    /// Hand the terminal to the editor until it exits
+    #[default]
+    Suspend,
+    /// Start the editor and carry on; for GUI editors like `code`
+    Detach,
+    /// Open the editor in a new tmux pane
+    Tmux,
+    /// Open the editor in a new zellij pane
+    Zellij,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
-    pub workspace_dir: String,
+    pub workspace_dir: WorkspaceDir,
     pub language: String,
     pub editor: String,
     #[serde(default)]
+    pub editor_mode: EditorMode,
+    /// Whether the editor needs the terminal until it exits. Unset means
+    /// yes, except for known GUI editors
+    #[serde(default)]
+    pub editor_blocking: Option<bool>,
+    #[serde(default)]
     pub leetcode_session: Option<String>,
     #[serde(default)]
     pub csrf_token: Option<String>,
+    /// Terminal width below which Home hides the preview pane
+    #[serde(default = "default_preview_min_width")]
+    pub preview_min_width: u16,
+    /// Capture the mouse for clicks and scrolling; off by default because it
+    /// disables the terminal's own text selection
+    #[serde(default)]
+    pub mouse: bool,
+    /// `j` past the last row goes to the first, and `k` past the first to
+    /// the last, instead of stopping
+    #[serde(default)]
+    pub wrap_navigation: bool,
+    /// Don't ask to log in at startup without cookies; Settings and
+    /// Ctrl+L there still log in
+    #[serde(default)]
+    pub skip_login_prompt: bool,
+    /// Ask before `s` sends a submission
+    #[serde(default = "default_confirm_submit")]
+    pub confirm_submit: bool,
+    /// Ask which language to scaffold a new project in, when the problem
+    /// has snippets for more than one
+    #[serde(default = "default_ask_language")]
+    pub ask_language: bool,
+    /// Keep the workspace in a git repository and commit a problem's
+    /// project when a submission is accepted
+    #[serde(default)]
+    pub git_commit: bool,
+    /// Shell command `t` runs in the project directory instead of the
+    /// language's usual test runner
+    #[serde(default)]
+    pub test_command: Option<String>,
+    /// `dark` when unset
+    #[serde(default)]
+    pub theme: Option<ThemeSetting>,
+    /// Draw Unicode glyphs; `false` falls back to ASCII. Detected from the
+    /// terminal and locale when unset
+    #[serde(default)]
+    pub unicode: Option<bool>,
+    #[serde(default, skip_serializing_if = "ScaffoldConfig::is_default")]
+    pub scaffold: ScaffoldConfig,
+    /// Download only this difficulty (`easy`, `medium` or `hard`) of the
+    /// problem list, rather than all of it
+    #[serde(default)]
+    pub fetch_difficulty: Option<String>,
+    /// Download only problems with all of these topic tag slugs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fetch_tags: Vec<String>,
+    /// Download only the problems in this favorite list, by its id
+    #[serde(default)]
+    pub fetch_list: Option<String>,
+    /// The Home status filter (`todo`, `attempted`, `solved` or
+    /// `unsolved`), kept from the last session
+    #[serde(default)]
+    pub status_filter: Option<String>,
+    /// Problems asked for per request when downloading the list
+    #[serde(default = "default_fetch_batch_size")]
+    pub fetch_batch_size: u32,
+    /// Batch requests in flight at once; `1` downloads one after another
+    #[serde(default = "default_fetch_concurrency")]
+    pub fetch_concurrency: usize,
+    /// The LeetCode site to talk to, e.g. `https://leetcode.cn`.
+    /// `https://leetcode.com` when unset
+    #[serde(default)]
+    pub site: Option<String>,
+}
+
+/// How projects are named and laid out in the workspace:
+///
+/// ```toml
+/// [scaffold]
+/// dir = "{slug}"
+///
+/// [scaffold.files]
+/// golang = "main.go"
+///
+/// [scaffold.templates]
+/// golang = "~/leetcode/template.go"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScaffoldConfig {
+    /// Project directory name, from `{id}` and `{slug}`. `{id}-{slug}`
+    /// when unset
+    #[serde(default)]
+    pub dir: Option<String>,
+    /// Make each Rust project a member of one cargo workspace at the
+    /// workspace root, sharing a `target/` dir
+    #[serde(default)]
+    pub rust_workspace_mode: bool,
+    /// Solution file per language, relative to the project directory
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    /// Per language, a file whose `{{snippet}}`, `{{description}}`, `{{id}}`,
+    /// `{{title}}` and `{{slug}}` are filled in to make the solution file
+    #[serde(default)]
+    pub templates: BTreeMap<String, String>,
+}
+
+const DEFAULT_DIR_PATTERN: &str = "{id}-{slug}";
+
+impl ScaffoldConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn dir_pattern(&self) -> &str {
+        self.dir.as_deref().unwrap_or(DEFAULT_DIR_PATTERN)
+    }
+
+    /// The project directory name for a problem.
+    pub fn dir_name(&self, id: &str, slug: &str) -> String {
+        self.dir_pattern()
+            .replace("{id}", id)
+            .replace("{slug}", slug)
+    }
+
+    /// The slug in a directory name made by `dir_name`, if it is one.
+    pub fn slug_from_dir<'a>(&self, name: &'a str) -> Option<&'a str> {
+        let (before, after) = self.dir_pattern().split_once("{slug}")?;
+        let slug = match before.split_once("{id}") {
+            Some((prefix, sep)) => {
+                let rest = name.strip_prefix(prefix)?;
+                let digits =
+                    rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+                if digits == 0 {
+                    return None;
+                }
+                rest[digits..].strip_prefix(sep)?
+            }
+            None => name.strip_prefix(before)?,
+        };
+        let slug = match after.split_once("{id}") {
+            Some((sep, suffix)) => {
+                let rest = slug.strip_suffix(suffix)?;
+                let digits = rest.len() - rest.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+                if digits == 0 {
+                    return None;
+                }
+                rest[..rest.len() - digits].strip_suffix(sep)?
+            }
+            None => slug.strip_suffix(after)?,
+        };
+        let valid = !slug.is_empty()
+            && slug
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        valid.then_some(slug)
+    }
+
+    /// The configured solution file for `language` or one of its aliases.
+    pub fn file_for(&self, language: &str) -> Option<&str> {
+        std::iter::once(language)
+            .chain(language_aliases(language).iter().copied())
+            .find_map(|l| self.files.get(l))
+            .map(String::as_str)
+    }
+
+    /// The configured template for `language` or one of its aliases, with
+    /// `~` expanded.
+    pub fn template_for(&self, language: &str) -> Option<PathBuf> {
+        std::iter::once(language)
+            .chain(language_aliases(language).iter().copied())
+            .find_map(|l| self.templates.get(l))
+            .map(|path| expand_tilde(path))
+    }
+}
+
+/// The `editor` setting split like a shell would, quotes and escapes
+/// included, with a leading `~` expanded in each word. The file to open
+/// goes after these.
+pub fn editor_argv(editor: &str) -> Result<Vec<String>> {
+    let words = shell_words::split(editor)
+        .with_context(|| format!("Can't parse editor command '{editor}'"))?;
+    if words.is_empty() {
+        anyhow::bail!("The editor setting is empty");
+    }
+    let home = dirs::home_dir();
+    Ok(words
+        .into_iter()
+        .map(|w| match (&home, w.strip_prefix("~/")) {
+            (Some(home), Some(rest)) => home.join(rest).to_string_lossy().into_owned(),
+            (Some(home), None) if w == "~" => home.to_string_lossy().into_owned(),
+            _ => w,
+        })
+        .collect())
+}
+
+/// Display name of a GUI editor command like `code --wait`, or `None` for
+/// anything else.
+pub fn gui_editor_name(editor: &str) -> Option<&'static str> {
+    let argv = editor_argv(editor).ok()?;
+    let program = std::path::Path::new(argv.first()?).file_stem()?.to_str()?;
+    match program {
+        "code" | "code-insiders" => Some("VS Code"),
+        "codium" => Some("VSCodium"),
+        "subl" => Some("Sublime Text"),
+        "zed" | "zeditor" => Some("Zed"),
+        _ => None,
+    }
+}
+
+fn default_preview_min_width() -> u16 {
+    120
+}
+
+fn default_confirm_submit() -> bool {
+    true
+}
+
+fn default_ask_language() -> bool {
+    true
+}
+
+fn default_fetch_batch_size() -> u32 {
+    100
+}
+
+fn default_fetch_concurrency() -> usize {
+    1
 }
 
+/// The most batch requests `fetch_concurrency` can put in flight at once.
+const MAX_FETCH_CONCURRENCY: usize = 16;
+
 impl Default for Config {
     fn default() -> Self {
         Self {
-            workspace_dir: "~/leetcode".to_string(),
+            workspace_dir: WorkspaceDir::Single("~/leetcode".to_string()),
             language: "rust".to_string(),
             editor: "vim".to_string(),
+            editor_mode: EditorMode::Suspend,
+            editor_blocking: None,
             leetcode_session: None,
             csrf_token: None,
+            preview_min_width: default_preview_min_width(),
+            mouse: false,
+            wrap_navigation: false,
+            skip_login_prompt: false,
+            confirm_submit: default_confirm_submit(),
+            ask_language: default_ask_language(),
+            git_commit: false,
+            test_command: None,
+            theme: None,
+            unicode: None,
+            scaffold: ScaffoldConfig::default(),
+            fetch_difficulty: None,
+            fetch_tags: Vec::new(),
+            fetch_list: None,
+            status_filter: None,
+            fetch_batch_size: default_fetch_batch_size(),
+            fetch_concurrency: default_fetch_concurrency(),
+            site: None,
         }
     }
 }
@@ -36,12 +358,66 @@ impl Config {
         Ok(config)
     }
 
+    /// `fetch_batch_size` as the API takes it: at least 1, and capped
+    /// rather than wrapped when it doesn't fit.
+    pub fn fetch_batch(&self) -> i32 {
+        i32::try_from(self.fetch_batch_size.max(1)).unwrap_or(i32::MAX)
+    }
+
+    /// `fetch_concurrency` kept to 1..=16, so a typo can't flood LeetCode
+    /// or overflow the semaphore.
+    pub fn fetch_parallelism(&self) -> usize {
+        self.fetch_concurrency.clamp(1, MAX_FETCH_CONCURRENCY)
+    }
+
+    /// `site` without a trailing slash, or leetcode.com when it's unset or
+    /// blank.
+    pub fn site(&self) -> &str {
+        self.site
+            .as_deref()
+            .map(|site| site.trim().trim_end_matches('/'))
+            .filter(|site| !site.is_empty())
+            .unwrap_or(LEETCODE_SITE)
+    }
+
     pub fn is_authenticated(&self) -> bool {
-        self.leetcode_session.as_ref().is_some_and(|s| !s.is_empty())
+        self.leetcode_session
+            .as_ref()
+            .is_some_and(|s| !s.is_empty())
             && self.csrf_token.as_ref().is_some_and(|s| !s.is_empty())
     }
 
+    /// `editor_mode`, except that a non-blocking editor is detached rather
+    /// than given the terminal.
+    pub fn effective_editor_mode(&self) -> EditorMode {
+        let blocking = self
+            .editor_blocking
+            .unwrap_or_else(|| gui_editor_name(&self.editor).is_none());
+        match self.editor_mode {
+            EditorMode::Suspend if !blocking => EditorMode::Detach,
+            mode => mode,
+        }
+    }
+
+    /// LeetCode's identifier for the configured language.
+    pub fn lang_slug(&self) -> &'static str {
+        match self.language.as_str() {
+            "python3" | "python" => "python3",
+            "cpp" | "c++" => "cpp",
+            "c" => "c",
+            "java" => "java",
+            "javascript" => "javascript",
+            "typescript" => "typescript",
+            "go" | "golang" => "golang",
+            _ => "rust",
+        }
+    }
+
     pub fn config_dir() -> PathBuf {
+        // Tests must never read or write the user's real config and cache
+        if cfg!(test) {
+            return std::env::temp_dir().join(format!("leetui-test-{}", std::process::id()));
+        }
         dirs::home_dir()
             .expect("Could not find home directory")
             .join(".leetcode-cli")
@@ -70,18 +446,325 @@ impl Config {
         let path = Self::config_path();
         let contents =
             toml::to_string_pretty(self).with_context(|| "Failed to serialize config")?;
-        std::fs::write(&path, contents)
+        write_atomic(&path, contents)
             .with_context(|| format!("Failed to write config to {}", path.display()))?;
         Ok(())
     }
 
+    /// The unexpanded workspace directory for `language`. A per-language
+    /// table also matches the language's aliases, then falls back to its
+    /// `default` entry.
+    pub fn workspace_for(&self, language: &str) -> &str {
+        match &self.workspace_dir {
+            WorkspaceDir::Single(dir) => dir,
+            WorkspaceDir::PerLanguage(dirs) => std::iter::once(language)
+                .chain(language_aliases(language).iter().copied())
+                .chain(["default"])
+                .find_map(|lang| dirs.get(lang))
+                .map(String::as_str)
+                .unwrap_or("~/leetcode"),
+        }
+    }
+
+    /// Point `language` at `dir`, keeping other languages' entries when the
+    /// workspace is a per-language table.
+    pub fn set_workspace(&mut self, language: &str, dir: String) {
+        match &mut self.workspace_dir {
+            WorkspaceDir::Single(current) => *current = dir,
+            WorkspaceDir::PerLanguage(dirs) => {
+                dirs.insert(language.to_string(), dir);
+            }
+        }
+    }
+
+    /// Workspace directory for the configured language, with `~` expanded.
     pub fn expanded_workspace(&self) -> PathBuf {
-        let expanded = if self.workspace_dir.starts_with('~') {
-            let home = dirs::home_dir().expect("Could not find home directory");
-            home.join(self.workspace_dir.strip_prefix("~/").unwrap_or(""))
-        } else {
-            PathBuf::from(&self.workspace_dir)
+        expand_tilde(self.workspace_for(&self.language))
+    }
+
+    /// This config with `language` switched to the one the problem's
+    /// project was scaffolded in, when it recorded one. A per-language
+    /// workspace is looked through for every language.
+    pub fn for_problem(&self, frontend_id: &str, slug: &str) -> Config {
+        let recorded = std::iter::once(self.language.as_str())
+            .chain(scaffold::LANGUAGES)
+            .find_map(|language| {
+                let workspace = expand_tilde(self.workspace_for(language));
+                scaffold::project_language(&scaffold::project_dir(
+                    &workspace,
+                    &self.scaffold,
+                    frontend_id,
+                    slug,
+                ))
+            });
+        Config {
+            language: recorded.unwrap_or_else(|| self.language.clone()),
+            ..self.clone()
+        }
+    }
+}
+
+/// `dir` with a leading `~` or `~/` replaced by the home directory.
+pub fn expand_tilde(dir: &str) -> PathBuf {
+    match (dirs::home_dir(), dir.strip_prefix("~/")) {
+        (Some(home), Some(rest)) => home.join(rest),
+        (Some(home), None) if dir == "~" => home,
+        _ => PathBuf::from(dir),
+    }
+}
+
+/// Languages run and submit can send to the judge, under the names
+/// `lang_slug` gives them. Only `scaffold::LANGUAGES` can be scaffolded.
+pub const LANGUAGES: [&str; 8] = [
+    "rust",
+    "golang",
+    "python3",
+    "cpp",
+    "c",
+    "java",
+    "javascript",
+    "typescript",
+];
+
+/// Whether `language`, or an alias of it, is one of `LANGUAGES`.
+pub fn is_known_language(language: &str) -> bool {
+    std::iter::once(language)
+        .chain(language_aliases(language).iter().copied())
+        .any(|l| LANGUAGES.contains(&l))
+}
+
+/// Other names the same language goes by in the config.
+fn language_aliases(language: &str) -> &'static [&'static str] {
+    match language {
+        "go" => &["golang"],
+        "golang" => &["go"],
+        "python" => &["python3"],
+        "python3" => &["python"],
+        "cpp" => &["c++"],
+        "c++" => &["cpp"],
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn home() -> String {
+        dirs::home_dir().unwrap().to_string_lossy().into_owned()
+    }
+
+    fn layout(dir: Option<&str>) -> ScaffoldConfig {
+        ScaffoldConfig {
+            dir: dir.map(String::from),
+            ..ScaffoldConfig::default()
+        }
+    }
+
+    #[test]
+    fn dir_names_round_trip_to_their_slug() {
+        let cases = [
+            (None, "1", "two-sum", "1-two-sum"),
+            (None, "3sum", "3sum", "3sum-3sum"),
+            (Some("{slug}"), "1", "two-sum", "two-sum"),
+            (
+                Some("{id}-{slug}"),
+                "0042",
+                "trapping-rain-water",
+                "0042-trapping-rain-water",
+            ),
+            (
+                Some("lc{id}_{slug}.d"),
+                "7",
+                "reverse-integer",
+                "lc7_reverse-integer.d",
+            ),
+            (Some("{slug}-{id}"), "1", "two-sum", "two-sum-1"),
+        ];
+        for (dir, id, slug, name) in cases {
+            let layout = layout(dir);
+            assert_eq!(layout.dir_name(id, slug), name, "dir = {dir:?}");
+            if id.chars().all(|c| c.is_ascii_digit()) {
+                assert_eq!(layout.slug_from_dir(name), Some(slug), "dir = {dir:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn dir_names_off_the_pattern_have_no_slug() {
+        let cases = [
+            (None, "two-sum"),
+            (None, "1-"),
+            (None, "-two-sum"),
+            (None, "1_two-sum"),
+            (None, "1-Two_Sum"),
+            (None, "target"),
+            (Some("{slug}"), ".git"),
+            (Some("{slug}"), "README.md"),
+            (Some("lc{id}_{slug}.d"), "lc7_reverse-integer"),
+            (Some("lc{id}_{slug}.d"), "7_reverse-integer.d"),
+            (Some("{id}"), "1"),
+            (Some("{slug}-{id}"), "two-sum"),
+            (Some("{slug}-{id}"), "two-sum-"),
+        ];
+        for (dir, name) in cases {
+            assert_eq!(
+                layout(dir).slug_from_dir(name),
+                None,
+                "dir = {dir:?}, name = {name:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn editor_argv_splits_like_a_shell() {
+        let cases: &[(&str, &[&str])] = &[
+            ("vim", &["vim"]),
+            ("code --wait", &["code", "--wait"]),
+            ("nvim +42", &["nvim", "+42"]),
+            ("  hx   -v  ", &["hx", "-v"]),
+            (
+                "'/Applications/My Editor/bin/ed' -n",
+                &["/Applications/My Editor/bin/ed", "-n"],
+            ),
+            ("\"/opt/my editor/ed\"", &["/opt/my editor/ed"]),
+            (
+                r"/opt/my\ editor/ed --flag",
+                &["/opt/my editor/ed", "--flag"],
+            ),
+            ("emacsclient -a '' -t", &["emacsclient", "-a", "", "-t"]),
+        ];
+        for (editor, expected) in cases {
+            assert_eq!(
+                editor_argv(editor).unwrap(),
+                *expected,
+                "editor = {editor:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn editor_argv_expands_tilde_per_word() {
+        assert_eq!(
+            editor_argv("nvim -u ~/leetcode.vim").unwrap(),
+            [
+                "nvim".to_string(),
+                "-u".to_string(),
+                format!("{}/leetcode.vim", home())
+            ]
+        );
+        assert_eq!(
+            editor_argv("~/bin/ed ~").unwrap(),
+            [format!("{}/bin/ed", home()), home()]
+        );
+        // Only a leading ~ is a home directory
+        assert_eq!(editor_argv("ed a~/b").unwrap(), ["ed", "a~/b"]);
+    }
+
+    #[test]
+    fn editor_argv_rejects_empty_and_unbalanced() {
+        assert!(editor_argv("").is_err());
+        assert!(editor_argv("   ").is_err());
+        assert!(editor_argv("code 'unterminated").is_err());
+    }
+
+    #[test]
+    fn gui_editors_are_recognized_by_program() {
+        let cases = [
+            ("code --wait", Some("VS Code")),
+            ("/usr/local/bin/code-insiders", Some("VS Code")),
+            ("codium", Some("VSCodium")),
+            ("'/opt/Sublime Text/subl' -w", Some("Sublime Text")),
+            ("zed", Some("Zed")),
+            ("nvim +42", None),
+            ("vim code", None),
+            ("", None),
+        ];
+        for (editor, expected) in cases {
+            assert_eq!(gui_editor_name(editor), expected, "editor = {editor:?}");
+        }
+    }
+
+    #[test]
+    fn effective_editor_mode_matrix() {
+        use EditorMode::*;
+        // (editor, editor_mode, editor_blocking, expected)
+        let cases = [
+            ("nvim", Suspend, None, Suspend),
+            ("code --wait", Suspend, None, Detach),
+            ("code --wait", Suspend, Some(true), Suspend),
+            ("nvim", Suspend, Some(false), Detach),
+            ("nvim", Detach, None, Detach),
+            ("nvim", Tmux, None, Tmux),
+            ("code", Tmux, None, Tmux),
+            ("nvim", Zellij, Some(false), Zellij),
+            ("code", Zellij, Some(true), Zellij),
+        ];
+        for (editor, mode, blocking, expected) in cases {
+            let config = Config {
+                editor: editor.to_string(),
+                editor_mode: mode,
+                editor_blocking: blocking,
+                ..Config::default()
+            };
+            assert_eq!(
+                config.effective_editor_mode(),
+                expected,
+                "editor = {editor:?}, mode = {mode:?}, blocking = {blocking:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn fetch_settings_stay_in_range() {
+        let config = |fetch_batch_size, fetch_concurrency| Config {
+            fetch_batch_size,
+            fetch_concurrency,
+            ..Config::default()
+        };
+        for (batch, concurrency, expected) in [
+            (100, 1, (100, 1)),
+            (0, 0, (1, 1)),
+            (u32::MAX, usize::MAX, (i32::MAX, 16)),
+            (i32::MAX as u32 + 1, 17, (i32::MAX, 16)),
+            (50, 8, (50, 8)),
+        ] {
+            let c = config(batch, concurrency);
+            assert_eq!(
+                (c.fetch_batch(), c.fetch_parallelism()),
+                expected,
+                "{batch}, {concurrency}"
+            );
+        }
+    }
+
+    #[test]
+    fn every_known_language_has_its_own_slug() {
+        for language in LANGUAGES {
+            let config = Config {
+                language: language.to_string(),
+                ..Config::default()
+            };
+            assert_eq!(config.lang_slug(), language);
+        }
+        for alias in ["go", "python", "c++"] {
+            assert!(is_known_language(alias), "{alias}");
+        }
+        assert!(!is_known_language("cobol"));
+    }
+
+    #[test]
+    fn site_defaults_to_leetcode_com() {
+        let site = |site: Option<&str>| {
+            Config {
+                site: site.map(String::from),
+                ..Config::default()
+            }
+            .site()
+            .to_string()
         };
-        expanded
+        assert_eq!(site(None), "https://leetcode.com");
+        assert_eq!(site(Some(" ")), "https://leetcode.com");
+        assert_eq!(site(Some("https://leetcode.cn/")), "https://leetcode.cn");
     }
 }
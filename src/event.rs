@@ -1,5 +1,8 @@
 use anyhow::Result;
-use crossterm::event::{Event as CrosstermEvent, EventStream, KeyEvent};
+use crossterm::event::{
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event as CrosstermEvent, EventStream, KeyEvent, MouseEvent,
+};
 use futures::StreamExt;
 use std::time::Duration;
 use tokio::sync::{mpsc, watch};
@@ -7,8 +10,10 @@ use tokio::sync::{mpsc, watch};
 #[derive(Debug)]
 pub enum Event {
     Key(KeyEvent),
+    Mouse(MouseEvent),
+    Paste(String),
     Tick,
-    Resize(u16, u16),
+    Resize,
 }
 
 pub struct EventHandler {
@@ -35,18 +40,17 @@ impl EventHandler {
                         }
                     }
                     Some(Ok(evt)) = reader.next() => {
-                        match evt {
-                            CrosstermEvent::Key(key) => {
-                                if tx.send(Event::Key(key)).is_err() {
-                                    break;
-                                }
-                            }
-                            CrosstermEvent::Resize(w, h) => {
-                                if tx.send(Event::Resize(w, h)).is_err() {
-                                    break;
-                                }
-                            }
-                            _ => {}
+                        let event = match evt {
+                            CrosstermEvent::Key(key) => Some(Event::Key(key)),
+                            CrosstermEvent::Mouse(mouse) => Some(Event::Mouse(mouse)),
+                            CrosstermEvent::Paste(text) => Some(Event::Paste(text)),
+                            CrosstermEvent::Resize(..) => Some(Event::Resize),
+                            _ => None,
+                        };
+                        if let Some(event) = event
+                            && tx.send(event).is_err()
+                        {
+                            break;
                         }
                     }
                     Ok(()) = pause_rx.changed() => {
@@ -73,6 +77,15 @@ impl EventHandler {
         Self { rx, _tx, pause_tx }
     }
 
+    /// A handler that never produces events, for driving `App` from tests
+    /// without a terminal.
+    #[cfg(test)]
+    pub fn idle() -> Self {
+        let (_tx, rx) = mpsc::unbounded_channel();
+        let (pause_tx, _) = watch::channel(false);
+        Self { rx, _tx, pause_tx }
+    }
+
     pub async fn next(&mut self) -> Result<Event> {
         self.rx
             .recv()
@@ -90,3 +103,25 @@ impl EventHandler {
         let _ = self.pause_tx.send(false);
     }
 }
+
+/// Mouse capture takes over the terminal's own text selection, so it is
+/// only turned on when the config asks for it.
+pub fn set_mouse_capture(enabled: bool) {
+    let mut stdout = std::io::stdout();
+    let _ = if enabled {
+        crossterm::execute!(stdout, EnableMouseCapture)
+    } else {
+        crossterm::execute!(stdout, DisableMouseCapture)
+    };
+}
+
+/// With bracketed paste on, a paste arrives as one event instead of a
+/// stream of keys that would trigger bindings along the way.
+pub fn set_bracketed_paste(enabled: bool) {
+    let mut stdout = std::io::stdout();
+    let _ = if enabled {
+        crossterm::execute!(stdout, EnableBracketedPaste)
+    } else {
+        crossterm::execute!(stdout, DisableBracketedPaste)
+    };
+}
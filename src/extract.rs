@@ -0,0 +1,615 @@
+use anyhow::Result;
+
+/// Strip local-only scaffolding (test harnesses, `main` functions, problem
+/// description headers) from a solution file before sending it to LeetCode.
+///
+/// Languages without a dedicated extractor, or files an extractor isn't sure
+/// about, are sent unchanged.
+pub fn extract_solution(language: &str, content: &str) -> Result<String> {
+    match language.to_ascii_lowercase().as_str() {
+        "rust" => extract_rust_solution(content),
+        "go" | "golang" => extract_go_solution(content),
+        "python" | "python3" => Ok(extract_python_solution(content)),
+        "java" => Ok(extract_java_solution(content)),
+        "cpp" | "c++" | "c" => Ok(extract_cpp_solution(content)),
+        _ => Ok(content.to_string()),
+    }
+}
+
+/// Extract the solution portion of a Rust file using tree-sitter.
+///
+/// Walks top-level AST nodes and keeps everything except:
+/// - Leading line comments (problem description)
+/// - `struct Solution;` (LSP shim we added)
+/// - `fn main() { ... }`
+/// - `mod tests { ... }` and any item marked `#[cfg(test)]`
+/// - Inner attributes such as `#![allow(dead_code)]`
+///
+/// Comments and attributes directly above an item travel with it, so doc
+/// comments on `impl Solution` survive. Kept items are separated by at most
+/// one blank line.
+fn extract_rust_solution(content: &str) -> Result<String> {
+    let mut parser = tree_sitter::Parser::new();
+    let language = tree_sitter_rust::LANGUAGE;
+    parser
+        .set_language(&language.into())
+        .map_err(|e| anyhow::anyhow!("Failed to set tree-sitter language: {e}"))?;
+
+    let tree = parser
+        .parse(content, None)
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse Rust file"))?;
+
+    let root = tree.root_node();
+    let mut parts: Vec<RustPart> = Vec::new();
+    // Comments and attributes waiting to be attached to the next item
+    let mut pending: Vec<tree_sitter::Node> = Vec::new();
+    let mut seen_item = false;
+    let mut dropped = false;
+    // Whether the file has a top-level `impl Solution`, and whether it's kept
+    let mut has_solution = false;
+    let mut kept_solution = false;
+
+    let mut cursor = root.walk();
+    for child in root.children(&mut cursor) {
+        let kind = child.kind();
+
+        if kind == "inner_attribute_item" {
+            dropped = true;
+            continue;
+        }
+
+        // A blank line between a comment and whatever follows detaches it
+        if let Some(&last) = pending.last()
+            && is_rust_comment(last)
+            && child.start_position().row > node_end_row(last) + 1
+        {
+            let group = std::mem::take(&mut pending);
+            if seen_item {
+                parts.push(RustPart::new(
+                    content,
+                    &group,
+                    group[group.len() - 1],
+                    dropped,
+                ));
+                dropped = false;
+            } else {
+                // Problem description block at the top of the file
+                dropped = true;
+            }
+        }
+
+        if is_rust_comment(child) || kind == "attribute_item" {
+            pending.push(child);
+            continue;
+        }
+
+        // Before the first item, only doc comments and attributes attach to it
+        if !seen_item
+            && let Some(pos) = pending
+                .iter()
+                .rposition(|n| is_rust_comment(*n) && !is_doc_comment(content, *n))
+        {
+            pending.drain(..=pos);
+            dropped = true;
+        }
+
+        let group = std::mem::take(&mut pending);
+        seen_item = true;
+        let is_solution = is_solution_impl(content, child);
+        has_solution |= is_solution;
+
+        if is_rust_noise(content, child, &group) {
+            dropped = true;
+            continue;
+        }
+        kept_solution |= is_solution;
+
+        let mut nodes = group;
+        nodes.push(child);
+        parts.push(RustPart::new(content, &nodes, child, dropped));
+        dropped = false;
+    }
+
+    // Trailing comments after the last item
+    if seen_item && !pending.is_empty() {
+        parts.push(RustPart::new(
+            content,
+            &pending,
+            pending[pending.len() - 1],
+            dropped,
+        ));
+    }
+
+    let mut result = String::new();
+    let mut prev_end_row = None;
+    for part in &parts {
+        if let Some(prev) = prev_end_row {
+            // Keep adjacent items adjacent; anything else gets one blank line
+            if part.after_drop || part.start_row > prev + 1 {
+                result.push_str("\n\n");
+            } else {
+                result.push('\n');
+            }
+        }
+        result.push_str(part.text);
+        prev_end_row = Some(part.end_row);
+    }
+
+    let result = result.trim().to_string();
+    if result.is_empty() {
+        // Fallback: return original content if parsing produced nothing
+        return Ok(content.to_string());
+    }
+
+    // Never submit a fragment without the solution itself — a broken parse
+    // would otherwise surface as a confusing compile error on LeetCode
+    if !kept_solution && (has_solution || root.has_error()) {
+        return Ok(content.to_string());
+    }
+
+    Ok(result)
+}
+
+/// A kept top-level item together with its attached comments and attributes.
+struct RustPart<'a> {
+    text: &'a str,
+    start_row: usize,
+    end_row: usize,
+    after_drop: bool,
+}
+
+impl<'a> RustPart<'a> {
+    fn new(
+        content: &'a str,
+        nodes: &[tree_sitter::Node],
+        last: tree_sitter::Node,
+        after_drop: bool,
+    ) -> Self {
+        let first = nodes[0];
+        Self {
+            text: content[first.start_byte()..last.end_byte()].trim_end(),
+            start_row: first.start_position().row,
+            end_row: node_end_row(last),
+            after_drop,
+        }
+    }
+}
+
+fn is_rust_comment(node: tree_sitter::Node) -> bool {
+    matches!(node.kind(), "line_comment" | "block_comment")
+}
+
+fn is_doc_comment(content: &str, node: tree_sitter::Node) -> bool {
+    let text = &content[node.byte_range()];
+    (text.starts_with("///") && !text.starts_with("////"))
+        || (text.starts_with("/**") && !text.starts_with("/***"))
+}
+
+/// Last row occupied by a node, ignoring a trailing newline that line
+/// comments include in their range.
+fn node_end_row(node: tree_sitter::Node) -> usize {
+    let end = node.end_position();
+    if end.column == 0 && end.row > node.start_position().row {
+        end.row - 1
+    } else {
+        end.row
+    }
+}
+
+/// Whether a top-level item is an `impl Solution` block.
+fn is_solution_impl(content: &str, item: tree_sitter::Node) -> bool {
+    item.kind() == "impl_item"
+        && item
+            .child_by_field_name("type")
+            .is_some_and(|t| &content[t.byte_range()] == "Solution")
+}
+
+/// Whether a top-level item is local scaffolding that LeetCode must not see.
+fn is_rust_noise(content: &str, item: tree_sitter::Node, attrs: &[tree_sitter::Node]) -> bool {
+    let cfg_test = attrs.iter().any(|a| {
+        let text = &content[a.byte_range()];
+        a.kind() == "attribute_item" && text.contains("cfg") && text.contains("test")
+    });
+    if cfg_test {
+        return true;
+    }
+
+    let name = item
+        .child_by_field_name("name")
+        .map(|n| &content[n.byte_range()]);
+
+    match (item.kind(), name) {
+        // Skip empty `struct Solution` in any form: `struct Solution;`, `struct Solution {}`, etc.
+        // These are LSP shims — LeetCode provides its own.
+        ("struct_item", Some("Solution")) => {
+            !item.child_by_field_name("body").is_some_and(|body| {
+                let mut bc = body.walk();
+                body.children(&mut bc)
+                    .any(|c| c.kind() == "field_declaration")
+            })
+        }
+        ("function_item", Some("main")) => true,
+        ("mod_item", Some("tests")) => true,
+        _ => false,
+    }
+}
+
+/// Extract the solution portion of a Go file using tree-sitter.
+///
+/// Walks top-level AST nodes and keeps everything except:
+/// - Leading line comments (problem description)
+/// - `package` clause
+/// - `func main() { ... }`
+fn extract_go_solution(content: &str) -> Result<String> {
+    let mut parser = tree_sitter::Parser::new();
+    let language = tree_sitter_go::LANGUAGE;
+    parser
+        .set_language(&language.into())
+        .map_err(|e| anyhow::anyhow!("Failed to set tree-sitter language: {e}"))?;
+
+    let tree = parser
+        .parse(content, None)
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse Go file"))?;
+
+    let root = tree.root_node();
+    let mut parts: Vec<&str> = Vec::new();
+    let mut in_leading_comments = true;
+
+    let mut cursor = root.walk();
+    for child in root.children(&mut cursor) {
+        let kind = child.kind();
+        let text = &content[child.byte_range()];
+
+        // Skip leading line comments (problem description block)
+        if in_leading_comments && kind == "comment" {
+            continue;
+        }
+        if kind != "comment" {
+            in_leading_comments = false;
+        }
+
+        // Skip `package` clause
+        if kind == "package_clause" {
+            continue;
+        }
+
+        // Skip `func main() { ... }`
+        if kind == "function_declaration"
+            && let Some(name_node) = child.child_by_field_name("name")
+            && &content[name_node.byte_range()] == "main"
+        {
+            continue;
+        }
+
+        parts.push(text);
+    }
+
+    let result = parts.join("\n").trim().to_string();
+    if result.is_empty() {
+        Ok(content.to_string())
+    } else {
+        Ok(result)
+    }
+}
+
+/// Extract a Python solution by dropping the `if __name__ == "__main__":`
+/// block and everything indented under it. Comments at column 0 don't end
+/// the block; the next top-level statement does.
+fn extract_python_solution(content: &str) -> String {
+    let mut out: Vec<&str> = Vec::new();
+    let mut in_main = false;
+
+    for line in content.lines() {
+        let is_top_level = !line.is_empty() && !line.starts_with([' ', '\t']);
+        if in_main {
+            if !is_top_level || line.starts_with('#') {
+                continue;
+            }
+            in_main = false;
+        }
+        let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+        if is_top_level
+            && (compact.starts_with("if__name__==\"__main__\"")
+                || compact.starts_with("if__name__=='__main__'"))
+        {
+            in_main = true;
+            continue;
+        }
+        out.push(line);
+    }
+
+    out.join("\n").trim().to_string()
+}
+
+/// Extract a Java solution by dropping any `public static void main` method.
+fn extract_java_solution(content: &str) -> String {
+    strip_c_like_function(
+        content,
+        &["public static void main", "static public void main"],
+    )
+}
+
+/// Extract a C++ solution by dropping a top-level `int main()`.
+fn extract_cpp_solution(content: &str) -> String {
+    strip_c_like_function(content, &["int main", "auto main"])
+}
+
+/// Remove the first function whose line starts with one of `signatures`,
+/// from the start of its line through its closing brace. Returns the input
+/// unchanged if the function body can't be matched confidently.
+fn strip_c_like_function(content: &str, signatures: &[&str]) -> String {
+    let mut line_start = 0;
+    let mut found = None;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if signatures.iter().any(|sig| {
+            trimmed
+                .strip_prefix(sig)
+                .is_some_and(|rest| rest.trim_start().starts_with('('))
+        }) {
+            found = Some(line_start);
+            break;
+        }
+        line_start += line.len();
+    }
+
+    let Some(start) = found else {
+        return content.to_string();
+    };
+    let Some(open) = content[start..].find('{').map(|i| start + i) else {
+        return content.to_string();
+    };
+    let Some(close) = matching_brace(content, open) else {
+        return content.to_string();
+    };
+
+    // Swallow the rest of the closing brace's line
+    let end = content[close..]
+        .find('\n')
+        .map_or(content.len(), |i| close + i + 1);
+
+    let mut out = String::with_capacity(content.len());
+    out.push_str(content[..start].trim_end());
+    out.push('\n');
+    out.push_str(&content[end..]);
+    out.trim().to_string()
+}
+
+/// Byte index of the `}` matching the `{` at `open`, skipping braces inside
+/// string/char literals and comments.
+fn matching_brace(content: &str, open: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut i = open;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            b'"' | b'\'' => {
+                let quote = bytes[i];
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = content[i + 2..].find("*/").map(|j| i + 2 + j + 1)?;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_drops_scaffolding() {
+        let src = r#"// 1. Two Sum
+// https://leetcode.com/problems/two-sum/
+
+#![allow(dead_code)]
+
+struct Solution;
+
+use std::collections::HashMap;
+
+/// One pass with a map from value to index.
+impl Solution {
+    pub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32> {
+        vec![]
+    }
+}
+
+fn main() {
+    println!("{:?}", Solution::two_sum(vec![2, 7], 9));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+}
+"#;
+        let expected = r#"use std::collections::HashMap;
+
+/// One pass with a map from value to index.
+impl Solution {
+    pub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32> {
+        vec![]
+    }
+}"#;
+        assert_eq!(extract_rust_solution(src).unwrap(), expected);
+    }
+
+    #[test]
+    fn rust_keeps_helpers_and_adjacent_items() {
+        let src = r#"struct Solution;
+use std::cmp::max;
+use std::cmp::min;
+// Helper for the recursion
+fn depth(n: i32) -> i32 {
+    max(n, 0)
+}
+
+
+
+impl Solution {
+    pub fn f() {}
+}
+mod tests {}
+"#;
+        let expected = r#"use std::cmp::max;
+use std::cmp::min;
+// Helper for the recursion
+fn depth(n: i32) -> i32 {
+    max(n, 0)
+}
+
+impl Solution {
+    pub fn f() {}
+}"#;
+        assert_eq!(extract_rust_solution(src).unwrap(), expected);
+    }
+
+    #[test]
+    fn rust_keeps_helper_structs() {
+        let src = r#"struct Solution;
+
+#[derive(Debug, Clone)]
+struct Node {
+    val: i32,
+}
+
+/// Sums the nodes.
+impl Solution {
+    pub fn sum(nodes: Vec<Node>) -> i32 {
+        nodes.iter().map(|n| n.val).sum()
+    }
+}
+"#;
+        let expected = r#"#[derive(Debug, Clone)]
+struct Node {
+    val: i32,
+}
+
+/// Sums the nodes.
+impl Solution {
+    pub fn sum(nodes: Vec<Node>) -> i32 {
+        nodes.iter().map(|n| n.val).sum()
+    }
+}"#;
+        assert_eq!(extract_rust_solution(src).unwrap(), expected);
+    }
+
+    #[test]
+    fn rust_keeps_a_solution_struct_with_fields() {
+        let src = "struct Solution {\n    n: i32,\n}\n\nimpl Solution {}\n";
+        assert_eq!(extract_rust_solution(src).unwrap(), src.trim());
+    }
+
+    #[test]
+    fn rust_ignores_impl_solution_in_comments() {
+        let src = r#"// Write your impl Solution below
+struct Solution;
+
+fn helper() -> i32 {
+    1
+}
+
+fn main() {}
+"#;
+        assert_eq!(
+            extract_rust_solution(src).unwrap(),
+            "fn helper() -> i32 {\n    1\n}"
+        );
+    }
+
+    #[test]
+    fn rust_ignores_impl_solution_in_test_module() {
+        let src = r#"fn helper() -> i32 {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    struct Solution;
+    impl Solution {}
+}
+"#;
+        assert_eq!(
+            extract_rust_solution(src).unwrap(),
+            "fn helper() -> i32 {\n    1\n}"
+        );
+    }
+
+    #[test]
+    fn rust_falls_back_when_the_solution_would_be_dropped() {
+        let src = "#[cfg(test)]\nimpl Solution {\n    pub fn f() {}\n}\n\nfn main() {}\n";
+        assert_eq!(extract_rust_solution(src).unwrap(), src);
+    }
+
+    #[test]
+    fn python_main_block_survives_top_level_comments() {
+        let src = r#"class Solution:
+    def f(self) -> int:
+        return 1
+
+if __name__ == "__main__":
+    s = Solution()
+# quick check
+    print(s.f())
+
+def helper():
+    pass
+"#;
+        let expected =
+            "class Solution:\n    def f(self) -> int:\n        return 1\n\ndef helper():\n    pass";
+        assert_eq!(extract_python_solution(src), expected);
+    }
+
+    #[test]
+    fn go_drops_package_main_and_header() {
+        let src = r#"// 1. Two Sum
+// https://leetcode.com/problems/two-sum/
+
+package main
+
+import "fmt"
+
+func twoSum(nums []int, target int) []int {
+	return nil
+}
+
+func main() {
+	fmt.Println(twoSum([]int{2, 7}, 9))
+}
+"#;
+        let expected =
+            "import \"fmt\"\nfunc twoSum(nums []int, target int) []int {\n\treturn nil\n}";
+        assert_eq!(extract_go_solution(src).unwrap(), expected);
+    }
+
+    #[test]
+    fn go_without_solution_is_unchanged() {
+        let src = "package main\n";
+        assert_eq!(extract_go_solution(src).unwrap(), src);
+    }
+}
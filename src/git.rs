@@ -0,0 +1,177 @@
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::process::Command;
+
+/// Run git in `dir`, returning its stdout, or its stderr as the error.
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            stderr.trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn in_repo(dir: &Path) -> bool {
+    dir.is_dir()
+        && git(dir, &["rev-parse", "--is-inside-work-tree"]).is_ok_and(|out| out.trim() == "true")
+}
+
+/// Make sure the project will land in a repository: one it's already in,
+/// one the workspace is in, or a new one at the workspace root that
+/// ignores build output. Returns whether a repository was created.
+pub fn ensure_repo(workspace: &Path, project_dir: &Path) -> Result<bool> {
+    if in_repo(project_dir) || in_repo(workspace) {
+        return Ok(false);
+    }
+    git(workspace, &["init", "--quiet"])?;
+    let ignore = workspace.join(".gitignore");
+    if !ignore.exists() {
+        std::fs::write(&ignore, "target/\n")
+            .with_context(|| format!("Failed to write {}", ignore.display()))?;
+    }
+    Ok(true)
+}
+
+/// Commit everything in `project_dir` that isn't ignored to whichever
+/// repository holds it. Returns `false` when there was nothing to commit.
+pub fn commit_project(project_dir: &Path, message: &str) -> Result<bool> {
+    if !in_repo(project_dir) {
+        bail!("{} isn't in a git repository", project_dir.display());
+    }
+    git(project_dir, &["add", "--all", "--", "."])?;
+    // Exits 1 when something is staged
+    if git(project_dir, &["diff", "--cached", "--quiet", "--", "."]).is_ok() {
+        return Ok(false);
+    }
+    git(
+        project_dir,
+        &["commit", "--quiet", "-m", message, "--", "."],
+    )?;
+    Ok(true)
+}
+
+/// `Solve 1. Two Sum (Accepted, 0 ms, 2.1 MB)`
+pub fn solve_message(id: &str, title: &str, runtime: Option<&str>, memory: Option<&str>) -> String {
+    let stats: Vec<&str> = std::iter::once("Accepted")
+        .chain(runtime)
+        .chain(memory)
+        .collect();
+    format!("Solve {id}. {title} ({})", stats.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scaffold;
+
+    /// A workspace with a git identity, so commits work without a global one.
+    fn workspace() -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("0001-two-sum");
+        assert!(ensure_repo(dir.path(), &project).unwrap());
+        git(dir.path(), &["config", "user.name", "leetui"]).unwrap();
+        git(dir.path(), &["config", "user.email", "leetui@example.com"]).unwrap();
+        std::fs::create_dir_all(project.join("src")).unwrap();
+        (dir, project)
+    }
+
+    #[test]
+    fn ensure_repo_creates_one_repository_at_the_workspace() {
+        let (dir, project) = workspace();
+        assert!(dir.path().join(".git").is_dir());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(".gitignore")).unwrap(),
+            "target/\n"
+        );
+        // Already in one now
+        assert!(!ensure_repo(dir.path(), &project).unwrap());
+    }
+
+    #[test]
+    fn ensure_repo_keeps_an_enclosing_repository() {
+        let outer = tempfile::tempdir().unwrap();
+        git(outer.path(), &["init", "--quiet"]).unwrap();
+        let ws = outer.path().join("leetcode");
+        std::fs::create_dir_all(&ws).unwrap();
+        assert!(!ensure_repo(&ws, &ws.join("0001-two-sum")).unwrap());
+        assert!(!ws.join(".git").exists());
+        assert!(!ws.join(".gitignore").exists());
+    }
+
+    #[test]
+    fn scaffolded_projects_share_the_workspace_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        let layout = crate::config::ScaffoldConfig::default();
+        for (id, slug) in [("1", "two-sum"), ("2", "add-two-numbers")] {
+            let detail = crate::api::mock::detail(id, slug);
+            scaffold::scaffold_problem(dir.path(), &layout, &detail, "rust", false).unwrap();
+            let project = scaffold::project_dir(dir.path(), &layout, id, slug);
+            ensure_repo(dir.path(), &project).unwrap();
+        }
+
+        let repos: Vec<_> = walk(dir.path())
+            .into_iter()
+            .filter(|p| p.ends_with(".git"))
+            .collect();
+        assert_eq!(repos, [dir.path().join(".git")]);
+    }
+
+    /// Every path under `dir`, not descending into repositories.
+    fn walk(dir: &Path) -> Vec<std::path::PathBuf> {
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() && !path.ends_with(".git") {
+                paths.extend(walk(&path));
+            }
+            paths.push(path);
+        }
+        paths
+    }
+
+    #[test]
+    fn commit_without_changes_commits_nothing() {
+        let (_dir, project) = workspace();
+        assert!(!commit_project(&project, "Solve 1. Two Sum (Accepted)").unwrap());
+
+        // Build output is ignored, so it's no change either
+        std::fs::create_dir_all(project.join("target")).unwrap();
+        std::fs::write(project.join("target/out"), "binary").unwrap();
+        assert!(!commit_project(&project, "Solve 1. Two Sum (Accepted)").unwrap());
+    }
+
+    #[test]
+    fn commit_with_changes_commits_the_project() {
+        let (dir, project) = workspace();
+        std::fs::write(project.join("src/main.rs"), "fn main() {}\n").unwrap();
+        // Changes outside the project are left alone
+        std::fs::write(dir.path().join("notes.md"), "todo\n").unwrap();
+        assert!(commit_project(&project, "Solve 1. Two Sum (Accepted, 0 ms)").unwrap());
+
+        let log = git(dir.path(), &["log", "--format=%s", "--name-only"]).unwrap();
+        assert_eq!(
+            log.trim(),
+            "Solve 1. Two Sum (Accepted, 0 ms)\n\n0001-two-sum/src/main.rs"
+        );
+        assert!(!commit_project(&project, "again").unwrap());
+
+        std::fs::write(project.join("src/main.rs"), "fn main() { }\n").unwrap();
+        assert!(commit_project(&project, "Solve 1. Two Sum (Accepted, 1 ms)").unwrap());
+    }
+
+    #[test]
+    fn commit_outside_a_repository_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = commit_project(dir.path(), "message").unwrap_err();
+        assert!(err.to_string().contains("isn't in a git repository"));
+    }
+}
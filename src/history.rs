@@ -0,0 +1,417 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::api::types::{QuestionDetail, ResultData, ResultKind};
+use crate::config::Config;
+use crate::files;
+
+const MAX_ENTRIES: usize = 50;
+
+/// Past Home search queries, oldest first, shared across sessions.
+pub struct SearchHistory {
+    pub entries: Vec<String>,
+}
+
+impl SearchHistory {
+    pub fn path() -> PathBuf {
+        Config::config_dir().join("search_history")
+    }
+
+    /// A missing or unreadable file just means an empty history.
+    pub fn load() -> Self {
+        let contents = std::fs::read_to_string(Self::path()).unwrap_or_default();
+        Self {
+            entries: Self::parse(&contents),
+        }
+    }
+
+    /// Every saved query. Saves end in a newline, so a last line without
+    /// one was cut short and is dropped.
+    fn parse(contents: &str) -> Vec<String> {
+        let complete = match contents.rfind('\n') {
+            Some(end) => &contents[..end],
+            None => "",
+        };
+        complete
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let dir = Config::config_dir();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create config dir {}", dir.display()))?;
+        let path = Self::path();
+        let mut contents = self.entries.join("\n");
+        contents.push('\n');
+        files::write_atomic(&path, contents)
+            .with_context(|| format!("Failed to write search history to {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Record a query, collapsing a repeat of the latest entry, and persist.
+    pub fn push(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty() || self.entries.last().is_some_and(|last| last == query) {
+            return;
+        }
+        self.entries.push(query.to_string());
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+        }
+        let _ = self.save();
+    }
+
+    /// Most recent entry before `before` containing `needle`.
+    pub fn find_older(&self, needle: &str, before: usize) -> Option<usize> {
+        let needle = needle.to_lowercase();
+        self.entries[..before.min(self.entries.len())]
+            .iter()
+            .rposition(|e| e.to_lowercase().contains(&needle))
+    }
+}
+
+const MAX_RECENT: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentEntry {
+    pub slug: String,
+    pub frontend_question_id: String,
+    pub title: String,
+    /// Unix seconds of the last view
+    pub viewed_at: u64,
+}
+
+/// Recently viewed problems, newest first.
+pub struct RecentProblems {
+    pub entries: Vec<RecentEntry>,
+}
+
+impl RecentProblems {
+    pub fn path() -> PathBuf {
+        Config::config_dir().join("recent.json")
+    }
+
+    pub fn load() -> Self {
+        let contents = std::fs::read_to_string(Self::path()).unwrap_or_default();
+        Self {
+            entries: Self::parse(&contents),
+        }
+    }
+
+    /// The entries of the saved list, skipping any that don't parse and
+    /// anything after the list.
+    fn parse(contents: &str) -> Vec<RecentEntry> {
+        let list = serde_json::Deserializer::from_str(contents)
+            .into_iter::<Vec<serde_json::Value>>()
+            .next();
+        list.and_then(|l| l.ok())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|e| serde_json::from_value(e).ok())
+            .collect()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let dir = Config::config_dir();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create config dir {}", dir.display()))?;
+        let path = Self::path();
+        let contents = serde_json::to_string_pretty(&self.entries)
+            .context("Failed to serialize recent problems")?;
+        files::write_atomic(&path, contents)
+            .with_context(|| format!("Failed to write recent problems to {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Move the problem to the front (adding it if new) and persist.
+    pub fn push(&mut self, detail: &QuestionDetail) {
+        self.entries.retain(|e| e.slug != detail.title_slug);
+        self.entries.insert(
+            0,
+            RecentEntry {
+                slug: detail.title_slug.clone(),
+                frontend_question_id: detail.frontend_question_id.clone(),
+                title: detail.title.clone(),
+                viewed_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0),
+            },
+        );
+        self.entries.truncate(MAX_RECENT);
+        let _ = self.save();
+    }
+
+    pub fn remove(&mut self, idx: usize) {
+        if idx < self.entries.len() {
+            self.entries.remove(idx);
+            let _ = self.save();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        let _ = self.save();
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub slug: String,
+    pub frontend_question_id: String,
+    pub title: String,
+    pub difficulty: String,
+    pub language: String,
+    /// Unix seconds of the accepted submission
+    pub solved_at: u64,
+}
+
+/// Accepted submissions, one JSON object per line, oldest first. Kept
+/// locally so the record survives switching LeetCode accounts.
+pub struct Journal;
+
+impl Journal {
+    pub fn path() -> PathBuf {
+        Config::config_dir().join("journal.jsonl")
+    }
+
+    pub fn append(detail: &QuestionDetail, language: &str) -> Result<()> {
+        let entry = JournalEntry {
+            slug: detail.title_slug.clone(),
+            frontend_question_id: detail.frontend_question_id.clone(),
+            title: detail.title.clone(),
+            difficulty: detail.difficulty.clone(),
+            language: language.to_string(),
+            solved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+        append_line(&Self::path(), &entry)
+    }
+
+    /// Up to `limit` entries, newest first. Lines that don't parse are skipped.
+    pub fn recent(limit: usize) -> Vec<JournalEntry> {
+        read_lines(&Self::path()).take(limit).collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    pub slug: String,
+    pub frontend_question_id: String,
+    pub title: String,
+    /// "run" or "submit"
+    pub kind: String,
+    pub verdict: String,
+    pub runtime: Option<String>,
+    pub memory: Option<String>,
+    pub language: String,
+    /// Unix seconds when the verdict came back
+    pub at: u64,
+}
+
+/// Every run and submission verdict, one JSON object per line, oldest first.
+pub struct ActivityLog;
+
+impl ActivityLog {
+    pub fn path() -> PathBuf {
+        Config::config_dir().join("history").join("activity.jsonl")
+    }
+
+    pub fn append(
+        detail: &QuestionDetail,
+        kind: ResultKind,
+        data: &ResultData,
+        language: &str,
+    ) -> Result<()> {
+        let entry = ActivityEntry {
+            slug: detail.title_slug.clone(),
+            frontend_question_id: detail.frontend_question_id.clone(),
+            title: detail.title.clone(),
+            kind: match kind {
+                ResultKind::Submit => "submit",
+                _ => "run",
+            }
+            .to_string(),
+            verdict: data.status_msg.clone(),
+            runtime: data.runtime.clone(),
+            memory: data.memory.clone(),
+            language: language.to_string(),
+            at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+        append_line(&Self::path(), &entry)
+    }
+
+    /// Up to `limit` entries across all problems, newest first.
+    pub fn recent(limit: usize) -> Vec<ActivityEntry> {
+        read_lines(&Self::path()).take(limit).collect()
+    }
+
+    /// Up to `limit` entries for one problem, newest first.
+    pub fn for_problem(slug: &str, limit: usize) -> Vec<ActivityEntry> {
+        read_lines(&Self::path())
+            .filter(|e: &ActivityEntry| e.slug == slug)
+            .take(limit)
+            .collect()
+    }
+}
+
+/// Append one JSON line, creating the file and its directory if needed.
+/// A last line cut short is ended first, so it doesn't swallow this one.
+fn append_line<T: Serialize>(path: &Path, entry: &T) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let mut line = serde_json::to_string(entry).context("Failed to serialize entry")?;
+    line.push('\n');
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    if ends_mid_line(&mut file) {
+        line.insert(0, '\n');
+    }
+    file.write_all(line.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Whether `file` has something after its last newline.
+fn ends_mid_line(file: &mut std::fs::File) -> bool {
+    use std::io::{Read, Seek, SeekFrom};
+    let mut last = [0u8];
+    file.seek(SeekFrom::End(-1)).is_ok() && file.read_exact(&mut last).is_ok() && last[0] != b'\n'
+}
+
+/// Entries of a JSON-lines file, newest (last) first. A missing file is
+/// empty; lines that don't parse, such as one cut short by a crash, are
+/// skipped.
+fn read_lines<T: serde::de::DeserializeOwned>(path: &Path) -> impl Iterator<Item = T> {
+    let contents = std::fs::read_to_string(path).unwrap_or_default();
+    let entries: Vec<T> = contents
+        .lines()
+        .rev()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect();
+    entries.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn journal_entry(slug: &str) -> JournalEntry {
+        JournalEntry {
+            slug: slug.to_string(),
+            frontend_question_id: "1".to_string(),
+            title: slug.replace('-', " "),
+            difficulty: "Easy".to_string(),
+            language: "rust".to_string(),
+            solved_at: 0,
+        }
+    }
+
+    fn activity_entry(slug: &str, verdict: &str) -> ActivityEntry {
+        ActivityEntry {
+            slug: slug.to_string(),
+            frontend_question_id: "1".to_string(),
+            title: slug.replace('-', " "),
+            kind: "submit".to_string(),
+            verdict: verdict.to_string(),
+            runtime: Some("0 ms".to_string()),
+            memory: None,
+            language: "rust".to_string(),
+            at: 0,
+        }
+    }
+
+    /// Cut `path` off partway through its last line, as a crash mid-append would.
+    fn truncate_last_line(path: &Path) {
+        let contents = std::fs::read_to_string(path).unwrap();
+        std::fs::write(path, &contents[..contents.len() - 10]).unwrap();
+    }
+
+    #[test]
+    fn a_search_history_cut_short_keeps_its_complete_queries() {
+        assert_eq!(
+            SearchHistory::parse("sliding window\nunion find\n"),
+            ["sliding window", "union find"]
+        );
+        assert_eq!(
+            SearchHistory::parse("sliding window\nunion fi"),
+            ["sliding window"]
+        );
+        assert!(SearchHistory::parse("").is_empty());
+    }
+
+    #[test]
+    fn recent_problems_skip_damaged_entries() {
+        let entry = |slug: &str| RecentEntry {
+            slug: slug.to_string(),
+            frontend_question_id: "1".to_string(),
+            title: slug.to_string(),
+            viewed_at: 0,
+        };
+        let mut saved = serde_json::to_value([entry("two-sum"), entry("three-sum")]).unwrap();
+        saved[0]["viewed_at"] = "yesterday".into();
+        let contents = format!("{saved}\n{{\"slug\": \"four-s");
+
+        let entries = RecentProblems::parse(&contents);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].slug, "three-sum");
+        assert!(RecentProblems::parse("[{\"slug\": \"two-s").is_empty());
+    }
+
+    #[test]
+    fn the_journal_skips_a_line_cut_short() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        append_line(&path, &journal_entry("two-sum")).unwrap();
+        append_line(&path, &journal_entry("add-two-numbers")).unwrap();
+        truncate_last_line(&path);
+        append_line(&path, &journal_entry("three-sum")).unwrap();
+
+        let slugs: Vec<String> = read_lines(&path).map(|e: JournalEntry| e.slug).collect();
+        assert_eq!(slugs, ["three-sum", "two-sum"]);
+    }
+
+    #[test]
+    fn the_activity_log_skips_a_line_cut_short() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history").join("activity.jsonl");
+        append_line(&path, &activity_entry("two-sum", "Wrong Answer")).unwrap();
+        append_line(&path, &activity_entry("two-sum", "Accepted")).unwrap();
+        truncate_last_line(&path);
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"\nnot json\n")
+            .unwrap();
+        append_line(&path, &activity_entry("three-sum", "Accepted")).unwrap();
+
+        let verdicts: Vec<(String, String)> = read_lines(&path)
+            .map(|e: ActivityEntry| (e.slug, e.verdict))
+            .collect();
+        assert_eq!(
+            verdicts,
+            [
+                ("three-sum".to_string(), "Accepted".to_string()),
+                ("two-sum".to_string(), "Wrong Answer".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::api::types::ProblemSummary;
+use crate::cache;
+use crate::config::Config;
+use crate::history::{Journal, JournalEntry};
+use crate::scaffold;
+
+/// The generated table sits between these in the workspace README, so
+/// whatever is written around them survives a rebuild.
+const BEGIN: &str = "<!-- leetui index: begin -->";
+const END: &str = "<!-- leetui index: end -->";
+
+/// One scaffolded problem as the index lists it.
+struct Entry {
+    id: String,
+    title: String,
+    slug: String,
+    difficulty: String,
+    language: String,
+    /// Unix seconds of the latest accepted submission
+    solved_at: Option<u64>,
+    /// Relative to the workspace
+    solution: PathBuf,
+}
+
+/// Write a table of every project in the workspace to the README.md at
+/// its root. `known` are problems already loaded, tried before the caches
+/// for titles and difficulties. Returns the README and how many problems
+/// it lists.
+pub fn write_index(config: &Config, known: &[ProblemSummary]) -> Result<(PathBuf, usize)> {
+    let workspace = config.expanded_workspace();
+    std::fs::create_dir_all(&workspace)
+        .with_context(|| format!("Failed to create workspace {}", workspace.display()))?;
+    let entries = entries(config, &workspace, known);
+    let path = workspace.join("README.md");
+    let existing = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    std::fs::write(&path, merge(&existing, &table(&entries)))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok((path, entries.len()))
+}
+
+fn entries(config: &Config, workspace: &Path, known: &[ProblemSummary]) -> Vec<Entry> {
+    let mut problems: HashMap<String, ProblemSummary> = HashMap::new();
+    for list in [None, Some("easy"), Some("medium"), Some("hard")]
+        .into_iter()
+        .filter_map(cache::load_cached_problems)
+        .chain([known.to_vec()])
+    {
+        problems.extend(list.into_iter().map(|p| (p.title_slug.clone(), p)));
+    }
+    // Newest first, so the first one seen for a slug is its latest
+    let mut solved: HashMap<String, JournalEntry> = HashMap::new();
+    for entry in Journal::recent(usize::MAX) {
+        solved.entry(entry.slug.clone()).or_insert(entry);
+    }
+
+    let mut entries: Vec<Entry> = scaffold::scan_projects(workspace, &config.scaffold)
+        .into_iter()
+        .map(|(slug, dir)| {
+            let journal = solved.get(&slug);
+            let (id, title, difficulty) = match problems.get(&slug) {
+                Some(p) => (
+                    p.frontend_question_id.clone(),
+                    p.title.clone(),
+                    p.difficulty.clone(),
+                ),
+                None => match cache::load_cached_detail(&slug) {
+                    Some(d) => (d.frontend_question_id, d.title, d.difficulty),
+                    None => journal.map_or_else(
+                        || (String::new(), slug.clone(), String::new()),
+                        |j| {
+                            (
+                                j.frontend_question_id.clone(),
+                                j.title.clone(),
+                                j.difficulty.clone(),
+                            )
+                        },
+                    ),
+                },
+            };
+            let language = scaffold::project_language(&dir)
+                .or_else(|| journal.map(|j| j.language.clone()))
+                .unwrap_or_else(|| config.language.clone());
+            let file = scaffold::solution_path(&dir, &config.scaffold, &language);
+            let file = if file.exists() { file } else { dir };
+            Entry {
+                id,
+                title,
+                difficulty,
+                language,
+                solved_at: journal.map(|j| j.solved_at),
+                solution: file
+                    .strip_prefix(workspace)
+                    .map(Path::to_path_buf)
+                    .unwrap_or(file),
+                slug,
+            }
+        })
+        .collect();
+    // By number; anything without one goes last
+    entries.sort_by_key(|e| (e.id.parse::<u32>().unwrap_or(u32::MAX), e.slug.clone()));
+    entries
+}
+
+fn table(entries: &[Entry]) -> String {
+    let mut out = String::from(
+        "| # | Title | Difficulty | Language | Solved | Solution |\n\
+         | --: | --- | --- | --- | --- | --- |\n",
+    );
+    for e in entries {
+        let solution = e.solution.to_string_lossy().replace('\\', "/");
+        out.push_str(&format!(
+            "| {} | [{}](https://leetcode.com/problems/{}/) | {} | {} | {} | [{solution}](<{solution}>) |\n",
+            e.id,
+            e.title.replace('|', "\\|"),
+            e.slug,
+            e.difficulty,
+            e.language,
+            e.solved_at.map(date).unwrap_or_default(),
+        ));
+    }
+    out
+}
+
+/// `existing` with the text between the markers replaced by `table`, or
+/// with the table added at the end when there are no markers yet.
+fn merge(existing: &str, table: &str) -> String {
+    let block = format!("{BEGIN}\n{table}{END}");
+    if let Some(start) = existing.find(BEGIN)
+        && let Some(len) = existing[start..].find(END)
+    {
+        let end = start + len + END.len();
+        return format!("{}{block}{}", &existing[..start], &existing[end..]);
+    }
+    if existing.trim().is_empty() {
+        return format!("# LeetCode solutions\n\n{block}\n");
+    }
+    format!("{}\n\n{block}\n", existing.trim_end())
+}
+
+/// `YYYY-MM-DD` in UTC for Unix seconds.
+fn date(secs: u64) -> String {
+    // Days to a civil date, after Howard Hinnant's `civil_from_days`
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,79 @@
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Tests still running after this long are killed.
+pub const TIMEOUT: Duration = Duration::from_secs(60);
+
+pub struct LocalTestRun {
+    pub passed: bool,
+    /// `None` when the tests timed out or were killed by a signal
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    /// stdout followed by stderr
+    pub output: String,
+}
+
+/// What `t` runs in a scaffolded project when `test_command` isn't set.
+fn default_command(language: &str) -> Option<&'static [&'static str]> {
+    match language {
+        "rust" => Some(&["cargo", "test"]),
+        "go" | "golang" => Some(&["go", "test", "./..."]),
+        "python3" | "python" => Some(&["pytest"]),
+        _ => None,
+    }
+}
+
+/// Run the project's tests, collecting their output. `custom` is a shell
+/// command that replaces the per-language default.
+pub async fn run(project_dir: &Path, language: &str, custom: Option<&str>) -> Result<LocalTestRun> {
+    if !project_dir.is_dir() {
+        bail!(
+            "{} doesn't exist\nScaffold the problem first with 'o'",
+            project_dir.display()
+        );
+    }
+    let mut cmd = match custom {
+        Some(line) => {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(line);
+            cmd
+        }
+        None => {
+            let Some(argv) = default_command(language) else {
+                bail!("No local test command for {language}\nSet test_command in the config");
+            };
+            let mut cmd = Command::new(argv[0]);
+            cmd.args(&argv[1..]);
+            cmd
+        }
+    };
+    cmd.current_dir(project_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let child = cmd.spawn().context("Failed to start the test command")?;
+    // Dropping the future on timeout drops the child, which kills it
+    let Ok(output) = tokio::time::timeout(TIMEOUT, child.wait_with_output()).await else {
+        return Ok(LocalTestRun {
+            passed: false,
+            exit_code: None,
+            timed_out: true,
+            output: String::new(),
+        });
+    };
+    let output = output.context("Failed to collect test output")?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(LocalTestRun {
+        passed: output.status.success(),
+        exit_code: output.status.code(),
+        timed_out: false,
+        output: text,
+    })
+}
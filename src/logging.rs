@@ -0,0 +1,78 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::EnvFilter;
+
+use crate::config::Config;
+
+/// Overrides the filter, e.g. `LEETUI_LOG=trace`; setting it also turns
+/// logging on without `--verbose`.
+const LOG_ENV: &str = "LEETUI_LOG";
+const DEFAULT_FILTER: &str = "leetui=debug";
+
+/// Response bodies are cut to this many bytes in the log.
+const MAX_BODY: usize = 2000;
+
+/// Cookie values that must never reach the log file.
+static SECRETS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+pub fn log_path() -> PathBuf {
+    Config::config_dir().join("leetui.log")
+}
+
+/// Start writing to `leetui.log` in the config dir when `verbose` or
+/// `LEETUI_LOG` asks for it. Keep the guard until exit so buffered lines
+/// get flushed.
+pub fn init(verbose: bool) -> Result<Option<WorkerGuard>> {
+    let env = std::env::var(LOG_ENV).ok().filter(|v| !v.is_empty());
+    if !verbose && env.is_none() {
+        return Ok(None);
+    }
+    let filter = EnvFilter::try_new(env.as_deref().unwrap_or(DEFAULT_FILTER))
+        .with_context(|| format!("Invalid {LOG_ENV} filter"))?;
+
+    let dir = Config::config_dir();
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let appender = tracing_appender::rolling::never(&dir, "leetui.log");
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(false)
+        .init();
+    tracing::info!(
+        version = env!("CARGO_PKG_VERSION"),
+        "logging to {}",
+        log_path().display()
+    );
+    Ok(Some(guard))
+}
+
+/// Register cookie values to scrub from anything logged.
+pub fn add_secrets<'a>(values: impl IntoIterator<Item = &'a str>) {
+    let mut secrets = SECRETS.write().unwrap_or_else(|e| e.into_inner());
+    for value in values.into_iter().filter(|v| !v.is_empty()) {
+        if !secrets.iter().any(|s| s == value) {
+            secrets.push(value.to_string());
+        }
+    }
+}
+
+/// `text` with every registered secret replaced.
+pub fn scrub(text: &str) -> String {
+    let mut text = text.to_string();
+    for secret in SECRETS.read().unwrap_or_else(|e| e.into_inner()).iter() {
+        text = text.replace(secret.as_str(), "[redacted]");
+    }
+    text
+}
+
+/// `scrub`bed and shortened to fit a log line.
+pub fn redact(text: &str) -> String {
+    let text = scrub(text);
+    match text.char_indices().nth(MAX_BODY) {
+        Some((i, _)) => format!("{}...", &text[..i]),
+        None => text,
+    }
+}
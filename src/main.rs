@@ -1,32 +1,137 @@
 mod api;
 mod app;
+mod cache;
+mod cli;
+mod clipboard;
 mod config;
 mod event;
+mod extract;
+mod files;
+mod git;
+mod history;
+mod index;
+mod local_test;
+mod logging;
+mod markdown;
 mod scaffold;
+mod tasks;
 mod ui;
+mod watch;
 
 use anyhow::Result;
+use clap::Parser;
 use std::time::Duration;
 
 use app::App;
+use cli::{Cli, Command};
 use config::Config;
 use event::EventHandler;
+use ui::icons;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let _log_guard = logging::init(cli.verbose)?;
+    // Before loading the config, so these still work when it's broken
+    match cli.command {
+        Some(Command::Cache {
+            action: cli::CacheAction::Clear,
+        }) => return cli::clear_cache(),
+        Some(Command::Config {
+            action: cli::ConfigAction::Reset { yes },
+        }) => {
+            return cli::reset_config(yes);
+        }
+        _ => {}
+    }
     let config = Config::load()?;
+    icons::set_unicode(
+        config
+            .as_ref()
+            .and_then(|c| c.unicode)
+            .unwrap_or_else(icons::detect_unicode),
+    );
+
+    let mut launch_problem = cli.problem;
+    match cli.command {
+        Some(Command::Scaffold { slug, force }) => {
+            return cli::scaffold(config, &slug, force, cli.cd_file.as_deref()).await;
+        }
+        Some(Command::Submit { problem, json }) => {
+            if !cli::submit(config, &problem, json).await? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Command::Run { problem, json }) => {
+            if !cli::run(config, &problem, json).await? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Command::List {
+            difficulty,
+            tag,
+            status,
+            search,
+            json,
+        }) => {
+            let query = cli::ListQuery {
+                difficulty,
+                tags: tag,
+                status,
+                search,
+            };
+            return cli::list(config.as_ref(), cli.offline, &query, json).await;
+        }
+        Some(Command::Index) => {
+            return cli::index(config.as_ref());
+        }
+        Some(Command::Login {
+            from_browser,
+            session,
+            csrf,
+        }) => {
+            let source = match (session, csrf) {
+                _ if from_browser => cli::LoginSource::Browser,
+                (Some(session), Some(csrf)) => cli::LoginSource::Given { session, csrf },
+                _ => cli::LoginSource::Prompt,
+            };
+            return cli::login(config, source).await;
+        }
+        Some(ref launch @ (Command::Daily | Command::Random { .. })) => {
+            launch_problem = cli::launch_slug(config.as_ref(), launch).await;
+        }
+        // Handled above
+        Some(Command::Cache { .. } | Command::Config { .. }) | None => {}
+    }
 
+    let mouse = config.as_ref().is_some_and(|c| c.mouse);
     let mut terminal = ratatui::init();
+    event::set_bracketed_paste(true);
+    if mouse {
+        event::set_mouse_capture(true);
+    }
     let mut events = EventHandler::new(Duration::from_millis(100));
-    let mut app = App::new(config)?;
+    let mut app = App::new(config, cli.offline)?;
+    app.launch_problem = launch_problem;
 
     let result = app.run(&mut terminal, &mut events).await;
 
+    if mouse {
+        event::set_mouse_capture(false);
+    }
+    event::set_bracketed_paste(false);
     ratatui::restore();
 
-    // Print last opened directory so a shell wrapper can cd into it
-    if let Some(dir) = &app.last_opened_dir {
-        println!("{}", dir.display());
+    // Hand the last opened directory to a shell wrapper so it can cd into it
+    match &cli.cd_file {
+        Some(path) => cli::write_cd_file(path, app.last_opened_dir.as_deref())?,
+        None => {
+            if let Some(dir) = &app.last_opened_dir {
+                println!("{}", dir.display());
+            }
+        }
     }
 
     result
@@ -0,0 +1,208 @@
+use anyhow::{Context, Result};
+use html2text::render::{TaggedLine, TextDecorator, TrivialDecorator};
+use std::path::{Path, PathBuf};
+
+use crate::api::types::QuestionDetail;
+
+/// Wide enough that html2text never wraps; Markdown reflows on its own.
+const NO_WRAP: usize = 10_000;
+
+/// Ends every README leetui writes, followed by a hash of the text above
+/// it, so an edited README can be told apart from a generated one.
+const GENERATED_MARK: &str = "<!-- generated by leetui ";
+
+/// Write `detail` as Markdown to `README.md` in `project_dir`, creating
+/// the directory if needed. Returns the file written.
+pub fn write_readme(project_dir: &Path, detail: &QuestionDetail) -> Result<PathBuf> {
+    std::fs::create_dir_all(project_dir)
+        .with_context(|| format!("Failed to create {}", project_dir.display()))?;
+    let path = project_dir.join("README.md");
+    let body = problem_markdown(detail);
+    let contents = format!("{body}\n{GENERATED_MARK}{:016x} -->\n", fnv1a(&body));
+    std::fs::write(&path, contents)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Whether `project_dir` has a README that leetui didn't write, or that
+/// was changed since.
+pub fn readme_edited(project_dir: &Path) -> bool {
+    let Ok(existing) = std::fs::read_to_string(project_dir.join("README.md")) else {
+        return false;
+    };
+    let Some((body, mark)) = existing.rsplit_once(GENERATED_MARK) else {
+        return true;
+    };
+    let body = body.strip_suffix('\n').unwrap_or(body);
+    mark.trim().trim_end_matches("-->").trim() != format!("{:016x}", fnv1a(body))
+}
+
+/// Stable across builds, unlike `DefaultHasher`.
+pub fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Title, difficulty, link, statement, example testcases and hints as one
+/// Markdown document.
+pub fn problem_markdown(detail: &QuestionDetail) -> String {
+    let mut out = format!(
+        "# {}. {}\n\n**Difficulty:** {}  \n**URL:** https://leetcode.com/problems/{}/\n",
+        detail.frontend_question_id, detail.title, detail.difficulty, detail.title_slug
+    );
+    if !detail.topic_tags.is_empty() {
+        let tags: Vec<&str> = detail.topic_tags.iter().map(|t| t.name.as_str()).collect();
+        out.push_str(&format!("**Topics:** {}\n", tags.join(", ")));
+    }
+
+    out.push_str("\n## Description\n\n");
+    match detail.content.as_deref() {
+        Some(html) => out.push_str(html_to_markdown(html).trim_end()),
+        None if detail.is_paid_only => {
+            out.push_str("_Premium problem, the statement isn't available._")
+        }
+        None => out.push_str("_No description._"),
+    }
+    out.push('\n');
+
+    let examples = match (&detail.example_testcase_list, &detail.sample_test_case) {
+        (Some(list), _) if !list.is_empty() => list.clone(),
+        (_, Some(sample)) if !sample.trim().is_empty() => vec![sample.clone()],
+        _ => Vec::new(),
+    };
+    if !examples.is_empty() {
+        out.push_str("\n## Example testcases\n");
+        for case in &examples {
+            out.push_str(&format!("\n```\n{}\n```\n", case.trim_end()));
+        }
+    }
+
+    if !detail.hints.is_empty() {
+        out.push_str("\n## Hints\n");
+        for (i, hint) in detail.hints.iter().enumerate() {
+            out.push_str(&format!("\n{}. {}\n", i + 1, html_to_markdown(hint).trim()));
+        }
+    }
+    out
+}
+
+/// HTML to Markdown. Examples live in `<pre>` blocks, which become fenced
+/// code; everything around them goes through html2text with Markdown markup.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<pre>") {
+        out.push_str(&render(&rest[..start], MarkdownDecorator::default()));
+        let body = &rest[start + "<pre>".len()..];
+        let end = body.find("</pre>").unwrap_or(body.len());
+        let code = render(
+            &format!("<pre>{}</pre>", &body[..end]),
+            TrivialDecorator::new(),
+        );
+        out.push_str(&format!("\n```\n{}\n```\n\n", code.trim_matches('\n')));
+        rest = body.get(end + "</pre>".len()..).unwrap_or("");
+    }
+    out.push_str(&render(rest, MarkdownDecorator::default()));
+    out
+}
+
+fn render<D: TextDecorator>(html: &str, decorator: D) -> String {
+    html2text::from_read_with_decorator(html.as_bytes(), NO_WRAP, decorator)
+        .unwrap_or_else(|_| html.to_string())
+}
+
+/// Emits Markdown syntax for html2text's inline and block markup.
+#[derive(Clone, Default)]
+struct MarkdownDecorator {
+    /// Targets of the links currently open, closed by `decorate_link_end`
+    links: Vec<String>,
+}
+
+impl TextDecorator for MarkdownDecorator {
+    type Annotation = ();
+
+    fn decorate_link_start(&mut self, url: &str) -> (String, ()) {
+        self.links.push(url.to_string());
+        ("[".to_string(), ())
+    }
+
+    fn decorate_link_end(&mut self) -> String {
+        format!("]({})", self.links.pop().unwrap_or_default())
+    }
+
+    fn decorate_em_start(&self) -> (String, ()) {
+        ("*".to_string(), ())
+    }
+
+    fn decorate_em_end(&self) -> String {
+        "*".to_string()
+    }
+
+    fn decorate_strong_start(&self) -> (String, ()) {
+        ("**".to_string(), ())
+    }
+
+    fn decorate_strong_end(&self) -> String {
+        "**".to_string()
+    }
+
+    fn decorate_strikeout_start(&self) -> (String, ()) {
+        ("~~".to_string(), ())
+    }
+
+    fn decorate_strikeout_end(&self) -> String {
+        "~~".to_string()
+    }
+
+    fn decorate_code_start(&self) -> (String, ()) {
+        ("`".to_string(), ())
+    }
+
+    fn decorate_code_end(&self) -> String {
+        "`".to_string()
+    }
+
+    fn decorate_preformat_first(&self) {}
+
+    fn decorate_preformat_cont(&self) {}
+
+    fn decorate_image(&mut self, src: &str, title: &str) -> (String, ()) {
+        (format!("![{title}]({src})"), ())
+    }
+
+    // One level down, under the problem title
+    fn header_prefix(&self, level: usize) -> String {
+        "#".repeat(level + 1) + " "
+    }
+
+    fn quote_prefix(&self) -> String {
+        "> ".to_string()
+    }
+
+    fn unordered_item_prefix(&self) -> String {
+        "- ".to_string()
+    }
+
+    fn ordered_item_prefix(&self, i: i64) -> String {
+        format!("{i}. ")
+    }
+
+    fn make_subblock_decorator(&self) -> Self {
+        Self::default()
+    }
+
+    // Constraints like 10<sup>4</sup>
+    fn decorate_superscript_start(&self) -> (String, ()) {
+        ("^".to_string(), ())
+    }
+
+    fn decorate_superscript_end(&self) -> String {
+        String::new()
+    }
+
+    // Links are written inline, so there are no footnotes to add
+    fn finalise(&mut self, _urls: Vec<String>) -> Vec<TaggedLine<()>> {
+        Vec::new()
+    }
+}
@@ -1,19 +1,359 @@
 pub mod go;
 pub mod rust;
 
-use anyhow::{Result, bail};
-use std::path::PathBuf;
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::api::types::QuestionDetail;
+use crate::config::ScaffoldConfig;
+use crate::markdown;
 
+/// Languages `scaffold_problem` can set up a project for.
+pub const LANGUAGES: [&str; 2] = ["rust", "golang"];
+
+/// Records the language a project was scaffolded in, so run and submit
+/// keep using it whatever the configured language is later.
+const LANGUAGE_MARKER: &str = ".leetui.toml";
+
+#[derive(Serialize, Deserialize)]
+struct ProjectMarker {
+    language: String,
+    /// Hash of the solution file as last written, to tell whether it was
+    /// edited since; missing for projects from before it was kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scaffolded: Option<String>,
+}
+
+/// Whether `language`, or an alias of it, can be scaffolded.
+pub fn is_supported(language: &str) -> bool {
+    LANGUAGES.contains(&language) || language == "go"
+}
+
+/// The languages `detail` can be scaffolded in: those LeetCode has a code
+/// snippet for.
+pub fn snippet_languages(detail: &QuestionDetail) -> Vec<&'static str> {
+    let snippets = detail.code_snippets.as_deref().unwrap_or_default();
+    LANGUAGES
+        .into_iter()
+        .filter(|lang| snippets.iter().any(|s| s.lang_slug == *lang))
+        .collect()
+}
+
+/// The language `project_dir` was scaffolded in, if it says.
+pub fn project_language(project_dir: &Path) -> Option<String> {
+    read_marker(project_dir).map(|m| m.language)
+}
+
+fn read_marker(project_dir: &Path) -> Option<ProjectMarker> {
+    let contents = std::fs::read_to_string(project_dir.join(LANGUAGE_MARKER)).ok()?;
+    toml::from_str(&contents).ok()
+}
+
+fn save_marker(project_dir: &Path, marker: &ProjectMarker) -> Result<()> {
+    let path = project_dir.join(LANGUAGE_MARKER);
+    let contents = toml::to_string(marker).context("Failed to serialize project marker")?;
+    std::fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// What scaffolding did with the solution file.
+pub enum Outcome {
+    /// There wasn't one; it was written
+    Fresh,
+    /// It was there and left alone
+    Reused,
+    /// Written again; it was as last scaffolded, so nothing was lost
+    Reset,
+    /// Written again after the old one was copied to this backup
+    BackedUp(PathBuf),
+}
+
+impl Outcome {
+    /// For the toast after `o` or `O`.
+    pub fn describe(&self) -> String {
+        match self {
+            Outcome::Fresh => "Scaffolded a new project".to_string(),
+            Outcome::Reused => "Opened the existing solution".to_string(),
+            Outcome::Reset => "Rewrote the solution, it had no changes".to_string(),
+            Outcome::BackedUp(backup) => format!(
+                "Rewrote the solution, old one saved as {}",
+                backup.file_name().unwrap_or_default().to_string_lossy()
+            ),
+        }
+    }
+}
+
+/// Set up the project for `detail` and return its solution file. A
+/// `[scaffold]` template for the language replaces the built-in source.
+/// An existing solution is kept, unless `force` rewrites it; one edited
+/// since it was scaffolded is backed up first. The
+/// statement goes to `README.md` alongside, refreshed on every call
+/// unless the README was edited and `force` isn't set.
 pub fn scaffold_problem(
-    workspace: &PathBuf,
+    workspace: &Path,
+    layout: &ScaffoldConfig,
     detail: &QuestionDetail,
     language: &str,
-) -> Result<PathBuf> {
-    match language {
-        "rust" => rust::scaffold_rust(workspace, detail),
-        "go" | "golang" => go::scaffold_go(workspace, detail),
+    force: bool,
+) -> Result<(PathBuf, Outcome)> {
+    let template = match layout.template_for(language) {
+        Some(path) => Some(
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read scaffold template {}", path.display()))?,
+        ),
+        None => None,
+    };
+    let project_dir = project_dir(
+        workspace,
+        layout,
+        &detail.frontend_question_id,
+        &detail.title_slug,
+    );
+    let file = solution_path(&project_dir, layout, language);
+    // Before the project tools run: `cargo init` writes a main.rs of its own
+    let existed = file.exists();
+    let (lang_slug, built_in) = match language {
+        "rust" => {
+            let cargo_workspace = layout.rust_workspace_mode.then_some(workspace);
+            rust::init_rust(&project_dir, cargo_workspace)?;
+            ("rust", rust::source as fn(&QuestionDetail) -> String)
+        }
+        "go" | "golang" => {
+            go::init_go(&project_dir, detail)?;
+            ("golang", go::source as fn(&QuestionDetail) -> String)
+        }
         _ => bail!("Unsupported language for scaffolding: {}", language),
+    };
+    let src = match template {
+        Some(template) => render_template(&template, detail, lang_slug),
+        None => built_in(detail),
+    };
+
+    // What was written last time, or for older projects, what would be now
+    let recorded = read_marker(&project_dir).and_then(|m| m.scaffolded);
+    let unedited = |current: &str| match recorded {
+        Some(ref hash) => *hash == source_hash(current),
+        None => current == src,
+    };
+    let outcome = if !existed {
+        write_solution(&file, &src)?;
+        Outcome::Fresh
+    } else if !force {
+        Outcome::Reused
+    } else if std::fs::read_to_string(&file).is_ok_and(|current| unedited(&current)) {
+        write_solution(&file, &src)?;
+        Outcome::Reset
+    } else {
+        let backup = backup_path(&file);
+        std::fs::copy(&file, &backup)
+            .with_context(|| format!("Failed to back up {}", file.display()))?;
+        write_solution(&file, &src)?;
+        Outcome::BackedUp(backup)
+    };
+
+    let scaffolded = match outcome {
+        Outcome::Reused => recorded,
+        _ => Some(source_hash(&src)),
+    };
+    save_marker(
+        &project_dir,
+        &ProjectMarker {
+            language: language.to_string(),
+            scaffolded,
+        },
+    )?;
+    if force || !markdown::readme_edited(&project_dir) {
+        markdown::write_readme(&project_dir, detail)?;
+    }
+    Ok((file, outcome))
+}
+
+fn source_hash(src: &str) -> String {
+    format!("{:016x}", markdown::fnv1a(src))
+}
+
+/// `main.rs.bak-<unix time>` next to `file`.
+fn backup_path(file: &Path) -> PathBuf {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let name = file.file_name().unwrap_or_default().to_string_lossy();
+    file.with_file_name(format!("{name}.bak-{secs}"))
+}
+
+pub fn project_dir(
+    workspace: &Path,
+    layout: &ScaffoldConfig,
+    frontend_id: &str,
+    slug: &str,
+) -> PathBuf {
+    workspace.join(layout.dir_name(frontend_id, slug))
+}
+
+/// Where the solution lives inside a scaffolded project.
+pub fn solution_path(project_dir: &Path, layout: &ScaffoldConfig, language: &str) -> PathBuf {
+    if let Some(file) = layout.file_for(language) {
+        return project_dir.join(file);
+    }
+    match language {
+        "python3" | "python" => project_dir.join("solution.py"),
+        "cpp" | "c++" => project_dir.join("solution.cpp"),
+        "c" => project_dir.join("solution.c"),
+        "java" => project_dir.join("Solution.java"),
+        "javascript" => project_dir.join("solution.js"),
+        "typescript" => project_dir.join("solution.ts"),
+        "go" | "golang" => project_dir.join("solution.go"),
+        _ => project_dir.join("src").join("main.rs"),
+    }
+}
+
+/// `template` with the problem's details filled in. The description is
+/// plain text; the template decides how to comment it out.
+fn render_template(template: &str, detail: &QuestionDetail, lang_slug: &str) -> String {
+    let snippet = detail
+        .code_snippets
+        .as_ref()
+        .and_then(|snippets| snippets.iter().find(|s| s.lang_slug == lang_slug))
+        .map(|s| s.code.as_str())
+        .unwrap_or_default();
+    let description = detail
+        .content
+        .as_ref()
+        .and_then(|html| html2text::from_read(html.as_bytes(), 80).ok())
+        .unwrap_or_default();
+    template
+        .replace("{{snippet}}", snippet)
+        .replace("{{description}}", description.trim_end())
+        .replace("{{id}}", &detail.frontend_question_id)
+        .replace("{{title}}", &detail.title)
+        .replace("{{slug}}", &detail.title_slug)
+}
+
+/// Write a solution file, creating any directories it sits in.
+fn write_solution(file: &Path, src: &str) -> Result<()> {
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create dir {}", dir.display()))?;
+    }
+    std::fs::write(file, src).with_context(|| format!("Failed to write {}", file.display()))
+}
+
+/// Slugs of the problems with a project directory in `workspace`. A
+/// missing workspace just means nothing is scaffolded yet.
+pub fn scan_workspace(workspace: &Path, layout: &ScaffoldConfig) -> HashSet<String> {
+    scan_projects(workspace, layout)
+        .into_iter()
+        .map(|(slug, _)| slug)
+        .collect()
+}
+
+/// Each project directory in `workspace` with the slug it's named for.
+pub fn scan_projects(workspace: &Path, layout: &ScaffoldConfig) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(workspace) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            let slug = layout.slug_from_dir(&name)?.to_string();
+            Some((slug, e.path()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock;
+
+    /// Scaffold Two Sum in Rust into `workspace` with `layout`.
+    fn scaffold(workspace: &Path, layout: &ScaffoldConfig, force: bool) -> (PathBuf, Outcome) {
+        let detail = mock::detail("1", "two-sum");
+        scaffold_problem(workspace, layout, &detail, "rust", force).unwrap()
+    }
+
+    fn backups(file: &Path) -> Vec<PathBuf> {
+        let prefix = format!("{}.bak-", file.file_name().unwrap().to_string_lossy());
+        std::fs::read_dir(file.parent().unwrap())
+            .unwrap()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .starts_with(&prefix)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn scaffolding_again_keeps_or_resets_the_solution() {
+        let dir = tempfile::tempdir().unwrap();
+        let layout = ScaffoldConfig::default();
+        let (file, outcome) = scaffold(dir.path(), &layout, false);
+        assert!(matches!(outcome, Outcome::Fresh));
+        let written = std::fs::read_to_string(&file).unwrap();
+        assert!(written.contains("pub fn f() -> i32"));
+
+        std::fs::write(&file, "// mine\n").unwrap();
+        assert!(matches!(
+            scaffold(dir.path(), &layout, false).1,
+            Outcome::Reused
+        ));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "// mine\n");
+
+        std::fs::write(&file, &written).unwrap();
+        assert!(matches!(
+            scaffold(dir.path(), &layout, true).1,
+            Outcome::Reset
+        ));
+        assert!(backups(&file).is_empty());
+    }
+
+    #[test]
+    fn an_edited_solution_is_backed_up_before_a_reset() {
+        let dir = tempfile::tempdir().unwrap();
+        let layout = ScaffoldConfig::default();
+        let (file, _) = scaffold(dir.path(), &layout, false);
+        let written = std::fs::read_to_string(&file).unwrap();
+        std::fs::write(&file, "// mine\n").unwrap();
+
+        let (_, outcome) = scaffold(dir.path(), &layout, true);
+        let Outcome::BackedUp(backup) = outcome else {
+            panic!("not backed up");
+        };
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "// mine\n");
+        assert_eq!(backups(&file), [backup]);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), written);
+    }
+
+    #[test]
+    fn a_changed_template_alone_doesnt_back_up_the_solution() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = dir.path().join("template.rs");
+        std::fs::write(&template, "// {{title}}\n{{snippet}}\n").unwrap();
+        let layout = ScaffoldConfig {
+            templates: [("rust".to_string(), template.display().to_string())].into(),
+            ..ScaffoldConfig::default()
+        };
+        let (file, _) = scaffold(dir.path(), &layout, false);
+
+        std::fs::write(&template, "// {{id}}. {{title}}\n{{snippet}}\n").unwrap();
+        assert!(matches!(
+            scaffold(dir.path(), &layout, true).1,
+            Outcome::Reset
+        ));
+        assert!(backups(&file).is_empty());
+        assert!(
+            std::fs::read_to_string(&file)
+                .unwrap()
+                .starts_with("// 1. two sum\n")
+        );
     }
 }
@@ -1,31 +1,38 @@
-use anyhow::{Context, Result};
-use std::path::PathBuf;
+use anyhow::{Context, Result, bail};
+use std::path::Path;
 use std::process::Command;
+use toml_edit::{Array, DocumentMut, Item, Table, Value};
 
 use crate::api::types::QuestionDetail;
 
-pub fn scaffold_rust(workspace: &PathBuf, detail: &QuestionDetail) -> Result<PathBuf> {
-    let dir_name = format!(
-        "{}-{}",
-        detail.frontend_question_id,
-        detail.title_slug
-    );
+/// A cargo project in `project_dir`, made a member of the cargo workspace
+/// at `cargo_workspace` when there is one. The solution file is written
+/// separately, from `source`.
+pub fn init_rust(project_dir: &Path, cargo_workspace: Option<&Path>) -> Result<()> {
+    let dir_name = project_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("solution");
     // Cargo package names can't start with a digit, so prefix with "p"
     let pkg_name = format!("p{dir_name}");
-    let project_dir = workspace.join(&dir_name);
 
-    // Idempotent: skip if already exists
+    // Idempotent: skip if already exists, but a project from before
+    // workspace mode still joins the workspace
     if project_dir.join("Cargo.toml").exists() {
-        return Ok(project_dir.join("src/main.rs"));
+        if let Some(root) = cargo_workspace {
+            join_workspace(root, project_dir)?;
+        }
+        return Ok(());
     }
 
-    // Create project with cargo init
-    std::fs::create_dir_all(&project_dir)
+    // Create project with cargo init, without a repository of its own: one
+    // at the workspace root holds every project (see `git::ensure_repo`)
+    std::fs::create_dir_all(project_dir)
         .with_context(|| format!("Failed to create dir {}", project_dir.display()))?;
 
     let output = Command::new("cargo")
-        .args(["init", "--name", &pkg_name])
-        .current_dir(&project_dir)
+        .args(["init", "--vcs", "none", "--name", &pkg_name])
+        .current_dir(project_dir)
         .output()
         .context("Failed to run cargo init")?;
 
@@ -34,11 +41,25 @@ pub fn scaffold_rust(workspace: &PathBuf, detail: &QuestionDetail) -> Result<Pat
         anyhow::bail!("cargo init failed: {}", stderr);
     }
 
+    // Only once the package exists: cargo can't init inside a workspace
+    // listing a member that has no manifest yet
+    if let Some(root) = cargo_workspace {
+        join_workspace(root, project_dir)?;
+    }
+    Ok(())
+}
+
+/// The built-in solution file: the statement as comments, the snippet,
+/// and an empty test module.
+pub fn source(detail: &QuestionDetail) -> String {
     // Build the source file content
     let mut src = String::new();
 
     // Problem description as comments
-    src.push_str(&format!("// {}: {}\n", detail.frontend_question_id, detail.title));
+    src.push_str(&format!(
+        "// {}: {}\n",
+        detail.frontend_question_id, detail.title
+    ));
     src.push_str(&format!("// Difficulty: {}\n", detail.difficulty));
     src.push_str(&format!(
         "// https://leetcode.com/problems/{}/\n",
@@ -48,8 +69,7 @@ pub fn scaffold_rust(workspace: &PathBuf, detail: &QuestionDetail) -> Result<Pat
 
     // Add description as comments
     if let Some(ref html) = detail.content {
-        let text = html2text::from_read(html.as_bytes(), 80)
-            .unwrap_or_default();
+        let text = html2text::from_read(html.as_bytes(), 80).unwrap_or_default();
         for line in text.lines().take(50) {
             src.push_str(&format!("// {}\n", line));
         }
@@ -81,10 +101,330 @@ pub fn scaffold_rust(workspace: &PathBuf, detail: &QuestionDetail) -> Result<Pat
     src.push_str("    #[test]\n    fn test_solution() {\n");
     src.push_str("        // TODO: add test cases\n");
     src.push_str("    }\n}\n");
+    src
+}
+
+/// Add `project_dir` to the `members` of the cargo workspace at `root`,
+/// creating the root manifest the first time. A new manifest lists every
+/// cargo project already in the workspace too, since cargo won't build a
+/// package under a workspace that doesn't include it.
+fn join_workspace(root: &Path, project_dir: &Path) -> Result<()> {
+    let manifest = root.join("Cargo.toml");
+    let created = !manifest.exists();
+    let mut doc = if created {
+        new_workspace(root)
+    } else {
+        std::fs::read_to_string(&manifest)
+            .with_context(|| format!("Failed to read {}", manifest.display()))?
+            .parse::<DocumentMut>()
+            .with_context(|| format!("Failed to parse {}", manifest.display()))?
+    };
+
+    let Some(workspace) = doc.get_mut("workspace").and_then(Item::as_table_like_mut) else {
+        bail!(
+            "{} is not a cargo workspace; remove it or turn off rust_workspace_mode",
+            manifest.display()
+        );
+    };
+    let members = workspace
+        .entry("members")
+        .or_insert(toml_edit::value(Array::new()))
+        .as_array_mut()
+        .with_context(|| format!("workspace.members in {} is not a list", manifest.display()))?;
+    let member = project_dir
+        .strip_prefix(root)
+        .ok()
+        .and_then(|p| p.to_str())
+        .with_context(|| format!("{} is outside the workspace", project_dir.display()))?
+        .replace('\\', "/");
+    let joined = !members.iter().any(|m| m.as_str() == Some(member.as_str()));
+    if joined {
+        push_member(members, member);
+    }
+
+    if created || joined {
+        std::fs::write(&manifest, doc.to_string())
+            .with_context(|| format!("Failed to write {}", manifest.display()))?;
+    }
+    Ok(())
+}
+
+/// A `[workspace]` manifest for `root`, with the cargo projects already
+/// there as members.
+fn new_workspace(root: &Path) -> DocumentMut {
+    let mut existing: Vec<String> = std::fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().join("Cargo.toml").is_file())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .collect();
+    existing.sort();
+
+    let mut members = Array::new();
+    for member in existing {
+        push_member(&mut members, member);
+    }
+    let mut workspace = Table::new();
+    workspace["resolver"] = toml_edit::value("2");
+    workspace["members"] = toml_edit::value(members);
+    let mut doc = DocumentMut::new();
+    doc["workspace"] = Item::Table(workspace);
+    doc
+}
 
-    let main_rs = project_dir.join("src/main.rs");
-    std::fs::write(&main_rs, src)
+/// One member per line, unless the list is already written on one line.
+fn push_member(members: &mut Array, member: String) {
+    let multiline = members.iter().next().is_none_or(|m| {
+        m.decor()
+            .prefix()
+            .and_then(|p| p.as_str())
+            .is_some_and(|p| p.contains('\n'))
+    });
+    if multiline {
+        members.push_formatted(Value::from(member).decorated("\n    ", ""));
+        members.set_trailing("\n");
+        members.set_trailing_comma(true);
+    } else {
+        members.push(member);
+    }
+}
+
+/// Append a failing case as a commented `#[test]` stub to the scaffolded
+/// project's tests module, creating the module if it's gone. Returns the
+/// test's name.
+pub fn append_test_case(
+    main_rs: &Path,
+    input: &str,
+    expected: Option<&str>,
+    output: Option<&str>,
+) -> Result<String> {
+    let mut src = std::fs::read_to_string(main_rs).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to read {}: {e}\nScaffold the problem first with 'o'",
+            main_rs.display()
+        )
+    })?;
+
+    let n = (1..)
+        .find(|n| !src.contains(&format!("fn failing_case_{n}(")))
+        .unwrap_or(1);
+    let name = format!("failing_case_{n}");
+    let method = solution_method(&src).unwrap_or("solve");
+
+    let mut stub = format!("    #[test]\n    fn {name}() {{\n");
+    stub.push_str("        // Input:\n");
+    for line in input.lines() {
+        stub.push_str(&format!("        //   {line}\n"));
+    }
+    if let Some(expected) = expected {
+        stub.push_str(&format!(
+            "        // Expected: {}\n",
+            expected.replace('\n', " ")
+        ));
+    }
+    if let Some(output) = output {
+        stub.push_str(&format!(
+            "        // Got:      {}\n",
+            output.replace('\n', " ")
+        ));
+    }
+    stub.push_str(&format!(
+        "        // assert_eq!(Solution::{method}(/* input */), /* expected */);\n"
+    ));
+    stub.push_str("    }\n");
+
+    match tests_mod_close(&src) {
+        Some(close) => {
+            // Keep a blank line between the previous test and the new one
+            let before = src[..close].trim_end().len();
+            src.replace_range(before..close, &format!("\n\n{stub}"));
+        }
+        None => {
+            if !src.ends_with('\n') {
+                src.push('\n');
+            }
+            src.push_str(&format!(
+                "\n#[cfg(test)]\nmod tests {{\n    use super::*;\n\n{stub}}}\n"
+            ));
+        }
+    }
+
+    std::fs::write(main_rs, src)
         .with_context(|| format!("Failed to write {}", main_rs.display()))?;
+    Ok(name)
+}
+
+/// Byte offset of the closing brace of the top-level `mod tests`.
+fn tests_mod_close(src: &str) -> Option<usize> {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_rust::LANGUAGE.into())
+        .ok()?;
+    let tree = parser.parse(src, None)?;
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let module = root.children(&mut cursor).find(|node| {
+        node.kind() == "mod_item"
+            && node
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(src.as_bytes()).ok())
+                == Some("tests")
+    })?;
+    let body = module.child_by_field_name("body")?;
+    Some(body.end_byte() - 1)
+}
 
-    Ok(main_rs)
+/// Name of the first `pub fn` in the file, i.e. the method the snippet
+/// asks us to implement.
+fn solution_method(src: &str) -> Option<&str> {
+    let start = src.find("pub fn ")? + "pub fn ".len();
+    let rest = &src[start..];
+    let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+    Some(&rest[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A workspace root holding cargo projects named `projects`.
+    fn workspace(projects: &[&str]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for name in projects {
+            std::fs::create_dir_all(dir.path().join(name)).unwrap();
+            std::fs::write(dir.path().join(name).join("Cargo.toml"), "[package]\n").unwrap();
+        }
+        dir
+    }
+
+    fn manifest(dir: &tempfile::TempDir) -> String {
+        std::fs::read_to_string(dir.path().join("Cargo.toml")).unwrap()
+    }
+
+    #[test]
+    fn a_new_workspace_lists_the_projects_already_there() {
+        let dir = workspace(&["1-two-sum", "2-add-two-numbers"]);
+        join_workspace(dir.path(), &dir.path().join("2-add-two-numbers")).unwrap();
+        let expected = "[workspace]\nresolver = \"2\"\nmembers = [\n    \"1-two-sum\",\n    \"2-add-two-numbers\",\n]\n";
+        assert_eq!(manifest(&dir), expected);
+
+        for _ in 0..2 {
+            join_workspace(dir.path(), &dir.path().join("1-two-sum")).unwrap();
+        }
+        assert_eq!(manifest(&dir), expected);
+
+        // Later members go one per line too
+        join_workspace(dir.path(), &dir.path().join("3-longest-substring")).unwrap();
+        assert_eq!(
+            manifest(&dir),
+            expected.replace("\n]", "\n    \"3-longest-substring\",\n]")
+        );
+    }
+
+    #[test]
+    fn joining_keeps_the_rest_of_the_manifest() {
+        let dir = workspace(&["1-two-sum", "2-add-two-numbers"]);
+        let original = "# Solutions\n[workspace]\nresolver = \"2\"\nmembers = [\"1-two-sum\"] # by hand\n\n[profile.release]\nopt-level = 3 # fast\n";
+        std::fs::write(dir.path().join("Cargo.toml"), original).unwrap();
+
+        for _ in 0..2 {
+            join_workspace(dir.path(), &dir.path().join("2-add-two-numbers")).unwrap();
+        }
+        assert_eq!(
+            manifest(&dir),
+            original.replace("[\"1-two-sum\"]", "[\"1-two-sum\", \"2-add-two-numbers\"]")
+        );
+    }
+
+    #[test]
+    fn a_manifest_that_isnt_a_workspace_is_left_alone() {
+        let dir = workspace(&["1-two-sum"]);
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"mine\"\n",
+        )
+        .unwrap();
+        let err = join_workspace(dir.path(), &dir.path().join("1-two-sum")).unwrap_err();
+        assert!(err.to_string().contains("is not a cargo workspace"));
+        assert_eq!(manifest(&dir), "[package]\nname = \"mine\"\n");
+    }
+
+    /// `src` as a solution file, with a failing case appended.
+    fn append(src: &str) -> (String, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let main_rs = dir.path().join("main.rs");
+        std::fs::write(&main_rs, src).unwrap();
+        let name = append_test_case(&main_rs, "[2,7]\n9", Some("[0,1]"), Some("[]")).unwrap();
+        (name, std::fs::read_to_string(&main_rs).unwrap())
+    }
+
+    const STUB: &str = "    #[test]
+    fn failing_case_1() {
+        // Input:
+        //   [2,7]
+        //   9
+        // Expected: [0,1]
+        // Got:      []
+        // assert_eq!(Solution::two_sum(/* input */), /* expected */);
+    }
+";
+
+    #[test]
+    fn failing_cases_go_at_the_end_of_the_tests_module() {
+        let src = "impl Solution {
+    pub fn two_sum() {}
+}
+
+mod helpers {
+    mod tests {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn braces() {
+        let s = \"}\";
+        let v = { vec![1] };
+        // }
+    }
+}
+
+fn after() {}
+";
+        let (name, out) = append(src);
+        assert_eq!(name, "failing_case_1");
+        let expected = src.replace(
+            "        // }\n    }\n}\n",
+            &format!("        // }}\n    }}\n\n{STUB}}}\n"),
+        );
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn a_missing_tests_module_is_created() {
+        let src = "impl Solution {\n    pub fn two_sum() {}\n}";
+        let (_, out) = append(src);
+        let expected =
+            format!("{src}\n\n#[cfg(test)]\nmod tests {{\n    use super::*;\n\n{STUB}}}\n");
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn each_failing_case_gets_its_own_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let main_rs = dir.path().join("main.rs");
+        std::fs::write(&main_rs, "impl Solution {\n    pub fn two_sum() {}\n}\n").unwrap();
+        let names: Vec<String> = (0..3)
+            .map(|_| append_test_case(&main_rs, "1", None, None).unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["failing_case_1", "failing_case_2", "failing_case_3"]
+        );
+        let src = std::fs::read_to_string(&main_rs).unwrap();
+        assert_eq!(src.matches("mod tests").count(), 1);
+    }
 }
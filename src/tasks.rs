@@ -0,0 +1,51 @@
+use std::sync::{Arc, Mutex};
+
+/// Labels of the background tasks still running, for the status bar
+/// indicator. Cheap to clone; every clone shares the same list.
+#[derive(Clone, Default)]
+pub struct TaskTracker {
+    running: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl TaskTracker {
+    /// Register a task; it counts as running until the guard is dropped,
+    /// so move the guard into the spawned future.
+    pub fn start(&self, label: &'static str) -> TaskGuard {
+        if let Ok(mut running) = self.running.lock() {
+            running.push(label);
+        }
+        TaskGuard {
+            tracker: self.clone(),
+            label,
+        }
+    }
+
+    /// Distinct labels, oldest first.
+    pub fn labels(&self) -> Vec<&'static str> {
+        let Ok(running) = self.running.lock() else {
+            return Vec::new();
+        };
+        let mut labels: Vec<&'static str> = Vec::new();
+        for label in running.iter() {
+            if !labels.contains(label) {
+                labels.push(label);
+            }
+        }
+        labels
+    }
+}
+
+pub struct TaskGuard {
+    tracker: TaskTracker,
+    label: &'static str,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        if let Ok(mut running) = self.tracker.running.lock()
+            && let Some(i) = running.iter().position(|l| *l == self.label)
+        {
+            running.remove(i);
+        }
+    }
+}
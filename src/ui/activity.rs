@@ -0,0 +1,112 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+};
+
+use super::icons::icons;
+use super::theme::theme;
+use crate::history::ActivityEntry;
+
+/// Local run and submission history, newest first. `with_problem` adds the
+/// problem columns for the all-problems view on Home.
+pub fn render_activity(
+    frame: &mut Frame,
+    area: Rect,
+    entries: &[ActivityEntry],
+    state: &mut TableState,
+    with_problem: bool,
+) {
+    if entries.is_empty() {
+        let p = Paragraph::new("  No runs or submissions recorded yet")
+            .style(Style::default().fg(theme().dim));
+        frame.render_widget(p, area);
+        return;
+    }
+
+    let mut header = vec!["Kind", "Verdict", "Runtime", "Memory", "Language", "When"];
+    if with_problem {
+        header.splice(0..0, [" # ", "Title"]);
+    }
+    let header = Row::new(header.into_iter().map(Cell::from)).style(
+        Style::default()
+            .fg(theme().accent)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let rows: Vec<Row> = entries
+        .iter()
+        .map(|e| {
+            let verdict_color = if e.verdict == "Accepted" {
+                theme().success
+            } else {
+                theme().error
+            };
+            let mut cells = vec![
+                Cell::from(if e.kind == "submit" { "Submit" } else { "Run" }),
+                Cell::from(Span::styled(
+                    e.verdict.clone(),
+                    Style::default().fg(verdict_color),
+                )),
+                Cell::from(e.runtime.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(e.memory.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(e.language.clone()),
+                Cell::from(Span::styled(
+                    format_age(now.saturating_sub(e.at)),
+                    Style::default().fg(theme().dim),
+                )),
+            ];
+            if with_problem {
+                cells.splice(
+                    0..0,
+                    [
+                        Cell::from(format!(" {}", e.frontend_question_id)),
+                        Cell::from(e.title.clone()),
+                    ],
+                );
+            }
+            Row::new(cells)
+        })
+        .collect();
+
+    let mut widths = vec![
+        Constraint::Length(7),
+        Constraint::Length(22),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ];
+    if with_problem {
+        widths.splice(0..0, [Constraint::Length(6), Constraint::Min(20)]);
+    }
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().borders(Borders::NONE))
+        .row_highlight_style(
+            Style::default()
+                .bg(theme().selection_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(format!("{} ", icons().pointer));
+
+    frame.render_stateful_widget(table, area, state);
+}
+
+pub fn format_age(secs: u64) -> String {
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
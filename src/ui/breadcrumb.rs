@@ -0,0 +1,33 @@
+use super::icons::icons;
+use super::theme::theme;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+/// One line of `Home › Lists › …`, with the current screen in bold.
+pub fn render_breadcrumb(frame: &mut Frame, area: Rect, crumbs: &[String]) {
+    let mut spans = vec![Span::raw(" ")];
+    for (i, crumb) in crumbs.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(
+                format!(" {} ", icons().breadcrumb),
+                Style::default().fg(theme().dim),
+            ));
+        }
+        let style = if i + 1 == crumbs.len() {
+            Style::default()
+                .fg(theme().text)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme().muted)
+        };
+        spans.push(Span::styled(crumb.clone(), style));
+    }
+
+    let bar = Paragraph::new(Line::from(spans)).style(Style::default().bg(theme().bar_bg));
+    frame.render_widget(bar, area);
+}
@@ -0,0 +1,282 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::api::types::{CompanyPeriod, CompanyTag};
+
+use super::fuzzy::{self, FuzzyFilter};
+use super::icons::{icons, spinner};
+use super::text_input::TextInput;
+use super::theme::theme;
+
+/// Picks a company whose interview problems Home narrows down to, opened
+/// from the filter popup. Typing narrows the companies; Tab picks how far
+/// back to look.
+pub struct CompanyPicker {
+    pub companies: Vec<CompanyTag>,
+    /// Indices into `companies` matching the query, with matched positions
+    visible: Vec<(usize, Vec<u32>)>,
+    pub query: TextInput,
+    pub state: ListState,
+    pub period: CompanyPeriod,
+    /// Waiting on the company list, or on the chosen company's problems
+    pub loading: bool,
+    /// Why the last load failed, e.g. the account isn't Premium
+    pub message: Option<String>,
+    pub spinner_frame: usize,
+    fuzzy: FuzzyFilter,
+}
+
+pub enum CompanyAction {
+    None,
+    Close,
+    /// Narrow Home to the problems this company asked within `period`
+    Load {
+        slug: String,
+        period: CompanyPeriod,
+    },
+}
+
+impl CompanyPicker {
+    /// A picker waiting for the company list.
+    pub fn new(period: CompanyPeriod) -> Self {
+        Self {
+            companies: Vec::new(),
+            visible: Vec::new(),
+            query: TextInput::default(),
+            state: ListState::default(),
+            period,
+            loading: true,
+            message: None,
+            spinner_frame: 0,
+            fuzzy: FuzzyFilter::default(),
+        }
+    }
+
+    pub fn set_companies(&mut self, companies: Vec<CompanyTag>) {
+        self.companies = companies;
+        self.loading = false;
+        self.refilter();
+    }
+
+    /// Stop the spinner and show why the load failed.
+    pub fn failed(&mut self, message: String) {
+        self.loading = false;
+        self.message = Some(message);
+    }
+
+    pub fn paste(&mut self, text: &str) {
+        if !self.loading {
+            self.query.insert_str(text);
+            self.refilter();
+        }
+    }
+
+    fn refilter(&mut self) {
+        let query = self.query.value().trim();
+        self.fuzzy.set_query(query);
+        self.visible = if query.is_empty() {
+            (0..self.companies.len()).map(|i| (i, Vec::new())).collect()
+        } else {
+            let mut matches: Vec<(u32, usize, Vec<u32>)> = self
+                .companies
+                .iter()
+                .enumerate()
+                .filter_map(|(i, c)| {
+                    let (score, positions) = self.fuzzy.match_str(&c.name)?;
+                    Some((score, i, positions))
+                })
+                .collect();
+            // Stable, so equal scores keep the busiest companies first
+            matches.sort_by_key(|m| std::cmp::Reverse(m.0));
+            matches
+                .into_iter()
+                .map(|(_, i, positions)| (i, positions))
+                .collect()
+        };
+        self.state.select((!self.visible.is_empty()).then_some(0));
+    }
+
+    fn selected(&self) -> Option<&CompanyTag> {
+        let (i, _) = self.visible.get(self.state.selected()?)?;
+        self.companies.get(*i)
+    }
+
+    fn move_selection(&mut self, delta: i32) {
+        let len = self.visible.len() as i32;
+        if len == 0 {
+            return;
+        }
+        let current = self.state.selected().unwrap_or(0) as i32;
+        self.state
+            .select(Some((current + delta).rem_euclid(len) as usize));
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> CompanyAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => CompanyAction::Close,
+            KeyCode::Down => {
+                self.move_selection(1);
+                CompanyAction::None
+            }
+            KeyCode::Char('n') if ctrl => {
+                self.move_selection(1);
+                CompanyAction::None
+            }
+            KeyCode::Up => {
+                self.move_selection(-1);
+                CompanyAction::None
+            }
+            KeyCode::Char('p') if ctrl => {
+                self.move_selection(-1);
+                CompanyAction::None
+            }
+            KeyCode::Tab => {
+                self.period = self.period.next();
+                CompanyAction::None
+            }
+            KeyCode::BackTab => {
+                self.period = self.period.prev();
+                CompanyAction::None
+            }
+            KeyCode::Enter if !self.loading => match self.selected() {
+                Some(company) => {
+                    let action = CompanyAction::Load {
+                        slug: company.slug.clone(),
+                        period: self.period,
+                    };
+                    self.loading = true;
+                    self.message = None;
+                    action
+                }
+                None => CompanyAction::None,
+            },
+            _ if !self.loading => {
+                let before = self.query.value().to_string();
+                if self.query.handle_key(key) && self.query.value() != before {
+                    self.message = None;
+                    self.refilter();
+                }
+                CompanyAction::None
+            }
+            _ => CompanyAction::None,
+        }
+    }
+}
+
+pub fn render_company_picker(frame: &mut Frame, area: Rect, picker: &mut CompanyPicker) {
+    let width = 48u16.min(area.width.saturating_sub(4));
+    let height = 20u16.min(area.height.saturating_sub(4));
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let popup = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(" Company ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().special));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let message_height = if picker.message.is_some() { 2 } else { 0 };
+    let rows = Layout::vertical([
+        Constraint::Length(1),              // search
+        Constraint::Length(1),              // period
+        Constraint::Min(1),                 // companies
+        Constraint::Length(message_height), // why the load failed
+        Constraint::Length(1),              // hint
+    ])
+    .split(inner);
+
+    let text_style = Style::default().fg(theme().text);
+    let mut search = vec![Span::styled(" / ", Style::default().fg(theme().accent))];
+    search.extend(picker.query.spans(
+        (rows[0].width as usize).saturating_sub(3),
+        !picker.loading,
+        text_style,
+    ));
+    frame.render_widget(Paragraph::new(Line::from(search)), rows[0]);
+
+    let mut period = vec![Span::styled(" Asked in ", Style::default().fg(theme().dim))];
+    for p in CompanyPeriod::ALL {
+        let style = if p == picker.period {
+            Style::default()
+                .fg(theme().special)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme().dim)
+        };
+        let marker = if p == picker.period {
+            icons().circle_checked
+        } else {
+            icons().circle
+        };
+        period.push(Span::styled(format!("{marker} {} ", p.label()), style));
+    }
+    frame.render_widget(Paragraph::new(Line::from(period)), rows[1]);
+
+    if picker.loading && picker.companies.is_empty() {
+        let loading = Paragraph::new(format!(
+            " {} Loading companies...",
+            spinner(picker.spinner_frame)
+        ))
+        .style(Style::default().fg(theme().warning));
+        frame.render_widget(loading, rows[2]);
+    } else if picker.visible.is_empty() {
+        let empty = if picker.companies.is_empty() {
+            " No companies"
+        } else {
+            " No match"
+        };
+        frame.render_widget(
+            Paragraph::new(empty).style(Style::default().fg(theme().dim)),
+            rows[2],
+        );
+    } else {
+        let name_width = (rows[2].width as usize).saturating_sub(10);
+        let items: Vec<ListItem> = picker
+            .visible
+            .iter()
+            .map(|(i, positions)| {
+                let company = &picker.companies[*i];
+                let count = format!(" ({})", company.question_count);
+                let mut spans =
+                    fuzzy::highlight_spans(&company.name, positions, "", name_width, text_style);
+                spans.push(Span::styled(count, Style::default().fg(theme().dim)));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .fg(theme().accent)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(format!("{} ", icons().pointer));
+        frame.render_stateful_widget(list, rows[2], &mut picker.state);
+    }
+
+    if let Some(ref message) = picker.message {
+        let message = Paragraph::new(format!(" {message}"))
+            .style(Style::default().fg(theme().error))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(message, rows[3]);
+    }
+
+    let hint = if picker.loading && !picker.companies.is_empty() {
+        format!(" {} Loading problems...", spinner(picker.spinner_frame))
+    } else {
+        " Enter: show  Tab: period  Esc: close".to_string()
+    };
+    frame.render_widget(
+        Paragraph::new(hint).style(Style::default().fg(theme().dim)),
+        rows[4],
+    );
+}
@@ -0,0 +1,468 @@
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+};
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Local, TimeZone};
+
+use crate::api::types::{ContestQuestion, ContestSummary};
+
+use super::icons::{icons, spinner};
+use super::motion::{Motion, PendingKeys};
+use super::mouse::{self, ClickTracker, WHEEL_STEP};
+use super::status_bar::render_status_bar;
+use super::theme::theme;
+
+const CONTEST_URL: &str = "https://leetcode.com/contest/";
+
+/// A past contest's problems, fetched when it's first expanded.
+pub enum ContestProblems {
+    Loading,
+    Loaded(Vec<ContestQuestion>),
+    Failed(String),
+}
+
+/// One table row: a contest, or a line under an expanded one.
+#[derive(Clone, Copy)]
+enum ContestRow {
+    Contest(usize),
+    /// A problem, by contest and position
+    Problem(usize, usize),
+    /// Loading or failed, for the contest at this index
+    Note(usize),
+}
+
+pub struct ContestsState {
+    /// Latest start first, so upcoming contests lead
+    pub contests: Vec<ContestSummary>,
+    /// Slugs of contests showing their problems
+    pub expanded: HashSet<String>,
+    pub problems: HashMap<String, ContestProblems>,
+    pub loading: bool,
+    pub error_message: Option<String>,
+    pub spinner_frame: usize,
+    /// Unix seconds, refreshed every tick for the countdowns
+    pub now: i64,
+    pub table_state: TableState,
+    rows: Vec<ContestRow>,
+    pub pending_keys: PendingKeys,
+    /// Visible table rows from the last render, for page motions
+    pub table_height: u16,
+    /// `j` on the last row goes to the first, and `k` on the first to the last
+    pub wrap_navigation: bool,
+    /// Where the table was last drawn, for mouse clicks
+    pub table_area: Rect,
+    clicks: ClickTracker,
+}
+
+pub enum ContestsAction {
+    None,
+    Back,
+    OpenDetail(String),
+    /// Fetch a past contest's problems to show under it
+    LoadProblems(String),
+    /// Open a contest's page in the browser
+    OpenPage(String),
+    /// Narrow Home to a contest's problems
+    FilterHome(String),
+    /// Clear the error and fetch the contests again
+    Retry,
+}
+
+/// Unix seconds now.
+pub fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+impl ContestsState {
+    pub fn new() -> Self {
+        Self {
+            contests: Vec::new(),
+            expanded: HashSet::new(),
+            problems: HashMap::new(),
+            loading: true,
+            error_message: None,
+            spinner_frame: 0,
+            now: unix_now(),
+            table_state: TableState::default(),
+            rows: Vec::new(),
+            pending_keys: PendingKeys::default(),
+            table_height: 0,
+            wrap_navigation: false,
+            table_area: Rect::default(),
+            clicks: ClickTracker::default(),
+        }
+    }
+
+    pub fn set_contests(&mut self, contests: Vec<ContestSummary>) {
+        self.contests = contests;
+        self.loading = false;
+        self.rebuild_rows();
+        // Start on the next contest to begin, or the latest past one
+        let next = self
+            .contests
+            .iter()
+            .rposition(|c| c.end_time() > self.now)
+            .unwrap_or(0);
+        let row = self
+            .rows
+            .iter()
+            .position(|r| matches!(r, ContestRow::Contest(i) if *i == next));
+        self.table_state.select(row);
+    }
+
+    pub fn set_problems(&mut self, slug: String, problems: ContestProblems) {
+        self.problems.insert(slug, problems);
+        self.rebuild_rows();
+    }
+
+    fn rebuild_rows(&mut self) {
+        let mut rows = Vec::new();
+        for (i, contest) in self.contests.iter().enumerate() {
+            rows.push(ContestRow::Contest(i));
+            if !self.expanded.contains(&contest.title_slug) {
+                continue;
+            }
+            match self.problems.get(&contest.title_slug) {
+                Some(ContestProblems::Loaded(questions)) => {
+                    rows.extend((0..questions.len()).map(|q| ContestRow::Problem(i, q)));
+                }
+                _ => rows.push(ContestRow::Note(i)),
+            }
+        }
+        self.rows = rows;
+        if let Some(selected) = self.table_state.selected()
+            && selected >= self.rows.len()
+        {
+            self.table_state.select(self.rows.len().checked_sub(1));
+        }
+    }
+
+    /// The contest under the cursor, or the one whose problem it is.
+    fn selected_contest(&self) -> Option<&ContestSummary> {
+        let i = match self.rows.get(self.table_state.selected()?)? {
+            ContestRow::Contest(i) | ContestRow::Problem(i, _) | ContestRow::Note(i) => *i,
+        };
+        self.contests.get(i)
+    }
+
+    /// Problem `q` of contest `i`, once its problems are loaded.
+    fn problem(&self, i: usize, q: usize) -> Option<&ContestQuestion> {
+        match self.problems.get(&self.contests.get(i)?.title_slug)? {
+            ContestProblems::Loaded(questions) => questions.get(q),
+            _ => None,
+        }
+    }
+
+    /// Show or hide a past contest's problems, asking for them the first
+    /// time.
+    fn toggle_expanded(&mut self, i: usize) -> ContestsAction {
+        let Some(contest) = self.contests.get(i) else {
+            return ContestsAction::None;
+        };
+        if contest.start_time > self.now {
+            return ContestsAction::None;
+        }
+        let slug = contest.title_slug.clone();
+        if self.expanded.remove(&slug) {
+            self.rebuild_rows();
+            return ContestsAction::None;
+        }
+        self.expanded.insert(slug.clone());
+        let action = match self.problems.get(&slug) {
+            Some(ContestProblems::Loaded(_)) => ContestsAction::None,
+            _ => {
+                self.problems.insert(slug.clone(), ContestProblems::Loading);
+                ContestsAction::LoadProblems(slug)
+            }
+        };
+        self.rebuild_rows();
+        action
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> ContestsAction {
+        if self.handle_motion(key) {
+            return ContestsAction::None;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('b') => ContestsAction::Back,
+            KeyCode::Char('R') if self.error_message.is_some() => ContestsAction::Retry,
+            KeyCode::Enter => match self.table_state.selected().and_then(|r| self.rows.get(r)) {
+                Some(&ContestRow::Contest(i)) => self.toggle_expanded(i),
+                Some(&ContestRow::Problem(i, q)) => match self.problem(i, q) {
+                    Some(question) => ContestsAction::OpenDetail(question.title_slug.clone()),
+                    None => ContestsAction::None,
+                },
+                _ => ContestsAction::None,
+            },
+            KeyCode::Char('o') => match self.selected_contest() {
+                Some(c) => ContestsAction::OpenPage(format!("{CONTEST_URL}{}/", c.title_slug)),
+                None => ContestsAction::None,
+            },
+            KeyCode::Char('f') => match self.selected_contest() {
+                Some(c) if c.start_time <= self.now => {
+                    ContestsAction::FilterHome(c.title_slug.clone())
+                }
+                _ => ContestsAction::None,
+            },
+            _ => ContestsAction::None,
+        }
+    }
+
+    /// Wheel moves the selection; clicking a row selects it and a
+    /// double-click acts like Enter.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> ContestsAction {
+        let count = self.rows.len();
+        match mouse.kind {
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp if count > 0 => {
+                let step = if matches!(mouse.kind, MouseEventKind::ScrollDown) {
+                    WHEEL_STEP
+                } else {
+                    -WHEEL_STEP
+                };
+                let current = self.table_state.selected().unwrap_or(0) as i32;
+                self.table_state
+                    .select(Some((current + step).clamp(0, count as i32 - 1) as usize));
+                ContestsAction::None
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(row) = mouse::table_row_at(
+                    self.table_area,
+                    self.table_state.offset(),
+                    mouse.column,
+                    mouse.row,
+                )
+                .filter(|&r| r < count) else {
+                    return ContestsAction::None;
+                };
+                self.table_state.select(Some(row));
+                if self.clicks.click(row) {
+                    self.handle_key(KeyEvent::from(KeyCode::Enter))
+                } else {
+                    ContestsAction::None
+                }
+            }
+            _ => ContestsAction::None,
+        }
+    }
+
+    /// Applies j/k, `gg`/`G`, counts, Ctrl+d/u and PageUp/PageDown.
+    /// Returns false when the key isn't a motion.
+    fn handle_motion(&mut self, key: KeyEvent) -> bool {
+        let count = self.rows.len();
+        let current = self.table_state.selected().unwrap_or(0) as i32;
+        let last = count as i32 - 1;
+        let page = (self.table_height as i32).max(1);
+        let half_page = (page / 2).max(1);
+        let target = match self.pending_keys.feed(key) {
+            Motion::Other => return false,
+            Motion::Pending => return true,
+            Motion::Down(_) if self.wrap_navigation && current == last => 0,
+            Motion::Up(_) if self.wrap_navigation && current == 0 => last,
+            Motion::Down(n) => current + n as i32,
+            Motion::Up(n) => current - n as i32,
+            Motion::HalfPageDown => current + half_page,
+            Motion::HalfPageUp => current - half_page,
+            Motion::PageDown => current + page,
+            Motion::PageUp => current - page,
+            Motion::Top => 0,
+            Motion::Bottom => last,
+            Motion::Goto(n) => n as i32 - 1,
+        };
+        if count > 0 {
+            self.table_state
+                .select(Some(target.clamp(0, last) as usize));
+        }
+        true
+    }
+}
+
+/// `Sun 2026-10-18 10:30` in the local time zone.
+fn local_time(secs: i64) -> String {
+    Local
+        .timestamp_opt(secs, 0)
+        .single()
+        .map_or_else(String::new, |t| t.format("%a %Y-%m-%d %H:%M").to_string())
+}
+
+/// `2d 03h 12m`, or `03:12:05` inside a day.
+fn countdown(secs: i64) -> String {
+    let secs = secs.max(0);
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{days}d {hours:02}h {minutes:02}m")
+    } else {
+        format!("{hours:02}:{minutes:02}:{:02}", secs % 60)
+    }
+}
+
+pub fn render_contests(frame: &mut Frame, area: Rect, state: &mut ContestsState) {
+    let layout = Layout::vertical([
+        Constraint::Length(1), // title bar
+        Constraint::Min(3),    // content
+        Constraint::Length(1), // status bar
+    ])
+    .split(area);
+
+    render_title_bar(frame, layout[0], state);
+
+    if state.loading && state.contests.is_empty() {
+        let s = spinner(state.spinner_frame);
+        let loading = Paragraph::new(format!(" {s} Loading contests..."))
+            .style(Style::default().fg(theme().warning));
+        frame.render_widget(loading, layout[1]);
+    } else if let Some(ref err) = state.error_message {
+        let error =
+            Paragraph::new(format!(" Error: {err}")).style(Style::default().fg(theme().error));
+        frame.render_widget(error, layout[1]);
+    } else {
+        render_table(frame, layout[1], state);
+    }
+
+    let hints = if state.error_message.is_some() {
+        vec![("R", "Retry"), ("Esc", "Back"), ("?", "Help")]
+    } else {
+        vec![
+            ("j/k", "Navigate"),
+            ("Enter", "Expand / View"),
+            ("o", "Open Page"),
+            ("f", "Filter Home"),
+            ("Esc", "Back"),
+            ("?", "Help"),
+        ]
+    };
+    render_status_bar(frame, layout[2], &hints);
+}
+
+fn render_title_bar(frame: &mut Frame, area: Rect, state: &ContestsState) {
+    let upcoming = state
+        .contests
+        .iter()
+        .filter(|c| c.start_time > state.now)
+        .count();
+    let spans = vec![
+        Span::styled(
+            " Contests ",
+            Style::default()
+                .fg(theme().on_accent)
+                .bg(theme().special)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!(
+                "{upcoming} upcoming, {} past",
+                state.contests.len() - upcoming
+            ),
+            Style::default().fg(theme().dim),
+        ),
+    ];
+    let title = Paragraph::new(Line::from(spans)).style(Style::default().bg(theme().bar_bg));
+    frame.render_widget(title, area);
+}
+
+fn render_table(frame: &mut Frame, area: Rect, state: &mut ContestsState) {
+    state.table_height = area.height.saturating_sub(1); // minus header row
+    state.table_area = area;
+
+    let header = Row::new([
+        Cell::from(" Contest"),
+        Cell::from("Starts"),
+        Cell::from("When"),
+    ])
+    .style(
+        Style::default()
+            .fg(theme().accent)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let now = state.now;
+    let rows: Vec<Row> = state
+        .rows
+        .iter()
+        .map(|row| match *row {
+            ContestRow::Contest(i) => {
+                let c = &state.contests[i];
+                let (when, color) = if c.start_time > now {
+                    (
+                        format!("in {}", countdown(c.start_time - now)),
+                        theme().warning,
+                    )
+                } else if c.end_time() > now {
+                    (
+                        format!("live, ends in {}", countdown(c.end_time() - now)),
+                        theme().success,
+                    )
+                } else {
+                    (String::new(), theme().dim)
+                };
+                // Only past contests expand
+                let marker = if c.start_time > now {
+                    " "
+                } else if state.expanded.contains(&c.title_slug) {
+                    icons().expanded
+                } else {
+                    icons().next
+                };
+                Row::new([
+                    Cell::from(format!("{marker} {}", c.title)),
+                    Cell::from(Span::styled(
+                        local_time(c.start_time),
+                        Style::default().fg(theme().dim),
+                    )),
+                    Cell::from(Span::styled(when, Style::default().fg(color))),
+                ])
+            }
+            ContestRow::Problem(i, q) => {
+                let (title, credit) = state
+                    .problem(i, q)
+                    .map_or((String::new(), 0), |q| (q.title.clone(), q.credit));
+                let points =
+                    Span::styled(format!("{credit} pts"), Style::default().fg(theme().dim));
+                Row::new([
+                    Cell::from(format!("    Q{} {title}", q + 1)),
+                    Cell::from(points),
+                    Cell::from(""),
+                ])
+            }
+            ContestRow::Note(i) => {
+                let (text, color) = match state.problems.get(&state.contests[i].title_slug) {
+                    Some(ContestProblems::Failed(e)) => (e.clone(), theme().error),
+                    _ => {
+                        let s = spinner(state.spinner_frame);
+                        (format!("{s} Loading problems..."), theme().warning)
+                    }
+                };
+                Row::new([Cell::from(Span::styled(
+                    format!("    {text}"),
+                    Style::default().fg(color),
+                ))])
+            }
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Min(30),
+        Constraint::Length(22),
+        Constraint::Length(24),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().borders(Borders::NONE))
+        .row_highlight_style(
+            Style::default()
+                .bg(theme().selection_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(format!("{} ", icons().pointer));
+
+    frame.render_stateful_widget(table, area, &mut state.table_state);
+}
@@ -1,49 +1,159 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 use ratatui::{
+    Frame,
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
-    Frame,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
 };
 
-use crate::api::types::QuestionDetail;
+use crate::api::types::{OfficialSolution, QuestionDetail, SimilarQuestion, TopicTag};
+use crate::history::{ActivityEntry, ActivityLog};
 
-use super::rich_text::html_to_lines;
+use super::activity::render_activity;
+use super::home::ListCandidate;
+use super::icons::icons;
+use super::mouse::WHEEL_STEP;
+use super::rich_text::{html_to_lines, markdown_to_lines};
+use super::solutions::{self, SolutionsAction, SolutionsState};
 use super::status_bar::render_status_bar;
+use super::theme::theme;
+
+/// What the content area shows. `e` and `c` switch to the editorial and
+/// the community solutions, and back.
+#[derive(Clone, Copy, PartialEq)]
+pub enum DetailTab {
+    Description,
+    Editorial,
+    Solutions,
+}
 
 pub struct DetailState {
     pub detail: QuestionDetail,
     pub content_lines: Vec<Line<'static>>,
     pub scroll_offset: u16,
     pub content_height: u16,
+    pub tab: DetailTab,
+    /// `None` until the editorial has been fetched
+    pub editorial_lines: Option<Vec<Line<'static>>>,
+    /// `None` until the Solutions tab is first opened
+    pub solutions: Option<SolutionsState>,
+    /// Scroll position of each tab while it's hidden
+    tab_scroll: [u16; 3],
+    /// Hints revealed so far with `h`, one per press
+    pub hints_shown: usize,
+    pub similar: Vec<SimilarQuestion>,
+    /// Selection in the similar questions panel; Tab moves it
+    pub similar_state: TableState,
+    /// This problem's local runs and submissions, shown with `H`
+    pub activity: Option<Vec<ActivityEntry>>,
+    pub activity_state: TableState,
 }
 
-impl DetailState {
-    pub fn new(detail: QuestionDetail) -> Self {
-        let content_lines = if detail.is_paid_only && detail.content.is_none() {
-            vec![Line::from(Span::styled(
-                " Premium content — not available without authentication.",
-                Style::default().fg(Color::Yellow),
-            ))]
-        } else if let Some(ref html) = detail.content {
-            html_to_lines(html)
-        } else {
+/// Rows of the similar questions panel, so a long list doesn't crowd out
+/// the statement.
+const SIMILAR_ROWS: u16 = 4;
+
+/// The editorial rendered for display, or why there isn't one to show.
+pub fn editorial_lines(solution: Option<&OfficialSolution>) -> Vec<Line<'static>> {
+    match solution {
+        Some(s) if (s.paid_only && !s.can_see_detail) || s.content.is_none() => {
             vec![Line::from(Span::styled(
-                " No content available.",
-                Style::default().fg(Color::DarkGray),
+                " Premium content — the editorial requires a LeetCode Premium subscription.",
+                Style::default().fg(theme().warning),
             ))]
-        };
+        }
+        Some(s) => markdown_to_lines(s.content.as_deref().unwrap_or_default()),
+        None => vec![Line::from(Span::styled(
+            " No editorial available for this problem.",
+            Style::default().fg(theme().dim),
+        ))],
+    }
+}
+
+/// The problem statement rendered for display, shared with the Home preview.
+pub fn statement_lines(detail: &QuestionDetail) -> Vec<Line<'static>> {
+    if detail.is_paid_only && detail.content.is_none() {
+        vec![Line::from(Span::styled(
+            " Premium content — not available without authentication.",
+            Style::default().fg(theme().warning),
+        ))]
+    } else if let Some(ref html) = detail.content {
+        html_to_lines(html)
+    } else {
+        vec![Line::from(Span::styled(
+            " No content available.",
+            Style::default().fg(theme().dim),
+        ))]
+    }
+}
+
+impl DetailState {
+    pub fn new(detail: QuestionDetail) -> Self {
+        let content_lines = statement_lines(&detail);
+        let similar = detail.similar();
 
         Self {
             detail,
             content_lines,
             scroll_offset: 0,
             content_height: 0,
+            tab: DetailTab::Description,
+            editorial_lines: None,
+            solutions: None,
+            tab_scroll: [0; 3],
+            hints_shown: 0,
+            similar,
+            similar_state: TableState::default(),
+            activity: None,
+            activity_state: TableState::default(),
         }
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> DetailAction {
+        if let Some(ref activity) = self.activity {
+            let len = activity.len();
+            let selected = self.activity_state.selected();
+            match key.code {
+                KeyCode::Char('H') | KeyCode::Esc => self.activity = None,
+                KeyCode::Char('j') | KeyCode::Down => {
+                    if let Some(i) = selected {
+                        self.activity_state
+                            .select(Some((i + 1).min(len.saturating_sub(1))));
+                    }
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    if let Some(i) = selected {
+                        self.activity_state.select(Some(i.saturating_sub(1)));
+                    }
+                }
+                KeyCode::Char('q') => return DetailAction::Quit,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return DetailAction::Quit;
+                }
+                _ => {}
+            }
+            return DetailAction::None;
+        }
+        if self.tab == DetailTab::Solutions
+            && let Some(ref mut solutions) = self.solutions
+        {
+            let slug = self.detail.title_slug.clone();
+            match solutions.handle_key(key) {
+                SolutionsAction::Unhandled => {}
+                SolutionsAction::None => return DetailAction::None,
+                SolutionsAction::LoadPage(page) => {
+                    return DetailAction::LoadSolutions { slug, page };
+                }
+                SolutionsAction::OpenPost(id) => {
+                    self.scroll_offset = 0;
+                    return DetailAction::LoadSolutionPost { slug, id };
+                }
+                SolutionsAction::Copy { language, code } => {
+                    return DetailAction::CopyCode { language, code };
+                }
+            }
+        }
         match key.code {
             KeyCode::Char('b') | KeyCode::Esc => DetailAction::Back,
             KeyCode::Char('j') | KeyCode::Down => {
@@ -62,10 +172,80 @@ impl DetailState {
                 self.scroll(-(self.content_height as i32 / 2));
                 DetailAction::None
             }
-            KeyCode::Char('o') => DetailAction::Scaffold(self.detail.title_slug.clone()),
-            KeyCode::Char('a') => DetailAction::AddToList(self.detail.question_id.clone()),
+            KeyCode::Char('e') => {
+                self.switch_tab(DetailTab::Editorial);
+                if self.tab == DetailTab::Editorial && self.editorial_lines.is_none() {
+                    DetailAction::LoadEditorial(self.detail.title_slug.clone())
+                } else {
+                    DetailAction::None
+                }
+            }
+            KeyCode::Char('c') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.switch_tab(DetailTab::Solutions);
+                if self.tab == DetailTab::Solutions && self.solutions.is_none() {
+                    self.solutions = Some(SolutionsState::new());
+                    DetailAction::LoadSolutions {
+                        slug: self.detail.title_slug.clone(),
+                        page: 0,
+                    }
+                } else {
+                    DetailAction::None
+                }
+            }
+            KeyCode::Char('h') => {
+                if self.tab == DetailTab::Description && self.hints_shown < self.detail.hints.len()
+                {
+                    self.hints_shown += 1;
+                    // Bring the new hint into view; render clamps this
+                    self.scroll_offset = u16::MAX;
+                }
+                DetailAction::None
+            }
+            KeyCode::Tab | KeyCode::BackTab
+                if self.tab == DetailTab::Description && !self.similar.is_empty() =>
+            {
+                let len = self.similar.len();
+                let next = match (self.similar_state.selected(), key.code) {
+                    (None, KeyCode::Tab) => 0,
+                    (None, _) => len - 1,
+                    (Some(i), KeyCode::Tab) => (i + 1) % len,
+                    (Some(i), _) => (i + len - 1) % len,
+                };
+                self.similar_state.select(Some(next));
+                DetailAction::None
+            }
+            KeyCode::Enter => match self.similar_state.selected() {
+                Some(i) if self.tab == DetailTab::Description => self
+                    .similar
+                    .get(i)
+                    .map(|q| DetailAction::OpenSimilar(q.title_slug.clone()))
+                    .unwrap_or(DetailAction::None),
+                _ => DetailAction::None,
+            },
+            KeyCode::Char('o') => DetailAction::Scaffold,
+            KeyCode::Char('O') => DetailAction::Rescaffold,
+            KeyCode::Char('a') => DetailAction::AddToList(ListCandidate {
+                question_id: self.detail.question_id.clone(),
+                slug: self.detail.title_slug.clone(),
+            }),
+            KeyCode::Char('m') => DetailAction::WriteMarkdown,
+            KeyCode::Char('y') => DetailAction::CopySolution,
+            KeyCode::Char('*') => DetailAction::ToggleStar(ListCandidate {
+                question_id: self.detail.question_id.clone(),
+                slug: self.detail.title_slug.clone(),
+            }),
             KeyCode::Char('r') => DetailAction::RunCode,
             KeyCode::Char('s') => DetailAction::SubmitCode,
+            KeyCode::Char('t') => DetailAction::TestLocally,
+            KeyCode::Char('w') => DetailAction::ToggleWatch,
+            KeyCode::Char('H') => {
+                const ACTIVITY_SHOWN: usize = 100;
+                let activity = ActivityLog::for_problem(&self.detail.title_slug, ACTIVITY_SHOWN);
+                self.activity_state
+                    .select((!activity.is_empty()).then_some(0));
+                self.activity = Some(activity);
+                DetailAction::None
+            }
             KeyCode::Char('q') => DetailAction::Quit,
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 DetailAction::Quit
@@ -74,6 +254,65 @@ impl DetailState {
         }
     }
 
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll(WHEEL_STEP),
+            MouseEventKind::ScrollUp => self.scroll(-WHEEL_STEP),
+            _ => {}
+        }
+    }
+
+    /// Open `tab`, or go back to the description if it's already open,
+    /// keeping each tab's scroll position.
+    fn switch_tab(&mut self, tab: DetailTab) {
+        let target = if self.tab == tab {
+            DetailTab::Description
+        } else {
+            tab
+        };
+        self.tab_scroll[self.tab as usize] = self.scroll_offset;
+        self.scroll_offset = self.tab_scroll[target as usize];
+        self.tab = target;
+    }
+
+    /// Back to the statement after the editorial failed to load, so `e`
+    /// tries again.
+    pub fn close_editorial(&mut self) {
+        if self.tab == DetailTab::Editorial {
+            self.switch_tab(DetailTab::Editorial);
+        }
+    }
+
+    /// Revealed hints, dimmed so they don't read as part of the statement,
+    /// followed by how to get the next one.
+    fn hint_lines(&self) -> Vec<Line<'static>> {
+        let total = self.detail.hints.len();
+        if total == 0 {
+            return Vec::new();
+        }
+        let dim = Style::default().fg(theme().dim);
+        let mut lines = vec![Line::from("")];
+        for (i, hint) in self.detail.hints.iter().take(self.hints_shown).enumerate() {
+            lines.push(Line::from(Span::styled(
+                format!("Hint {}/{total}", i + 1),
+                dim.add_modifier(Modifier::BOLD),
+            )));
+            for line in html_to_lines(hint) {
+                let spans: Vec<Span<'static>> =
+                    line.spans.into_iter().map(|s| s.patch_style(dim)).collect();
+                lines.push(Line::from(spans));
+            }
+            lines.push(Line::from(""));
+        }
+        if self.hints_shown < total {
+            lines.push(Line::from(Span::styled(
+                format!("Press h for hint {} of {total}", self.hints_shown + 1),
+                dim.add_modifier(Modifier::ITALIC),
+            )));
+        }
+        lines
+    }
+
     fn scroll(&mut self, delta: i32) {
         let new_offset = self.scroll_offset as i32 + delta;
         self.scroll_offset = new_offset.max(0) as u16;
@@ -84,35 +323,126 @@ pub enum DetailAction {
     None,
     Back,
     Quit,
-    Scaffold(String),
-    AddToList(String),
+    Scaffold,
+    /// Scaffold again, rewriting the solution file
+    Rescaffold,
+    AddToList(ListCandidate),
+    /// Star or unstar this problem
+    ToggleStar(ListCandidate),
+    /// Save the problem as README.md in its project directory
+    WriteMarkdown,
+    /// Copy the solution as it would be submitted
+    CopySolution,
     RunCode,
     SubmitCode,
+    TestLocally,
+    /// Turn watch mode on or off
+    ToggleWatch,
+    LoadEditorial(String),
+    /// A page of community solutions for the problem
+    LoadSolutions {
+        slug: String,
+        page: u32,
+    },
+    /// A community solution's post, by topic id
+    LoadSolutionPost {
+        slug: String,
+        id: String,
+    },
+    /// Copy a code block from a community solution
+    CopyCode {
+        language: String,
+        code: String,
+    },
+    OpenSimilar(String),
 }
 
 pub fn render_detail(frame: &mut Frame, area: Rect, state: &mut DetailState) {
+    let similar_height = if state.similar.is_empty() || state.tab != DetailTab::Description {
+        0
+    } else {
+        (state.similar.len() as u16).min(SIMILAR_ROWS) + 1
+    };
+    let tag_lines = tag_chip_lines(&state.detail.topic_tags, area.width);
+    let title_height = 2 + tag_lines.len().max(1) as u16;
     let layout = Layout::vertical([
-        Constraint::Length(3), // title bar
-        Constraint::Min(3),   // content
-        Constraint::Length(1), // status bar
+        Constraint::Length(title_height),   // title bar
+        Constraint::Min(3),                 // content
+        Constraint::Length(similar_height), // similar questions
+        Constraint::Length(1),              // status bar
     ])
     .split(area);
 
     // Title bar
-    render_detail_title(frame, layout[0], state);
+    render_detail_title(frame, layout[0], state, tag_lines);
+
+    if let Some(ref activity) = state.activity {
+        render_activity(frame, layout[1], activity, &mut state.activity_state, false);
+        render_status_bar(
+            frame,
+            layout[3],
+            &[
+                ("j/k", "Navigate"),
+                ("H/Esc", "Back"),
+                ("q", "Quit"),
+                ("?", "Help"),
+            ],
+        );
+        return;
+    }
+
+    if state.tab == DetailTab::Solutions
+        && let Some(ref mut solutions) = state.solutions
+        && solutions.post.is_none()
+    {
+        solutions::render_solution_list(frame, layout[1], solutions);
+        render_status_bar(frame, layout[3], &solutions.hints().0);
+        return;
+    }
 
     // Content area
     state.content_height = layout[1].height;
 
-    let total_lines = state.content_lines.len() as u16;
+    let loading = |what: &str| {
+        [Line::from(Span::styled(
+            format!(" Loading {what}..."),
+            Style::default().fg(theme().warning),
+        ))]
+    };
+    let (loading_editorial, loading_post) = (loading("editorial"), loading("solution"));
+    let with_hints: Vec<Line<'static>>;
+    let lines: &[Line<'static>] = if state.tab == DetailTab::Editorial {
+        state
+            .editorial_lines
+            .as_deref()
+            .unwrap_or(&loading_editorial)
+    } else if state.tab == DetailTab::Solutions {
+        state
+            .solutions
+            .as_ref()
+            .and_then(|s| s.post.as_ref())
+            .and_then(|p| p.lines.as_deref())
+            .unwrap_or(&loading_post)
+    } else if state.detail.hints.is_empty() {
+        &state.content_lines
+    } else {
+        with_hints = state
+            .content_lines
+            .iter()
+            .cloned()
+            .chain(state.hint_lines())
+            .collect();
+        &with_hints
+    };
+
+    let total_lines = lines.len() as u16;
     let max_scroll = total_lines.saturating_sub(state.content_height);
     if state.scroll_offset > max_scroll {
         state.scroll_offset = max_scroll;
     }
 
     // Add left padding to each line
-    let padded_lines: Vec<Line> = state
-        .content_lines
+    let padded_lines: Vec<Line> = lines
         .iter()
         .map(|line| {
             let mut spans = vec![Span::raw("  ")];
@@ -143,94 +473,206 @@ pub fn render_detail(frame: &mut Frame, area: Rect, state: &mut DetailState) {
             1,
         );
         frame.render_widget(
-            Paragraph::new(indicator).style(Style::default().fg(Color::DarkGray)),
+            Paragraph::new(indicator).style(Style::default().fg(theme().dim)),
             ind_area,
         );
     }
 
+    if similar_height > 0 {
+        render_similar(frame, layout[2], state);
+    }
+
     // Status bar
-    render_status_bar(
-        frame,
-        layout[2],
-        &[
-            ("j/k", "Scroll"),
-            ("d/u", "Half page"),
-            ("o", "Open"),
-            ("a", "Add to List"),
-            ("r", "Run"),
-            ("s", "Submit"),
-            ("b/Esc", "Back"),
-            ("q", "Quit"),
-            ("?", "Help"),
-        ],
-    );
+    if state.tab == DetailTab::Solutions
+        && let Some(ref solutions) = state.solutions
+    {
+        let (hints, copy) = solutions.hints();
+        let mut hints: Vec<(&str, &str)> = hints;
+        if let Some(ref copy) = copy {
+            hints.insert(2, ("y", copy));
+        }
+        render_status_bar(frame, layout[3], &hints);
+        return;
+    }
+    let editorial_open = state.tab == DetailTab::Editorial;
+    let mut hints = vec![
+        ("j/k", "Scroll"),
+        ("d/u", "Half page"),
+        (
+            "e",
+            if editorial_open {
+                "Description"
+            } else {
+                "Editorial"
+            },
+        ),
+        ("c", "Solutions"),
+        ("o", "Open"),
+        ("a", "Add to List"),
+        ("r", "Run"),
+        ("s", "Submit"),
+        ("t", "Test"),
+        ("w", "Watch"),
+        ("H", "History"),
+        ("b/Esc", "Back"),
+        ("q", "Quit"),
+        ("?", "Help"),
+    ];
+    if !editorial_open && state.hints_shown < state.detail.hints.len() {
+        hints.insert(2, ("h", "Hint"));
+    }
+    if similar_height > 0 {
+        hints.insert(2, ("Tab", "Similar"));
+        if state.similar_state.selected().is_some() {
+            hints.insert(3, ("Enter", "Open"));
+        }
+    }
+    render_status_bar(frame, layout[3], &hints);
+}
+
+fn render_similar(frame: &mut Frame, area: Rect, state: &mut DetailState) {
+    let rows: Vec<Row> = state
+        .similar
+        .iter()
+        .map(|q| {
+            let diff_color = theme().difficulty(q.difficulty.as_str());
+            Row::new(vec![
+                Cell::from(q.title.clone()),
+                Cell::from(Span::styled(
+                    q.difficulty.clone(),
+                    Style::default().fg(diff_color),
+                )),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Min(20), Constraint::Length(8)])
+        .block(
+            Block::default()
+                .title(format!(" Similar Questions ({}) ", state.similar.len()))
+                .borders(Borders::TOP)
+                .border_style(Style::default().fg(theme().dim)),
+        )
+        .row_highlight_style(
+            Style::default()
+                .bg(theme().selection_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(format!("{} ", icons().pointer));
+
+    frame.render_stateful_widget(table, area, &mut state.similar_state);
+}
+
+/// Most rows of tag chips shown before the rest are cut off
+const MAX_TAG_ROWS: usize = 3;
+const TAG_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Blue,
+    Color::Green,
+    Color::Yellow,
+    Color::LightRed,
+];
+
+/// A stable color per tag, so the same topic looks the same everywhere.
+fn tag_color(slug: &str) -> Color {
+    let hash = slug
+        .bytes()
+        .fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+    TAG_COLORS[hash as usize % TAG_COLORS.len()]
 }
 
-fn render_detail_title(frame: &mut Frame, area: Rect, state: &DetailState) {
+/// Topic tags as colored chips, wrapped to `width` columns.
+fn tag_chip_lines(tags: &[TopicTag], width: u16) -> Vec<Line<'static>> {
+    let width = width as usize;
+    let mut lines = Vec::new();
+    let mut line = vec![Span::raw(" ")];
+    let mut used = 1;
+    for tag in tags {
+        let chip = format!(" {} ", tag.name);
+        let len = chip.chars().count();
+        if used > 1 && used + 1 + len > width {
+            lines.push(Line::from(std::mem::replace(
+                &mut line,
+                vec![Span::raw(" ")],
+            )));
+            used = 1;
+        }
+        if used > 1 {
+            line.push(Span::raw(" "));
+            used += 1;
+        }
+        line.push(Span::styled(
+            chip,
+            Style::default()
+                .fg(theme().on_accent)
+                .bg(tag_color(&tag.slug)),
+        ));
+        used += len;
+    }
+    if used > 1 {
+        lines.push(Line::from(line));
+    }
+    lines.truncate(MAX_TAG_ROWS);
+    lines
+}
+
+fn render_detail_title(frame: &mut Frame, area: Rect, state: &DetailState, tags: Vec<Line>) {
     let d = &state.detail;
-    let diff_color = match d.difficulty.as_str() {
-        "Easy" => Color::Green,
-        "Medium" => Color::Yellow,
-        "Hard" => Color::Red,
-        _ => Color::White,
-    };
+    let diff_color = theme().difficulty(d.difficulty.as_str());
 
     let mut title_spans = vec![
         Span::styled(
             format!(" {}. {} ", d.frontend_question_id, d.title),
             Style::default()
-                .fg(Color::White)
+                .fg(theme().text)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!("[{}]", d.difficulty),
-            Style::default()
-                .fg(diff_color)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(diff_color).add_modifier(Modifier::BOLD),
         ),
     ];
 
     match d.status.as_deref() {
         Some("ac") => title_spans.push(Span::styled(
-            " \u{2714} Solved",
-            Style::default().fg(Color::Green),
+            format!(" {} Solved", icons().check),
+            Style::default().fg(theme().success),
         )),
         Some("notac") => title_spans.push(Span::styled(
-            " \u{25cf} Attempted",
-            Style::default().fg(Color::Yellow),
+            format!(" {} Attempted", icons().dot),
+            Style::default().fg(theme().warning),
         )),
         _ => {}
     }
 
-    let title_line = Line::from(title_spans);
-
-    let tags: Vec<Span> = d
-        .topic_tags
-        .iter()
-        .enumerate()
-        .flat_map(|(i, t)| {
-            let mut spans = vec![Span::styled(
-                format!(" {} ", t.name),
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::DarkGray),
-            )];
-            if i < d.topic_tags.len() - 1 {
-                spans.push(Span::raw(" "));
-            }
-            spans
-        })
-        .collect();
+    // Which tab `e` and `c` switch between is showing
+    title_spans.push(Span::raw("  "));
+    let tabs = [
+        ("Description", DetailTab::Description),
+        ("Editorial", DetailTab::Editorial),
+        ("Solutions", DetailTab::Solutions),
+    ];
+    for (name, tab) in tabs {
+        let style = if state.tab == tab {
+            Style::default()
+                .fg(theme().accent)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            Style::default().fg(theme().dim)
+        };
+        title_spans.push(Span::raw(" "));
+        title_spans.push(Span::styled(name, style));
+    }
 
-    let mut tags_line_spans = vec![Span::styled(" ", Style::default())];
-    tags_line_spans.extend(tags);
+    let mut lines = vec![Line::from(title_spans)];
+    lines.extend(tags);
 
-    let title_block = Paragraph::new(vec![title_line, Line::from(tags_line_spans)])
-        .block(
-            Block::default()
-                .borders(Borders::BOTTOM)
-                .border_style(Style::default().fg(Color::DarkGray)),
-        );
+    let title_block = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(theme().dim)),
+    );
 
     frame.render_widget(title_block, area);
 }
@@ -0,0 +1,189 @@
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Matcher, Utf32Str};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Span;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use super::icons::icons;
+use super::theme::theme;
+
+/// Fuzzy matcher for the problem search, reused across keystrokes so the
+/// matcher's scratch memory is only allocated once.
+#[derive(Default)]
+pub struct FuzzyFilter {
+    matcher: Matcher,
+    pattern: Pattern,
+    buf: Vec<char>,
+}
+
+impl FuzzyFilter {
+    /// Whitespace-separated words are matched independently, so word order
+    /// in the query doesn't matter.
+    pub fn set_query(&mut self, query: &str) {
+        self.pattern
+            .reparse(query, CaseMatching::Ignore, Normalization::Smart);
+    }
+
+    /// Score `text` against the current query, returning the sorted char
+    /// positions that matched.
+    pub fn match_str(&mut self, text: &str) -> Option<(u32, Vec<u32>)> {
+        let mut positions = Vec::new();
+        let haystack = Utf32Str::new(text, &mut self.buf);
+        let score = self
+            .pattern
+            .indices(haystack, &mut self.matcher, &mut positions)?;
+        positions.sort_unstable();
+        positions.dedup();
+        Some((score, positions))
+    }
+}
+
+/// Split `text` into spans with the chars at `positions` highlighted,
+/// fitted to `width` columns. `suffix` is always kept visible; when the text
+/// has to be cut, an ellipsis takes its place and is itself highlighted if a
+/// match fell in the hidden part.
+pub fn highlight_spans<'a>(
+    text: &str,
+    positions: &[u32],
+    suffix: &str,
+    width: usize,
+    base: Style,
+) -> Vec<Span<'a>> {
+    let matched = base.fg(theme().warning).add_modifier(Modifier::BOLD);
+    let budget = width.saturating_sub(suffix.width());
+    let truncate = text.width() > budget;
+    let room = if truncate {
+        budget.saturating_sub(1)
+    } else {
+        budget
+    };
+
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    let mut used = 0;
+    let mut cut_at = None;
+
+    for (i, c) in text.chars().enumerate() {
+        let w = c.width().unwrap_or(0);
+        if truncate && used + w > room {
+            cut_at = Some(i as u32);
+            break;
+        }
+        used += w;
+
+        let is_match = positions.binary_search(&(i as u32)).is_ok();
+        if is_match != run_matched && !run.is_empty() {
+            let style = if run_matched { matched } else { base };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_matched = is_match;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        let style = if run_matched { matched } else { base };
+        spans.push(Span::styled(run, style));
+    }
+    if let Some(cut) = cut_at {
+        let hidden_match = positions.last().is_some_and(|&p| p >= cut);
+        spans.push(Span::styled(
+            icons().ellipsis,
+            if hidden_match { matched } else { base },
+        ));
+    }
+    if !suffix.is_empty() {
+        spans.push(Span::styled(suffix.to_string(), base));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(text, highlighted)` for each span.
+    fn runs(spans: &[Span]) -> Vec<(String, bool)> {
+        spans
+            .iter()
+            .map(|s| {
+                (
+                    s.content.to_string(),
+                    s.style.add_modifier.contains(Modifier::BOLD),
+                )
+            })
+            .collect()
+    }
+
+    fn run(text: &str, highlighted: bool) -> (String, bool) {
+        (text.to_string(), highlighted)
+    }
+
+    #[test]
+    fn matches_are_char_positions() {
+        let mut filter = FuzzyFilter::default();
+        filter.set_query("sum");
+        let (_, positions) = filter.match_str("Ünïcödé Sum").unwrap();
+        assert_eq!(positions, [8, 9, 10]);
+        assert!(filter.match_str("Ünïcödé").is_none());
+    }
+
+    #[test]
+    fn closer_matches_rank_higher() {
+        let mut filter = FuzzyFilter::default();
+        let mut score = |query: &str, title: &str| {
+            filter.set_query(query);
+            filter.match_str(title).map(|(score, _)| score)
+        };
+        let two_sum = score("twosum", "Two Sum").unwrap();
+        let spread = score("twosum", "Two Best Non-Overlapping Events Summed").unwrap();
+        let scattered = score("twosum", "Count Pairs Whose Sum is Less than Target").unwrap();
+        assert!(two_sum > spread && spread > scattered);
+        assert!(
+            score("sum", "Path Sum").unwrap() > score("sum", "Strictly Unique Matrix").unwrap()
+        );
+
+        // Words match independently, but letters within one stay in order
+        assert!(score("twosum", "Sum of Two Integers").is_none());
+        assert_eq!(
+            score("two sum", "Sum of Two Integers"),
+            score("two sum", "Two Sum")
+        );
+    }
+
+    #[test]
+    fn highlights_split_on_multi_byte_chars() {
+        let spans = highlight_spans("Café Crème", &[3, 6], "", 20, Style::default());
+        assert_eq!(
+            runs(&spans),
+            [
+                run("Caf", false),
+                run("é", true),
+                run(" C", false),
+                run("r", true),
+                run("ème", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn cut_text_keeps_the_suffix_and_flags_hidden_matches() {
+        let ellipsis = icons().ellipsis;
+        // Wide chars take two columns each
+        let spans = highlight_spans("漢字漢字", &[0], " ✓", 7, Style::default());
+        assert_eq!(
+            runs(&spans),
+            [
+                run("漢", true),
+                run("字", false),
+                run(ellipsis, false),
+                run(" ✓", false)
+            ]
+        );
+
+        let spans = highlight_spans("漢字漢字", &[3], "", 5, Style::default());
+        assert_eq!(runs(&spans), [run("漢字", false), run(ellipsis, true)]);
+
+        let spans = highlight_spans("Two Sum", &[], "", 7, Style::default());
+        assert_eq!(runs(&spans), [run("Two Sum", false)]);
+    }
+}
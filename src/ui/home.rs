@@ -1,21 +1,102 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
+    Frame,
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
-    Frame,
+    widgets::{Block, Borders, Cell, Clear, Gauge, Paragraph, Row, Table, TableState, Wrap},
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::api::types::{ProblemSummary, UserStats};
+use crate::history::{
+    ActivityEntry, ActivityLog, Journal, JournalEntry, RecentProblems, SearchHistory,
+};
 
+use super::activity::{format_age, render_activity};
+use super::fuzzy::{self, FuzzyFilter};
+use super::icons::{icons, spinner};
+use super::motion::{Motion, PendingKeys};
+use super::mouse::{self, ClickTracker, WHEEL_STEP};
 use super::status_bar::render_status_bar;
+use super::text_input::TextInput;
+use super::theme::theme;
+
+/// Which problems the filter popup lets through by how far along they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusFilter {
+    All,
+    Todo,
+    Attempted,
+    Solved,
+    /// Todo or attempted
+    Unsolved,
+}
+
+impl StatusFilter {
+    /// In the order the popup lists them.
+    pub const ALL: [StatusFilter; 5] = [
+        StatusFilter::All,
+        StatusFilter::Todo,
+        StatusFilter::Attempted,
+        StatusFilter::Solved,
+        StatusFilter::Unsolved,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            StatusFilter::All => "Any Status",
+            StatusFilter::Todo => "Todo",
+            StatusFilter::Attempted => "Attempted",
+            StatusFilter::Solved => "Solved",
+            StatusFilter::Unsolved => "Unsolved",
+        }
+    }
+
+    /// How the `status_filter` setting names it; `None` for no filter.
+    pub fn config_name(self) -> Option<&'static str> {
+        match self {
+            StatusFilter::All => None,
+            StatusFilter::Todo => Some("todo"),
+            StatusFilter::Attempted => Some("attempted"),
+            StatusFilter::Solved => Some("solved"),
+            StatusFilter::Unsolved => Some("unsolved"),
+        }
+    }
+
+    pub fn from_config(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| {
+            s.config_name()
+                .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        })
+    }
+
+    fn accepts(self, status: Option<&str>) -> bool {
+        match self {
+            StatusFilter::All => true,
+            StatusFilter::Todo => !matches!(status, Some("ac" | "notac")),
+            StatusFilter::Attempted => status == Some("notac"),
+            StatusFilter::Solved => status == Some("ac"),
+            StatusFilter::Unsolved => status != Some("ac"),
+        }
+    }
+}
+
+/// Popup rows before the status group.
+const FIRST_STATUS_ITEM: usize = 3;
 
 pub struct FilterState {
     pub easy: bool,
     pub medium: bool,
     pub hard: bool,
-    pub hide_solved: bool,
+    pub status: StatusFilter,
+    pub only_scaffolded: bool,
+    /// Only this difficulty (`"Hard"`) is downloaded, rather than the
+    /// whole list
+    pub server_difficulty: Option<&'static str>,
     pub active_item: usize,
     pub open: bool,
 }
@@ -26,103 +107,463 @@ impl FilterState {
             easy: true,
             medium: true,
             hard: true,
-            hide_solved: false,
+            status: StatusFilter::All,
+            only_scaffolded: false,
+            server_difficulty: None,
             active_item: 0,
             open: false,
         }
     }
 
     fn item_count(&self) -> usize {
-        4
+        FIRST_STATUS_ITEM + StatusFilter::ALL.len() + 3
+    }
+
+    /// The difficulty shown, when it's the only one.
+    fn single_difficulty(&self) -> Option<&'static str> {
+        match (self.easy, self.medium, self.hard) {
+            (true, false, false) => Some("Easy"),
+            (false, true, false) => Some("Medium"),
+            (false, false, true) => Some("Hard"),
+            _ => None,
+        }
+    }
+
+    /// Check every difficulty and stop narrowing the download to one.
+    pub fn show_all_difficulties(&mut self) {
+        self.easy = true;
+        self.medium = true;
+        self.hard = true;
+        self.server_difficulty = None;
+    }
+
+    /// Show, and download, only `difficulty` (any case), as the
+    /// `fetch_difficulty` setting asks.
+    pub fn restrict_download(&mut self, difficulty: &str) {
+        let Some(label) = ["Easy", "Medium", "Hard"]
+            .into_iter()
+            .find(|d| d.eq_ignore_ascii_case(difficulty))
+        else {
+            return;
+        };
+        self.easy = label == "Easy";
+        self.medium = label == "Medium";
+        self.hard = label == "Hard";
+        self.server_difficulty = Some(label);
+    }
+
+    fn accepts(&self, p: &ProblemSummary, scaffolded: &HashSet<String>) -> bool {
+        let diff_ok = match p.difficulty.as_str() {
+            "Easy" => self.easy,
+            "Medium" => self.medium,
+            "Hard" => self.hard,
+            _ => true,
+        };
+        diff_ok
+            && self.status.accepts(p.status.as_deref())
+            && (!self.only_scaffolded || scaffolded.contains(&p.title_slug))
     }
 
     pub fn summary(&self) -> Option<String> {
-        let all = self.easy && self.medium && self.hard && !self.hide_solved;
+        let all = self.easy
+            && self.medium
+            && self.hard
+            && self.status == StatusFilter::All
+            && !self.only_scaffolded;
         if all {
             return None;
         }
         let mut parts = Vec::new();
-        if self.easy { parts.push("E"); }
-        if self.medium { parts.push("M"); }
-        if self.hard { parts.push("H"); }
+        if self.easy {
+            parts.push("E");
+        }
+        if self.medium {
+            parts.push("M");
+        }
+        if self.hard {
+            parts.push("H");
+        }
         let mut s = parts.join("+");
-        if self.hide_solved {
-            s.push_str(" -Solved");
+        if self.status != StatusFilter::All {
+            s.push(' ');
+            s.push_str(self.status.label());
+        }
+        if self.only_scaffolded {
+            s.push_str(" +Local");
         }
         Some(format!("[{s}]"))
     }
 }
 
+/// A favorite list, a contest or a company, narrowing Home down to its
+/// problems.
+pub struct ListFilter {
+    pub name: String,
+    pub slugs: HashSet<String>,
+    pub kind: ListKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListKind {
+    /// Picked on the Lists screen
+    List,
+    /// Loaded with `C`
+    Contest,
+    /// Picked from the filter popup; Premium only
+    Company,
+}
+
+impl ListKind {
+    fn label(self) -> &'static str {
+        match self {
+            ListKind::List => "List",
+            ListKind::Contest => "Contest",
+            ListKind::Company => "Company",
+        }
+    }
+}
+
+/// A problem headed for the add-to-list popup. The slug is what list
+/// membership is matched on.
+pub struct ListCandidate {
+    pub question_id: String,
+    pub slug: String,
+}
+
 pub enum HomeFocus {
     Search,
     Table,
 }
 
+/// Statement of the selected problem, shown beside the table on wide terminals.
+pub struct Preview {
+    pub slug: String,
+    pub lines: Vec<Line<'static>>,
+}
+
+/// The `:` prompt for jumping to a problem by number or slug, also used
+/// to load a contest picked on the Contests screen.
+pub struct JumpPrompt {
+    pub input: String,
+    pub message: Option<String>,
+    pub loading: bool,
+    pub contest: bool,
+}
+
 pub struct HomeState {
     pub table_state: TableState,
+    /// Every problem received so far, ordered by problem number
     pub problems: Vec<ProblemSummary>,
     pub filtered_indices: Vec<usize>,
-    pub search_query: String,
+    /// Matched title char positions, parallel to `filtered_indices`
+    pub match_positions: Vec<Vec<u32>>,
+    pub search_query: TextInput,
     pub focus: HomeFocus,
     pub filter: FilterState,
+    /// Set from the Lists screen with `f`; cleared with `F`
+    pub list_filter: Option<ListFilter>,
+    /// The full problem list is still downloading
+    pub loading: bool,
     pub search_loading: bool,
-    pub search_total: i32,
+    pub total_problems: i32,
+    /// Rows received from the current list download, where a resume picks up
+    pub fetched: usize,
+    /// The list download stopped partway; `R` resumes it
+    pub fetch_error: Option<String>,
     pub error_message: Option<String>,
     pub spinner_frame: usize,
     pub user_stats: Option<UserStats>,
+    pub pending_keys: PendingKeys,
+    /// Visible table rows from the last render, for page motions
+    pub table_height: u16,
+    /// `j` on the last row goes to the first, and `k` on the first to the last
+    pub wrap_navigation: bool,
+    /// Where the problem or Recent table was last drawn, for mouse clicks
+    pub table_area: Rect,
+    clicks: ClickTracker,
+    pub jump: Option<JumpPrompt>,
+    /// `problems` came from the disk cache and a refresh may still replace it
+    pub from_cache: bool,
+    pub offline: bool,
+    /// The configured server-side filter, described for the title bar
+    pub server_filter: Option<String>,
+    /// `D` is showing the whole list instead
+    pub server_filter_lifted: bool,
+    /// Preview pane toggled on with `p`
+    pub preview_enabled: bool,
+    /// Terminal width below which the preview pane is hidden
+    pub preview_min_width: u16,
+    /// Whether the last render had room for the preview pane
+    pub preview_visible: bool,
+    pub preview: Option<Preview>,
+    /// Last random pick, so repeated presses don't land on it twice in a row
+    pub last_random: Option<String>,
+    pub history: SearchHistory,
+    pub recent: RecentProblems,
+    /// Slugs with a project directory in the workspace
+    pub scaffolded: HashSet<String>,
+    /// The Recent view (toggled with `'`) replaces the problem table
+    pub recent_open: bool,
+    pub recent_state: TableState,
+    /// The solve journal (toggled with `J`), read from disk when opened
+    pub journal_open: bool,
+    pub journal: Vec<JournalEntry>,
+    pub journal_state: TableState,
+    /// Runs and submissions across all problems (toggled with `A`)
+    pub activity_open: bool,
+    pub activity: Vec<ActivityEntry>,
+    pub activity_state: TableState,
+    /// Problems marked with Space for `a`, as indices into `problems`
+    pub selected: HashSet<usize>,
+    /// Index into `history` while recalling with Up/Down or Ctrl+R
+    pub history_pos: Option<usize>,
+    /// What was typed before history recall started
+    history_draft: String,
+    fuzzy: FuzzyFilter,
 }
 
 impl HomeState {
-    pub fn new() -> Self {
+    pub fn new(history: SearchHistory, recent: RecentProblems) -> Self {
         Self {
             table_state: TableState::default(),
             problems: Vec::new(),
             filtered_indices: Vec::new(),
-            search_query: String::new(),
+            match_positions: Vec::new(),
+            search_query: TextInput::default(),
             focus: HomeFocus::Search,
             filter: FilterState::new(),
+            list_filter: None,
+            loading: true,
             search_loading: false,
-            search_total: 0,
+            total_problems: 0,
+            fetched: 0,
+            fetch_error: None,
             error_message: None,
             spinner_frame: 0,
             user_stats: None,
+            pending_keys: PendingKeys::default(),
+            table_height: 0,
+            wrap_navigation: false,
+            table_area: Rect::default(),
+            clicks: ClickTracker::default(),
+            jump: None,
+            from_cache: false,
+            offline: false,
+            server_filter: None,
+            server_filter_lifted: false,
+            preview_enabled: true,
+            preview_min_width: 120,
+            preview_visible: false,
+            preview: None,
+            last_random: None,
+            history,
+            recent,
+            scaffolded: HashSet::new(),
+            recent_open: false,
+            recent_state: TableState::default(),
+            journal_open: false,
+            journal: Vec::new(),
+            journal_state: TableState::default(),
+            activity_open: false,
+            activity: Vec::new(),
+            activity_state: TableState::default(),
+            selected: HashSet::new(),
+            history_pos: None,
+            history_draft: String::new(),
+            fuzzy: FuzzyFilter::default(),
         }
     }
 
+    /// Recompute the visible rows from the difficulty/status filter and the
+    /// search query. While a query is active rows are ranked by fuzzy score,
+    /// with an exact problem-number match always first.
     pub fn rebuild_filter(&mut self) {
-        self.filtered_indices = self
-            .problems
-            .iter()
-            .enumerate()
-            .filter(|(_, p)| {
-                let diff_ok = match p.difficulty.as_str() {
-                    "Easy" => self.filter.easy,
-                    "Medium" => self.filter.medium,
-                    "Hard" => self.filter.hard,
-                    _ => true,
-                };
-                if !diff_ok {
-                    return false;
-                }
-                if self.filter.hide_solved && p.status.as_deref() == Some("ac") {
-                    return false;
-                }
-                true
-            })
-            .map(|(i, _)| i)
-            .collect();
+        let selected_slug = self.selected_problem().map(|p| p.title_slug.clone());
+        let query = self.search_query.value().trim();
+        self.fuzzy.set_query(query);
+
+        let mut matches: Vec<(u32, usize, Vec<u32>)> = Vec::new();
+        for (i, p) in self.problems.iter().enumerate() {
+            if !self.filter.accepts(p, &self.scaffolded)
+                || self
+                    .list_filter
+                    .as_ref()
+                    .is_some_and(|l| !l.slugs.contains(&p.title_slug))
+            {
+                continue;
+            }
+            if query.is_empty() {
+                matches.push((0, i, Vec::new()));
+            } else if p.frontend_question_id == query {
+                matches.push((u32::MAX, i, Vec::new()));
+            } else if let Some((score, positions)) = self.fuzzy.match_str(&p.title) {
+                matches.push((score, i, positions));
+            }
+        }
+        if !query.is_empty() {
+            // Stable, so equal scores stay in problem-number order
+            matches.sort_by_key(|m| std::cmp::Reverse(m.0));
+        }
+        self.filtered_indices = matches.iter().map(|m| m.1).collect();
+        self.match_positions = matches.into_iter().map(|m| m.2).collect();
 
+        let reselected = selected_slug.and_then(|slug| {
+            self.filtered_indices
+                .iter()
+                .position(|&i| self.problems[i].title_slug == slug)
+        });
         if self.filtered_indices.is_empty() {
             self.table_state.select(None);
+        } else if let Some(row) = reselected {
+            self.table_state.select(Some(row));
         } else if let Some(selected) = self.table_state.selected() {
             if selected >= self.filtered_indices.len() {
-                self.table_state.select(Some(self.filtered_indices.len() - 1));
+                self.table_state
+                    .select(Some(self.filtered_indices.len() - 1));
             }
         } else {
             self.table_state.select(Some(0));
         }
     }
 
+    pub fn set_list_filter(&mut self, list_filter: Option<ListFilter>) {
+        self.list_filter = list_filter;
+        self.rebuild_filter();
+    }
+
+    /// Add problems that aren't in the list yet, keeping it ordered by number.
+    pub fn merge_problems(&mut self, batch: Vec<ProblemSummary>) {
+        let selected_slug = self.selected_problem().map(|p| p.title_slug.clone());
+        let marked = self.marked_slugs();
+        let known: HashSet<String> = self.problems.iter().map(|p| p.title_slug.clone()).collect();
+        self.problems
+            .extend(batch.into_iter().filter(|p| !known.contains(&p.title_slug)));
+        self.sort_problems();
+        self.filtered_indices.clear();
+        self.rebuild_filter();
+        self.reselect(selected_slug);
+        self.remark(&marked);
+    }
+
+    /// Swap in a freshly fetched list, keeping the selected problem selected.
+    pub fn replace_problems(&mut self, problems: Vec<ProblemSummary>) {
+        let selected_slug = self.selected_problem().map(|p| p.title_slug.clone());
+        let marked = self.marked_slugs();
+        self.problems = problems;
+        self.from_cache = false;
+        self.sort_problems();
+        self.filtered_indices.clear();
+        self.rebuild_filter();
+        self.reselect(selected_slug);
+        self.remark(&marked);
+    }
+
+    /// Mark or unmark the problem under the cursor and move to the next row.
+    fn toggle_marked(&mut self) {
+        let Some(&idx) = self
+            .table_state
+            .selected()
+            .and_then(|row| self.filtered_indices.get(row))
+        else {
+            return;
+        };
+        if !self.selected.remove(&idx) {
+            self.selected.insert(idx);
+        }
+        self.move_selection(1);
+    }
+
+    /// The marked problems, in problem order.
+    pub fn marked_candidates(&self) -> Vec<ListCandidate> {
+        let mut indices: Vec<usize> = self.selected.iter().copied().collect();
+        indices.sort_unstable();
+        indices
+            .into_iter()
+            .filter_map(|i| self.problems.get(i))
+            .map(|p| ListCandidate {
+                question_id: p.frontend_question_id.clone(),
+                slug: p.title_slug.clone(),
+            })
+            .collect()
+    }
+
+    fn marked_slugs(&self) -> HashSet<String> {
+        self.selected
+            .iter()
+            .filter_map(|&i| self.problems.get(i))
+            .map(|p| p.title_slug.clone())
+            .collect()
+    }
+
+    /// Re-point the marks after `problems` was reordered.
+    fn remark(&mut self, slugs: &HashSet<String>) {
+        self.selected = self
+            .problems
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| slugs.contains(&p.title_slug))
+            .map(|(i, _)| i)
+            .collect();
+    }
+
+    fn reselect(&mut self, slug: Option<String>) {
+        if let Some(slug) = slug
+            && let Some(row) = self
+                .filtered_indices
+                .iter()
+                .position(|&i| self.problems[i].title_slug == slug)
+        {
+            self.table_state.select(Some(row));
+        }
+    }
+
+    fn sort_problems(&mut self) {
+        self.problems.sort_by(|a, b| {
+            let num =
+                |p: &ProblemSummary| p.frontend_question_id.parse::<u32>().unwrap_or(u32::MAX);
+            num(a)
+                .cmp(&num(b))
+                .then_with(|| a.frontend_question_id.cmp(&b.frontend_question_id))
+        });
+    }
+
+    /// Re-filter after the query changed and put the best match on top.
+    fn refresh_search(&mut self) -> HomeAction {
+        self.rebuild_filter();
+        if !self.filtered_indices.is_empty() {
+            self.table_state.select(Some(0));
+        }
+        // Until the full list has arrived, also ask the server so problems
+        // that haven't been downloaded yet still turn up.
+        if self.loading && !self.search_query.value().trim().is_empty() {
+            HomeAction::SearchFetch(self.search_query.value().trim().to_string())
+        } else {
+            HomeAction::None
+        }
+    }
+
+    /// Bracketed paste into the search bar, when it has focus.
+    pub fn paste(&mut self, text: &str) -> HomeAction {
+        if !matches!(self.focus, HomeFocus::Search) {
+            return HomeAction::None;
+        }
+        self.search_query.insert_str(text);
+        self.refresh_search()
+    }
+
+    pub fn set_scaffolded(&mut self, slugs: HashSet<String>) {
+        self.scaffolded = slugs;
+        if self.filter.only_scaffolded {
+            self.rebuild_filter();
+        }
+    }
+
+    pub fn mark_scaffolded(&mut self, slug: &str) {
+        if self.scaffolded.insert(slug.to_string()) && self.filter.only_scaffolded {
+            self.rebuild_filter();
+        }
+    }
+
     pub fn selected_problem(&self) -> Option<&ProblemSummary> {
         let selected = self.table_state.selected()?;
         let idx = *self.filtered_indices.get(selected)?;
@@ -130,103 +571,236 @@ impl HomeState {
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> HomeAction {
+        if self.jump.is_some() {
+            return self.handle_jump_key(key);
+        }
         if self.filter.open {
             return self.handle_filter_key(key);
         }
+        if self.recent_open {
+            return self.handle_recent_key(key);
+        }
+        if self.journal_open {
+            return self.handle_journal_key(key);
+        }
+        if self.activity_open {
+            return self.handle_activity_key(key);
+        }
         match self.focus {
             HomeFocus::Search => self.handle_search_key(key),
             HomeFocus::Table => self.handle_table_key(key),
         }
     }
 
+    /// Wheel moves the selection; clicking a row selects it and a
+    /// double-click opens it.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> HomeAction {
+        if self.jump.is_some() || self.filter.open || self.journal_open || self.activity_open {
+            return HomeAction::None;
+        }
+        match mouse.kind {
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                let step = if matches!(mouse.kind, MouseEventKind::ScrollDown) {
+                    WHEEL_STEP
+                } else {
+                    -WHEEL_STEP
+                };
+                if self.recent_open {
+                    let len = self.recent.entries.len() as i32;
+                    if len > 0 {
+                        let current = self.recent_state.selected().unwrap_or(0) as i32;
+                        self.recent_state
+                            .select(Some((current + step).clamp(0, len - 1) as usize));
+                    }
+                } else {
+                    self.move_selection(step);
+                }
+                HomeAction::None
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let (offset, len) = if self.recent_open {
+                    (self.recent_state.offset(), self.recent.entries.len())
+                } else {
+                    (self.table_state.offset(), self.filtered_indices.len())
+                };
+                let Some(row) =
+                    mouse::table_row_at(self.table_area, offset, mouse.column, mouse.row)
+                        .filter(|&r| r < len)
+                else {
+                    return HomeAction::None;
+                };
+                if self.recent_open {
+                    self.recent_state.select(Some(row));
+                } else {
+                    self.commit_search();
+                    self.focus = HomeFocus::Table;
+                    self.pending_keys.clear();
+                    self.table_state.select(Some(row));
+                }
+                if self.clicks.click(row) {
+                    self.handle_key(KeyEvent::from(KeyCode::Enter))
+                } else {
+                    HomeAction::None
+                }
+            }
+            _ => HomeAction::None,
+        }
+    }
+
     fn handle_search_key(&mut self, key: KeyEvent) -> HomeAction {
         match key.code {
-            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                HomeAction::Quit
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => HomeAction::Quit,
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.recall_matching()
             }
             KeyCode::Esc => {
+                self.history_pos = None;
                 if !self.search_query.is_empty() {
                     self.search_query.clear();
-                    self.problems.clear();
-                    self.filtered_indices.clear();
-                    self.search_total = 0;
-                    self.error_message = None;
+                    self.refresh_search();
                 }
                 HomeAction::None
             }
             KeyCode::Enter => {
-                if !self.search_query.is_empty() {
-                    if !self.filtered_indices.is_empty() {
-                        self.focus = HomeFocus::Table;
-                    }
-                    HomeAction::SearchFetch(self.search_query.clone())
-                } else {
-                    HomeAction::None
+                self.commit_search();
+                if !self.filtered_indices.is_empty() {
+                    self.focus = HomeFocus::Table;
+                    self.pending_keys.clear();
                 }
+                HomeAction::None
             }
+            KeyCode::Up => self.recall_older(),
+            KeyCode::Down if self.history_pos.is_some() => self.recall_newer(),
             KeyCode::Down | KeyCode::Tab => {
-                if !self.filtered_indices.is_empty() {
+                self.commit_search();
+                // An empty table still takes focus while there's an error,
+                // so `R` can retry
+                if !self.filtered_indices.is_empty() || self.error_message.is_some() {
                     self.focus = HomeFocus::Table;
-                    if self.table_state.selected().is_none() {
+                    self.pending_keys.clear();
+                    if self.table_state.selected().is_none() && !self.filtered_indices.is_empty() {
                         self.table_state.select(Some(0));
                     }
                 }
                 HomeAction::None
             }
-            KeyCode::Char(c) => {
-                self.search_query.push(c);
-                HomeAction::SearchFetch(self.search_query.clone())
-            }
-            KeyCode::Backspace => {
-                self.search_query.pop();
-                if self.search_query.is_empty() {
-                    self.problems.clear();
-                    self.filtered_indices.clear();
-                    self.search_total = 0;
-                    self.error_message = None;
-                    HomeAction::None
-                } else {
-                    HomeAction::SearchFetch(self.search_query.clone())
+            _ => {
+                let before = self.search_query.value().to_string();
+                if !self.search_query.handle_key(key) {
+                    return HomeAction::None;
                 }
+                if self.search_query.value() == before {
+                    // Only the cursor moved
+                    return HomeAction::None;
+                }
+                self.history_pos = None;
+                self.refresh_search()
             }
-            _ => HomeAction::None,
         }
     }
 
     fn handle_table_key(&mut self, key: KeyEvent) -> HomeAction {
-        match key.code {
-            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                HomeAction::Quit
+        let page = (self.table_height as i32).max(1);
+        let half_page = (page / 2).max(1);
+        match self.pending_keys.feed(key) {
+            Motion::Other => {}
+            Motion::Pending => return HomeAction::None,
+            Motion::Down(n) => {
+                self.step_selection(n as i32);
+                return HomeAction::None;
             }
-            KeyCode::Char('q') => HomeAction::Quit,
-            KeyCode::Char('j') | KeyCode::Down => {
-                self.move_selection(1);
-                HomeAction::None
+            Motion::Up(n) => {
+                self.step_selection(-(n as i32));
+                return HomeAction::None;
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                self.move_selection(-1);
-                HomeAction::None
+            Motion::HalfPageDown => {
+                self.move_selection(half_page);
+                return HomeAction::None;
             }
-            KeyCode::Char('g') => {
+            Motion::HalfPageUp => {
+                self.move_selection(-half_page);
+                return HomeAction::None;
+            }
+            Motion::PageDown => {
+                self.move_selection(page);
+                return HomeAction::None;
+            }
+            Motion::PageUp => {
+                self.move_selection(-page);
+                return HomeAction::None;
+            }
+            Motion::Top => {
                 if !self.filtered_indices.is_empty() {
                     self.table_state.select(Some(0));
                 }
-                HomeAction::None
+                return HomeAction::None;
             }
-            KeyCode::Char('G') => {
+            Motion::Bottom => {
                 if !self.filtered_indices.is_empty() {
-                    self.table_state.select(Some(self.filtered_indices.len() - 1));
+                    self.table_state
+                        .select(Some(self.filtered_indices.len() - 1));
                 }
+                return HomeAction::None;
+            }
+            Motion::Goto(n) => {
+                self.goto_problem(n);
+                return HomeAction::None;
+            }
+        }
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => HomeAction::Quit,
+            KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                HomeAction::WriteIndex
+            }
+            KeyCode::Char('q') => HomeAction::Quit,
+            KeyCode::Esc if !self.selected.is_empty() => {
+                self.selected.clear();
                 HomeAction::None
             }
             KeyCode::Char('/') | KeyCode::Esc => {
                 self.focus = HomeFocus::Search;
                 HomeAction::None
             }
+            KeyCode::Char(' ') => {
+                self.toggle_marked();
+                HomeAction::None
+            }
             KeyCode::Char('f') => {
                 self.filter.open = true;
                 HomeAction::None
             }
+            KeyCode::Char(':') => {
+                self.open_jump();
+                HomeAction::None
+            }
+            KeyCode::Char('C') => HomeAction::Contests,
+            KeyCode::Char('\'') => {
+                self.open_recent();
+                HomeAction::None
+            }
+            KeyCode::Char('J') => {
+                self.open_journal();
+                HomeAction::None
+            }
+            KeyCode::Char('A') => {
+                self.open_activity();
+                HomeAction::None
+            }
+            KeyCode::Char('F') if self.list_filter.is_some() => {
+                self.set_list_filter(None);
+                HomeAction::None
+            }
+            KeyCode::Char('x') => self.pick_random(),
+            KeyCode::Char('R') if self.error_message.is_some() => HomeAction::RetryFetch,
+            KeyCode::Char('R') if self.fetch_error.is_some() => HomeAction::ResumeFetch,
+            KeyCode::Char('D') if self.server_filter.is_some() && !self.loading => {
+                HomeAction::ToggleFetchFilter
+            }
+            KeyCode::Char('p') => {
+                self.preview_enabled = !self.preview_enabled;
+                HomeAction::None
+            }
             KeyCode::Enter => {
                 if let Some(problem) = self.selected_problem() {
                     HomeAction::OpenDetail(problem.title_slug.clone())
@@ -241,46 +815,566 @@ impl HomeState {
                     HomeAction::None
                 }
             }
+            KeyCode::Char('a') if !self.selected.is_empty() => {
+                HomeAction::AddToList(self.marked_candidates())
+            }
             KeyCode::Char('a') => {
                 if let Some(problem) = self.selected_problem() {
-                    HomeAction::AddToList(problem.frontend_question_id.clone())
+                    HomeAction::AddToList(vec![ListCandidate {
+                        question_id: problem.frontend_question_id.clone(),
+                        slug: problem.title_slug.clone(),
+                    }])
                 } else {
                     HomeAction::None
                 }
             }
+            KeyCode::Char('*') => match self.selected_problem() {
+                Some(problem) => HomeAction::ToggleStar(ListCandidate {
+                    question_id: problem.frontend_question_id.clone(),
+                    slug: problem.title_slug.clone(),
+                }),
+                None => HomeAction::None,
+            },
             KeyCode::Char('L') => HomeAction::Lists,
             KeyCode::Char('S') => HomeAction::Settings,
             _ => HomeAction::None,
         }
     }
 
-    fn handle_filter_key(&mut self, key: KeyEvent) -> HomeAction {
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
-                self.filter.active_item = (self.filter.active_item + 1) % self.filter.item_count();
-                HomeAction::None
-            }
-            KeyCode::Char('k') | KeyCode::Up => {
-                self.filter.active_item = (self.filter.active_item + self.filter.item_count() - 1)
-                    % self.filter.item_count();
-                HomeAction::None
-            }
-            KeyCode::Char(' ') => {
-                match self.filter.active_item {
-                    0 => self.filter.easy = !self.filter.easy,
-                    1 => self.filter.medium = !self.filter.medium,
-                    2 => self.filter.hard = !self.filter.hard,
-                    3 => self.filter.hide_solved = !self.filter.hide_solved,
-                    _ => {}
-                }
-                self.rebuild_filter();
-                HomeAction::None
+    fn handle_filter_key(&mut self, key: KeyEvent) -> HomeAction {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.filter.active_item = (self.filter.active_item + 1) % self.filter.item_count();
+                HomeAction::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.filter.active_item = (self.filter.active_item + self.filter.item_count() - 1)
+                    % self.filter.item_count();
+                HomeAction::None
+            }
+            KeyCode::Char(' ') => {
+                let statuses = FIRST_STATUS_ITEM..FIRST_STATUS_ITEM + StatusFilter::ALL.len();
+                match self.filter.active_item {
+                    0 => self.filter.easy = !self.filter.easy,
+                    1 => self.filter.medium = !self.filter.medium,
+                    2 => self.filter.hard = !self.filter.hard,
+                    i if statuses.contains(&i) => {
+                        let status = StatusFilter::ALL[i - FIRST_STATUS_ITEM];
+                        if self.filter.status == status {
+                            return HomeAction::None;
+                        }
+                        self.filter.status = status;
+                        self.rebuild_filter();
+                        return HomeAction::StatusFilter(status);
+                    }
+                    i if i == statuses.end => {
+                        self.filter.only_scaffolded = !self.filter.only_scaffolded
+                    }
+                    i if i == statuses.end + 1 => return self.toggle_server_difficulty(),
+                    i if i == statuses.end + 2 => return self.pick_company(),
+                    _ => {}
+                }
+                self.rebuild_filter();
+                // Showing another difficulty again means downloading it
+                match self.filter.server_difficulty {
+                    Some(d) if self.filter.single_difficulty() != Some(d) && !self.loading => {
+                        self.filter.server_difficulty = None;
+                        let reload = self.problems.iter().all(|p| p.difficulty == d);
+                        HomeAction::ServerFilter {
+                            difficulty: None,
+                            reload,
+                        }
+                    }
+                    _ => HomeAction::None,
+                }
+            }
+            KeyCode::Enter if self.filter.active_item == self.filter.item_count() - 1 => {
+                self.pick_company()
+            }
+            KeyCode::Enter | KeyCode::Esc | KeyCode::Char('f') => {
+                self.filter.open = false;
+                HomeAction::None
+            }
+            _ => HomeAction::None,
+        }
+    }
+
+    /// The popup's last row: close it and let the app open the company
+    /// picker.
+    fn pick_company(&mut self) -> HomeAction {
+        self.filter.open = false;
+        HomeAction::PickCompany
+    }
+
+    /// Space on "Download only": switch between downloading just the one
+    /// checked difficulty and the whole list. Turning it on never needs a
+    /// download; the rows already loaded are filtered as before.
+    fn toggle_server_difficulty(&mut self) -> HomeAction {
+        if self.loading {
+            return HomeAction::None;
+        }
+        match self.filter.server_difficulty.take() {
+            Some(d) => {
+                let reload = self.problems.iter().all(|p| p.difficulty == d);
+                HomeAction::ServerFilter {
+                    difficulty: None,
+                    reload,
+                }
+            }
+            None => match self.filter.single_difficulty() {
+                Some(d) => {
+                    self.filter.server_difficulty = Some(d);
+                    HomeAction::ServerFilter {
+                        difficulty: Some(d),
+                        reload: false,
+                    }
+                }
+                None => HomeAction::None,
+            },
+        }
+    }
+
+    /// Open a random problem from the visible rows. With no filter or
+    /// search narrowing the list, let LeetCode pick instead.
+    fn pick_random(&mut self) -> HomeAction {
+        let narrowed = self.server_filter.is_some() && !self.server_filter_lifted;
+        if self.filter.summary().is_none()
+            && !narrowed
+            && self.search_query.value().trim().is_empty()
+            && !self.offline
+        {
+            return HomeAction::RandomRemote(self.last_random.clone());
+        }
+
+        let candidates: Vec<usize> = self
+            .filtered_indices
+            .iter()
+            .copied()
+            .filter(|&i| {
+                self.filtered_indices.len() == 1
+                    || self.last_random.as_deref() != Some(&self.problems[i].title_slug)
+            })
+            .collect();
+        if candidates.is_empty() {
+            return HomeAction::None;
+        }
+        let slug = self.problems[candidates[random_index(candidates.len())]]
+            .title_slug
+            .clone();
+        self.last_random = Some(slug.clone());
+        HomeAction::OpenRandom(slug)
+    }
+
+    fn commit_search(&mut self) {
+        self.history_pos = None;
+        self.history.push(self.search_query.value());
+    }
+
+    fn recall(&mut self, pos: Option<usize>) -> HomeAction {
+        if self.history_pos.is_none() {
+            self.history_draft = self.search_query.value().to_string();
+        }
+        self.history_pos = pos;
+        let query = match pos {
+            Some(i) => self.history.entries[i].clone(),
+            None => self.history_draft.clone(),
+        };
+        self.search_query.set(query);
+        self.refresh_search()
+    }
+
+    fn recall_older(&mut self) -> HomeAction {
+        match self.history_pos.unwrap_or(self.history.entries.len()) {
+            0 => HomeAction::None,
+            pos => self.recall(Some(pos - 1)),
+        }
+    }
+
+    fn recall_newer(&mut self) -> HomeAction {
+        match self.history_pos {
+            Some(pos) if pos + 1 < self.history.entries.len() => self.recall(Some(pos + 1)),
+            Some(_) => self.recall(None),
+            None => HomeAction::None,
+        }
+    }
+
+    /// Ctrl+R: step back to the next older entry containing what was typed.
+    fn recall_matching(&mut self) -> HomeAction {
+        let needle = match self.history_pos {
+            Some(_) => self.history_draft.clone(),
+            None => self.search_query.value().to_string(),
+        };
+        let before = self.history_pos.unwrap_or(self.history.entries.len());
+        match self.history.find_older(needle.trim(), before) {
+            Some(pos) => self.recall(Some(pos)),
+            None => HomeAction::None,
+        }
+    }
+
+    fn open_recent(&mut self) {
+        self.recent_open = true;
+        self.recent_state
+            .select((!self.recent.entries.is_empty()).then_some(0));
+    }
+
+    fn handle_recent_key(&mut self, key: KeyEvent) -> HomeAction {
+        let len = self.recent.entries.len();
+        let selected = self.recent_state.selected();
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => HomeAction::Quit,
+            KeyCode::Char('q') => HomeAction::Quit,
+            KeyCode::Char('\'') | KeyCode::Esc => {
+                self.recent_open = false;
+                HomeAction::None
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                if let Some(i) = selected {
+                    self.recent_state
+                        .select(Some((i + 1).min(len.saturating_sub(1))));
+                }
+                HomeAction::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                if let Some(i) = selected {
+                    self.recent_state.select(Some(i.saturating_sub(1)));
+                }
+                HomeAction::None
+            }
+            KeyCode::Char('g') => {
+                if len > 0 {
+                    self.recent_state.select(Some(0));
+                }
+                HomeAction::None
+            }
+            KeyCode::Char('G') => {
+                if len > 0 {
+                    self.recent_state.select(Some(len - 1));
+                }
+                HomeAction::None
+            }
+            KeyCode::Enter => match selected.and_then(|i| self.recent.entries.get(i)) {
+                Some(entry) => HomeAction::OpenDetail(entry.slug.clone()),
+                None => HomeAction::None,
+            },
+            KeyCode::Char('d') => {
+                if let Some(i) = selected {
+                    self.recent.remove(i);
+                    let len = self.recent.entries.len();
+                    self.recent_state.select((len > 0).then(|| i.min(len - 1)));
+                }
+                HomeAction::None
+            }
+            KeyCode::Char('C') => {
+                self.recent.clear();
+                self.recent_state.select(None);
+                HomeAction::None
+            }
+            _ => HomeAction::None,
+        }
+    }
+
+    fn open_journal(&mut self) {
+        const JOURNAL_SHOWN: usize = 200;
+        self.journal = Journal::recent(JOURNAL_SHOWN);
+        self.journal_open = true;
+        self.journal_state
+            .select((!self.journal.is_empty()).then_some(0));
+    }
+
+    fn handle_journal_key(&mut self, key: KeyEvent) -> HomeAction {
+        let len = self.journal.len();
+        let selected = self.journal_state.selected();
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => HomeAction::Quit,
+            KeyCode::Char('q') => HomeAction::Quit,
+            KeyCode::Char('J') | KeyCode::Esc => {
+                self.journal_open = false;
+                HomeAction::None
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                if let Some(i) = selected {
+                    self.journal_state
+                        .select(Some((i + 1).min(len.saturating_sub(1))));
+                }
+                HomeAction::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                if let Some(i) = selected {
+                    self.journal_state.select(Some(i.saturating_sub(1)));
+                }
+                HomeAction::None
+            }
+            KeyCode::Char('g') => {
+                if len > 0 {
+                    self.journal_state.select(Some(0));
+                }
+                HomeAction::None
+            }
+            KeyCode::Char('G') => {
+                if len > 0 {
+                    self.journal_state.select(Some(len - 1));
+                }
+                HomeAction::None
+            }
+            KeyCode::Enter => match selected.and_then(|i| self.journal.get(i)) {
+                Some(entry) => HomeAction::OpenDetail(entry.slug.clone()),
+                None => HomeAction::None,
+            },
+            _ => HomeAction::None,
+        }
+    }
+
+    fn open_activity(&mut self) {
+        const ACTIVITY_SHOWN: usize = 200;
+        self.activity = ActivityLog::recent(ACTIVITY_SHOWN);
+        self.activity_open = true;
+        self.activity_state
+            .select((!self.activity.is_empty()).then_some(0));
+    }
+
+    fn handle_activity_key(&mut self, key: KeyEvent) -> HomeAction {
+        let len = self.activity.len();
+        let selected = self.activity_state.selected();
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => HomeAction::Quit,
+            KeyCode::Char('q') => HomeAction::Quit,
+            KeyCode::Char('A') | KeyCode::Esc => {
+                self.activity_open = false;
+                HomeAction::None
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                if let Some(i) = selected {
+                    self.activity_state
+                        .select(Some((i + 1).min(len.saturating_sub(1))));
+                }
+                HomeAction::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                if let Some(i) = selected {
+                    self.activity_state.select(Some(i.saturating_sub(1)));
+                }
+                HomeAction::None
+            }
+            KeyCode::Char('g') => {
+                if len > 0 {
+                    self.activity_state.select(Some(0));
+                }
+                HomeAction::None
+            }
+            KeyCode::Char('G') => {
+                if len > 0 {
+                    self.activity_state.select(Some(len - 1));
+                }
+                HomeAction::None
+            }
+            KeyCode::Enter => match selected.and_then(|i| self.activity.get(i)) {
+                Some(entry) => HomeAction::OpenDetail(entry.slug.clone()),
+                None => HomeAction::None,
+            },
+            _ => HomeAction::None,
+        }
+    }
+
+    fn open_jump(&mut self) {
+        self.jump = Some(JumpPrompt {
+            input: String::new(),
+            message: None,
+            loading: false,
+            contest: false,
+        });
+    }
+
+    fn handle_jump_key(&mut self, key: KeyEvent) -> HomeAction {
+        let Some(jump) = self.jump.as_mut() else {
+            return HomeAction::None;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.jump = None;
+                HomeAction::None
+            }
+            KeyCode::Enter => {
+                let query = jump.input.trim().to_string();
+                if query.is_empty() || jump.loading {
+                    return HomeAction::None;
+                }
+                jump.message = None;
+                if jump.contest {
+                    jump.loading = true;
+                    return HomeAction::LoadContest(contest_slug(&query));
+                }
+                if query.chars().all(|c| c.is_ascii_digit()) {
+                    self.jump_to_id(&query)
+                } else {
+                    jump.loading = true;
+                    HomeAction::JumpToSlug(query.to_lowercase())
+                }
+            }
+            KeyCode::Char(c) if !jump.loading => {
+                jump.input.push(c);
+                jump.message = None;
+                HomeAction::None
+            }
+            KeyCode::Backspace if !jump.loading => {
+                jump.input.pop();
+                jump.message = None;
+                HomeAction::None
+            }
+            _ => HomeAction::None,
+        }
+    }
+
+    /// Select problem `id` if it's in the table, clearing the filters
+    /// first when they hide it; otherwise ask the app to look it up
+    /// remotely.
+    fn jump_to_id(&mut self, id: &str) -> HomeAction {
+        let known = self.problems.iter().any(|p| p.frontend_question_id == id);
+        if known && self.visible_row(id).is_none() {
+            self.clear_filters();
+        }
+        if let Some(row) = self.visible_row(id) {
+            self.table_state.select(Some(row));
+            self.focus = HomeFocus::Table;
+            self.jump = None;
+            return HomeAction::None;
+        }
+
+        if let Some(jump) = self.jump.as_mut() {
+            if known {
+                // Only when it isn't a difficulty that gets downloaded
+                jump.message = Some("Hidden by current filter".to_string());
+                return HomeAction::None;
+            }
+            jump.loading = true;
+        }
+        HomeAction::JumpToId(id.to_string())
+    }
+
+    fn visible_row(&self, id: &str) -> Option<usize> {
+        self.filtered_indices
+            .iter()
+            .position(|&i| self.problems[i].frontend_question_id == id)
+    }
+
+    /// Show every row: all difficulties that are downloaded, and no status,
+    /// list or search narrowing.
+    fn clear_filters(&mut self) {
+        let server_difficulty = self.filter.server_difficulty;
+        self.filter = FilterState {
+            active_item: self.filter.active_item,
+            ..FilterState::new()
+        };
+        if let Some(difficulty) = server_difficulty {
+            self.filter.restrict_download(difficulty);
+        }
+        self.list_filter = None;
+        self.search_query.clear();
+        self.rebuild_filter();
+    }
+
+    /// Apply a remote lookup for `id`: add the problem to the table and
+    /// select it, or report the miss inline in the prompt.
+    pub fn finish_jump(&mut self, id: &str, found: Option<ProblemSummary>) {
+        let Some(problem) = found else {
+            self.jump_missed();
+            return;
+        };
+
+        if !self.problems.iter().any(|p| p.frontend_question_id == id) {
+            self.merge_problems(vec![problem]);
+        }
+        if let Some(jump) = self.jump.as_mut() {
+            jump.loading = false;
+        }
+        self.jump_to_id(id);
+    }
+
+    pub fn jump_missed(&mut self) {
+        self.jump_failed("No such problem".to_string());
+    }
+
+    /// Show a contest loading in the prompt, where it can be edited and
+    /// tried again if the load fails.
+    pub fn open_contest(&mut self, slug: &str) {
+        self.jump = Some(JumpPrompt {
+            input: slug.to_string(),
+            message: None,
+            loading: true,
+            contest: true,
+        });
+    }
+
+    /// Stop the prompt's spinner and show why the lookup failed.
+    pub fn jump_failed(&mut self, message: String) {
+        if let Some(jump) = self.jump.as_mut() {
+            jump.loading = false;
+            jump.message = Some(message);
+        }
+    }
+
+    /// Narrow the table to a contest's problems, adding any the problem
+    /// list doesn't have yet.
+    pub fn show_contest(
+        &mut self,
+        title: String,
+        slugs: HashSet<String>,
+        missing: Vec<ProblemSummary>,
+    ) {
+        self.merge_problems(missing);
+        self.jump = None;
+        self.focus = HomeFocus::Table;
+        self.set_list_filter(Some(ListFilter {
+            name: title,
+            slugs,
+            kind: ListKind::Contest,
+        }));
+    }
+
+    /// Narrow the table to the problems a company asked, adding any the
+    /// problem list doesn't have yet. `name` says which period too.
+    pub fn show_company(
+        &mut self,
+        name: String,
+        slugs: HashSet<String>,
+        missing: Vec<ProblemSummary>,
+    ) {
+        self.merge_problems(missing);
+        self.focus = HomeFocus::Table;
+        self.set_list_filter(Some(ListFilter {
+            name,
+            slugs,
+            kind: ListKind::Company,
+        }));
+        self.table_state
+            .select((!self.filtered_indices.is_empty()).then_some(0));
+    }
+
+    /// `{n}G`: jump to problem number `n` if it's in the table, else to row `n`.
+    fn goto_problem(&mut self, n: usize) {
+        if self.filtered_indices.is_empty() {
+            return;
+        }
+        let id = n.to_string();
+        let row = self
+            .filtered_indices
+            .iter()
+            .position(|&i| self.problems[i].frontend_question_id == id)
+            .unwrap_or_else(|| n.saturating_sub(1).min(self.filtered_indices.len() - 1));
+        self.table_state.select(Some(row));
+    }
+
+    /// `j`/`k`: `move_selection`, except that with `wrap_navigation` a
+    /// step past either end comes back in at the other.
+    fn step_selection(&mut self, delta: i32) {
+        let last = self.filtered_indices.len().saturating_sub(1);
+        match self.table_state.selected() {
+            Some(row) if self.wrap_navigation && delta > 0 && row == last => {
+                self.table_state.select(Some(0));
             }
-            KeyCode::Enter | KeyCode::Esc | KeyCode::Char('f') => {
-                self.filter.open = false;
-                HomeAction::None
+            Some(0) if self.wrap_navigation && delta < 0 && last > 0 => {
+                self.table_state.select(Some(last));
             }
-            _ => HomeAction::None,
+            _ => self.move_selection(delta),
         }
     }
 
@@ -301,7 +1395,38 @@ pub enum HomeAction {
     OpenDetail(String),
     Scaffold(String),
     SearchFetch(String),
-    AddToList(String),
+    OpenRandom(String),
+    /// Ask LeetCode for a random problem, avoiding the given slug
+    RandomRemote(Option<String>),
+    JumpToId(String),
+    JumpToSlug(String),
+    /// Fetch a contest by slug and narrow the table to its problems
+    LoadContest(String),
+    /// Browse upcoming and past contests
+    Contests,
+    /// The marked problems, or just the one under the cursor
+    AddToList(Vec<ListCandidate>),
+    /// Star or unstar the problem under the cursor
+    ToggleStar(ListCandidate),
+    /// Continue a problem list download that failed partway
+    ResumeFetch,
+    /// Clear the error and download the problem list again
+    RetryFetch,
+    /// Download only this difficulty from now on, or everything again.
+    /// `reload` when the rows on screen don't cover the new choice
+    ServerFilter {
+        difficulty: Option<&'static str>,
+        reload: bool,
+    },
+    /// Rebuild the table of scaffolded problems in the workspace README
+    WriteIndex,
+    /// The status filter was changed in the popup, to be remembered
+    StatusFilter(StatusFilter),
+    /// Open the company picker from the filter popup
+    PickCompany,
+    /// Download the whole list instead of the configured server-side
+    /// filter's part of it, or go back
+    ToggleFetchFilter,
     Settings,
     Lists,
 }
@@ -309,13 +1434,19 @@ pub enum HomeAction {
 pub fn render_home(frame: &mut Frame, area: Rect, state: &mut HomeState) {
     let has_stats = state.user_stats.is_some();
     let stats_height: u16 = if has_stats { 2 } else { 0 };
+    let progress_height: u16 = if state.loading || state.fetch_error.is_some() {
+        1
+    } else {
+        0
+    };
 
     let layout = Layout::vertical([
-        Constraint::Length(1),            // title bar
-        Constraint::Length(stats_height), // stats header
-        Constraint::Length(1),            // search bar
-        Constraint::Min(3),              // table / empty state
-        Constraint::Length(1),           // status bar
+        Constraint::Length(1),               // title bar
+        Constraint::Length(stats_height),    // stats header
+        Constraint::Length(1),               // search bar
+        Constraint::Length(progress_height), // list download progress
+        Constraint::Min(3),                  // table / empty state
+        Constraint::Length(1),               // status bar
     ])
     .split(area);
 
@@ -325,35 +1456,87 @@ pub fn render_home(frame: &mut Frame, area: Rect, state: &mut HomeState) {
         render_stats_header(frame, layout[1], stats);
     }
 
-    render_search_bar(frame, layout[2], state);
+    if let Some(ref jump) = state.jump {
+        render_jump_prompt(frame, layout[2], jump, state.spinner_frame);
+    } else {
+        render_search_bar(frame, layout[2], state);
+    }
+
+    if progress_height > 0 {
+        render_fetch_progress(frame, layout[3], state);
+    }
 
-    if state.search_loading && state.problems.is_empty() {
-        let spinner = ["\u{280b}", "\u{2819}", "\u{2839}", "\u{2838}", "\u{283c}", "\u{2834}", "\u{2826}", "\u{2827}", "\u{2807}", "\u{280f}"];
-        let s = spinner[state.spinner_frame % spinner.len()];
-        let loading = Paragraph::new(format!("  {s} Searching..."))
-            .style(Style::default().fg(Color::Yellow));
-        frame.render_widget(loading, layout[3]);
+    if state.recent_open {
+        state.preview_visible = false;
+        render_recent(frame, layout[4], state);
+    } else if state.journal_open {
+        state.preview_visible = false;
+        render_journal(frame, layout[4], state);
+    } else if state.activity_open {
+        state.preview_visible = false;
+        render_activity(
+            frame,
+            layout[4],
+            &state.activity,
+            &mut state.activity_state,
+            true,
+        );
+    } else if state.loading && state.problems.is_empty() {
+        // Nothing to browse until the first batch; the gauge says why
     } else if let Some(ref err) = state.error_message {
-        let error = Paragraph::new(format!("  Error: {err}"))
-            .style(Style::default().fg(Color::Red));
-        frame.render_widget(error, layout[3]);
-    } else if state.problems.is_empty() {
+        let error =
+            Paragraph::new(format!("  Error: {err}")).style(Style::default().fg(theme().error));
+        frame.render_widget(error, layout[4]);
+    } else if state.filtered_indices.is_empty() {
         let msg = if state.search_query.is_empty() {
-            "  Type to search problems..."
+            "  No problems match the current filter"
         } else {
             "  No results found"
         };
-        let p = Paragraph::new(msg)
-            .style(Style::default().fg(Color::DarkGray));
-        frame.render_widget(p, layout[3]);
+        let p = Paragraph::new(msg).style(Style::default().fg(theme().dim));
+        frame.render_widget(p, layout[4]);
     } else {
-        render_table(frame, layout[3], state);
+        state.preview_visible = state.preview_enabled && area.width >= state.preview_min_width;
+        if state.preview_visible {
+            let panes =
+                Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                    .split(layout[4]);
+            render_table(frame, panes[0], state);
+            render_preview(frame, panes[1], state);
+        } else {
+            render_table(frame, layout[4], state);
+        }
     }
 
-    let hints = match state.focus {
+    let mut hints = match state.focus {
+        _ if state.jump.as_ref().is_some_and(|j| j.contest) => {
+            vec![("Enter", "Load Contest"), ("Esc", "Cancel")]
+        }
+        _ if state.jump.is_some() => vec![("Enter", "Jump"), ("Esc", "Cancel")],
+        _ if state.recent_open => vec![
+            ("j/k", "Navigate"),
+            ("Enter", "View"),
+            ("d", "Remove"),
+            ("C", "Clear All"),
+            ("'/Esc", "Back"),
+            ("?", "Help"),
+        ],
+        _ if state.journal_open => vec![
+            ("j/k", "Navigate"),
+            ("Enter", "View"),
+            ("J/Esc", "Back"),
+            ("?", "Help"),
+        ],
+        _ if state.activity_open => vec![
+            ("j/k", "Navigate"),
+            ("Enter", "View"),
+            ("A/Esc", "Back"),
+            ("?", "Help"),
+        ],
         HomeFocus::Search => vec![
             ("Enter", "Search"),
             ("Tab/\u{2193}", "Table"),
+            ("\u{2191}", "History"),
             ("Esc", "Clear"),
             ("?", "Help"),
         ],
@@ -363,6 +1546,12 @@ pub fn render_home(frame: &mut Frame, area: Rect, state: &mut HomeState) {
             ("o", "Open"),
             ("a", "Add to List"),
             ("/", "Search"),
+            (":", "Jump"),
+            ("'", "Recent"),
+            ("J", "Journal"),
+            ("A", "Activity"),
+            ("x", "Random"),
+            ("p", "Preview"),
             ("f", "Filter"),
             ("L", "Lists"),
             ("S", "Settings"),
@@ -370,35 +1559,154 @@ pub fn render_home(frame: &mut Frame, area: Rect, state: &mut HomeState) {
             ("?", "Help"),
         ],
     };
-    render_status_bar(frame, layout[4], &hints);
+    if state.jump.is_none() && !state.recent_open && !state.journal_open && !state.activity_open {
+        if state.error_message.is_some() && matches!(state.focus, HomeFocus::Table) {
+            hints.insert(0, ("R", "Retry"));
+        } else if state.fetch_error.is_some() && matches!(state.focus, HomeFocus::Table) {
+            hints.insert(0, ("R", "Resume"));
+        }
+        if state.list_filter.is_some() && matches!(state.focus, HomeFocus::Table) {
+            hints.insert(0, ("F", "All Problems"));
+        }
+        if matches!(state.focus, HomeFocus::Table) {
+            if state.selected.is_empty() {
+                hints.insert(2, ("Space", "Select"));
+            } else {
+                hints.insert(0, ("Esc", "Unselect All"));
+                hints.insert(0, ("a", "Add Selected"));
+            }
+        }
+    }
+    render_status_bar(frame, layout[5], &hints);
 
     if state.filter.open {
-        render_filter_popup(frame, area, &state.filter);
+        render_filter_popup(frame, area, &state.filter, state.loading);
+    }
+}
+
+/// Gauge for the list download, or where it stopped if it failed.
+fn render_fetch_progress(frame: &mut Frame, area: Rect, state: &HomeState) {
+    let total = state.total_problems.max(0) as usize;
+    if let Some(ref err) = state.fetch_error {
+        let msg = format!(
+            "  Loaded {} of {total} problems, then: {err}  (R to resume)",
+            state.fetched
+        );
+        frame.render_widget(
+            Paragraph::new(msg).style(Style::default().fg(theme().error).bg(theme().bar_bg)),
+            area,
+        );
+        return;
     }
+    let label = if total == 0 {
+        let s = spinner(state.spinner_frame);
+        format!("{s} Loading problems...")
+    } else {
+        format!("{} / {total} problems", state.fetched)
+    };
+    let ratio = if total == 0 {
+        0.0
+    } else {
+        (state.fetched as f64 / total as f64).min(1.0)
+    };
+    let gauge = Gauge::default()
+        .ratio(ratio)
+        .label(Span::styled(label, Style::default().fg(theme().text)))
+        .gauge_style(Style::default().fg(theme().warning).bg(theme().bar_bg));
+    frame.render_widget(gauge, area);
 }
 
 fn render_search_bar(frame: &mut Frame, area: Rect, state: &HomeState) {
     let is_focused = matches!(state.focus, HomeFocus::Search);
-    let cursor = if is_focused { "\u{258e}" } else { "" };
     let icon_style = if is_focused {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(theme().accent)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme().dim)
     };
-    let line = Line::from(vec![
-        Span::styled("  / ", icon_style),
-        Span::styled(&state.search_query, Style::default().fg(Color::White)),
-        Span::styled(cursor, Style::default().fg(Color::Cyan)),
-    ]);
-    let bar = Paragraph::new(line).style(Style::default().bg(Color::Black));
+    let history = state
+        .history_pos
+        .map(|pos| format!("  history {}/{}", pos + 1, state.history.entries.len()));
+    // A server-side search is waiting to be sent or to come back
+    let pending = state
+        .search_loading
+        .then(|| format!(" {}", spinner(state.spinner_frame)));
+    let width = (area.width as usize).saturating_sub(
+        4 + history.as_ref().map_or(0, String::len) + pending.as_ref().map_or(0, |p| p.width()),
+    );
+    let mut spans = vec![Span::styled("  / ", icon_style)];
+    spans.extend(
+        state
+            .search_query
+            .spans(width, is_focused, Style::default().fg(theme().text)),
+    );
+    if let Some(pending) = pending {
+        spans.push(Span::styled(pending, Style::default().fg(theme().warning)));
+    }
+    if let Some(history) = history {
+        spans.push(Span::styled(history, Style::default().fg(theme().dim)));
+    }
+    let line = Line::from(spans);
+    let bar = Paragraph::new(line).style(Style::default().bg(theme().bar_bg));
+    frame.render_widget(bar, area);
+}
+
+/// Uniform-enough index in `0..len` without pulling in a RNG crate: every
+/// `RandomState` is seeded from the OS.
+fn random_index(len: usize) -> usize {
+    use std::hash::{BuildHasher, Hasher};
+    let seed = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    (seed % len as u64) as usize
+}
+
+/// A contest slug from what was typed in the prompt: a bare number means
+/// that weekly contest.
+fn contest_slug(input: &str) -> String {
+    if input.chars().all(|c| c.is_ascii_digit()) {
+        format!("weekly-contest-{input}")
+    } else {
+        input.to_lowercase().replace(' ', "-")
+    }
+}
+
+fn render_jump_prompt(frame: &mut Frame, area: Rect, jump: &JumpPrompt, spinner_frame: usize) {
+    let prompt = if jump.contest { "  contest: " } else { "  : " };
+    let mut spans = vec![
+        Span::styled(prompt, Style::default().fg(theme().accent)),
+        Span::styled(jump.input.clone(), Style::default().fg(theme().text)),
+    ];
+    if jump.loading {
+        let s = spinner(spinner_frame);
+        spans.push(Span::styled(
+            format!(" {s}"),
+            Style::default().fg(theme().warning),
+        ));
+    } else {
+        spans.push(Span::styled(
+            icons().cursor,
+            Style::default().fg(theme().accent),
+        ));
+    }
+    if let Some(ref msg) = jump.message {
+        spans.push(Span::styled(
+            format!("  {msg}"),
+            Style::default().fg(theme().error),
+        ));
+    } else if jump.input.is_empty() {
+        let placeholder = if jump.contest {
+            "  weekly-contest-400, biweekly-contest-130, or a weekly number"
+        } else {
+            "  problem number or slug"
+        };
+        spans.push(Span::styled(placeholder, Style::default().fg(theme().dim)));
+    }
+    let bar = Paragraph::new(Line::from(spans)).style(Style::default().bg(theme().bar_bg));
     frame.render_widget(bar, area);
 }
 
 fn render_stats_header(frame: &mut Frame, area: Rect, stats: &UserStats) {
-    let rows = Layout::vertical([
-        Constraint::Length(1),
-        Constraint::Length(1),
-    ]).split(area);
+    let rows = Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).split(area);
 
     let total_solved = stats.easy_solved + stats.medium_solved + stats.hard_solved;
     let total_all = stats.easy_total + stats.medium_total + stats.hard_total;
@@ -406,32 +1714,34 @@ fn render_stats_header(frame: &mut Frame, area: Rect, stats: &UserStats) {
     let line0 = Line::from(vec![
         Span::styled(
             format!("  {} ", stats.username),
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme().accent)
+                .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!("{total_solved}/{total_all} solved"),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme().dim),
         ),
     ]);
     frame.render_widget(Paragraph::new(line0), rows[0]);
 
     let line1 = Line::from(vec![
-        Span::styled("  Easy ", Style::default().fg(Color::Green)),
+        Span::styled("  Easy ", Style::default().fg(theme().success)),
         Span::styled(
             format!("{}/{}", stats.easy_solved, stats.easy_total),
-            Style::default().fg(Color::White),
+            Style::default().fg(theme().text),
         ),
         Span::raw("  "),
-        Span::styled("Med ", Style::default().fg(Color::Yellow)),
+        Span::styled("Med ", Style::default().fg(theme().warning)),
         Span::styled(
             format!("{}/{}", stats.medium_solved, stats.medium_total),
-            Style::default().fg(Color::White),
+            Style::default().fg(theme().text),
         ),
         Span::raw("  "),
-        Span::styled("Hard ", Style::default().fg(Color::Red)),
+        Span::styled("Hard ", Style::default().fg(theme().error)),
         Span::styled(
             format!("{}/{}", stats.hard_solved, stats.hard_total),
-            Style::default().fg(Color::White),
+            Style::default().fg(theme().text),
         ),
     ]);
     frame.render_widget(Paragraph::new(line1), rows[1]);
@@ -442,44 +1752,149 @@ fn render_title_bar(frame: &mut Frame, area: Rect, state: &HomeState) {
         Span::styled(
             " LeetCode ",
             Style::default()
-                .fg(Color::Black)
-                .bg(Color::Yellow)
+                .fg(theme().on_accent)
+                .bg(theme().warning)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" "),
     ];
 
+    if state.offline {
+        spans.push(Span::styled(
+            "OFFLINE ",
+            Style::default()
+                .fg(theme().error)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if let Some(ref filter) = state.server_filter {
+        let (text, color) = if state.server_filter_lifted {
+            ("Server filter off ".to_string(), theme().dim)
+        } else {
+            (format!("Server: {filter} "), theme().warning)
+        };
+        spans.push(Span::styled(
+            text,
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if let Some(ref list) = state.list_filter {
+        spans.push(Span::styled(
+            format!("{}: {} ", list.kind.label(), list.name),
+            Style::default()
+                .fg(theme().accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     if let Some(summary) = state.filter.summary() {
         spans.push(Span::styled(
             format!("{summary} "),
             Style::default()
-                .fg(Color::Magenta)
+                .fg(theme().special)
                 .add_modifier(Modifier::BOLD),
         ));
     }
 
-    if !state.problems.is_empty() {
+    if !state.selected.is_empty() {
+        spans.push(Span::styled(
+            format!("{} selected ", state.selected.len()),
+            Style::default()
+                .fg(theme().success)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    let query = state.search_query.value().trim();
+    if state.recent_open {
+        spans.push(Span::styled(
+            format!("Recent ({})", state.recent.entries.len()),
+            Style::default()
+                .fg(theme().accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else if state.journal_open {
+        spans.push(Span::styled(
+            format!("Journal ({})", state.journal.len()),
+            Style::default()
+                .fg(theme().accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else if state.activity_open {
+        spans.push(Span::styled(
+            format!("Activity ({})", state.activity.len()),
+            Style::default()
+                .fg(theme().accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else if !query.is_empty() {
+        let n = state.filtered_indices.len();
+        spans.push(Span::styled(
+            format!("/{query} "),
+            Style::default().fg(theme().accent),
+        ));
         spans.push(Span::styled(
-            format!("{} / {} results", state.filtered_indices.len(), state.search_total),
-            Style::default().fg(Color::DarkGray),
+            format!("{n} {}", if n == 1 { "match" } else { "matches" }),
+            Style::default().fg(theme().warning),
+        ));
+    } else if !state.problems.is_empty() {
+        spans.push(Span::styled(
+            format!(
+                "{} / {} problems",
+                state.filtered_indices.len(),
+                state.total_problems
+            ),
+            Style::default().fg(theme().dim),
         ));
     }
 
-    if state.search_loading {
-        let spinner = ["\u{280b}", "\u{2819}", "\u{2839}", "\u{2838}", "\u{283c}", "\u{2834}", "\u{2826}", "\u{2827}", "\u{2807}", "\u{280f}"];
-        let s = spinner[state.spinner_frame % spinner.len()];
+    if state.loading {
+        let s = spinner(state.spinner_frame);
         spans.push(Span::styled(
             format!(" {s}"),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme().warning),
         ));
     }
 
-    let title = Paragraph::new(Line::from(spans)).style(Style::default().bg(Color::Black));
+    let title = Paragraph::new(Line::from(spans)).style(Style::default().bg(theme().bar_bg));
     frame.render_widget(title, area);
 }
 
+/// Cells in the acceptance rate bar, and the whole column: `xx.x% ` then
+/// the bar.
+const AC_BAR_WIDTH: usize = 5;
+const AC_RATE_WIDTH: u16 = 7 + AC_BAR_WIDTH as u16;
+
+/// `percent` as a bar `width` cells long, drawn with partially filled
+/// cells so small differences still show.
+fn rate_bar(percent: f64, width: usize) -> String {
+    let steps = icons().bar;
+    let per_cell = steps.len() - 1;
+    let filled = ((percent.clamp(0.0, 100.0) / 100.0) * (width * per_cell) as f64).round() as usize;
+    (0..width)
+        .map(|cell| steps[filled.saturating_sub(cell * per_cell).min(per_cell)])
+        .collect()
+}
+
+/// Green for problems most submissions pass, red for ones most don't.
+fn rate_color(percent: f64) -> Color {
+    if percent >= 55.0 {
+        theme().success
+    } else if percent >= 35.0 {
+        theme().warning
+    } else {
+        theme().error
+    }
+}
+
 fn render_table(frame: &mut Frame, area: Rect, state: &mut HomeState) {
+    state.table_height = area.height.saturating_sub(1); // minus header row
+    state.table_area = area;
+
     let header = Row::new([
+        Cell::from(" "),
         Cell::from(" "),
         Cell::from(" # "),
         Cell::from("Title"),
@@ -488,47 +1903,96 @@ fn render_table(frame: &mut Frame, area: Rect, state: &mut HomeState) {
     ])
     .style(
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme().accent)
             .add_modifier(Modifier::BOLD),
     )
     .bottom_margin(0);
 
+    // Everything but the Title column: fixed widths, column gaps and the
+    // highlight symbol
+    let status_width = icons().status_width();
+    let local_width = icons().folder.width() as u16;
+    let title_width = area
+        .width
+        .saturating_sub(status_width + local_width + 6 + 10 + AC_RATE_WIDTH + 5 + 2)
+        as usize;
+
     let rows: Vec<Row> = state
         .filtered_indices
         .iter()
-        .map(|&idx| {
+        .zip(&state.match_positions)
+        .map(|(&idx, positions)| {
             let p = &state.problems[idx];
-            let diff_color = match p.difficulty.as_str() {
-                "Easy" => Color::Green,
-                "Medium" => Color::Yellow,
-                "Hard" => Color::Red,
-                _ => Color::White,
-            };
-            let paid = if p.is_paid_only { " \u{1f512}" } else { "" };
+            let diff_color = theme().difficulty(p.difficulty.as_str());
+            let suffix = format!(
+                "{}{}",
+                if p.is_favor {
+                    format!(" {}", icons().star)
+                } else {
+                    String::new()
+                },
+                if p.is_paid_only {
+                    format!(" {}", icons().paid)
+                } else {
+                    String::new()
+                },
+            );
+            let title =
+                fuzzy::highlight_spans(&p.title, positions, &suffix, title_width, Style::default());
             let status_cell = match p.status.as_deref() {
-                Some("ac") => Cell::from(Span::styled(" \u{2714}", Style::default().fg(Color::Green))),
-                Some("notac") => Cell::from(Span::styled(" \u{25cf}", Style::default().fg(Color::Yellow))),
-                _ => Cell::from("  "),
+                Some("ac") => Cell::from(Span::styled(
+                    format!(" {}", icons().check),
+                    Style::default().fg(theme().success),
+                )),
+                Some("notac") => Cell::from(Span::styled(
+                    format!(" {}", icons().dot),
+                    Style::default().fg(theme().warning),
+                )),
+                _ => Cell::from(""),
+            };
+            let local_cell = if state.scaffolded.contains(&p.title_slug) {
+                Cell::from(icons().folder)
+            } else {
+                Cell::from("")
+            };
+            let marked = state.selected.contains(&idx);
+            let id_cell = if marked {
+                Cell::from(Span::styled(
+                    format!("+{}", p.frontend_question_id),
+                    Style::default()
+                        .fg(theme().success)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Cell::from(format!(" {}", p.frontend_question_id))
             };
             Row::new([
                 status_cell,
-                Cell::from(format!(" {}", p.frontend_question_id)),
-                Cell::from(format!("{}{}", p.title, paid)),
+                local_cell,
+                id_cell,
+                Cell::from(Line::from(title)),
                 Cell::from(Span::styled(
                     p.difficulty.clone(),
                     Style::default().fg(diff_color),
                 )),
-                Cell::from(format!("{:.1}%", p.ac_rate)),
+                Cell::from(Line::from(vec![
+                    Span::raw(format!("{:>5.1}% ", p.ac_rate)),
+                    Span::styled(
+                        rate_bar(p.ac_rate, AC_BAR_WIDTH),
+                        Style::default().fg(rate_color(p.ac_rate)),
+                    ),
+                ])),
             ])
         })
         .collect();
 
     let widths = [
-        Constraint::Length(3),
+        Constraint::Length(status_width),
+        Constraint::Length(local_width),
         Constraint::Length(6),
         Constraint::Min(20),
         Constraint::Length(10),
-        Constraint::Length(8),
+        Constraint::Length(AC_RATE_WIDTH),
     ];
 
     let table = Table::new(rows, widths)
@@ -536,17 +2000,172 @@ fn render_table(frame: &mut Frame, area: Rect, state: &mut HomeState) {
         .block(Block::default().borders(Borders::NONE))
         .row_highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme().selection_bg)
                 .add_modifier(Modifier::BOLD),
         )
-        .highlight_symbol("\u{25b8} ");
+        .highlight_symbol(format!("{} ", icons().pointer));
 
     frame.render_stateful_widget(table, area, &mut state.table_state);
 }
 
-fn render_filter_popup(frame: &mut Frame, area: Rect, filter: &FilterState) {
+fn render_recent(frame: &mut Frame, area: Rect, state: &mut HomeState) {
+    state.table_area = area;
+    if state.recent.entries.is_empty() {
+        let p =
+            Paragraph::new("  No recently viewed problems").style(Style::default().fg(theme().dim));
+        frame.render_widget(p, area);
+        return;
+    }
+
+    let header = Row::new([
+        Cell::from(" "),
+        Cell::from(" # "),
+        Cell::from("Title"),
+        Cell::from("Viewed"),
+    ])
+    .style(
+        Style::default()
+            .fg(theme().accent)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let rows: Vec<Row> = state
+        .recent
+        .entries
+        .iter()
+        .map(|e| {
+            Row::new([
+                Cell::from("  "),
+                Cell::from(format!(" {}", e.frontend_question_id)),
+                Cell::from(e.title.clone()),
+                Cell::from(Span::styled(
+                    format_age(now.saturating_sub(e.viewed_at)),
+                    Style::default().fg(theme().dim),
+                )),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(3),
+        Constraint::Length(6),
+        Constraint::Min(20),
+        Constraint::Length(10),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().borders(Borders::NONE))
+        .row_highlight_style(
+            Style::default()
+                .bg(theme().selection_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(format!("{} ", icons().pointer));
+
+    frame.render_stateful_widget(table, area, &mut state.recent_state);
+}
+
+fn render_journal(frame: &mut Frame, area: Rect, state: &mut HomeState) {
+    if state.journal.is_empty() {
+        let p = Paragraph::new("  No accepted submissions recorded yet")
+            .style(Style::default().fg(theme().dim));
+        frame.render_widget(p, area);
+        return;
+    }
+
+    let header = Row::new([
+        Cell::from(" # "),
+        Cell::from("Title"),
+        Cell::from("Difficulty"),
+        Cell::from("Language"),
+        Cell::from("Solved"),
+    ])
+    .style(
+        Style::default()
+            .fg(theme().accent)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let rows: Vec<Row> = state
+        .journal
+        .iter()
+        .map(|e| {
+            let diff_color = theme().difficulty(e.difficulty.as_str());
+            Row::new([
+                Cell::from(format!(" {}", e.frontend_question_id)),
+                Cell::from(e.title.clone()),
+                Cell::from(Span::styled(
+                    e.difficulty.clone(),
+                    Style::default().fg(diff_color),
+                )),
+                Cell::from(e.language.clone()),
+                Cell::from(Span::styled(
+                    format_age(now.saturating_sub(e.solved_at)),
+                    Style::default().fg(theme().dim),
+                )),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(6),
+        Constraint::Min(20),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().borders(Borders::NONE))
+        .row_highlight_style(
+            Style::default()
+                .bg(theme().selection_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(format!("{} ", icons().pointer));
+
+    frame.render_stateful_widget(table, area, &mut state.journal_state);
+}
+
+fn render_preview(frame: &mut Frame, area: Rect, state: &HomeState) {
+    let Some(problem) = state.selected_problem() else {
+        return;
+    };
+    let block = Block::default()
+        .borders(Borders::LEFT)
+        .border_style(Style::default().fg(theme().dim))
+        .title(Span::styled(
+            format!(" {}. {} ", problem.frontend_question_id, problem.title),
+            Style::default()
+                .fg(theme().text)
+                .add_modifier(Modifier::BOLD),
+        ));
+
+    let content = match state.preview {
+        Some(ref preview) if preview.slug == problem.title_slug => {
+            Paragraph::new(preview.lines.clone()).wrap(Wrap { trim: false })
+        }
+        _ => {
+            let s = spinner(state.spinner_frame);
+            Paragraph::new(format!(" {s} Loading...")).style(Style::default().fg(theme().warning))
+        }
+    };
+    frame.render_widget(content.block(block), area);
+}
+
+fn render_filter_popup(frame: &mut Frame, area: Rect, filter: &FilterState, loading: bool) {
     let popup_width = 30u16.min(area.width.saturating_sub(4));
-    let popup_height = 9u16;
+    let popup_height = 16u16;
     let x = area.x + (area.width.saturating_sub(popup_width)) / 2;
     let y = area.y + (area.height.saturating_sub(popup_height)) / 2;
     let popup_area = Rect::new(x, y, popup_width, popup_height);
@@ -556,16 +2175,48 @@ fn render_filter_popup(frame: &mut Frame, area: Rect, filter: &FilterState) {
     let block = Block::default()
         .title(" Filter ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Magenta));
+        .border_style(Style::default().fg(theme().special));
     frame.render_widget(block, popup_area);
 
-    let inner = Rect::new(popup_area.x + 2, popup_area.y + 1, popup_area.width.saturating_sub(4), popup_area.height.saturating_sub(2));
-    let items = [
-        ("Easy", filter.easy, Color::Green),
-        ("Medium", filter.medium, Color::Yellow),
-        ("Hard", filter.hard, Color::Red),
-        ("Hide Solved", filter.hide_solved, Color::Cyan),
+    let inner = Rect::new(
+        popup_area.x + 2,
+        popup_area.y + 1,
+        popup_area.width.saturating_sub(4),
+        popup_area.height.saturating_sub(2),
+    );
+    // Needs exactly one difficulty checked, and no download running
+    let download = filter.server_difficulty.or(filter.single_difficulty());
+    let download_color = if download.is_some() && !loading {
+        theme().accent
+    } else {
+        theme().dim
+    };
+    let mut items = vec![
+        ("Easy".to_string(), filter.easy, theme().success),
+        ("Medium".to_string(), filter.medium, theme().warning),
+        ("Hard".to_string(), filter.hard, theme().error),
     ];
+    // One of these at a time
+    items.extend(
+        StatusFilter::ALL
+            .iter()
+            .map(|&s| (s.label().to_string(), filter.status == s, theme().special)),
+    );
+    items.push((
+        "Only Scaffolded".to_string(),
+        filter.only_scaffolded,
+        theme().accent,
+    ));
+    items.push((
+        format!("Download only {}", download.unwrap_or("one")),
+        filter.server_difficulty.is_some(),
+        download_color,
+    ));
+    items.push((
+        format!("Company{}", icons().ellipsis),
+        false,
+        theme().accent,
+    ));
 
     let mut constraints: Vec<Constraint> = items.iter().map(|_| Constraint::Length(1)).collect();
     constraints.push(Constraint::Length(1));
@@ -573,25 +2224,34 @@ fn render_filter_popup(frame: &mut Frame, area: Rect, filter: &FilterState) {
     let rows = Layout::vertical(constraints).split(inner);
 
     for (i, ((label, checked, color), row)) in items.iter().zip(rows.iter()).enumerate() {
-        let marker = if *checked { "\u{25c9}" } else { "\u{25cb}" };
+        let marker = match (*checked, i + 1 == items.len()) {
+            // The company row opens the picker rather than toggling
+            (_, true) => icons().next,
+            (true, _) => icons().circle_checked,
+            (false, _) => icons().circle,
+        };
         let highlight = i == filter.active_item;
         let style = if highlight {
             Style::default().fg(*color).add_modifier(Modifier::BOLD)
         } else {
             Style::default().fg(*color)
         };
-        let prefix = if highlight { "\u{25b8} " } else { "  " };
+        let prefix = if highlight {
+            format!("{} ", icons().pointer)
+        } else {
+            "  ".to_string()
+        };
         let line = Line::from(vec![
             Span::styled(prefix, style),
             Span::styled(format!("{marker} "), style),
-            Span::styled(*label, style),
+            Span::styled(label.as_str(), style),
         ]);
         frame.render_widget(Paragraph::new(line), *row);
     }
 
     let hint = Paragraph::new(Line::from(Span::styled(
         "  Space: toggle  Esc: close",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme().dim),
     )));
     frame.render_widget(hint, rows[items.len() + 1]);
 }
@@ -0,0 +1,143 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use unicode_width::UnicodeWidthStr;
+
+/// Every decorative glyph the UI draws, so terminals and fonts without them
+/// can get plain ASCII instead.
+pub struct Icons {
+    pub spinner: &'static [&'static str],
+    /// Highlighted row in tables and lists
+    pub pointer: &'static str,
+    pub check: &'static str,
+    pub cross: &'static str,
+    /// Attempted but not solved; also "authenticated"
+    pub dot: &'static str,
+    pub circle: &'static str,
+    /// A checked filter option
+    pub circle_checked: &'static str,
+    pub star: &'static str,
+    pub paid: &'static str,
+    /// A project exists in the workspace
+    pub folder: &'static str,
+    pub timer: &'static str,
+    /// Background work in progress
+    pub busy: &'static str,
+    /// Text input cursor
+    pub cursor: &'static str,
+    pub more_above: &'static str,
+    pub more_below: &'static str,
+    /// Picker arrows around the current value
+    pub prev: &'static str,
+    pub next: &'static str,
+    pub expanded: &'static str,
+    pub breadcrumb: &'static str,
+    pub bullet: &'static str,
+    pub separator: &'static str,
+    pub ellipsis: &'static str,
+    /// Stands in for each hidden char of a secret
+    pub mask: &'static str,
+    /// One bar cell filled from empty to full, in even steps
+    pub bar: &'static [&'static str],
+}
+
+pub const UNICODE: Icons = Icons {
+    spinner: &[
+        "\u{280b}", "\u{2819}", "\u{2839}", "\u{2838}", "\u{283c}", "\u{2834}", "\u{2826}",
+        "\u{2827}", "\u{2807}", "\u{280f}",
+    ],
+    pointer: "\u{25b8}",
+    check: "\u{2714}",
+    cross: "\u{2718}",
+    dot: "\u{25cf}",
+    circle: "\u{25cb}",
+    circle_checked: "\u{25c9}",
+    star: "\u{2605}",
+    paid: "\u{1f512}",
+    folder: "\u{1f4c1}",
+    timer: "\u{23f1}",
+    busy: "\u{27f3}",
+    cursor: "\u{258e}",
+    more_above: "\u{25b2}",
+    more_below: "\u{25bc}",
+    prev: "\u{25c2}",
+    next: "\u{25b8}",
+    expanded: "\u{25be}",
+    breadcrumb: "\u{203a}",
+    bullet: "\u{2022}",
+    separator: "\u{b7}",
+    ellipsis: "\u{2026}",
+    mask: "\u{2022}",
+    bar: &[
+        " ", "\u{258f}", "\u{258e}", "\u{258d}", "\u{258c}", "\u{258b}", "\u{258a}", "\u{2589}",
+        "\u{2588}",
+    ],
+};
+
+pub const ASCII: Icons = Icons {
+    spinner: &["|", "/", "-", "\\"],
+    pointer: ">",
+    check: "[x]",
+    cross: "[!]",
+    dot: "*",
+    circle: "( )",
+    circle_checked: "(*)",
+    star: "*",
+    paid: "$",
+    folder: "+",
+    timer: "[t]",
+    busy: "~",
+    cursor: "|",
+    more_above: "^",
+    more_below: "v",
+    prev: "<",
+    next: ">",
+    expanded: "v",
+    breadcrumb: ">",
+    bullet: "-",
+    separator: ",",
+    ellipsis: "...",
+    mask: "*",
+    bar: &[" ", "-", "="],
+};
+
+static ASCII_MODE: AtomicBool = AtomicBool::new(false);
+
+pub fn icons() -> &'static Icons {
+    if ASCII_MODE.load(Ordering::Relaxed) {
+        &ASCII
+    } else {
+        &UNICODE
+    }
+}
+
+pub fn set_unicode(unicode: bool) {
+    ASCII_MODE.store(!unicode, Ordering::Relaxed);
+}
+
+impl Icons {
+    /// Columns a solved/attempted status cell needs, leading space included.
+    pub fn status_width(&self) -> u16 {
+        1 + self.check.width().max(self.dot.width()) as u16
+    }
+}
+
+/// The spinner frame for a tick counter.
+pub fn spinner(frame: usize) -> &'static str {
+    let frames = icons().spinner;
+    frames[frame % frames.len()]
+}
+
+/// Whether the terminal can be trusted with Unicode glyphs: not on the
+/// Linux console, and only under a UTF-8 locale when one is set.
+pub fn detect_unicode() -> bool {
+    if std::env::var("TERM").is_ok_and(|t| t == "linux") {
+        return false;
+    }
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        .is_none_or(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
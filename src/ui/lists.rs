@@ -1,18 +1,67 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
+    Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
-    Frame,
 };
 
-use crate::api::types::FavoriteList;
+use std::collections::HashMap;
 
+use crate::api::types::{FavoriteList, ProblemSummary};
+
+use super::home::{ListFilter, ListKind};
+use super::icons::{icons, spinner};
+use super::motion::{Motion, PendingKeys};
+use super::mouse::{self, ClickTracker, WHEEL_STEP};
 use super::status_bar::render_status_bar;
+use super::text_input::TextInput;
+use super::theme::theme;
+
+#[derive(Default, Clone, Copy, PartialEq)]
+pub enum NewListField {
+    #[default]
+    Name,
+    Description,
+    Public,
+}
+
+/// The `n` overlay. Lists are private unless toggled otherwise.
+#[derive(Default)]
+pub struct NewListForm {
+    pub name: TextInput,
+    pub description: TextInput,
+    pub public: bool,
+    pub field: NewListField,
+    /// Why Enter didn't create the list
+    pub error: Option<String>,
+}
+
+/// Topic tags shown per problem row.
+const TOPICS_SHOWN: usize = 2;
+
+/// Longest list name accepted, in characters.
+const MAX_LIST_NAME: usize = 30;
+
+/// The trimmed name, or what's wrong with it.
+fn validate_list_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Name can't be empty".to_string());
+    }
+    let len = name.chars().count();
+    if len > MAX_LIST_NAME {
+        return Err(format!("Name is {len} characters, max {MAX_LIST_NAME}"));
+    }
+    Ok(name.to_string())
+}
 
 pub struct ListsState {
     pub lists: Vec<FavoriteList>,
+    /// Problem list rows for the listed problems, by slug. The lists
+    /// themselves carry no number, difficulty or tags
+    pub problems: HashMap<String, ProblemSummary>,
     pub loading: bool,
     pub error_message: Option<String>,
     pub spinner_frame: usize,
@@ -22,25 +71,72 @@ pub struct ListsState {
     pub viewing_list: Option<usize>,
     pub problem_table_state: TableState,
     // Create mode
-    pub create_mode: bool,
-    pub create_input: String,
+    pub create: Option<NewListForm>,
+    /// New name being typed for the selected list (`r`)
+    pub rename_input: Option<TextInput>,
+    pub rename_error: Option<String>,
     // Confirm delete
     pub confirm_delete: bool,
+    pub pending_keys: PendingKeys,
+    /// Visible table rows from the last render, for page motions
+    pub table_height: u16,
+    /// `j` on the last row goes to the first, and `k` on the first to the last
+    pub wrap_navigation: bool,
+    /// Where the current table was last drawn, for mouse clicks
+    pub table_area: Rect,
+    clicks: ClickTracker,
 }
 
 impl ListsState {
     pub fn new() -> Self {
         Self {
             lists: Vec::new(),
+            problems: HashMap::new(),
             loading: true,
             error_message: None,
             spinner_frame: 0,
             list_table_state: TableState::default(),
             viewing_list: None,
             problem_table_state: TableState::default(),
-            create_mode: false,
-            create_input: String::new(),
+            create: None,
+            rename_input: None,
+            rename_error: None,
             confirm_delete: false,
+            pending_keys: PendingKeys::default(),
+            table_height: 0,
+            wrap_navigation: false,
+            table_area: Rect::default(),
+            clicks: ClickTracker::default(),
+        }
+    }
+
+    /// Swap in freshly fetched lists, keeping the selected and open list
+    /// (matched by id, since the order can change) and the problem row.
+    pub fn set_lists(&mut self, lists: Vec<FavoriteList>) {
+        let selected = self.selected_list().map(|l| l.id_hash.clone());
+        let viewing = self.viewing_list_ref().map(|l| l.id_hash.clone());
+        self.lists = lists;
+        let position =
+            |id: Option<String>| id.and_then(|id| self.lists.iter().position(|l| l.id_hash == id));
+
+        let selected = position(selected).or_else(|| {
+            let last = self.lists.len().checked_sub(1)?;
+            Some(self.list_table_state.selected().unwrap_or(0).min(last))
+        });
+        self.list_table_state.select(selected);
+        if self.viewing_list.is_some() {
+            self.viewing_list = position(viewing);
+        }
+        match self.viewing_list_ref().map(|l| l.questions.len()) {
+            Some(0) | None => self.problem_table_state.select(None),
+            Some(len) => {
+                let row = self
+                    .problem_table_state
+                    .selected()
+                    .unwrap_or(0)
+                    .min(len - 1);
+                self.problem_table_state.select(Some(row));
+            }
         }
     }
 
@@ -49,11 +145,7 @@ impl ListsState {
         self.lists.get(idx)
     }
 
-    pub fn selected_list_idx(&self) -> Option<usize> {
-        self.list_table_state.selected()
-    }
-
-    fn viewing_list_ref(&self) -> Option<&FavoriteList> {
+    pub fn viewing_list_ref(&self) -> Option<&FavoriteList> {
         let idx = self.viewing_list?;
         self.lists.get(idx)
     }
@@ -65,9 +157,16 @@ impl ListsState {
         }
 
         // Create mode
-        if self.create_mode {
+        if self.create.is_some() {
             return self.handle_create_key(key);
         }
+        if self.rename_input.is_some() {
+            return self.handle_rename_key(key);
+        }
+
+        if self.handle_motion(key) {
+            return ListsAction::None;
+        }
 
         // Problem view within a list
         if self.viewing_list.is_some() {
@@ -78,32 +177,72 @@ impl ListsState {
         self.handle_list_key(key)
     }
 
-    fn handle_list_key(&mut self, key: KeyEvent) -> ListsAction {
-        match key.code {
-            KeyCode::Esc | KeyCode::Char('q') => ListsAction::Back,
-            KeyCode::Char('j') | KeyCode::Down => {
-                self.move_list_selection(1);
+    /// Wheel moves the selection; clicking a row selects it and a
+    /// double-click acts like Enter.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> ListsAction {
+        if self.confirm_delete || self.create.is_some() || self.rename_input.is_some() {
+            return ListsAction::None;
+        }
+        let count = self.current_len();
+        let table_state = if self.viewing_list.is_some() {
+            &mut self.problem_table_state
+        } else {
+            &mut self.list_table_state
+        };
+        match mouse.kind {
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp if count > 0 => {
+                let step = if matches!(mouse.kind, MouseEventKind::ScrollDown) {
+                    WHEEL_STEP
+                } else {
+                    -WHEEL_STEP
+                };
+                let current = table_state.selected().unwrap_or(0) as i32;
+                table_state.select(Some((current + step).clamp(0, count as i32 - 1) as usize));
                 ListsAction::None
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                self.move_list_selection(-1);
-                ListsAction::None
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(row) = mouse::table_row_at(
+                    self.table_area,
+                    table_state.offset(),
+                    mouse.column,
+                    mouse.row,
+                )
+                .filter(|&r| r < count) else {
+                    return ListsAction::None;
+                };
+                table_state.select(Some(row));
+                if self.clicks.click(row) {
+                    self.handle_key(KeyEvent::from(KeyCode::Enter))
+                } else {
+                    ListsAction::None
+                }
             }
+            _ => ListsAction::None,
+        }
+    }
+
+    fn handle_list_key(&mut self, key: KeyEvent) -> ListsAction {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => ListsAction::Back,
+            KeyCode::Char('R') if self.error_message.is_some() => ListsAction::Retry,
+            KeyCode::Char('f') => match self.selected_list() {
+                Some(list) => ListsAction::FilterHome(list_filter(list)),
+                None => ListsAction::None,
+            },
             KeyCode::Enter => {
                 if let Some(idx) = self.list_table_state.selected() {
                     self.viewing_list = Some(idx);
                     self.problem_table_state = TableState::default();
-                    if let Some(list) = self.lists.get(idx) {
-                        if !list.questions.is_empty() {
-                            self.problem_table_state.select(Some(0));
-                        }
+                    if let Some(list) = self.lists.get(idx)
+                        && !list.questions.is_empty()
+                    {
+                        self.problem_table_state.select(Some(0));
                     }
                 }
                 ListsAction::None
             }
             KeyCode::Char('n') => {
-                self.create_mode = true;
-                self.create_input.clear();
+                self.create = Some(NewListForm::default());
                 ListsAction::None
             }
             KeyCode::Char('d') => {
@@ -112,79 +251,158 @@ impl ListsState {
                 }
                 ListsAction::None
             }
+            KeyCode::Char('r') => {
+                self.rename_input = self.selected_list().map(|l| TextInput::new(l.name.clone()));
+                ListsAction::None
+            }
             _ => ListsAction::None,
         }
     }
 
     fn handle_problem_key(&mut self, key: KeyEvent) -> ListsAction {
         match key.code {
+            KeyCode::Char('f') => match self.viewing_list_ref() {
+                Some(list) => ListsAction::FilterHome(list_filter(list)),
+                None => ListsAction::None,
+            },
             KeyCode::Esc | KeyCode::Char('b') => {
                 self.viewing_list = None;
                 ListsAction::None
             }
-            KeyCode::Char('j') | KeyCode::Down => {
-                self.move_problem_selection(1);
+            KeyCode::Enter => {
+                if let Some(list) = self.viewing_list_ref()
+                    && let Some(idx) = self.problem_table_state.selected()
+                    && let Some(q) = list.questions.get(idx)
+                {
+                    return ListsAction::OpenDetail(q.title_slug.clone());
+                }
                 ListsAction::None
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                self.move_problem_selection(-1);
+            KeyCode::Char('d') => {
+                if let Some(list) = self.viewing_list_ref()
+                    && let Some(idx) = self.problem_table_state.selected()
+                    && let Some(q) = list.questions.get(idx)
+                {
+                    return ListsAction::RemoveProblem {
+                        id_hash: list.id_hash.clone(),
+                        list_name: list.name.clone(),
+                        question_id: q.question_id.clone(),
+                    };
+                }
+                ListsAction::None
+            }
+            _ => ListsAction::None,
+        }
+    }
+
+    fn handle_create_key(&mut self, key: KeyEvent) -> ListsAction {
+        let Some(form) = self.create.as_mut() else {
+            return ListsAction::None;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.create = None;
                 ListsAction::None
             }
             KeyCode::Enter => {
-                if let Some(list) = self.viewing_list_ref() {
-                    if let Some(idx) = self.problem_table_state.selected() {
-                        if let Some(q) = list.questions.get(idx) {
-                            return ListsAction::OpenDetail(q.title_slug.clone());
-                        }
+                let name = match validate_list_name(form.name.value()) {
+                    Ok(name) => name,
+                    Err(e) => {
+                        form.error = Some(e);
+                        form.field = NewListField::Name;
+                        return ListsAction::None;
                     }
+                };
+                let form = self.create.take().unwrap_or_default();
+                let description = form.description.value().trim();
+                ListsAction::CreateList {
+                    name,
+                    description: (!description.is_empty()).then(|| description.to_string()),
+                    public: form.public,
                 }
+            }
+            KeyCode::Tab | KeyCode::Down => {
+                form.field = match form.field {
+                    NewListField::Name => NewListField::Description,
+                    NewListField::Description => NewListField::Public,
+                    NewListField::Public => NewListField::Name,
+                };
                 ListsAction::None
             }
-            KeyCode::Char('d') => {
-                if let Some(list) = self.viewing_list_ref() {
-                    if let Some(idx) = self.problem_table_state.selected() {
-                        if let Some(q) = list.questions.get(idx) {
-                            return ListsAction::RemoveProblem {
-                                id_hash: list.id_hash.clone(),
-                                question_id: q.question_id.clone(),
-                            };
-                        }
-                    }
+            KeyCode::BackTab | KeyCode::Up => {
+                form.field = match form.field {
+                    NewListField::Name => NewListField::Public,
+                    NewListField::Description => NewListField::Name,
+                    NewListField::Public => NewListField::Description,
+                };
+                ListsAction::None
+            }
+            KeyCode::Char(' ') if form.field == NewListField::Public => {
+                form.public = !form.public;
+                ListsAction::None
+            }
+            _ => {
+                let used = match form.field {
+                    NewListField::Name => form.name.handle_key(key),
+                    NewListField::Description => form.description.handle_key(key),
+                    NewListField::Public => false,
+                };
+                if used {
+                    form.error = None;
                 }
                 ListsAction::None
             }
-            _ => ListsAction::None,
         }
     }
 
-    fn handle_create_key(&mut self, key: KeyEvent) -> ListsAction {
+    fn handle_rename_key(&mut self, key: KeyEvent) -> ListsAction {
+        let Some(input) = self.rename_input.as_mut() else {
+            return ListsAction::None;
+        };
         match key.code {
             KeyCode::Esc => {
-                self.create_mode = false;
-                self.create_input.clear();
+                self.rename_input = None;
+                self.rename_error = None;
                 ListsAction::None
             }
             KeyCode::Enter => {
-                if !self.create_input.trim().is_empty() {
-                    let name = self.create_input.trim().to_string();
-                    self.create_mode = false;
-                    self.create_input.clear();
-                    ListsAction::CreateList(name)
-                } else {
-                    self.create_mode = false;
-                    self.create_input.clear();
-                    ListsAction::None
+                let name = match validate_list_name(input.value()) {
+                    Ok(name) => name,
+                    Err(e) => {
+                        self.rename_error = Some(e);
+                        return ListsAction::None;
+                    }
+                };
+                self.rename_input = None;
+                match self.selected_list() {
+                    Some(list) if name != list.name => ListsAction::RenameList {
+                        id_hash: list.id_hash.clone(),
+                        name,
+                    },
+                    _ => ListsAction::None,
                 }
             }
-            KeyCode::Char(c) => {
-                self.create_input.push(c);
+            _ => {
+                if input.handle_key(key) {
+                    self.rename_error = None;
+                }
                 ListsAction::None
             }
-            KeyCode::Backspace => {
-                self.create_input.pop();
-                ListsAction::None
+        }
+    }
+
+    /// Bracketed paste goes into whichever text field is open.
+    pub fn paste(&mut self, text: &str) {
+        if let Some(ref mut input) = self.rename_input {
+            input.insert_str(text);
+            self.rename_error = None;
+        } else if let Some(ref mut form) = self.create {
+            match form.field {
+                NewListField::Name => form.name.insert_str(text),
+                NewListField::Description => form.description.insert_str(text),
+                NewListField::Public => {}
             }
-            _ => ListsAction::None,
+            form.error = None;
         }
     }
 
@@ -204,28 +422,51 @@ impl ListsState {
         }
     }
 
-    fn move_list_selection(&mut self, delta: i32) {
-        if self.lists.is_empty() {
-            return;
+    /// Rows in whichever table is showing.
+    fn current_len(&self) -> usize {
+        if self.viewing_list.is_some() {
+            self.viewing_list_ref()
+                .map(|l| l.questions.len())
+                .unwrap_or(0)
+        } else {
+            self.lists.len()
         }
-        let current = self.list_table_state.selected().unwrap_or(0) as i32;
-        let max = self.lists.len() as i32 - 1;
-        let next = (current + delta).clamp(0, max) as usize;
-        self.list_table_state.select(Some(next));
     }
 
-    fn move_problem_selection(&mut self, delta: i32) {
-        let count = self
-            .viewing_list_ref()
-            .map(|l| l.questions.len())
-            .unwrap_or(0);
-        if count == 0 {
-            return;
+    /// Applies j/k, `gg`/`G`, counts, Ctrl+d/u and PageUp/PageDown to whichever table is
+    /// showing. Returns false when the key isn't a motion.
+    fn handle_motion(&mut self, key: KeyEvent) -> bool {
+        let count = self.current_len();
+        let table_state = if self.viewing_list.is_some() {
+            &mut self.problem_table_state
+        } else {
+            &mut self.list_table_state
+        };
+
+        let current = table_state.selected().unwrap_or(0) as i32;
+        let last = count as i32 - 1;
+        let page = (self.table_height as i32).max(1);
+        let half_page = (page / 2).max(1);
+        let target = match self.pending_keys.feed(key) {
+            Motion::Other => return false,
+            Motion::Pending => return true,
+            Motion::Down(_) if self.wrap_navigation && current == last => 0,
+            Motion::Up(_) if self.wrap_navigation && current == 0 => last,
+            Motion::Down(n) => current + n as i32,
+            Motion::Up(n) => current - n as i32,
+            Motion::HalfPageDown => current + half_page,
+            Motion::HalfPageUp => current - half_page,
+            Motion::PageDown => current + page,
+            Motion::PageUp => current - page,
+            Motion::Top => 0,
+            Motion::Bottom => last,
+            Motion::Goto(n) => n as i32 - 1,
+        };
+
+        if count > 0 {
+            table_state.select(Some(target.clamp(0, last) as usize));
         }
-        let current = self.problem_table_state.selected().unwrap_or(0) as i32;
-        let max = count as i32 - 1;
-        let next = (current + delta).clamp(0, max) as usize;
-        self.problem_table_state.select(Some(next));
+        true
     }
 }
 
@@ -233,15 +474,43 @@ pub enum ListsAction {
     None,
     Back,
     OpenDetail(String),
-    CreateList(String),
+    CreateList {
+        name: String,
+        description: Option<String>,
+        public: bool,
+    },
     DeleteList(String),
-    RemoveProblem { id_hash: String, question_id: String },
+    RenameList {
+        id_hash: String,
+        name: String,
+    },
+    RemoveProblem {
+        id_hash: String,
+        list_name: String,
+        question_id: String,
+    },
+    /// Clear the error and fetch the lists again
+    Retry,
+    /// Browse Home with only this list's problems
+    FilterHome(ListFilter),
+}
+
+fn list_filter(list: &FavoriteList) -> ListFilter {
+    ListFilter {
+        name: list.name.clone(),
+        slugs: list
+            .questions
+            .iter()
+            .map(|q| q.title_slug.clone())
+            .collect(),
+        kind: ListKind::List,
+    }
 }
 
 pub fn render_lists(frame: &mut Frame, area: Rect, state: &mut ListsState) {
     let layout = Layout::vertical([
         Constraint::Length(1), // title bar
-        Constraint::Min(3),   // content
+        Constraint::Min(3),    // content
         Constraint::Length(1), // status bar
     ])
     .split(area);
@@ -251,14 +520,13 @@ pub fn render_lists(frame: &mut Frame, area: Rect, state: &mut ListsState) {
 
     // Content
     if state.loading && state.lists.is_empty() {
-        let spinner = ["\u{280b}", "\u{2819}", "\u{2839}", "\u{2838}", "\u{283c}", "\u{2834}", "\u{2826}", "\u{2827}", "\u{2807}", "\u{280f}"];
-        let s = spinner[state.spinner_frame % spinner.len()];
+        let s = spinner(state.spinner_frame);
         let loading = Paragraph::new(format!(" {s} Loading lists..."))
-            .style(Style::default().fg(Color::Yellow));
+            .style(Style::default().fg(theme().warning));
         frame.render_widget(loading, layout[1]);
     } else if let Some(ref err) = state.error_message {
-        let error = Paragraph::new(format!(" Error: {err}"))
-            .style(Style::default().fg(Color::Red));
+        let error =
+            Paragraph::new(format!(" Error: {err}")).style(Style::default().fg(theme().error));
         frame.render_widget(error, layout[1]);
     } else if state.viewing_list.is_some() {
         render_problem_table(frame, layout[1], state);
@@ -267,15 +535,24 @@ pub fn render_lists(frame: &mut Frame, area: Rect, state: &mut ListsState) {
     }
 
     // Status bar
-    let hints = if state.create_mode {
-        vec![("Enter", "Create"), ("Esc", "Cancel")]
+    let hints = if state.create.is_some() {
+        vec![
+            ("Tab", "Next Field"),
+            ("Enter", "Create"),
+            ("Esc", "Cancel"),
+        ]
+    } else if state.rename_input.is_some() {
+        vec![("Enter", "Rename"), ("Esc", "Cancel")]
     } else if state.confirm_delete {
-        vec![("y", "Confirm"), ("any", "Cancel")]
+        vec![("y", "Delete"), ("any", "Cancel")]
+    } else if state.error_message.is_some() {
+        vec![("R", "Retry"), ("Esc", "Back"), ("?", "Help")]
     } else if state.viewing_list.is_some() {
         vec![
             ("j/k", "Navigate"),
             ("Enter", "View"),
             ("d", "Remove"),
+            ("f", "Filter Home"),
             ("b/Esc", "Back"),
             ("?", "Help"),
         ]
@@ -284,7 +561,9 @@ pub fn render_lists(frame: &mut Frame, area: Rect, state: &mut ListsState) {
             ("j/k", "Navigate"),
             ("Enter", "Open"),
             ("n", "New List"),
+            ("r", "Rename"),
             ("d", "Delete"),
+            ("f", "Filter Home"),
             ("Esc", "Back"),
             ("?", "Help"),
         ]
@@ -292,15 +571,20 @@ pub fn render_lists(frame: &mut Frame, area: Rect, state: &mut ListsState) {
     render_status_bar(frame, layout[2], &hints);
 
     // Create overlay
-    if state.create_mode {
-        render_create_overlay(frame, area, &state.create_input);
+    if let Some(ref form) = state.create {
+        render_create_overlay(frame, area, form);
+    }
+
+    // Rename overlay
+    if let Some(ref input) = state.rename_input {
+        render_rename_overlay(frame, area, input, state.rename_error.as_deref());
     }
 
     // Confirm delete overlay
-    if state.confirm_delete {
-        if let Some(list) = state.selected_list() {
-            render_confirm_delete(frame, area, &list.name, list.questions.len());
-        }
+    if state.confirm_delete
+        && let Some(list) = state.selected_list()
+    {
+        render_confirm_delete(frame, area, &list.name, list.questions.len());
     }
 }
 
@@ -309,8 +593,8 @@ fn render_title_bar(frame: &mut Frame, area: Rect, state: &ListsState) {
         Span::styled(
             " Lists ",
             Style::default()
-                .fg(Color::Black)
-                .bg(Color::Magenta)
+                .fg(theme().on_accent)
+                .bg(theme().special)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" "),
@@ -320,25 +604,28 @@ fn render_title_bar(frame: &mut Frame, area: Rect, state: &ListsState) {
         spans.push(Span::styled(
             format!("{} ", list.name),
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme().accent)
                 .add_modifier(Modifier::BOLD),
         ));
         spans.push(Span::styled(
             format!("{} problems", list.questions.len()),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme().dim),
         ));
     } else {
         spans.push(Span::styled(
             format!("{} lists", state.lists.len()),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme().dim),
         ));
     }
 
-    let title = Paragraph::new(Line::from(spans)).style(Style::default().bg(Color::Black));
+    let title = Paragraph::new(Line::from(spans)).style(Style::default().bg(theme().bar_bg));
     frame.render_widget(title, area);
 }
 
 fn render_list_table(frame: &mut Frame, area: Rect, state: &mut ListsState) {
+    state.table_height = area.height.saturating_sub(1); // minus header row
+    state.table_area = area;
+
     let header = Row::new([
         Cell::from("Name"),
         Cell::from("Problems"),
@@ -346,7 +633,7 @@ fn render_list_table(frame: &mut Frame, area: Rect, state: &mut ListsState) {
     ])
     .style(
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme().accent)
             .add_modifier(Modifier::BOLD),
     );
 
@@ -355,9 +642,9 @@ fn render_list_table(frame: &mut Frame, area: Rect, state: &mut ListsState) {
         .iter()
         .map(|list| {
             let vis = if list.is_public_favorite {
-                Span::styled("Public", Style::default().fg(Color::Green))
+                Span::styled("Public", Style::default().fg(theme().success))
             } else {
-                Span::styled("Private", Style::default().fg(Color::DarkGray))
+                Span::styled("Private", Style::default().fg(theme().dim))
             };
             Row::new([
                 Cell::from(format!(" {}", list.name)),
@@ -378,15 +665,18 @@ fn render_list_table(frame: &mut Frame, area: Rect, state: &mut ListsState) {
         .block(Block::default().borders(Borders::NONE))
         .row_highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme().selection_bg)
                 .add_modifier(Modifier::BOLD),
         )
-        .highlight_symbol("\u{25b8} ");
+        .highlight_symbol(format!("{} ", icons().pointer));
 
     frame.render_stateful_widget(table, area, &mut state.list_table_state);
 }
 
 fn render_problem_table(frame: &mut Frame, area: Rect, state: &mut ListsState) {
+    state.table_height = area.height.saturating_sub(1); // minus header row
+    state.table_area = area;
+
     let list = match state.viewing_list.and_then(|i| state.lists.get(i)) {
         Some(l) => l,
         None => return,
@@ -394,11 +684,14 @@ fn render_problem_table(frame: &mut Frame, area: Rect, state: &mut ListsState) {
 
     let header = Row::new([
         Cell::from(" "),
+        Cell::from(" # "),
         Cell::from("Title"),
+        Cell::from("Difficulty"),
+        Cell::from("Topics"),
     ])
     .style(
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme().accent)
             .add_modifier(Modifier::BOLD),
     );
 
@@ -408,61 +701,166 @@ fn render_problem_table(frame: &mut Frame, area: Rect, state: &mut ListsState) {
         .map(|q| {
             let status_cell = match q.status.as_deref() {
                 Some("ac") => Cell::from(Span::styled(
-                    " \u{2714}",
-                    Style::default().fg(Color::Green),
+                    format!(" {}", icons().check),
+                    Style::default().fg(theme().success),
                 )),
                 Some("notac") => Cell::from(Span::styled(
-                    " \u{25cf}",
-                    Style::default().fg(Color::Yellow),
+                    format!(" {}", icons().dot),
+                    Style::default().fg(theme().warning),
                 )),
                 _ => Cell::from("  "),
             };
+            // Blank when the problem list doesn't have it either
+            let info = state.problems.get(&q.title_slug);
+            let id = info.map_or("", |p| p.frontend_question_id.as_str());
+            let difficulty = info.map_or("", |p| p.difficulty.as_str());
+            let topics = info.map_or(String::new(), |p| {
+                let names: Vec<&str> = p
+                    .topic_tags
+                    .iter()
+                    .take(TOPICS_SHOWN)
+                    .map(|t| t.name.as_str())
+                    .collect();
+                names.join(", ")
+            });
             Row::new([
                 status_cell,
+                Cell::from(format!(" {id}")),
                 Cell::from(format!(" {}", q.title)),
+                Cell::from(Span::styled(
+                    difficulty.to_string(),
+                    Style::default().fg(theme().difficulty(difficulty)),
+                )),
+                Cell::from(Span::styled(topics, Style::default().fg(theme().dim))),
             ])
         })
         .collect();
 
-    let widths = [Constraint::Length(3), Constraint::Min(20)];
+    let widths = [
+        Constraint::Length(icons().status_width()),
+        Constraint::Length(6),
+        Constraint::Min(20),
+        Constraint::Length(10),
+        Constraint::Length(28),
+    ];
 
     let table = Table::new(rows, widths)
         .header(header)
         .block(Block::default().borders(Borders::NONE))
         .row_highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme().selection_bg)
                 .add_modifier(Modifier::BOLD),
         )
-        .highlight_symbol("\u{25b8} ");
+        .highlight_symbol(format!("{} ", icons().pointer));
 
     frame.render_stateful_widget(table, area, &mut state.problem_table_state);
 }
 
-fn render_create_overlay(frame: &mut Frame, area: Rect, input: &str) {
-    let w = 40u16.min(area.width.saturating_sub(4));
-    let h = 5u16;
+fn render_create_overlay(frame: &mut Frame, area: Rect, form: &NewListForm) {
+    let w = 52u16.min(area.width.saturating_sub(4));
+    let h = 8u16;
     let x = area.x + (area.width.saturating_sub(w)) / 2;
     let y = area.y + (area.height.saturating_sub(h)) / 2;
     let overlay = Rect::new(x, y, w, h);
 
+    let label = |field: NewListField, text: &'static str| {
+        let style = if form.field == field {
+            Style::default()
+                .fg(theme().accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme().dim)
+        };
+        Span::styled(text, style)
+    };
+    // Inside the borders, after the 14-column label
+    let field_width = (w as usize).saturating_sub(2 + 14);
+    let input = |field: NewListField, text: &TextInput| {
+        text.spans(field_width, form.field == field, Style::default())
+    };
+    let lines = vec![
+        Line::from(""),
+        Line::from(
+            [
+                vec![label(NewListField::Name, " Name:        ")],
+                input(NewListField::Name, &form.name),
+            ]
+            .concat(),
+        ),
+        Line::from(
+            [
+                vec![label(NewListField::Description, " Description: ")],
+                input(NewListField::Description, &form.description),
+            ]
+            .concat(),
+        ),
+        Line::from(vec![
+            label(NewListField::Public, " Public:      "),
+            Span::raw(if form.public {
+                "[x] yes"
+            } else {
+                "[ ] no, private"
+            }),
+        ]),
+        Line::from(""),
+        match form.error {
+            Some(ref e) => Line::from(Span::styled(
+                format!(" {e}"),
+                Style::default().fg(theme().error),
+            )),
+            None => Line::from(Span::styled(
+                " Space toggles Public",
+                Style::default().fg(theme().dim),
+            )),
+        },
+    ];
+
     frame.render_widget(Clear, overlay);
-    let text = format!("\n {input}\u{258e}");
-    let p = Paragraph::new(text)
+    let p = Paragraph::new(lines)
         .block(
             Block::default()
                 .title(" New List ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(theme().accent)),
         )
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme().text))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(p, overlay);
+}
+
+fn render_rename_overlay(frame: &mut Frame, area: Rect, input: &TextInput, error: Option<&str>) {
+    let w = 40u16.min(area.width.saturating_sub(4));
+    let h = 6u16;
+    let x = area.x + (area.width.saturating_sub(w)) / 2;
+    let y = area.y + (area.height.saturating_sub(h)) / 2;
+    let overlay = Rect::new(x, y, w, h);
+
+    frame.render_widget(Clear, overlay);
+    let mut field = vec![Span::raw(" ")];
+    field.extend(input.spans((w as usize).saturating_sub(3), true, Style::default()));
+    let mut lines = vec![Line::from(""), Line::from(field)];
+    if let Some(e) = error {
+        lines.push(Line::from(Span::styled(
+            format!(" {e}"),
+            Style::default().fg(theme().error),
+        )));
+    }
+    let p = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Rename List ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent)),
+        )
+        .style(Style::default().fg(theme().text))
         .wrap(Wrap { trim: false });
     frame.render_widget(p, overlay);
 }
 
 fn render_confirm_delete(frame: &mut Frame, area: Rect, name: &str, problem_count: usize) {
     let w = 44u16.min(area.width.saturating_sub(4));
-    let h = 5u16;
+    let h = 7u16;
     let x = area.x + (area.width.saturating_sub(w)) / 2;
     let y = area.y + (area.height.saturating_sub(h)) / 2;
     let overlay = Rect::new(x, y, w, h);
@@ -473,15 +871,17 @@ fn render_confirm_delete(frame: &mut Frame, area: Rect, name: &str, problem_coun
     } else {
         String::new()
     };
-    let text = format!("\n Delete \"{name}\"{count_hint}?\n (y) Yes  (any) Cancel");
+    let text = format!(
+        "\n Delete \"{name}\"{count_hint}?\n This can't be undone.\n\n (y) Delete  (N) Cancel"
+    );
     let p = Paragraph::new(text)
         .block(
             Block::default()
                 .title(" Confirm Delete ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red)),
+                .border_style(Style::default().fg(theme().error)),
         )
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme().text))
         .wrap(Wrap { trim: true });
     frame.render_widget(p, overlay);
 }
@@ -1,7 +1,18 @@
-pub mod home;
+pub mod activity;
+pub mod breadcrumb;
+pub mod company;
+pub mod contests;
 pub mod detail;
+pub mod fuzzy;
+pub mod home;
+pub mod icons;
 pub mod lists;
+pub mod motion;
+pub mod mouse;
 pub mod result;
 pub mod rich_text;
 pub mod setup;
+pub mod solutions;
 pub mod status_bar;
+pub mod text_input;
+pub mod theme;
@@ -0,0 +1,140 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::time::{Duration, Instant};
+
+/// How long a count prefix or a lone `g` waits for the rest of the motion.
+const PENDING_TIMEOUT: Duration = Duration::from_millis(1500);
+const MAX_COUNT: usize = 99_999;
+
+/// A table movement decoded from vim-style keys.
+pub enum Motion {
+    /// Key was swallowed into the pending buffer
+    Pending,
+    Down(usize),
+    Up(usize),
+    Top,
+    Bottom,
+    /// `{count}G` / `{count}gg`
+    Goto(usize),
+    HalfPageDown,
+    HalfPageUp,
+    /// PageDown / PageUp: a full screen of rows
+    PageDown,
+    PageUp,
+    /// Not a motion — the screen should handle the key itself
+    Other,
+}
+
+/// Buffers count prefixes (`25j`) and the first `g` of `gg`.
+#[derive(Default)]
+pub struct PendingKeys {
+    count: Option<usize>,
+    g: bool,
+    started: Option<Instant>,
+}
+
+impl PendingKeys {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn feed(&mut self, key: KeyEvent) -> Motion {
+        if self.started.is_some_and(|t| t.elapsed() > PENDING_TIMEOUT) {
+            self.clear();
+        }
+
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let count = self.count;
+
+        let motion = match key.code {
+            KeyCode::Char('d') if ctrl => Motion::HalfPageDown,
+            KeyCode::Char('u') if ctrl => Motion::HalfPageUp,
+            _ if ctrl => Motion::Other,
+            KeyCode::PageDown => Motion::PageDown,
+            KeyCode::PageUp => Motion::PageUp,
+            KeyCode::Char(c @ '0'..='9') if !self.g && (c != '0' || count.is_some()) => {
+                let digit = c as usize - '0' as usize;
+                self.count = Some((count.unwrap_or(0) * 10 + digit).min(MAX_COUNT));
+                self.started.get_or_insert_with(Instant::now);
+                return Motion::Pending;
+            }
+            KeyCode::Char('g') if self.g => count.map_or(Motion::Top, Motion::Goto),
+            KeyCode::Char('g') => {
+                self.g = true;
+                self.started.get_or_insert_with(Instant::now);
+                return Motion::Pending;
+            }
+            KeyCode::Char('G') => count.map_or(Motion::Bottom, Motion::Goto),
+            KeyCode::Char('j') | KeyCode::Down => Motion::Down(count.unwrap_or(1)),
+            KeyCode::Char('k') | KeyCode::Up => Motion::Up(count.unwrap_or(1)),
+            _ => Motion::Other,
+        };
+
+        self.clear();
+        motion
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(pending: &mut PendingKeys, keys: &str) -> Motion {
+        let mut motion = Motion::Other;
+        for c in keys.chars() {
+            motion = pending.feed(KeyEvent::from(KeyCode::Char(c)));
+        }
+        motion
+    }
+
+    #[test]
+    fn counts_accumulate_until_a_motion() {
+        let mut pending = PendingKeys::default();
+        assert!(matches!(feed(&mut pending, "2"), Motion::Pending));
+        assert!(matches!(feed(&mut pending, "5j"), Motion::Down(25)));
+        assert!(matches!(feed(&mut pending, "k"), Motion::Up(1)));
+        assert!(matches!(feed(&mut pending, "10k"), Motion::Up(10)));
+        assert!(matches!(
+            feed(&mut pending, "1234567j"),
+            Motion::Down(MAX_COUNT)
+        ));
+        // A lone 0 isn't a count
+        assert!(matches!(feed(&mut pending, "0"), Motion::Other));
+        assert!(matches!(
+            pending.feed(KeyEvent::from(KeyCode::Down)),
+            Motion::Down(1)
+        ));
+    }
+
+    #[test]
+    fn g_motions_go_to_the_top_bottom_or_a_row() {
+        let mut pending = PendingKeys::default();
+        assert!(matches!(feed(&mut pending, "g"), Motion::Pending));
+        assert!(matches!(feed(&mut pending, "g"), Motion::Top));
+        assert!(matches!(feed(&mut pending, "G"), Motion::Bottom));
+        assert!(matches!(feed(&mut pending, "42gg"), Motion::Goto(42)));
+        assert!(matches!(feed(&mut pending, "7G"), Motion::Goto(7)));
+        // Digits after the first g don't count
+        assert!(matches!(feed(&mut pending, "g5"), Motion::Other));
+    }
+
+    #[test]
+    fn an_unrelated_key_or_a_pause_resets_pending_keys() {
+        let mut pending = PendingKeys::default();
+        assert!(matches!(feed(&mut pending, "3x"), Motion::Other));
+        assert!(matches!(feed(&mut pending, "j"), Motion::Down(1)));
+        assert!(matches!(feed(&mut pending, "gx"), Motion::Other));
+        assert!(matches!(feed(&mut pending, "g"), Motion::Pending));
+
+        let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        let mut pending = PendingKeys::default();
+        feed(&mut pending, "4");
+        assert!(matches!(pending.feed(ctrl_d), Motion::HalfPageDown));
+        assert!(matches!(feed(&mut pending, "j"), Motion::Down(1)));
+
+        let mut pending = PendingKeys::default();
+        feed(&mut pending, "9g");
+        pending.started = Instant::now().checked_sub(PENDING_TIMEOUT * 2);
+        assert!(matches!(feed(&mut pending, "g"), Motion::Pending));
+        assert!(matches!(feed(&mut pending, "g"), Motion::Top));
+    }
+}
@@ -0,0 +1,38 @@
+use ratatui::layout::{Position, Rect};
+use std::time::{Duration, Instant};
+
+/// Rows or lines moved per scroll-wheel notch.
+pub const WHEEL_STEP: i32 = 3;
+
+/// Terminals only report single clicks, so a double-click is two clicks on
+/// the same row within this window.
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+#[derive(Default)]
+pub struct ClickTracker {
+    last: Option<(usize, Instant)>,
+}
+
+impl ClickTracker {
+    /// Record a click on `row`, returning whether it completes a double-click.
+    pub fn click(&mut self, row: usize) -> bool {
+        let double = self
+            .last
+            .is_some_and(|(last, at)| last == row && at.elapsed() <= DOUBLE_CLICK);
+        self.last = if double {
+            None
+        } else {
+            Some((row, Instant::now()))
+        };
+        double
+    }
+}
+
+/// Index of the table row under the cursor, for a table drawn in `area`
+/// with a one-line header and scrolled to `offset`.
+pub fn table_row_at(area: Rect, offset: usize, column: u16, row: u16) -> Option<usize> {
+    if !area.contains(Position::new(column, row)) || row <= area.y {
+        return None;
+    }
+    Some(offset + (row - area.y - 1) as usize)
+}
@@ -1,60 +1,36 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 use ratatui::{
+    Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
-    Frame,
 };
 
-use crate::api::types::CheckResponse;
+use crate::api::types::{ResultData, ResultKind};
+use crate::local_test::{self, LocalTestRun};
 
+use super::icons::{icons, spinner};
+use super::mouse::WHEEL_STEP;
 use super::status_bar::render_status_bar;
+use super::theme::theme;
 
-#[derive(Debug, Clone, Copy)]
-pub enum ResultKind {
-    Run,
-    Submit,
-}
-
+/// The first test case that failed, as reported or worked out from the
+/// per-case answers.
 #[derive(Debug, Clone)]
-pub struct ResultData {
-    pub status_msg: String,
-    pub status_code: i32,
-    pub total_correct: Option<i32>,
-    pub total_testcases: Option<i32>,
-    pub runtime: Option<String>,
-    pub memory: Option<String>,
-    pub code_output: Option<Vec<String>>,
-    pub expected_output: Option<String>,
-    pub last_testcase: Option<String>,
-    pub compile_error: Option<String>,
-}
-
-impl ResultData {
-    pub fn from_check(resp: &CheckResponse) -> Self {
-        Self {
-            status_msg: resp.status_msg.clone().unwrap_or_default(),
-            status_code: resp.status_code.unwrap_or(-1),
-            total_correct: resp.total_correct,
-            total_testcases: resp.total_testcases,
-            runtime: resp.status_runtime.clone(),
-            memory: resp.status_memory.clone(),
-            code_output: resp.code_answer.clone().or(resp.code_output.clone()),
-            expected_output: resp.expected_output.clone().or_else(|| {
-                resp.expected_code_answer.as_ref().map(|v| v.join("\n"))
-            }),
-            last_testcase: resp.last_testcase.clone(),
-            compile_error: resp.full_compile_error.clone().or(resp.compile_error.clone()),
-        }
-    }
+pub struct FailingCase {
+    pub input: String,
+    pub expected: Option<String>,
+    pub output: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub enum ResultStatus {
     Pending,
-    Success(ResultData),
-    Error(String),
+    Success(Box<ResultData>),
+    /// Local tests finished; there's no LeetCode verdict
+    Done,
+    Error,
 }
 
 pub struct ResultState {
@@ -62,29 +38,57 @@ pub struct ResultState {
     pub status: ResultStatus,
     pub problem_title: String,
     pub scroll_offset: u16,
+    /// Horizontal offset, only used when `wrap` is off
+    pub h_scroll: u16,
+    /// Compile errors are shown unwrapped so column markers stay aligned
+    pub wrap: bool,
     pub spinner_frame: usize,
     pub content_lines: Vec<Line<'static>>,
     pub content_height: u16,
     pub detail: crate::api::types::QuestionDetail,
+    pub failing_case: Option<FailingCase>,
+    /// Stdout shown in full instead of a few lines per case (toggled with `o`)
+    pub stdout_expanded: bool,
 }
 
+/// Stdout lines shown per test case before it's cut off.
+const STDOUT_PREVIEW: usize = 8;
+
 impl ResultState {
-    pub fn new(kind: ResultKind, problem_title: String, detail: crate::api::types::QuestionDetail) -> Self {
+    pub fn new(
+        kind: ResultKind,
+        problem_title: String,
+        detail: crate::api::types::QuestionDetail,
+    ) -> Self {
         Self {
             kind,
             status: ResultStatus::Pending,
             problem_title,
             scroll_offset: 0,
+            h_scroll: 0,
+            wrap: true,
             spinner_frame: 0,
             content_lines: Vec::new(),
             content_height: 0,
             detail,
+            failing_case: None,
+            stdout_expanded: false,
         }
     }
 
     pub fn set_result(&mut self, data: ResultData) {
         self.content_lines = build_result_lines(&data, self.kind);
-        self.status = ResultStatus::Success(data);
+        self.content_lines
+            .extend(stdout_lines(&data, self.stdout_expanded));
+        self.wrap = data.compile_error.is_none();
+        self.failing_case = self.find_failing_case(&data);
+        self.status = ResultStatus::Success(Box::new(data));
+    }
+
+    pub fn set_local_result(&mut self, run: LocalTestRun) {
+        self.content_lines = local_test_lines(&run);
+        self.wrap = true;
+        self.status = ResultStatus::Done;
     }
 
     pub fn set_error(&mut self, msg: String) {
@@ -92,10 +96,10 @@ impl ResultState {
             Line::from(""),
             Line::from(Span::styled(
                 format!("  Error: {msg}"),
-                Style::default().fg(Color::Red),
+                Style::default().fg(theme().error),
             )),
         ];
-        self.status = ResultStatus::Error(msg);
+        self.status = ResultStatus::Error;
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> ResultAction {
@@ -113,10 +117,95 @@ impl ResultState {
                 self.scroll(-1);
                 ResultAction::None
             }
+            KeyCode::Char('y') => match self.failing_case {
+                Some(ref case) => ResultAction::CopyTestcase(case.input.clone()),
+                None => ResultAction::None,
+            },
+            KeyCode::Char('t') => match self.failing_case {
+                Some(ref case) => ResultAction::AddTestCase(case.clone()),
+                None => ResultAction::None,
+            },
+            KeyCode::Char('e') => ResultAction::Edit,
+            KeyCode::Char('w') => ResultAction::ToggleWatch,
+            // Not while pending, or the old verdict could land on the new attempt
+            KeyCode::Char('r') if !matches!(self.status, ResultStatus::Pending) => {
+                ResultAction::Rerun
+            }
+            KeyCode::Char('s') if !matches!(self.status, ResultStatus::Pending) => {
+                ResultAction::Resubmit
+            }
+            KeyCode::Char('o') => {
+                if let ResultStatus::Success(ref data) = self.status
+                    && !data.stdout.is_empty()
+                {
+                    let data = ResultData::clone(data);
+                    self.stdout_expanded = !self.stdout_expanded;
+                    self.set_result(data);
+                }
+                ResultAction::None
+            }
+            KeyCode::Char('d') => {
+                self.scroll(self.content_height as i32 / 2);
+                ResultAction::None
+            }
+            KeyCode::Char('u') => {
+                self.scroll(-(self.content_height as i32 / 2));
+                ResultAction::None
+            }
+            KeyCode::Char('l') | KeyCode::Right if !self.wrap => {
+                self.h_scroll = self.h_scroll.saturating_add(4);
+                ResultAction::None
+            }
+            KeyCode::Char('h') | KeyCode::Left if !self.wrap => {
+                self.h_scroll = self.h_scroll.saturating_sub(4);
+                ResultAction::None
+            }
             _ => ResultAction::None,
         }
     }
 
+    /// Submissions report the failing case directly. Runs only report
+    /// per-case answers, so find the first sample whose answer differs.
+    fn find_failing_case(&self, data: &ResultData) -> Option<FailingCase> {
+        if let Some(ref input) = data.last_testcase
+            && data.status_code != 10
+            && !input.is_empty()
+        {
+            return Some(FailingCase {
+                input: input.clone(),
+                expected: data.expected_output.clone(),
+                output: data.code_output.as_ref().map(|o| o.join("\n")),
+            });
+        }
+        if !matches!(self.kind, ResultKind::Run) || data.status_code != 10 {
+            return None;
+        }
+        let outputs = data.code_output.as_ref()?;
+        let expected: Vec<&str> = data.expected_output.as_deref()?.lines().collect();
+        let case = outputs
+            .iter()
+            .zip(&expected)
+            .position(|(out, exp)| out != exp)?;
+        Some(FailingCase {
+            input: self
+                .detail
+                .example_testcase_list
+                .as_ref()?
+                .get(case)?
+                .clone(),
+            expected: Some(expected[case].to_string()),
+            output: Some(outputs[case].clone()),
+        })
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll(WHEEL_STEP),
+            MouseEventKind::ScrollUp => self.scroll(-WHEEL_STEP),
+            _ => {}
+        }
+    }
+
     fn scroll(&mut self, delta: i32) {
         let new_offset = self.scroll_offset as i32 + delta;
         self.scroll_offset = new_offset.max(0) as u16;
@@ -126,13 +215,21 @@ impl ResultState {
 pub enum ResultAction {
     None,
     Back,
+    CopyTestcase(String),
+    AddTestCase(FailingCase),
+    /// Open the solution file in the editor
+    Edit,
+    Rerun,
+    Resubmit,
+    /// Turn watch mode on or off
+    ToggleWatch,
     Quit,
 }
 
 pub fn render_result(frame: &mut Frame, area: Rect, state: &mut ResultState) {
     let layout = Layout::vertical([
         Constraint::Length(3), // title bar
-        Constraint::Min(3),   // content
+        Constraint::Min(3),    // content
         Constraint::Length(1), // status bar
     ])
     .split(area);
@@ -141,86 +238,140 @@ pub fn render_result(frame: &mut Frame, area: Rect, state: &mut ResultState) {
     let kind_label = match state.kind {
         ResultKind::Run => "Run (sample cases)",
         ResultKind::Submit => "Submit (all cases)",
+        ResultKind::LocalTest => "Local Tests",
     };
     let title_line = Line::from(vec![
         Span::styled(
             format!(" {kind_label} Result "),
             Style::default()
-                .fg(Color::Black)
-                .bg(Color::Yellow)
+                .fg(theme().on_accent)
+                .bg(theme().warning)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" "),
         Span::styled(
             &state.problem_title,
             Style::default()
-                .fg(Color::White)
+                .fg(theme().text)
                 .add_modifier(Modifier::BOLD),
         ),
     ]);
 
-    let title_block = Paragraph::new(vec![title_line])
-        .block(
-            Block::default()
-                .borders(Borders::BOTTOM)
-                .border_style(Style::default().fg(Color::DarkGray)),
-        );
+    let title_block = Paragraph::new(vec![title_line]).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(theme().dim)),
+    );
     frame.render_widget(title_block, layout[0]);
 
     // Content area
     state.content_height = layout[1].height;
 
     if matches!(state.status, ResultStatus::Pending) {
-        let spinner = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
-        let s = spinner[state.spinner_frame % spinner.len()];
+        let s = spinner(state.spinner_frame);
         let elapsed = state.spinner_frame / 10; // 100ms tick rate
         let kind_verb = match state.kind {
             ResultKind::Run => "Running",
             ResultKind::Submit => "Submitting",
+            ResultKind::LocalTest => "Testing locally",
         };
         let loading = Paragraph::new(format!("\n  {s} {kind_verb}... ({elapsed}s)"))
-            .style(Style::default().fg(Color::Yellow));
+            .style(Style::default().fg(theme().warning));
         frame.render_widget(loading, layout[1]);
     } else {
-        let total_lines = state.content_lines.len() as u16;
+        let mut content = Paragraph::new(state.content_lines.clone())
+            .block(Block::default().borders(Borders::NONE));
+        if state.wrap {
+            content = content.wrap(Wrap { trim: false });
+        }
+
+        // Count rendered rows, not source lines, so wrapped output can be
+        // scrolled to its end but not past it
+        let total_lines = content.line_count(layout[1].width) as u16;
         let max_scroll = total_lines.saturating_sub(state.content_height);
         if state.scroll_offset > max_scroll {
             state.scroll_offset = max_scroll;
         }
 
-        let content = Paragraph::new(state.content_lines.clone())
-            .block(Block::default().borders(Borders::NONE))
-            .wrap(Wrap { trim: false })
-            .scroll((state.scroll_offset, 0));
-
-        frame.render_widget(content, layout[1]);
+        let max_width = state
+            .content_lines
+            .iter()
+            .map(|l| l.width())
+            .max()
+            .unwrap_or(0) as u16;
+        state.h_scroll = state
+            .h_scroll
+            .min(max_width.saturating_sub(layout[1].width));
+        let h_scroll = if state.wrap { 0 } else { state.h_scroll };
+
+        frame.render_widget(content.scroll((state.scroll_offset, h_scroll)), layout[1]);
+
+        // Scroll indicator
+        if total_lines > state.content_height {
+            let pct = if max_scroll > 0 {
+                (state.scroll_offset as f64 / max_scroll as f64 * 100.0) as u16
+            } else {
+                100
+            };
+            let indicator = format!(" {}% ", pct);
+            let ind_area = Rect::new(
+                layout[1].right().saturating_sub(indicator.len() as u16 + 1),
+                layout[1].y,
+                indicator.len() as u16,
+                1,
+            );
+            frame.render_widget(
+                Paragraph::new(indicator).style(Style::default().fg(theme().dim)),
+                ind_area,
+            );
+        }
     }
 
     // Status bar
-    render_status_bar(
-        frame,
-        layout[2],
-        &[
-            ("j/k", "Scroll"),
-            ("b/Esc", "Back"),
-            ("q", "Quit"),
-            ("?", "Help"),
-        ],
-    );
+    let mut hints = vec![("j/k", "Scroll"), ("d/u", "Half page")];
+    if !state.wrap {
+        hints.push(("h/l", "Scroll sideways"));
+    }
+    if let ResultStatus::Success(ref data) = state.status
+        && !data.stdout.is_empty()
+    {
+        hints.push((
+            "o",
+            if state.stdout_expanded {
+                "Less stdout"
+            } else {
+                "All stdout"
+            },
+        ));
+    }
+    if state.failing_case.is_some() {
+        hints.push(("y", "Copy failing case"));
+        hints.push(("t", "Add as test"));
+    }
+    hints.extend([
+        ("e", "Edit"),
+        ("r", "Run"),
+        ("s", "Submit"),
+        ("w", "Watch"),
+        ("b/Esc", "Back"),
+        ("q", "Quit"),
+        ("?", "Help"),
+    ]);
+    render_status_bar(frame, layout[2], &hints);
 }
 
-fn build_result_lines(data: &ResultData, kind: ResultKind) -> Vec<Line<'static>> {
+pub fn build_result_lines(data: &ResultData, kind: ResultKind) -> Vec<Line<'static>> {
     let mut lines: Vec<Line<'static>> = Vec::new();
     lines.push(Line::from(""));
 
     // Status code 10 = Accepted, 11 = Wrong Answer, 12 = MLE, 13 = Output Limit,
     // 14 = TLE, 15 = Runtime Error, 20 = Compile Error
     let (icon, color) = match data.status_code {
-        10 => ("✔", Color::Green),
-        20 => ("✘", Color::Red),
-        14 => ("⏱", Color::Yellow),
-        15 => ("!", Color::Red),
-        _ => ("✘", Color::Red),
+        10 => (icons().check, theme().success),
+        20 => (icons().cross, theme().error),
+        14 => (icons().timer, theme().warning),
+        15 => ("!", theme().error),
+        _ => (icons().cross, theme().error),
     };
 
     lines.push(Line::from(Span::styled(
@@ -232,10 +383,14 @@ fn build_result_lines(data: &ResultData, kind: ResultKind) -> Vec<Line<'static>>
     // Passed count
     if let (Some(correct), Some(total)) = (data.total_correct, data.total_testcases) {
         lines.push(Line::from(vec![
-            Span::styled("  Passed: ", Style::default().fg(Color::White)),
+            Span::styled("  Passed: ", Style::default().fg(theme().text)),
             Span::styled(
                 format!("{correct} / {total}"),
-                Style::default().fg(if correct == total { Color::Green } else { Color::Yellow }),
+                Style::default().fg(if correct == total {
+                    theme().success
+                } else {
+                    theme().warning
+                }),
             ),
         ]));
     }
@@ -243,14 +398,14 @@ fn build_result_lines(data: &ResultData, kind: ResultKind) -> Vec<Line<'static>>
     // Runtime & memory (for accepted/submit)
     if let Some(ref rt) = data.runtime {
         lines.push(Line::from(vec![
-            Span::styled("  Runtime: ", Style::default().fg(Color::White)),
-            Span::styled(rt.clone(), Style::default().fg(Color::Cyan)),
+            Span::styled("  Runtime: ", Style::default().fg(theme().text)),
+            Span::styled(rt.clone(), Style::default().fg(theme().accent)),
         ]));
     }
     if let Some(ref mem) = data.memory {
         lines.push(Line::from(vec![
-            Span::styled("  Memory: ", Style::default().fg(Color::White)),
-            Span::styled(mem.clone(), Style::default().fg(Color::Cyan)),
+            Span::styled("  Memory: ", Style::default().fg(theme().text)),
+            Span::styled(mem.clone(), Style::default().fg(theme().accent)),
         ]));
     }
 
@@ -259,56 +414,98 @@ fn build_result_lines(data: &ResultData, kind: ResultKind) -> Vec<Line<'static>>
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "  Compile Error:",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme().error)
+                .add_modifier(Modifier::BOLD),
+        )));
+        // Verbatim apart from tabs, which the terminal would render at an
+        // unpredictable width and throw off the column markers
+        for line in err.lines() {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", line.replace('\t', "    ")),
+                Style::default().fg(theme().error),
+            )));
+        }
+    }
+
+    // Runtime error
+    if let Some(ref err) = data.runtime_error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  Runtime Error:",
+            Style::default()
+                .fg(theme().error)
+                .add_modifier(Modifier::BOLD),
         )));
         for line in err.lines() {
             lines.push(Line::from(Span::styled(
-                format!    ("  {line}"),
-                Style::default().fg(Color::Red),
+                format!("  {line}"),
+                Style::default().fg(theme().error),
             )));
         }
     }
 
-    // Wrong answer diff
-    if data.status_code != 10 && data.status_code != 20 || (data.status_code == 11 || (data.status_code != 10 && data.last_testcase.is_some())) {
-        if let Some(ref input) = data.last_testcase {
+    // Failing case: Wrong Answer, Runtime Error, TLE and friends
+    let failed = data.status_code != 10 && (data.status_code != 20 || data.last_testcase.is_some());
+    if failed {
+        if data.last_testcase.is_some() || data.code_output.is_some() {
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
-                "  Last Testcase:",
-                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                "  Failing Case",
+                Style::default()
+                    .fg(theme().warning)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            )));
+        }
+
+        if let Some(input) = data
+            .input_formatted
+            .as_ref()
+            .or(data.last_testcase.as_ref())
+        {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "  Input:",
+                Style::default()
+                    .fg(theme().text)
+                    .add_modifier(Modifier::BOLD),
             )));
             for line in input.lines() {
                 lines.push(Line::from(Span::styled(
                     format!("    {line}"),
-                    Style::default().fg(Color::Gray),
+                    Style::default().fg(theme().muted),
                 )));
             }
         }
 
-        if let Some(ref expected) = data.expected_output {
+        if let Some(ref output) = data.code_output {
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
-                "  Expected:",
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+                "  Output:",
+                Style::default()
+                    .fg(theme().error)
+                    .add_modifier(Modifier::BOLD),
             )));
-            for line in expected.lines() {
+            for line in output {
                 lines.push(Line::from(Span::styled(
                     format!("    {line}"),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme().error),
                 )));
             }
         }
 
-        if let Some(ref output) = data.code_output {
+        if let Some(ref expected) = data.expected_output {
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
-                "  Output:",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                "  Expected:",
+                Style::default()
+                    .fg(theme().success)
+                    .add_modifier(Modifier::BOLD),
             )));
-            for line in output {
+            for line in expected.lines() {
                 lines.push(Line::from(Span::styled(
                     format!("    {line}"),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme().success),
                 )));
             }
         }
@@ -316,31 +513,35 @@ fn build_result_lines(data: &ResultData, kind: ResultKind) -> Vec<Line<'static>>
 
     // For run mode show output even on success
     if matches!(kind, ResultKind::Run) && data.status_code == 10 {
-        if let Some(ref output) = data.code_output {
-            if !output.is_empty() {
-                lines.push(Line::from(""));
+        if let Some(ref output) = data.code_output
+            && !output.is_empty()
+        {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "  Output:",
+                Style::default()
+                    .fg(theme().text)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for line in output {
                 lines.push(Line::from(Span::styled(
-                    "  Output:",
-                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                    format!("    {line}"),
+                    Style::default().fg(theme().text),
                 )));
-                for line in output {
-                    lines.push(Line::from(Span::styled(
-                        format!("    {line}"),
-                        Style::default().fg(Color::White),
-                    )));
-                }
             }
         }
         if let Some(ref expected) = data.expected_output {
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
                 "  Expected:",
-                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme().text)
+                    .add_modifier(Modifier::BOLD),
             )));
             for line in expected.lines() {
                 lines.push(Line::from(Span::styled(
                     format!("    {line}"),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme().success),
                 )));
             }
         }
@@ -348,3 +549,108 @@ fn build_result_lines(data: &ResultData, kind: ResultKind) -> Vec<Line<'static>>
 
     lines
 }
+
+/// Verdict of a local test run followed by everything it printed.
+fn local_test_lines(run: &LocalTestRun) -> Vec<Line<'static>> {
+    let (verdict, color) = if run.timed_out {
+        (
+            format!(
+                "{} Timed out after {}s",
+                icons().timer,
+                local_test::TIMEOUT.as_secs()
+            ),
+            theme().warning,
+        )
+    } else if run.passed {
+        (
+            format!("{} Local tests passed", icons().check),
+            theme().success,
+        )
+    } else {
+        let code = run
+            .exit_code
+            .map(|c| format!(" (exit code {c})"))
+            .unwrap_or_default();
+        (
+            format!("{} Local tests failed{code}", icons().cross),
+            theme().error,
+        )
+    };
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {verdict}"),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )),
+    ];
+    if !run.output.trim().is_empty() {
+        lines.push(Line::from(""));
+        for line in run.output.lines() {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", line.replace('\t', "    ")),
+                Style::default().fg(theme().muted),
+            )));
+        }
+    }
+    lines
+}
+
+/// The "Stdout" section: each case's output, cut to a few lines unless
+/// `full`. Empty when nothing was printed.
+pub fn stdout_lines(data: &ResultData, full: bool) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    if data.stdout.is_empty() {
+        return lines;
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "  {} Stdout:",
+            if full {
+                icons().expanded
+            } else {
+                icons().pointer
+            }
+        ),
+        Style::default()
+            .fg(theme().text)
+            .add_modifier(Modifier::BOLD),
+    )));
+    let several = data.stdout.len() > 1;
+    for (i, out) in data.stdout.iter().enumerate() {
+        if out.trim().is_empty() {
+            continue;
+        }
+        if several {
+            lines.push(Line::from(Span::styled(
+                format!("    Case {}:", i + 1),
+                Style::default().fg(theme().dim),
+            )));
+        }
+        let out_lines: Vec<&str> = out.trim_end_matches('\n').lines().collect();
+        let shown = if full {
+            out_lines.len()
+        } else {
+            out_lines.len().min(STDOUT_PREVIEW)
+        };
+        for line in &out_lines[..shown] {
+            lines.push(Line::from(Span::styled(
+                format!("    {line}"),
+                Style::default().fg(theme().muted),
+            )));
+        }
+        if shown < out_lines.len() {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "    {} {} more lines (o to show all)",
+                    icons().ellipsis,
+                    out_lines.len() - shown
+                ),
+                Style::default()
+                    .fg(theme().dim)
+                    .add_modifier(Modifier::ITALIC),
+            )));
+        }
+    }
+    lines
+}
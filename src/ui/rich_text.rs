@@ -1,11 +1,10 @@
+use super::icons::icons;
+use super::theme::theme;
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
 };
 
-const BOX_STYLE: Color = Color::DarkGray;
-const CODE_BG: Color = Color::Rgb(40, 40, 55);
-
 struct Parser {
     lines: Vec<Line<'static>>,
     current_spans: Vec<Span<'static>>,
@@ -17,6 +16,8 @@ struct Parser {
     buf: String,
     last_was_blank: bool,
     pre_lines: Vec<Line<'static>>,
+    /// From a fenced block's `language-*` class, shown on its top border
+    pre_language: Option<String>,
 }
 
 impl Parser {
@@ -32,6 +33,7 @@ impl Parser {
             buf: String::new(),
             last_was_blank: false,
             pre_lines: Vec::new(),
+            pre_language: None,
         }
     }
 
@@ -39,25 +41,25 @@ impl Parser {
         let mut s = Style::default();
 
         if self.code && !self.pre {
-            s = s.fg(Color::Yellow).bg(CODE_BG);
+            s = s.fg(theme().warning).bg(theme().code_bg);
         } else if self.pre {
             if self.bold {
-                s = s.fg(Color::Cyan).add_modifier(Modifier::BOLD);
+                s = s.fg(theme().accent).add_modifier(Modifier::BOLD);
             } else {
-                s = s.fg(Color::White);
+                s = s.fg(theme().text);
             }
         } else {
-            s = s.fg(Color::White);
+            s = s.fg(theme().text);
         }
 
         if self.bold && !self.pre {
-            s = s.add_modifier(Modifier::BOLD).fg(Color::Cyan);
+            s = s.add_modifier(Modifier::BOLD).fg(theme().accent);
         }
 
         if self.italic && !self.pre {
             s = s.add_modifier(Modifier::ITALIC);
             if !self.bold && !self.code {
-                s = s.fg(Color::Gray);
+                s = s.fg(theme().muted);
             }
         }
 
@@ -107,17 +109,22 @@ impl Parser {
             .max()
             .unwrap_or(0)
             .max(20);
-        let box_w = max_w + 2; // 1 space padding each side
+        let language = self.pre_language.take().map(|l| format!(" {l} "));
+        let label_w = language.as_ref().map_or(0, |l| l.chars().count() + 1);
+        let box_w = (max_w + 2).max(label_w); // 1 space padding each side
 
-        let border_style = Style::default().fg(BOX_STYLE);
-        let bg_style = Style::default().bg(CODE_BG);
+        let border_style = Style::default().fg(theme().dim);
+        let bg_style = Style::default().bg(theme().code_bg);
 
-        // Top border
-        self.lines.push(Line::from(vec![
-            Span::styled("  ╭", border_style),
-            Span::styled("─".repeat(box_w), border_style),
-            Span::styled("╮", border_style),
-        ]));
+        // Top border, with the language after one dash
+        let mut top = vec![Span::styled("  ╭", border_style)];
+        if let Some(language) = language {
+            top.push(Span::styled("─", border_style));
+            top.push(Span::styled(language, Style::default().fg(theme().accent)));
+        }
+        top.push(Span::styled("─".repeat(box_w - label_w), border_style));
+        top.push(Span::styled("╮", border_style));
+        self.lines.push(Line::from(top));
 
         // Content lines
         for line in self.pre_lines.drain(..) {
@@ -127,9 +134,11 @@ impl Parser {
                 Span::styled("  │", border_style),
                 Span::styled(" ", bg_style),
             ];
-            spans.extend(line.spans.into_iter().map(|s| {
-                Span::styled(s.content, s.style.bg(CODE_BG))
-            }));
+            spans.extend(
+                line.spans
+                    .into_iter()
+                    .map(|s| Span::styled(s.content, s.style.bg(theme().code_bg))),
+            );
             spans.push(Span::styled(" ".repeat(pad), bg_style));
             spans.push(Span::styled("│", border_style));
             self.lines.push(Line::from(spans));
@@ -146,6 +155,64 @@ impl Parser {
     }
 }
 
+/// Editorials are Markdown with embedded HTML; render them to HTML first so
+/// they go through the same parser as problem statements.
+pub fn markdown_to_lines(markdown: &str) -> Vec<Line<'static>> {
+    let markdown = markdown.replace("[TOC]", "");
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, pulldown_cmark::Parser::new(&markdown));
+    html_to_lines(&html)
+}
+
+/// A fenced code block's language and text, for copying it out.
+pub struct CodeBlock {
+    /// As written after the fence, e.g. `Python3`; empty when unmarked
+    pub language: String,
+    pub code: String,
+}
+
+/// Every fenced or indented code block in `markdown`, in order.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    use pulldown_cmark::{CodeBlockKind, Event, Tag, TagEnd};
+
+    let mut blocks = Vec::new();
+    let mut current: Option<CodeBlock> = None;
+    for event in pulldown_cmark::Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match kind {
+                    // LeetCode marks tabbed blocks as `Python3 []`
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().unwrap_or("").to_string()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                };
+                current = Some(CodeBlock {
+                    language,
+                    code: String::new(),
+                });
+            }
+            Event::Text(text) => {
+                if let Some(ref mut block) = current {
+                    block.code.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => blocks.extend(current.take()),
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// `python` from `code class="language-python"`.
+fn code_language(tag: &str) -> Option<String> {
+    let class = tag.split("class=\"").nth(1)?.split('"').next()?;
+    class
+        .split_whitespace()
+        .find_map(|c| c.strip_prefix("language-"))
+        .map(str::to_string)
+}
+
 pub fn html_to_lines(html: &str) -> Vec<Line<'static>> {
     let mut p = Parser::new();
     let mut chars = html.chars().peekable();
@@ -185,6 +252,8 @@ pub fn html_to_lines(html: &str) -> Vec<Line<'static>> {
                     p.flush_buf();
                     if !p.pre {
                         p.code = !is_closing;
+                    } else if !is_closing {
+                        p.pre_language = code_language(&tag);
                     }
                 }
                 "pre" => {
@@ -235,13 +304,25 @@ pub fn html_to_lines(html: &str) -> Vec<Line<'static>> {
                         }
                         let indent = "  ".repeat(p.list_depth.saturating_sub(1));
                         p.current_spans.push(Span::styled(
-                            format!("{indent}  • "),
-                            Style::default().fg(Color::Cyan),
+                            format!("{indent}  {} ", icons().bullet),
+                            Style::default().fg(theme().accent),
                         ));
                     } else {
                         p.push_line();
                     }
                 }
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                    p.flush_buf();
+                    if is_closing {
+                        p.bold = false;
+                        p.push_line();
+                    } else {
+                        if !p.lines.is_empty() && !p.last_was_blank {
+                            p.ensure_blank_line();
+                        }
+                        p.bold = true;
+                    }
+                }
                 "sup" | "sub" | "div" | "span" => {}
                 _ => {}
             }
@@ -338,8 +419,8 @@ pub fn html_to_lines(html: &str) -> Vec<Line<'static>> {
     let mut result: Vec<Line<'static>> = Vec::with_capacity(p.lines.len());
     let mut prev_blank = false;
     for line in p.lines {
-        let is_blank = line.spans.is_empty()
-            || line.spans.iter().all(|s| s.content.trim().is_empty());
+        let is_blank =
+            line.spans.is_empty() || line.spans.iter().all(|s| s.content.trim().is_empty());
         if is_blank {
             if !prev_blank {
                 result.push(Line::from(""));
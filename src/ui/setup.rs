@@ -1,74 +1,218 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
+    Frame,
     layout::{Constraint, Layout, Margin, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
-    Frame,
 };
 
+use super::icons::icons;
 use super::status_bar::render_status_bar;
+use super::text_input::TextInput;
+use super::theme::{Theme, set_theme, theme};
 
-const FIELD_COUNT: usize = 5;
+const FIELD_COUNT: usize = 7;
 const FIELD_LABELS: [&str; FIELD_COUNT] = [
     "Workspace Directory",
     "Language",
     "Editor",
     "LeetCode Session Cookie",
     "CSRF Token",
+    "Theme",
+    "Login Prompt",
 ];
-const FIELD_DEFAULTS: [&str; FIELD_COUNT] = ["~/leetcode", "rust", "vim", "", ""];
+const FIELD_DEFAULTS: [&str; FIELD_COUNT] = ["~/leetcode", "rust", "vim", "", "", "dark", "show"];
 const FIELD_HINTS: [&str; FIELD_COUNT] = [
     "Directory where problem projects will be created",
-    "Default language for code snippets (rust, python3, cpp, java, ...)",
-    "Editor command to open files (vim, nvim, code, ...)",
+    "Language you solve in (\u{2190}/\u{2192} to change)",
+    "Editor command, flags allowed and quoted like a shell (nvim +42, code --wait, ...)",
     "(Optional) LEETCODE_SESSION cookie value for authentication",
     "(Optional) csrftoken cookie value for authentication",
+    "Colors, previewed as you pick (\u{2190}/\u{2192} to change)",
+    "Ask to log in at startup without cookies (\u{2190}/\u{2192} to change)",
 ];
+/// Fields holding credentials, masked unless revealed with Ctrl+S
+const SECRET_FIELDS: [usize; 2] = [3, 4];
+/// The session cookie, where Settings starts and a failed browser login
+/// sends the cursor
+pub const SESSION_FIELD: usize = 3;
+const WORKSPACE_FIELD: usize = 0;
+/// Picked from `config::LANGUAGES` rather than typed
+const LANGUAGE_FIELD: usize = 1;
+/// Picked from `Theme::PRESETS`
+pub const THEME_FIELD: usize = 5;
+/// `skip` leaves anonymous browsing alone at startup
+pub const LOGIN_PROMPT_FIELD: usize = 6;
+
+/// Outcome of Ctrl+T for one pair of cookie values.
+pub enum ConnectionStatus {
+    Checking,
+    LoggedIn(String),
+    Failed(String),
+}
 
 pub struct SetupState {
-    pub fields: [String; FIELD_COUNT],
+    pub fields: [TextInput; FIELD_COUNT],
     pub active_field: usize,
     pub is_editing: bool,
-    pub authenticated: bool,
+    /// Secret fields the user has chosen to show in clear
+    pub revealed: [bool; FIELD_COUNT],
+    /// The last connection test and the (session, csrf) it was run with;
+    /// shown only while the fields still hold those values
+    pub connection: Option<(String, String, ConnectionStatus)>,
+    /// Why a field can't be saved, shown in red under it
+    pub errors: [Option<String>; FIELD_COUNT],
+    /// A missing workspace the user was warned about; Enter again with the
+    /// same value creates it
+    pub confirm_create: Option<String>,
 }
 
 impl SetupState {
     pub fn new() -> Self {
         Self {
-            fields: [
-                FIELD_DEFAULTS[0].to_string(),
-                FIELD_DEFAULTS[1].to_string(),
-                FIELD_DEFAULTS[2].to_string(),
-                FIELD_DEFAULTS[3].to_string(),
-                FIELD_DEFAULTS[4].to_string(),
-            ],
+            fields: FIELD_DEFAULTS.map(TextInput::new),
             active_field: 0,
             is_editing: false,
-            authenticated: false,
+            revealed: [false; FIELD_COUNT],
+            connection: None,
+            errors: Default::default(),
+            confirm_create: None,
         }
     }
 
     pub fn from_config(config: &crate::config::Config) -> Self {
         Self {
             fields: [
-                config.workspace_dir.clone(),
-                config.language.clone(),
-                config.editor.clone(),
-                config.leetcode_session.clone().unwrap_or_default(),
-                config.csrf_token.clone().unwrap_or_default(),
+                TextInput::new(config.workspace_for(&config.language)),
+                TextInput::new(config.language.clone()),
+                TextInput::new(config.editor.clone()),
+                TextInput::new(config.leetcode_session.clone().unwrap_or_default()),
+                TextInput::new(config.csrf_token.clone().unwrap_or_default()),
+                TextInput::new(config.theme.as_ref().map_or("dark", |t| t.preset_name())),
+                TextInput::new(if config.skip_login_prompt {
+                    "skip"
+                } else {
+                    "show"
+                }),
             ],
-            active_field: 3,
+            active_field: SESSION_FIELD,
             is_editing: true,
-            authenticated: config.is_authenticated(),
+            revealed: [false; FIELD_COUNT],
+            connection: None,
+            errors: Default::default(),
+            confirm_create: None,
+        }
+    }
+
+    /// Whether both cookies are filled in, judged from the values being
+    /// edited rather than what is shown.
+    pub fn has_credentials(&self) -> bool {
+        SECRET_FIELDS
+            .iter()
+            .all(|&i| !self.fields[i].value().trim().is_empty())
+    }
+
+    /// Check the workspace and language before saving. A workspace that
+    /// doesn't exist yet only passes once the user has been told it will be
+    /// created.
+    fn validate(&mut self) -> bool {
+        self.errors = Default::default();
+
+        let language = self.fields[LANGUAGE_FIELD].value().trim();
+        if !crate::config::is_known_language(language) {
+            self.errors[LANGUAGE_FIELD] = Some(format!(
+                "Unknown language '{language}', pick one of: {}",
+                crate::config::LANGUAGES.join(", ")
+            ));
+        }
+
+        let theme_name = self.fields[THEME_FIELD].value();
+        if Theme::preset(theme_name).is_none() {
+            self.errors[THEME_FIELD] = Some(format!("Unknown theme '{theme_name}'"));
+        }
+
+        let workspace = self.fields[WORKSPACE_FIELD].value().trim().to_string();
+        let path = crate::config::expand_tilde(&workspace);
+        if workspace.is_empty() {
+            self.errors[WORKSPACE_FIELD] = Some("The workspace directory is required".to_string());
+        } else if path.is_file() {
+            self.errors[WORKSPACE_FIELD] =
+                Some(format!("{} is a file, not a directory", path.display()));
+        } else if !path.exists() && self.confirm_create.as_deref() != Some(workspace.as_str()) {
+            self.errors[WORKSPACE_FIELD] = Some(format!(
+                "{} doesn't exist, press Enter again to create it",
+                path.display()
+            ));
+            self.confirm_create = Some(workspace);
+        }
+
+        let valid = self.errors.iter().all(Option::is_none);
+        if let Some(field) = self.errors.iter().position(Option::is_some) {
+            self.active_field = field;
+        }
+        valid
+    }
+
+    /// The values a picker field steps through, or `None` for typed fields.
+    fn picker_options(field: usize) -> Option<Vec<&'static str>> {
+        match field {
+            LANGUAGE_FIELD => Some(crate::config::LANGUAGES.to_vec()),
+            THEME_FIELD => Some(Theme::PRESETS.iter().map(|(name, _)| *name).collect()),
+            LOGIN_PROMPT_FIELD => Some(vec!["show", "skip"]),
+            _ => None,
+        }
+    }
+
+    /// Step the active picker; a new theme is shown straight away.
+    fn cycle(&mut self, step: isize) {
+        let field = self.active_field;
+        let Some(options) = Self::picker_options(field) else {
+            return;
+        };
+        let current = self.fields[field].value();
+        let next = match options.iter().position(|o| *o == current) {
+            Some(i) => (i as isize + step).rem_euclid(options.len() as isize) as usize,
+            None => 0,
+        };
+        self.fields[field].set(options[next]);
+        self.errors[field] = None;
+        if field == THEME_FIELD
+            && let Some(theme) = Theme::preset(options[next])
+        {
+            set_theme(theme);
         }
     }
 
+    /// The session cookie and CSRF token as currently typed.
+    pub fn credentials(&self) -> (String, String) {
+        (
+            self.fields[3].value().trim().to_string(),
+            self.fields[4].value().trim().to_string(),
+        )
+    }
+
+    /// The connection test result, unless the cookies were edited since.
+    fn current_connection(&self) -> Option<&ConnectionStatus> {
+        let (session, csrf, status) = self.connection.as_ref()?;
+        (self.credentials() == (session.clone(), csrf.clone())).then_some(status)
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> SetupAction {
         // Ctrl+L for browser login
         if key.code == KeyCode::Char('l') && key.modifiers.contains(KeyModifiers::CONTROL) {
             return SetupAction::BrowserLogin;
         }
+        if key.code == KeyCode::Char('t') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return SetupAction::TestConnection;
+        }
+        if key.code == KeyCode::Char('s')
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+            && SECRET_FIELDS.contains(&self.active_field)
+        {
+            self.revealed[self.active_field] ^= true;
+            return SetupAction::None;
+        }
 
         match key.code {
             KeyCode::Tab | KeyCode::Down => {
@@ -79,15 +223,13 @@ impl SetupState {
                 self.active_field = (self.active_field + FIELD_COUNT - 1) % FIELD_COUNT;
                 SetupAction::None
             }
-            KeyCode::Char(c) => {
-                self.fields[self.active_field].push(c);
-                SetupAction::None
-            }
-            KeyCode::Backspace => {
-                self.fields[self.active_field].pop();
-                SetupAction::None
+            KeyCode::Enter => {
+                if self.validate() {
+                    SetupAction::Submit
+                } else {
+                    SetupAction::None
+                }
             }
-            KeyCode::Enter => SetupAction::Submit,
             KeyCode::Esc => {
                 if self.is_editing {
                     SetupAction::Cancel
@@ -95,7 +237,28 @@ impl SetupState {
                     SetupAction::Quit
                 }
             }
-            _ => SetupAction::None,
+            _ if Self::picker_options(self.active_field).is_some() => {
+                match key.code {
+                    KeyCode::Left => self.cycle(-1),
+                    KeyCode::Right | KeyCode::Char(' ') => self.cycle(1),
+                    _ => {}
+                }
+                SetupAction::None
+            }
+            _ => {
+                if self.fields[self.active_field].handle_key(key) {
+                    self.errors[self.active_field] = None;
+                }
+                SetupAction::None
+            }
+        }
+    }
+
+    /// Bracketed paste goes into the active field at the cursor.
+    pub fn paste(&mut self, text: &str) {
+        if Self::picker_options(self.active_field).is_none() {
+            self.fields[self.active_field].insert_str(text.trim());
+            self.errors[self.active_field] = None;
         }
     }
 }
@@ -106,19 +269,20 @@ pub enum SetupAction {
     Cancel,
     Quit,
     BrowserLogin,
+    TestConnection,
 }
 
 pub fn render_setup(frame: &mut Frame, state: &SetupState) {
     let area = frame.area();
 
     let form_width = 70u16.min(area.width.saturating_sub(4));
-    let form_height = 24u16.min(area.height.saturating_sub(2));
+    let form_height = 30u16.min(area.height.saturating_sub(2));
     let form_area = centered_rect(form_width, form_height, area);
 
     let block = Block::default()
         .title(" LeetCode CLI \u{2014} Setup ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(theme().accent));
 
     frame.render_widget(Clear, form_area);
     frame.render_widget(block, form_area);
@@ -133,6 +297,8 @@ pub fn render_setup(frame: &mut Frame, state: &SetupState) {
         Constraint::Length(3), // field 2
         Constraint::Length(3), // field 3
         Constraint::Length(3), // field 4
+        Constraint::Length(3), // field 5
+        Constraint::Length(3), // field 6
         Constraint::Length(1), // auth status
         Constraint::Length(1), // spacer
         Constraint::Length(1), // status bar
@@ -140,7 +306,7 @@ pub fn render_setup(frame: &mut Frame, state: &SetupState) {
     .split(inner);
 
     let welcome = Paragraph::new("Configure your LeetCode CLI settings:")
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(theme().text));
     frame.render_widget(welcome, layout[0]);
 
     for i in 0..FIELD_COUNT {
@@ -148,33 +314,55 @@ pub fn render_setup(frame: &mut Frame, state: &SetupState) {
     }
 
     // Auth status line
-    let auth_line = if state.authenticated {
-        Line::from(Span::styled(
-            "\u{25cf} Authenticated",
-            Style::default().fg(Color::Green),
-        ))
+    let auth_line = if let Some(status) = state.current_connection() {
+        match status {
+            ConnectionStatus::Checking => Line::from(Span::styled(
+                format!("{} Testing connection", icons().ellipsis),
+                Style::default().fg(theme().warning),
+            )),
+            ConnectionStatus::LoggedIn(name) => Line::from(Span::styled(
+                format!("{} Logged in as {name}", icons().check),
+                Style::default().fg(theme().success),
+            )),
+            ConnectionStatus::Failed(err) => Line::from(Span::styled(
+                format!("{} {err}", icons().cross),
+                Style::default().fg(theme().error),
+            )),
+        }
+    } else if state.has_credentials() {
+        Line::from(vec![
+            Span::styled(
+                format!("{} Authenticated", icons().dot),
+                Style::default().fg(theme().success),
+            ),
+            Span::styled(
+                "  (Ctrl+T: test connection)",
+                Style::default().fg(theme().dim),
+            ),
+        ])
     } else {
         Line::from(vec![
             Span::styled(
-                "\u{25cb} Not authenticated",
-                Style::default().fg(Color::DarkGray),
+                format!("{} Not authenticated", icons().circle),
+                Style::default().fg(theme().dim),
             ),
             Span::styled(
                 "  (Ctrl+L: auto-login from browser)",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme().dim),
             ),
         ])
     };
-    frame.render_widget(Paragraph::new(auth_line), layout[7]);
+    frame.render_widget(Paragraph::new(auth_line), layout[9]);
 
     let esc_label = if state.is_editing { "Back" } else { "Quit" };
     render_status_bar(
         frame,
-        layout[9],
+        layout[11],
         &[
             ("Tab/\u{2193}", "Next"),
             ("Shift+Tab/\u{2191}", "Prev"),
             ("Ctrl+L", "Auto-login"),
+            ("Ctrl+S", "Show/hide"),
             ("Enter", "Save"),
             ("Esc", esc_label),
         ],
@@ -185,53 +373,212 @@ fn render_field(frame: &mut Frame, area: Rect, index: usize, state: &SetupState)
     let is_active = state.active_field == index;
     let label_style = if is_active {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme().accent)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::Gray)
+        Style::default().fg(theme().muted)
     };
 
     let value = &state.fields[index];
-    let cursor = if is_active { "\u{258e}" } else { "" };
 
-    let layout = Layout::vertical([Constraint::Length(1), Constraint::Length(1), Constraint::Length(1)])
-        .split(area);
+    let layout = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .split(area);
 
     let label = Line::from(vec![
         Span::styled(FIELD_LABELS[index], label_style),
-        Span::styled(format!("  {}", FIELD_HINTS[index]), Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!("  {}", FIELD_HINTS[index]),
+            Style::default().fg(theme().dim),
+        ),
     ]);
     frame.render_widget(Paragraph::new(label), layout[0]);
 
     let input_style = if is_active {
-        Style::default().fg(Color::White)
+        Style::default().fg(theme().text)
     } else {
-        Style::default().fg(Color::Gray)
+        Style::default().fg(theme().muted)
     };
 
-    // Mask session/csrf values with dots for security
-    let display_value = if (index == 3 || index == 4) && !value.is_empty() {
-        let shown = value.len().min(4);
-        format!("{}{}",
-            &value[..shown],
-            "\u{2022}".repeat(value.len().saturating_sub(shown))
-        )
+    // Credentials stay masked on screen unless revealed; the value itself
+    // is never touched
+    let mut spans = vec![Span::raw(" ")];
+    if SetupState::picker_options(index).is_some() {
+        let arrow = Style::default().fg(if is_active {
+            theme().accent
+        } else {
+            theme().dim
+        });
+        spans.push(Span::styled(format!("{} ", icons().prev), arrow));
+        spans.push(Span::styled(value.value().to_string(), input_style));
+        spans.push(Span::styled(format!(" {}", icons().next), arrow));
+        if index == LANGUAGE_FIELD && !crate::scaffold::is_supported(value.value()) {
+            spans.push(Span::styled(
+                "  run and submit only; o can't scaffold it, so write the solution file yourself",
+                Style::default().fg(theme().dim),
+            ));
+        }
+    } else if SECRET_FIELDS.contains(&index) && !state.revealed[index] {
+        spans.extend(value.masked_spans(is_active, input_style));
     } else {
-        value.clone()
-    };
+        spans.extend(value.spans(
+            (layout[1].width as usize).saturating_sub(2),
+            is_active,
+            input_style,
+        ));
+    }
+    let input = Line::from(spans);
+    let input_block = Paragraph::new(input).style(Style::default().bg(if is_active {
+        theme().dim
+    } else {
+        theme().bar_bg
+    }));
+    frame.render_widget(input_block, layout[1]);
 
-    let input = Line::from(vec![
-        Span::styled(format!(" {display_value}"), input_style),
-        Span::styled(cursor, Style::default().fg(Color::Cyan)),
-    ]);
-    let input_block = Paragraph::new(input).style(
-        Style::default().bg(if is_active {
-            Color::DarkGray
+    if let Some(ref err) = state.errors[index] {
+        frame.render_widget(
+            Paragraph::new(format!(" {err}")).style(Style::default().fg(theme().error)),
+            layout[2],
+        );
+    }
+}
+
+/// Session cookie and CSRF token typed or pasted in by hand, for when
+/// they can't be read from the browser. Masked like the Setup fields.
+#[derive(Default)]
+pub struct CookieForm {
+    pub fields: [TextInput; 2],
+    pub active: usize,
+    pub revealed: [bool; 2],
+    /// Why Enter didn't log in
+    pub error: Option<String>,
+}
+
+pub enum CookieAction {
+    None,
+    Cancel,
+    /// The session cookie and CSRF token, trimmed
+    Submit(String, String),
+}
+
+impl CookieForm {
+    pub fn handle_key(&mut self, key: KeyEvent) -> CookieAction {
+        if key.code == KeyCode::Char('s') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.revealed[self.active] ^= true;
+            return CookieAction::None;
+        }
+        match key.code {
+            KeyCode::Esc => CookieAction::Cancel,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Down | KeyCode::Up => {
+                self.active ^= 1;
+                CookieAction::None
+            }
+            KeyCode::Enter => {
+                let [session, csrf] = self.fields.each_ref().map(|f| f.value().trim().to_string());
+                if let Some(empty) = [&session, &csrf].iter().position(|v| v.is_empty()) {
+                    self.active = empty;
+                    self.error = Some(format!("{} is empty", FIELD_LABELS[SECRET_FIELDS[empty]]));
+                    return CookieAction::None;
+                }
+                CookieAction::Submit(session, csrf)
+            }
+            _ => {
+                if self.fields[self.active].handle_key(key) {
+                    self.error = None;
+                }
+                CookieAction::None
+            }
+        }
+    }
+
+    pub fn paste(&mut self, text: &str) {
+        self.fields[self.active].insert_str(text.trim());
+        self.error = None;
+    }
+}
+
+pub fn render_cookie_form(frame: &mut Frame, area: Rect, form: &CookieForm) {
+    let width = 60u16.min(area.width.saturating_sub(4));
+    let height = 10u16.min(area.height.saturating_sub(2));
+    let popup = centered_rect(width, height, area);
+
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(" Paste Cookies ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().accent));
+    let inner = block.inner(popup).inner(Margin::new(1, 0));
+    frame.render_widget(block, popup);
+
+    let rows = Layout::vertical([
+        Constraint::Length(2), // where to find them
+        Constraint::Length(2), // session
+        Constraint::Length(2), // csrf
+        Constraint::Length(1), // error
+        Constraint::Length(1), // hint
+    ])
+    .split(inner);
+
+    let help = Paragraph::new(
+        "From leetcode.com in your browser's dev tools, under\nApplication (or Storage) > Cookies:",
+    )
+    .style(Style::default().fg(theme().dim));
+    frame.render_widget(help, rows[0]);
+
+    for (i, field) in form.fields.iter().enumerate() {
+        let is_active = form.active == i;
+        let (label_style, input_style) = if is_active {
+            (
+                Style::default()
+                    .fg(theme().accent)
+                    .add_modifier(Modifier::BOLD),
+                Style::default().fg(theme().text),
+            )
+        } else {
+            (
+                Style::default().fg(theme().muted),
+                Style::default().fg(theme().muted),
+            )
+        };
+        let mut spans = vec![Span::raw(" ")];
+        if form.revealed[i] {
+            spans.extend(field.spans(
+                (inner.width as usize).saturating_sub(2),
+                is_active,
+                input_style,
+            ));
+        } else {
+            spans.extend(field.masked_spans(is_active, input_style));
+        }
+        let [label_area, input_area] =
+            Layout::vertical([Constraint::Length(1); 2]).areas(rows[i + 1]);
+        let label = Span::styled(FIELD_LABELS[SECRET_FIELDS[i]], label_style);
+        frame.render_widget(Paragraph::new(label), label_area);
+        let bg = if is_active {
+            theme().dim
         } else {
-            Color::Black
-        }),
+            theme().bar_bg
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(spans)).style(Style::default().bg(bg)),
+            input_area,
+        );
+    }
+
+    if let Some(ref err) = form.error {
+        frame.render_widget(
+            Paragraph::new(err.as_str()).style(Style::default().fg(theme().error)),
+            rows[3],
+        );
+    }
+    frame.render_widget(
+        Paragraph::new("Enter: log in  Tab: next  Ctrl+S: show/hide  Esc: cancel")
+            .style(Style::default().fg(theme().dim)),
+        rows[4],
     );
-    frame.render_widget(input_block, layout[1]);
 }
 
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
@@ -239,3 +586,47 @@ fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let y = area.y + (area.height.saturating_sub(height)) / 2;
     Rect::new(x, y, width, height)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A form set to `language`, with a workspace that exists as long as
+    /// the returned directory does.
+    fn setup_with_language(language: &str) -> (tempfile::TempDir, SetupState) {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = SetupState::new();
+        state.fields[WORKSPACE_FIELD].set(dir.path().display().to_string());
+        state.fields[LANGUAGE_FIELD].set(language);
+        (dir, state)
+    }
+
+    #[test]
+    fn every_judged_language_can_be_picked_and_saved() {
+        let mut state = SetupState::new();
+        state.active_field = LANGUAGE_FIELD;
+        let mut seen = Vec::new();
+        for _ in crate::config::LANGUAGES {
+            seen.push(state.fields[LANGUAGE_FIELD].value().to_string());
+            state.cycle(1);
+        }
+        assert_eq!(seen, crate::config::LANGUAGES);
+
+        for language in ["python3", "cpp", "java", "typescript", "go"] {
+            let (_dir, mut state) = setup_with_language(language);
+            assert!(
+                state.validate(),
+                "{language}: {:?}",
+                state.errors[LANGUAGE_FIELD]
+            );
+        }
+        let (_dir, mut state) = setup_with_language("cobol");
+        assert!(!state.validate());
+        assert!(
+            state.errors[LANGUAGE_FIELD]
+                .as_deref()
+                .unwrap()
+                .starts_with("Unknown language")
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+};
+
+use crate::api::types::CommunitySolution;
+
+use super::icons::icons;
+use super::rich_text::{CodeBlock, code_blocks, markdown_to_lines};
+use super::theme::theme;
+
+/// Community solutions fetched at a time.
+pub const PAGE_SIZE: u32 = 15;
+
+/// Tags shown per solution row.
+const TAGS_SHOWN: usize = 3;
+
+/// The Solutions tab in Detail: a page of community solutions, most
+/// upvoted first, with one of them open to read.
+pub struct SolutionsState {
+    pub solutions: Vec<CommunitySolution>,
+    /// How many there are across all pages
+    pub total: u32,
+    /// Zero-based, of `PAGE_SIZE` solutions each
+    pub page: u32,
+    pub loading: bool,
+    pub error: Option<String>,
+    pub table_state: TableState,
+    pub post: Option<SolutionPost>,
+}
+
+/// A solution being read.
+pub struct SolutionPost {
+    pub id: String,
+    pub title: String,
+    /// `None` while the post is loading
+    pub lines: Option<Vec<Line<'static>>>,
+    /// Code blocks in the post; posts often have one per language
+    pub blocks: Vec<CodeBlock>,
+    /// The block `y` copies, picked with Tab
+    pub block: usize,
+}
+
+pub enum SolutionsAction {
+    None,
+    /// Not a key for the solutions; Detail's own handling applies
+    Unhandled,
+    LoadPage(u32),
+    /// Fetch a post by its topic id
+    OpenPost(String),
+    /// Copy a code block, with its language for the message
+    Copy {
+        language: String,
+        code: String,
+    },
+}
+
+impl SolutionsState {
+    pub fn new() -> Self {
+        Self {
+            solutions: Vec::new(),
+            total: 0,
+            page: 0,
+            loading: true,
+            error: None,
+            table_state: TableState::default(),
+            post: None,
+        }
+    }
+
+    pub fn set_page(&mut self, page: u32, solutions: Vec<CommunitySolution>, total: u32) {
+        self.page = page;
+        self.total = total;
+        self.loading = false;
+        self.error = None;
+        self.table_state
+            .select((!solutions.is_empty()).then_some(0));
+        self.solutions = solutions;
+    }
+
+    pub fn failed(&mut self, message: String) {
+        self.loading = false;
+        self.error = Some(message);
+    }
+
+    fn page_count(&self) -> u32 {
+        self.total.div_ceil(PAGE_SIZE).max(1)
+    }
+
+    /// Show a post's content, starting on the code block in `language`
+    /// when it has one.
+    pub fn set_post(&mut self, id: &str, content: &str, language: &str) {
+        let Some(ref mut post) = self.post else {
+            return;
+        };
+        if post.id != id {
+            return;
+        }
+        let heading = Line::from(Span::styled(
+            post.title.clone(),
+            Style::default()
+                .fg(theme().accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+        let mut lines = vec![heading, Line::from("")];
+        lines.extend(markdown_to_lines(content));
+        post.lines = Some(lines);
+        post.blocks = code_blocks(content);
+        post.block = post
+            .blocks
+            .iter()
+            .position(|b| same_language(&b.language, language))
+            .unwrap_or(0);
+    }
+
+    /// Show why a post couldn't be loaded in its place.
+    pub fn post_failed(&mut self, id: &str, message: &str) {
+        if let Some(ref mut post) = self.post
+            && post.id == id
+        {
+            post.lines = Some(vec![Line::from(Span::styled(
+                format!(" {message}"),
+                Style::default().fg(theme().error),
+            ))]);
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> SolutionsAction {
+        if let Some(ref mut post) = self.post {
+            return match key.code {
+                KeyCode::Esc | KeyCode::Char('b') => {
+                    self.post = None;
+                    SolutionsAction::None
+                }
+                KeyCode::Tab | KeyCode::BackTab if !post.blocks.is_empty() => {
+                    let len = post.blocks.len();
+                    post.block = if key.code == KeyCode::Tab {
+                        (post.block + 1) % len
+                    } else {
+                        (post.block + len - 1) % len
+                    };
+                    SolutionsAction::None
+                }
+                KeyCode::Char('y') => match post.blocks.get(post.block) {
+                    Some(block) => SolutionsAction::Copy {
+                        language: block.language.clone(),
+                        code: block.code.clone(),
+                    },
+                    None => SolutionsAction::None,
+                },
+                _ => SolutionsAction::Unhandled,
+            };
+        }
+
+        let len = self.solutions.len();
+        let selected = self.table_state.selected();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if let Some(i) = selected {
+                    self.table_state
+                        .select(Some((i + 1).min(len.saturating_sub(1))));
+                }
+                SolutionsAction::None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                if let Some(i) = selected {
+                    self.table_state.select(Some(i.saturating_sub(1)));
+                }
+                SolutionsAction::None
+            }
+            KeyCode::Char('n') if !self.loading && self.page + 1 < self.page_count() => {
+                self.loading = true;
+                SolutionsAction::LoadPage(self.page + 1)
+            }
+            KeyCode::Char('p') if !self.loading && self.page > 0 => {
+                self.loading = true;
+                SolutionsAction::LoadPage(self.page - 1)
+            }
+            KeyCode::Char('R') if self.error.is_some() => {
+                self.loading = true;
+                self.error = None;
+                SolutionsAction::LoadPage(self.page)
+            }
+            KeyCode::Enter => match selected.and_then(|i| self.solutions.get(i)) {
+                Some(solution) => {
+                    let id = solution.id.clone();
+                    self.post = Some(SolutionPost {
+                        id: id.clone(),
+                        title: solution.title.clone(),
+                        lines: None,
+                        blocks: Vec::new(),
+                        block: 0,
+                    });
+                    SolutionsAction::OpenPost(id)
+                }
+                None => SolutionsAction::None,
+            },
+            _ => SolutionsAction::Unhandled,
+        }
+    }
+
+    /// Status bar hints for the list or the open post. The post's copy
+    /// hint names the block's language, so it's built here.
+    pub fn hints(&self) -> (Vec<(&'static str, &'static str)>, Option<String>) {
+        match self.post {
+            Some(ref post) => {
+                let copy = post.blocks.get(post.block).map(|b| {
+                    let language = if b.language.is_empty() {
+                        "code"
+                    } else {
+                        &b.language
+                    };
+                    format!("Copy {language} ({}/{})", post.block + 1, post.blocks.len())
+                });
+                let mut hints = vec![("j/k", "Scroll"), ("d/u", "Half page")];
+                if post.blocks.len() > 1 {
+                    hints.push(("Tab", "Next block"));
+                }
+                hints.extend([("b/Esc", "Solutions"), ("c", "Description"), ("?", "Help")]);
+                (hints, copy)
+            }
+            None => {
+                let mut hints = vec![("j/k", "Navigate"), ("Enter", "Read"), ("n/p", "Page")];
+                if self.error.is_some() {
+                    hints.push(("R", "Retry"));
+                }
+                hints.extend([("c", "Description"), ("b/Esc", "Back"), ("?", "Help")]);
+                (hints, None)
+            }
+        }
+    }
+}
+
+/// Whether a fence's language is the configured one, allowing for
+/// versions and LeetCode's names: `Python3` is `python`, `Go` is `golang`.
+fn same_language(fence: &str, configured: &str) -> bool {
+    let normalize = |s: &str| {
+        let s = s
+            .trim_end_matches(|c: char| c.is_ascii_digit())
+            .to_lowercase();
+        match s.as_str() {
+            "golang" => "go".to_string(),
+            "rs" => "rust".to_string(),
+            "py" => "python".to_string(),
+            "c++" => "cpp".to_string(),
+            _ => s,
+        }
+    };
+    !fence.is_empty() && normalize(fence) == normalize(configured)
+}
+
+pub fn render_solution_list(frame: &mut Frame, area: Rect, state: &mut SolutionsState) {
+    let title = format!(
+        " Solutions, most votes first (page {}/{}, {} in all) ",
+        state.page + 1,
+        state.page_count(),
+        state.total
+    );
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(theme().dim));
+
+    if let Some(ref err) = state.error {
+        let error = Paragraph::new(format!(" Error: {err}"))
+            .style(Style::default().fg(theme().error))
+            .block(block);
+        frame.render_widget(error, area);
+        return;
+    }
+    if state.loading {
+        let loading = Paragraph::new(" Loading solutions...")
+            .style(Style::default().fg(theme().warning))
+            .block(block);
+        frame.render_widget(loading, area);
+        return;
+    }
+    if state.solutions.is_empty() {
+        let empty = Paragraph::new(" No community solutions yet.")
+            .style(Style::default().fg(theme().dim))
+            .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let rows: Vec<Row> = state
+        .solutions
+        .iter()
+        .map(|s| {
+            let tags: Vec<&str> = s
+                .solution_tags
+                .iter()
+                .take(TAGS_SHOWN)
+                .map(|t| t.name.as_str())
+                .collect();
+            let author = s.post.author.as_ref().map_or("", |a| a.username.as_str());
+            Row::new([
+                Cell::from(Span::styled(
+                    format!("{:>6}", s.post.vote_count),
+                    Style::default().fg(theme().success),
+                )),
+                Cell::from(s.title.clone()),
+                Cell::from(Span::styled(
+                    author.to_string(),
+                    Style::default().fg(theme().muted),
+                )),
+                Cell::from(Span::styled(
+                    tags.join(", "),
+                    Style::default().fg(theme().dim),
+                )),
+            ])
+        })
+        .collect();
+
+    let header = Row::new([
+        Cell::from(" Votes"),
+        Cell::from("Title"),
+        Cell::from("Author"),
+        Cell::from("Tags"),
+    ])
+    .style(
+        Style::default()
+            .fg(theme().accent)
+            .add_modifier(Modifier::BOLD),
+    );
+    let widths = [
+        Constraint::Length(7),
+        Constraint::Min(30),
+        Constraint::Length(18),
+        Constraint::Length(28),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(
+            Style::default()
+                .bg(theme().selection_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(format!("{} ", icons().pointer));
+    frame.render_stateful_widget(table, area, &mut state.table_state);
+}
@@ -1,9 +1,10 @@
+use super::theme::theme;
 use ratatui::{
+    Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
-    Frame,
 };
 
 pub fn render_status_bar(frame: &mut Frame, area: Rect, hints: &[(&str, &str)]) {
@@ -15,11 +16,11 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, hints: &[(&str, &str)])
                 Span::styled(
                     format!(" {key} "),
                     Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::DarkGray)
+                        .fg(theme().on_accent)
+                        .bg(theme().chip_bg)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(format!(" {desc} "), Style::default().fg(Color::Gray)),
+                Span::styled(format!(" {desc} "), Style::default().fg(theme().muted)),
             ];
             if i < hints.len() - 1 {
                 s.push(Span::raw(" "));
@@ -28,7 +29,6 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, hints: &[(&str, &str)])
         })
         .collect();
 
-    let bar = Paragraph::new(Line::from(spans))
-        .style(Style::default().bg(Color::Black));
+    let bar = Paragraph::new(Line::from(spans)).style(Style::default().bg(theme().bar_bg));
     frame.render_widget(bar, area);
 }
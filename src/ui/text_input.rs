@@ -0,0 +1,260 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{style::Style, text::Span};
+
+use super::icons::icons;
+use super::theme::theme;
+
+/// A single-line text field with a movable cursor, shared by every place
+/// that takes typed input.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    /// Cursor position in chars, `0..=len`
+    cursor: usize,
+}
+
+impl TextInput {
+    /// A field holding `value`, with the cursor at the end.
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        let cursor = value.chars().count();
+        Self { value, cursor }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Replace the contents, moving the cursor to the end.
+    pub fn set(&mut self, value: impl Into<String>) {
+        *self = Self::new(value);
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn byte_at(&self, cursor: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(cursor)
+            .map_or(self.value.len(), |(i, _)| i)
+    }
+
+    /// Insert at the cursor. Line breaks, as in a pasted cookie, are dropped.
+    pub fn insert_str(&mut self, text: &str) {
+        let text: String = text.chars().filter(|c| !matches!(c, '\n' | '\r')).collect();
+        let at = self.byte_at(self.cursor);
+        self.value.insert_str(at, &text);
+        self.cursor += text.chars().count();
+    }
+
+    /// Apply an editing key. Returns whether the key was used, so callers
+    /// can fall through to their own bindings for the rest.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let len = self.value.chars().count();
+        match key.code {
+            KeyCode::Char('w') if ctrl => self.delete_word(),
+            KeyCode::Char('u') if ctrl => {
+                let at = self.byte_at(self.cursor);
+                self.value.drain(..at);
+                self.cursor = 0;
+            }
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = len,
+            KeyCode::Char(c) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => {
+                self.insert_str(c.encode_utf8(&mut [0; 4]));
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let at = self.byte_at(self.cursor);
+                self.value.remove(at);
+            }
+            KeyCode::Delete if self.cursor < len => {
+                let at = self.byte_at(self.cursor);
+                self.value.remove(at);
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(len),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = len,
+            KeyCode::Backspace | KeyCode::Delete => {}
+            _ => return false,
+        }
+        true
+    }
+
+    /// Ctrl+W: the word before the cursor and any spaces after it.
+    fn delete_word(&mut self) {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut start = self.cursor;
+        while start > 0 && chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        while start > 0 && !chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        let (from, to) = (self.byte_at(start), self.byte_at(self.cursor));
+        self.value.drain(from..to);
+        self.cursor = start;
+    }
+
+    /// The part of the value that fits in `width` columns, scrolled to keep
+    /// the cursor in view, with a cursor bar when focused.
+    pub fn spans(&self, width: usize, focused: bool, style: Style) -> Vec<Span<'static>> {
+        let chars: Vec<char> = self.value.chars().collect();
+        // One column goes to the cursor bar
+        let room = width.saturating_sub(1).max(1);
+        let start = if focused {
+            self.cursor.saturating_sub(room - 1).min(chars.len())
+        } else {
+            0
+        };
+        let end = (start + room).min(chars.len());
+        let cursor = self.cursor.clamp(start, end);
+
+        let before: String = chars[start..cursor].iter().collect();
+        let after: String = chars[cursor..end].iter().collect();
+        let mut spans = vec![Span::styled(before, style)];
+        if focused {
+            spans.push(Span::styled(
+                icons().cursor,
+                Style::default().fg(theme().accent),
+            ));
+        }
+        spans.push(Span::styled(after, style));
+        spans
+    }
+
+    /// A secret shown as `••••…abcd`: only the last four chars are visible,
+    /// and the cursor bar sits at the end whatever its real position.
+    pub fn masked_spans(&self, focused: bool, style: Style) -> Vec<Span<'static>> {
+        let len = self.value.chars().count();
+        let text = if len > 4 {
+            let tail: String = self.value.chars().skip(len - 4).collect();
+            format!("{}{}{tail}", icons().mask.repeat(4), icons().ellipsis)
+        } else {
+            icons().mask.repeat(len)
+        };
+        let mut spans = vec![Span::styled(text, style)];
+        if focused {
+            spans.push(Span::styled(
+                icons().cursor,
+                Style::default().fg(theme().accent),
+            ));
+        }
+        spans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut TextInput, code: KeyCode) -> bool {
+        input.handle_key(KeyEvent::from(code))
+    }
+
+    fn ctrl(input: &mut TextInput, c: char) -> bool {
+        input.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL))
+    }
+
+    /// The value with the cursor drawn as `|`.
+    fn shown(input: &TextInput) -> String {
+        let mut value = input.value().to_string();
+        value.insert(input.byte_at(input.cursor), '|');
+        value
+    }
+
+    #[test]
+    fn edits_happen_at_the_cursor_across_multi_byte_chars() {
+        let mut input = TextInput::new("naïve");
+        assert_eq!(shown(&input), "naïve|");
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(shown(&input), "na|ve");
+        press(&mut input, KeyCode::Char('ï'));
+        input.insert_str("漢字");
+        assert_eq!(shown(&input), "naï漢字|ve");
+        press(&mut input, KeyCode::Delete);
+        assert_eq!(shown(&input), "naï漢字|e");
+
+        press(&mut input, KeyCode::Home);
+        press(&mut input, KeyCode::Left);
+        assert_eq!(shown(&input), "|naï漢字e");
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.value(), "naï漢字e");
+        ctrl(&mut input, 'e');
+        press(&mut input, KeyCode::Right);
+        press(&mut input, KeyCode::Delete);
+        assert_eq!(shown(&input), "naï漢字e|");
+    }
+
+    #[test]
+    fn pasted_line_breaks_are_dropped() {
+        let mut input = TextInput::default();
+        input.insert_str("abc\r\ndef\n");
+        assert_eq!(shown(&input), "abcdef|");
+    }
+
+    #[test]
+    fn ctrl_w_deletes_the_word_before_the_cursor() {
+        let mut input = TextInput::new("two sum  ");
+        ctrl(&mut input, 'w');
+        assert_eq!(shown(&input), "two |");
+        ctrl(&mut input, 'w');
+        assert_eq!(shown(&input), "|");
+        ctrl(&mut input, 'w');
+        assert_eq!(shown(&input), "|");
+
+        let mut input = TextInput::new("sliding wïndow max");
+        for _ in 0..4 {
+            press(&mut input, KeyCode::Left);
+        }
+        ctrl(&mut input, 'w');
+        assert_eq!(shown(&input), "sliding | max");
+
+        ctrl(&mut input, 'a');
+        press(&mut input, KeyCode::Right);
+        ctrl(&mut input, 'u');
+        assert_eq!(shown(&input), "|liding  max");
+    }
+
+    #[test]
+    fn keys_it_doesnt_edit_with_fall_through() {
+        let mut input = TextInput::new("x");
+        assert!(!press(&mut input, KeyCode::Enter));
+        assert!(!press(&mut input, KeyCode::Esc));
+        assert!(!ctrl(&mut input, 'r'));
+        assert!(!input.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::ALT)));
+        assert_eq!(input.value(), "x");
+    }
+
+    #[test]
+    fn long_values_scroll_to_keep_the_cursor_in_view() {
+        let text = |spans: Vec<Span>| {
+            spans
+                .iter()
+                .map(|s| s.content.to_string())
+                .collect::<Vec<_>>()
+        };
+        let cursor = icons().cursor.to_string();
+        let mut input = TextInput::new("abcdefgh");
+        assert_eq!(
+            text(input.spans(5, true, Style::default())),
+            ["fgh", &cursor, ""]
+        );
+        ctrl(&mut input, 'a');
+        assert_eq!(
+            text(input.spans(5, true, Style::default())),
+            ["", &cursor, "abcd"]
+        );
+        assert_eq!(text(input.spans(5, false, Style::default())), ["", "abcd"]);
+    }
+}
@@ -0,0 +1,178 @@
+use anyhow::{Context, Result, bail};
+use ratatui::style::Color;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use crate::config::ThemeSetting;
+
+/// Every color the UI draws with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Titles, focused borders, keys in help
+    pub accent: Color,
+    pub text: Color,
+    /// Hints, borders, secondary details
+    pub dim: Color,
+    /// Unfocused text that should still be readable
+    pub muted: Color,
+    pub success: Color,
+    /// Spinners, pending states, search matches
+    pub warning: Color,
+    pub error: Color,
+    pub easy: Color,
+    pub medium: Color,
+    pub hard: Color,
+    /// Lists and the watch badge
+    pub special: Color,
+    /// The highlighted table row and the active input
+    pub selection_bg: Color,
+    /// Title and status bars
+    pub bar_bg: Color,
+    /// Key chips in the status bar
+    pub chip_bg: Color,
+    /// Text on a colored background
+    pub on_accent: Color,
+    /// Code in problem statements
+    pub code_bg: Color,
+}
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        accent: Color::Cyan,
+        text: Color::White,
+        dim: Color::DarkGray,
+        muted: Color::Gray,
+        success: Color::Green,
+        warning: Color::Yellow,
+        error: Color::Red,
+        easy: Color::Green,
+        medium: Color::Yellow,
+        hard: Color::Red,
+        special: Color::Magenta,
+        selection_bg: Color::DarkGray,
+        bar_bg: Color::Black,
+        chip_bg: Color::DarkGray,
+        on_accent: Color::Black,
+        code_bg: Color::Rgb(40, 40, 55),
+    };
+
+    pub const LIGHT: Theme = Theme {
+        accent: Color::Blue,
+        text: Color::Black,
+        dim: Color::DarkGray,
+        muted: Color::Rgb(88, 88, 88),
+        success: Color::Rgb(0, 128, 0),
+        warning: Color::Rgb(175, 95, 0),
+        error: Color::Rgb(192, 0, 0),
+        easy: Color::Rgb(0, 128, 0),
+        medium: Color::Rgb(175, 95, 0),
+        hard: Color::Rgb(192, 0, 0),
+        special: Color::Magenta,
+        selection_bg: Color::Rgb(200, 215, 240),
+        bar_bg: Color::Rgb(230, 230, 230),
+        chip_bg: Color::DarkGray,
+        on_accent: Color::White,
+        code_bg: Color::Rgb(235, 235, 242),
+    };
+
+    pub const SOLARIZED: Theme = Theme {
+        accent: Color::Rgb(38, 139, 210),
+        text: Color::Rgb(147, 161, 161),
+        dim: Color::Rgb(88, 110, 117),
+        muted: Color::Rgb(131, 148, 150),
+        success: Color::Rgb(133, 153, 0),
+        warning: Color::Rgb(181, 137, 0),
+        error: Color::Rgb(220, 50, 47),
+        easy: Color::Rgb(133, 153, 0),
+        medium: Color::Rgb(181, 137, 0),
+        hard: Color::Rgb(220, 50, 47),
+        special: Color::Rgb(211, 54, 130),
+        selection_bg: Color::Rgb(7, 54, 66),
+        bar_bg: Color::Rgb(0, 43, 54),
+        chip_bg: Color::Rgb(88, 110, 117),
+        on_accent: Color::Rgb(0, 43, 54),
+        code_bg: Color::Rgb(7, 54, 66),
+    };
+
+    /// Built-in themes, in the order Setup cycles through them.
+    pub const PRESETS: [(&str, Theme); 3] = [
+        ("dark", Theme::DARK),
+        ("light", Theme::LIGHT),
+        ("solarized", Theme::SOLARIZED),
+    ];
+
+    pub fn preset(name: &str) -> Option<Theme> {
+        Self::PRESETS
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, theme)| *theme)
+    }
+
+    /// The theme a config setting describes: a preset, with any colors the
+    /// `[theme]` table overrides.
+    pub fn from_setting(setting: Option<&ThemeSetting>) -> Result<Theme> {
+        let name = setting.map_or("dark", ThemeSetting::preset_name);
+        let Some(mut theme) = Self::preset(name) else {
+            let names: Vec<&str> = Self::PRESETS.iter().map(|(n, _)| *n).collect();
+            bail!(
+                "Unknown theme '{name}', expected one of: {}",
+                names.join(", ")
+            );
+        };
+        if let Some(ThemeSetting::Custom(table)) = setting {
+            for (key, value) in &table.colors {
+                let color = Color::from_str(value)
+                    .ok()
+                    .with_context(|| format!("Invalid color '{value}' for theme.{key}"))?;
+                *theme
+                    .slot(key)
+                    .with_context(|| format!("Unknown theme color '{key}'"))? = color;
+            }
+        }
+        Ok(theme)
+    }
+
+    fn slot(&mut self, key: &str) -> Option<&mut Color> {
+        Some(match key {
+            "accent" => &mut self.accent,
+            "text" => &mut self.text,
+            "dim" => &mut self.dim,
+            "muted" => &mut self.muted,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "easy" => &mut self.easy,
+            "medium" => &mut self.medium,
+            "hard" => &mut self.hard,
+            "special" => &mut self.special,
+            "selection_bg" => &mut self.selection_bg,
+            "bar_bg" => &mut self.bar_bg,
+            "chip_bg" => &mut self.chip_bg,
+            "on_accent" => &mut self.on_accent,
+            "code_bg" => &mut self.code_bg,
+            _ => return None,
+        })
+    }
+
+    /// Color for a problem's difficulty label.
+    pub fn difficulty(&self, difficulty: &str) -> Color {
+        match difficulty {
+            "Easy" => self.easy,
+            "Medium" => self.medium,
+            "Hard" => self.hard,
+            _ => self.text,
+        }
+    }
+}
+
+/// Shared by every render function, so switching themes in Setup doesn't
+/// have to be threaded through each screen.
+static CURRENT: RwLock<Theme> = RwLock::new(Theme::DARK);
+
+pub fn theme() -> Theme {
+    *CURRENT.read().unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn set_theme(theme: Theme) {
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = theme;
+}
@@ -0,0 +1,45 @@
+use anyhow::{Context, Result};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+
+/// Watches one problem's solution file while watch mode is on. Dropping it
+/// stops the notifications.
+pub struct SolutionWatcher {
+    pub slug: String,
+    _watcher: RecommendedWatcher,
+}
+
+impl SolutionWatcher {
+    /// Call `on_change` from the watcher thread whenever `file` is written.
+    /// The parent directory is watched since many editors save by writing a
+    /// new file and renaming it over the old one.
+    pub fn start(slug: String, file: &Path, on_change: impl Fn() + Send + 'static) -> Result<Self> {
+        let dir = file
+            .parent()
+            .map(Path::to_path_buf)
+            .context("Solution file has no parent directory")?;
+        let target: PathBuf = file.file_name().map(PathBuf::from).unwrap_or_default();
+
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            let Ok(event) = res else {
+                return;
+            };
+            let touches_file = event
+                .paths
+                .iter()
+                .any(|p| p.file_name() == Some(target.as_os_str()));
+            if touches_file && matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                on_change();
+            }
+        })
+        .context("Failed to start file watcher")?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Failed to watch {}", dir.display()))?;
+
+        Ok(Self {
+            slug,
+            _watcher: watcher,
+        })
+    }
+}